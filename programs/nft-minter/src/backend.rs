@@ -0,0 +1,316 @@
+//! 🧱 Asset backend - every asset-level CPI the program makes goes through here
+//!
+//! Handlers describe *what* happens to an asset (create, rewrite a plugin,
+//! transfer, burn) through `AssetBackend`; `MplCore` turns that into mpl-core
+//! CPIs. `RecordingBackend` only records the calls, so game logic can be
+//! exercised without the mpl-core program, and another asset standard only
+//! needs a new implementation. Collection-level setup (create, hand-off) and
+//! the Attributes rewrites built on `UpdateV1` stay mpl-core specific.
+
+use std::cell::RefCell;
+
+use anchor_lang::prelude::*;
+use mpl_core::{
+    instructions::{
+        AddPluginV1CpiBuilder, ApprovePluginAuthorityV1CpiBuilder, BurnV1CpiBuilder, CreateV1CpiBuilder,
+        RemovePluginV1CpiBuilder, TransferV1CpiBuilder, UpdatePluginV1CpiBuilder, UpdateV1CpiBuilder,
+    },
+    types::{Plugin, PluginAuthority, PluginAuthorityPair, PluginType},
+};
+
+/// 🧱 The accounts one asset operation touches
+#[derive(Clone, Copy)]
+pub struct AssetCall<'a, 'info> {
+    pub asset: &'a AccountInfo<'info>,
+    pub collection: &'a AccountInfo<'info>,
+    pub payer: &'a AccountInfo<'info>,
+    pub authority: &'a AccountInfo<'info>,
+    /// Seeds the authority (and, on create, the asset) sign with; empty for keypairs
+    pub signer_seeds: &'a [&'a [&'a [u8]]],
+}
+
+impl<'a, 'info> AssetCall<'a, 'info> {
+    /// A call signed by keypairs already on the transaction
+    pub fn new(
+        asset: &'a AccountInfo<'info>,
+        collection: &'a AccountInfo<'info>,
+        payer: &'a AccountInfo<'info>,
+        authority: &'a AccountInfo<'info>,
+    ) -> Self {
+        Self { asset, collection, payer, authority, signer_seeds: &[] }
+    }
+
+    /// The same call with PDA signer seeds
+    pub fn signed(self, signer_seeds: &'a [&'a [&'a [u8]]]) -> Self {
+        Self { signer_seeds, ..self }
+    }
+}
+
+/// 🧱 What the program needs from an asset standard
+pub trait AssetBackend<'info> {
+    /// Create `call.asset` in its collection, owned by `owner`, with `plugins`
+    fn create(
+        &self,
+        call: AssetCall<'_, 'info>,
+        owner: &AccountInfo<'info>,
+        name: String,
+        uri: String,
+        plugins: Vec<PluginAuthorityPair>,
+    ) -> Result<()>;
+
+    /// Replace the data of a plugin the asset already has
+    fn update_plugin(&self, call: AssetCall<'_, 'info>, plugin: Plugin) -> Result<()>;
+
+    /// Add a plugin, held by `init_authority` (default: the plugin type's own)
+    fn add_plugin(
+        &self,
+        call: AssetCall<'_, 'info>,
+        plugin: Plugin,
+        init_authority: Option<PluginAuthority>,
+    ) -> Result<()>;
+
+    /// Hand an existing plugin to `new_authority`
+    fn approve_plugin_authority(
+        &self,
+        call: AssetCall<'_, 'info>,
+        plugin_type: PluginType,
+        new_authority: PluginAuthority,
+    ) -> Result<()>;
+
+    fn remove_plugin(&self, call: AssetCall<'_, 'info>, plugin_type: PluginType) -> Result<()>;
+
+    /// Change the asset's name and/or URI
+    fn update(&self, call: AssetCall<'_, 'info>, new_name: Option<String>, new_uri: Option<String>) -> Result<()>;
+
+    fn transfer(&self, call: AssetCall<'_, 'info>, new_owner: &AccountInfo<'info>) -> Result<()>;
+
+    fn burn(&self, call: AssetCall<'_, 'info>) -> Result<()>;
+}
+
+/// 🧱 The mpl-core backend the program runs on
+pub struct MplCore<'a, 'info> {
+    pub program: &'a AccountInfo<'info>,
+    pub system_program: &'a AccountInfo<'info>,
+}
+
+impl<'a, 'info> MplCore<'a, 'info> {
+    pub fn new(program: &'a AccountInfo<'info>, system_program: &'a AccountInfo<'info>) -> Self {
+        Self { program, system_program }
+    }
+}
+
+impl<'info> AssetBackend<'info> for MplCore<'_, 'info> {
+    fn create(
+        &self,
+        call: AssetCall<'_, 'info>,
+        owner: &AccountInfo<'info>,
+        name: String,
+        uri: String,
+        plugins: Vec<PluginAuthorityPair>,
+    ) -> Result<()> {
+        CreateV1CpiBuilder::new(self.program)
+            .asset(call.asset)
+            .collection(Some(call.collection))
+            .authority(Some(call.authority))
+            .payer(call.payer)
+            .owner(Some(owner))
+            .update_authority(Some(call.authority))
+            .system_program(self.system_program)
+            .name(name)
+            .uri(uri)
+            .plugins(plugins)
+            .invoke_signed(call.signer_seeds)?;
+        Ok(())
+    }
+
+    fn update_plugin(&self, call: AssetCall<'_, 'info>, plugin: Plugin) -> Result<()> {
+        UpdatePluginV1CpiBuilder::new(self.program)
+            .asset(call.asset)
+            .collection(Some(call.collection))
+            .payer(call.payer)
+            .authority(Some(call.authority))
+            .system_program(self.system_program)
+            .plugin(plugin)
+            .invoke_signed(call.signer_seeds)?;
+        Ok(())
+    }
+
+    fn add_plugin(
+        &self,
+        call: AssetCall<'_, 'info>,
+        plugin: Plugin,
+        init_authority: Option<PluginAuthority>,
+    ) -> Result<()> {
+        let mut add = AddPluginV1CpiBuilder::new(self.program);
+        add.asset(call.asset)
+            .collection(Some(call.collection))
+            .payer(call.payer)
+            .authority(Some(call.authority))
+            .system_program(self.system_program)
+            .plugin(plugin);
+        if let Some(init_authority) = init_authority {
+            add.init_authority(init_authority);
+        }
+        add.invoke_signed(call.signer_seeds)?;
+        Ok(())
+    }
+
+    fn approve_plugin_authority(
+        &self,
+        call: AssetCall<'_, 'info>,
+        plugin_type: PluginType,
+        new_authority: PluginAuthority,
+    ) -> Result<()> {
+        ApprovePluginAuthorityV1CpiBuilder::new(self.program)
+            .asset(call.asset)
+            .collection(Some(call.collection))
+            .payer(call.payer)
+            .authority(Some(call.authority))
+            .system_program(self.system_program)
+            .plugin_type(plugin_type)
+            .new_authority(new_authority)
+            .invoke_signed(call.signer_seeds)?;
+        Ok(())
+    }
+
+    fn remove_plugin(&self, call: AssetCall<'_, 'info>, plugin_type: PluginType) -> Result<()> {
+        RemovePluginV1CpiBuilder::new(self.program)
+            .asset(call.asset)
+            .collection(Some(call.collection))
+            .payer(call.payer)
+            .authority(Some(call.authority))
+            .system_program(self.system_program)
+            .plugin_type(plugin_type)
+            .invoke_signed(call.signer_seeds)?;
+        Ok(())
+    }
+
+    fn update(&self, call: AssetCall<'_, 'info>, new_name: Option<String>, new_uri: Option<String>) -> Result<()> {
+        let mut update = UpdateV1CpiBuilder::new(self.program);
+        update
+            .asset(call.asset)
+            .collection(Some(call.collection))
+            .payer(call.payer)
+            .authority(Some(call.authority))
+            .system_program(self.system_program);
+        if let Some(name) = new_name {
+            update.new_name(name);
+        }
+        if let Some(uri) = new_uri {
+            update.new_uri(uri);
+        }
+        update.invoke_signed(call.signer_seeds)?;
+        Ok(())
+    }
+
+    fn transfer(&self, call: AssetCall<'_, 'info>, new_owner: &AccountInfo<'info>) -> Result<()> {
+        TransferV1CpiBuilder::new(self.program)
+            .asset(call.asset)
+            .collection(Some(call.collection))
+            .payer(call.payer)
+            .authority(Some(call.authority))
+            .new_owner(new_owner)
+            .system_program(Some(self.system_program))
+            .invoke_signed(call.signer_seeds)?;
+        Ok(())
+    }
+
+    fn burn(&self, call: AssetCall<'_, 'info>) -> Result<()> {
+        BurnV1CpiBuilder::new(self.program)
+            .asset(call.asset)
+            .collection(Some(call.collection))
+            .payer(call.payer)
+            .authority(Some(call.authority))
+            .system_program(Some(self.system_program))
+            .invoke_signed(call.signer_seeds)?;
+        Ok(())
+    }
+}
+
+/// 📼 One operation a `RecordingBackend` saw
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum BackendCall {
+    Create { asset: Pubkey, owner: Pubkey, name: String, uri: String, plugins: Vec<PluginAuthorityPair> },
+    UpdatePlugin { asset: Pubkey, plugin: Plugin },
+    AddPlugin { asset: Pubkey, plugin: Plugin, init_authority: Option<PluginAuthority> },
+    ApprovePluginAuthority { asset: Pubkey, plugin_type: PluginType, new_authority: PluginAuthority },
+    RemovePlugin { asset: Pubkey, plugin_type: PluginType },
+    Update { asset: Pubkey, new_name: Option<String>, new_uri: Option<String> },
+    Transfer { asset: Pubkey, new_owner: Pubkey },
+    Burn { asset: Pubkey },
+}
+
+/// 📼 Test backend: performs nothing and records every call in order
+#[derive(Default)]
+pub struct RecordingBackend {
+    pub calls: RefCell<Vec<BackendCall>>,
+}
+
+impl<'info> AssetBackend<'info> for RecordingBackend {
+    fn create(
+        &self,
+        call: AssetCall<'_, 'info>,
+        owner: &AccountInfo<'info>,
+        name: String,
+        uri: String,
+        plugins: Vec<PluginAuthorityPair>,
+    ) -> Result<()> {
+        self.calls.borrow_mut().push(BackendCall::Create {
+            asset: call.asset.key(),
+            owner: owner.key(),
+            name,
+            uri,
+            plugins,
+        });
+        Ok(())
+    }
+
+    fn update_plugin(&self, call: AssetCall<'_, 'info>, plugin: Plugin) -> Result<()> {
+        self.calls.borrow_mut().push(BackendCall::UpdatePlugin { asset: call.asset.key(), plugin });
+        Ok(())
+    }
+
+    fn add_plugin(
+        &self,
+        call: AssetCall<'_, 'info>,
+        plugin: Plugin,
+        init_authority: Option<PluginAuthority>,
+    ) -> Result<()> {
+        self.calls.borrow_mut().push(BackendCall::AddPlugin { asset: call.asset.key(), plugin, init_authority });
+        Ok(())
+    }
+
+    fn approve_plugin_authority(
+        &self,
+        call: AssetCall<'_, 'info>,
+        plugin_type: PluginType,
+        new_authority: PluginAuthority,
+    ) -> Result<()> {
+        self.calls.borrow_mut().push(BackendCall::ApprovePluginAuthority {
+            asset: call.asset.key(),
+            plugin_type,
+            new_authority,
+        });
+        Ok(())
+    }
+
+    fn remove_plugin(&self, call: AssetCall<'_, 'info>, plugin_type: PluginType) -> Result<()> {
+        self.calls.borrow_mut().push(BackendCall::RemovePlugin { asset: call.asset.key(), plugin_type });
+        Ok(())
+    }
+
+    fn update(&self, call: AssetCall<'_, 'info>, new_name: Option<String>, new_uri: Option<String>) -> Result<()> {
+        self.calls.borrow_mut().push(BackendCall::Update { asset: call.asset.key(), new_name, new_uri });
+        Ok(())
+    }
+
+    fn transfer(&self, call: AssetCall<'_, 'info>, new_owner: &AccountInfo<'info>) -> Result<()> {
+        self.calls.borrow_mut().push(BackendCall::Transfer { asset: call.asset.key(), new_owner: new_owner.key() });
+        Ok(())
+    }
+
+    fn burn(&self, call: AssetCall<'_, 'info>) -> Result<()> {
+        self.calls.borrow_mut().push(BackendCall::Burn { asset: call.asset.key() });
+        Ok(())
+    }
+}
@@ -1,9 +1,34 @@
 use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use anchor_spl::token::{self, Mint, Token, TokenAccount};
 use mpl_core::{
-    instructions::{CreateV1CpiBuilder, UpdateV1CpiBuilder},
-    types::{Attribute, Key, Plugin, PluginAuthorityPair},
+    accounts::{BaseAssetV1, BaseCollectionV1},
+    fetch_plugin,
+    instructions::{
+        AddCollectionPluginV1CpiBuilder, CreateCollectionV1CpiBuilder, UpdateCollectionPluginV1CpiBuilder,
+        UpdateCollectionV1CpiBuilder,
+    },
+    types::{
+        Attribute, Attributes, BurnDelegate, Creator, FreezeDelegate, MasterEdition, PermanentFreezeDelegate,
+        Plugin, PluginAuthority, PluginAuthorityPair, PluginType, Royalties, RuleSet,
+        TransferDelegate, UpdateAuthority, UpdateDelegate,
+    },
 };
 use anchor_lang::solana_program::clock::Clock;
+use anchor_lang::solana_program::ed25519_program;
+use anchor_lang::solana_program::hash::{hashv, Hasher};
+use anchor_lang::solana_program::incinerator;
+use anchor_lang::solana_program::instruction::{get_stack_height, Instruction, TRANSACTION_LEVEL_STACK_HEIGHT};
+use anchor_lang::solana_program::program::{get_return_data, invoke_signed};
+use anchor_lang::solana_program::sysvar::{
+    self,
+    instructions::{load_current_index_checked, load_instruction_at_checked},
+};
+
+pub mod backend;
+pub mod math;
+use backend::{AssetBackend, AssetCall, MplCore};
+use math::{bps_of, compound_bps, discounted, mul_div, pow_bps, split_bps, treasury_fee_split, Rounding, Split};
 
 declare_id!("C9PLf3qMCVqtUCJtEBy8NCcseNp3KTZwFJxAtDdN1bto");
 
@@ -12,7 +37,16 @@ pub mod nft_minter {
     use super::*;
 
     /// 🎯 Advanced NFT Minting with Dynamic Attributes
-    /// Features: Time-based rarity, fusion potential, achievement tracking
+    /// Features: Time-based rarity, fusion potential, achievement tracking,
+    /// staged launch phases (allowlist-only phases need `mint_allowlisted`).
+    /// The asset is the `["asset", collection, mint_index]` PDA, so a retried
+    /// mint lands on the same address and can never mint twice. `mint_index`
+    /// is an opaque client nonce, not a serial number: mints are counted on
+    /// sharded counters, so there is no next index to check it against. Any
+    /// unused value works; edition numbers and supply are tracked apart. An optional
+    /// `recipient` receives the asset while the payer pays and is counted.
+    /// `soulbound` mints a non-transferable badge (see `soulbound_plugin`)
+    #[allow(clippy::too_many_arguments)]
     pub fn mint_nft(
         ctx: Context<MintNFT>,
         name: String,
@@ -20,55 +54,737 @@ pub mod nft_minter {
         level: u64,
         rarity: String,
         fusion_potential: u64,
+        mint_index: u64,
+        region_attestation: Option<RegionAttestation>,
+        soulbound: bool,
     ) -> Result<()> {
-        let payer = &ctx.accounts.payer;
-        let asset = &ctx.accounts.asset;
-        let collection = &ctx.accounts.collection;
-        let clock = Clock::get()?;
-
-        // 🎲 Dynamic rarity based on mint time (more rare at specific hours)
-        let hour = (clock.unix_timestamp / 3600) % 24;
-        let dynamic_rarity = if hour == 0 || hour == 12 { "Legendary" } else { &rarity };
-
-        // 🏆 Achievement system - track minting milestones
-        let achievement_level = match level {
-            1..=10 => "Novice",
-            11..=25 => "Apprentice", 
-            26..=50 => "Expert",
-            51..=75 => "Master",
-            _ => "Grandmaster",
-        };
+        process_mint_nft(
+            ctx,
+            name,
+            uri,
+            level,
+            rarity,
+            fusion_potential,
+            mint_index,
+            region_attestation,
+            None,
+            soulbound,
+        )
+    }
 
-        // 🧬 Fusion potential affects future evolution
-        let fusion_bonus = fusion_potential * 10;
+    /// 📋 Allowlisted Minting
+    /// Features: `mint_nft` for wallets in the collection's allowlist merkle
+    /// root, proven with a `hashv([wallet, allocation])` leaf; a non-zero
+    /// allocation caps the wallet's lifetime mints. Works in any phase.
+    #[allow(clippy::too_many_arguments)]
+    pub fn mint_allowlisted(
+        ctx: Context<MintNFT>,
+        name: String,
+        uri: String,
+        level: u64,
+        rarity: String,
+        fusion_potential: u64,
+        mint_index: u64,
+        region_attestation: Option<RegionAttestation>,
+        allowlist: AllowlistProof,
+    ) -> Result<()> {
+        process_mint_nft(
+            ctx,
+            name,
+            uri,
+            level,
+            rarity,
+            fusion_potential,
+            mint_index,
+            region_attestation,
+            Some(&allowlist),
+            false,
+        )
+    }
 
-        let attributes = vec![
-            Attribute { key: "level".to_string(), value: level.to_string() },
-            Attribute { key: "rarity".to_string(), value: dynamic_rarity.to_string() },
-            Attribute { key: "mint_date".to_string(), value: clock.unix_timestamp.to_string() },
-            Attribute { key: "fusion_potential".to_string(), value: fusion_potential.to_string() },
-            Attribute { key: "achievement_level".to_string(), value: achievement_level.to_string() },
-            Attribute { key: "fusion_bonus".to_string(), value: fusion_bonus.to_string() },
-            Attribute { key: "mint_hour".to_string(), value: hour.to_string() },
-        ];
+    /// 🪙 Mint Paid in an SPL Token
+    /// Features: `mint_nft` priced in the collection's configured payment
+    /// mint (e.g. USDC) instead of lamports; the token price is transferred
+    /// to the treasury PDA's token account. Phase windows, wallet limits and
+    /// supply caps apply as usual; phase lamport prices do not.
+    pub fn mint_nft_with_token(
+        ctx: Context<MintNFTWithToken>,
+        name: String,
+        uri: String,
+        level: u64,
+        rarity: String,
+        fusion_potential: u64,
+        region_attestation: Option<RegionAttestation>,
+    ) -> Result<()> {
+        let clock = program_clock(ctx.remaining_accounts)?;
+        let now = clock.unix_timestamp;
+        let payer = ctx.accounts.payer.to_account_info();
 
-        CreateV1CpiBuilder::new(&ctx.accounts.mpl_core_program)
-            .asset(&asset)
-            .collection(Some(collection))
-            .authority(Some(&payer))
-            .owner(Some(&payer))
-            .update_authority(Some(&payer))
-            .name(name)
-            .uri(uri)
-            .plugins(vec![PluginAuthorityPair {
-                plugin: Plugin::Attributes { attributes },
-                authority: None,
-            }])
-            .invoke()?;
+        enforce_region_lock(
+            &ctx.accounts.region_lock,
+            &ctx.accounts.instructions_sysvar,
+            payer.key,
+            ctx.accounts.collection.key,
+            region_attestation,
+            now,
+        )?;
+        enforce_single_mint(&ctx.accounts.collection_config.to_account_info(), &ctx.accounts.instructions_sysvar)?;
+
+        let lucky = ctx.accounts.player_profile.take_mint_luck(payer.key(), ctx.bumps.player_profile, now);
+        ctx.accounts.mint_receipt.bind(ctx.accounts.collection.key(), payer.key(), ctx.bumps.mint_receipt);
+        let terms = apply_collection_config(
+            &ctx.accounts.collection_config.to_account_info(),
+            &ctx.accounts.mint_shard,
+            &mut ctx.accounts.mint_receipt,
+            1,
+            now,
+            None,
+        )?;
+        let achievement_tiers = load_if_initialized::<AchievementTiers>(&ctx.accounts.achievement_tiers)?;
+        let edition = claim_editions(
+            &ctx.accounts.collection_config.to_account_info(),
+            ctx.accounts.edition_counter.as_deref_mut(),
+            1,
+        )?;
+
+        // 🪙 Token price instead of the lamport price
+        let token_price = ctx.accounts.collection_config.token_price;
+        if token_price > 0 {
+            token::transfer(
+                CpiContext::new(
+                    ctx.accounts.token_program.to_account_info(),
+                    token::Transfer {
+                        from: ctx.accounts.payer_token_account.to_account_info(),
+                        to: ctx.accounts.treasury_token_account.to_account_info(),
+                        authority: payer.clone(),
+                    },
+                ),
+                token_price,
+            )?;
+        }
+        ctx.accounts.economy_ledger.record_tokens_collected(token_price)?;
+        // 💎 VIP tiers are in lamports: count the lamport price the token payment stood in for
+        ctx.accounts
+            .player_profile
+            .record_spend(payer.key(), ctx.bumps.player_profile, terms.price_lamports)?;
+
+        // ✍️ Collections handed to the program are signed for by their authority PDA
+        let collection_key = ctx.accounts.collection.key();
+        let authority_bump = [ctx.bumps.collection_authority];
+        let authority_seeds: &[&[u8]] = &[b"collection_authority", collection_key.as_ref(), &authority_bump];
+        let program_signer = program_signer(&ctx.accounts.collection, &ctx.accounts.collection_authority, authority_seeds)?;
+
+        let minted_rarity = mint_core_asset(
+            &MplCore::new(&ctx.accounts.mpl_core_program, &ctx.accounts.system_program.to_account_info()),
+            &ctx.accounts.asset,
+            &ctx.accounts.collection,
+            &payer,
+            name,
+            uri,
+            level,
+            &rarity,
+            fusion_potential,
+            lucky,
+            terms.plugins,
+            edition,
+            program_signer,
+            None,
+            None,
+            achievement_tiers.as_ref(),
+            &ctx.accounts.program_config,
+            &clock,
+        )?;
+        ctx.accounts.signal_board.record(
+            SignalKind::Mint,
+            ctx.accounts.asset.key(),
+            level,
+            &minted_rarity,
+            &program_clock(ctx.remaining_accounts)?,
+        );
+
+        msg!("🪙 Minted for {} of {}", token_price, ctx.accounts.collection_config.payment_mint);
+
+        Ok(())
+    }
+
+    /// 🔥 Burn-to-Mint
+    /// Features: The mint cost is burning the collection's configured amount
+    /// of its whitelist token, or one asset from its burn collection, instead
+    /// of lamports. Pass either the token mint and account or the asset, its
+    /// collection and its NftState PDA, which is closed to the payer. Phase
+    /// windows, wallet limits and supply caps apply as usual.
+    pub fn mint_with_burn(
+        ctx: Context<MintWithBurn>,
+        name: String,
+        uri: String,
+        level: u64,
+        rarity: String,
+        fusion_potential: u64,
+        region_attestation: Option<RegionAttestation>,
+    ) -> Result<()> {
+        let clock = program_clock(ctx.remaining_accounts)?;
+        let now = clock.unix_timestamp;
+        let payer = ctx.accounts.payer.to_account_info();
+
+        enforce_region_lock(
+            &ctx.accounts.region_lock,
+            &ctx.accounts.instructions_sysvar,
+            payer.key,
+            ctx.accounts.collection.key,
+            region_attestation,
+            now,
+        )?;
+        enforce_single_mint(&ctx.accounts.collection_config.to_account_info(), &ctx.accounts.instructions_sysvar)?;
+
+        let lucky = ctx.accounts.player_profile.take_mint_luck(payer.key(), ctx.bumps.player_profile, now);
+        ctx.accounts.mint_receipt.bind(ctx.accounts.collection.key(), payer.key(), ctx.bumps.mint_receipt);
+        let terms = apply_collection_config(
+            &ctx.accounts.collection_config.to_account_info(),
+            &ctx.accounts.mint_shard,
+            &mut ctx.accounts.mint_receipt,
+            1,
+            now,
+            None,
+        )?;
+        let achievement_tiers = load_if_initialized::<AchievementTiers>(&ctx.accounts.achievement_tiers)?;
+        let edition = claim_editions(
+            &ctx.accounts.collection_config.to_account_info(),
+            ctx.accounts.edition_counter.as_deref_mut(),
+            1,
+        )?;
+
+        // 🔥 Burn the cost: whitelist tokens or an eligible asset
+        let mut burned_checkpoint = None;
+        let config = &ctx.accounts.collection_config;
+        let accounts = &ctx.accounts;
+        match (
+            &accounts.burn_token_mint,
+            &accounts.burn_token_account,
+            &accounts.burn_asset,
+            &accounts.burn_asset_collection,
+            &accounts.burn_asset_state,
+        ) {
+            (Some(burn_mint), Some(burn_account), None, None, None) => {
+                require!(
+                    config.burn_token_amount > 0 && burn_mint.key() == config.burn_token_mint,
+                    NftError::InvalidBurnPayment
+                );
+                require!(
+                    burn_account.mint == burn_mint.key() && burn_account.owner == payer.key(),
+                    NftError::InvalidBurnPayment
+                );
+                token::burn(
+                    CpiContext::new(
+                        accounts.token_program.to_account_info(),
+                        token::Burn {
+                            mint: burn_mint.to_account_info(),
+                            from: burn_account.to_account_info(),
+                            authority: payer.clone(),
+                        },
+                    ),
+                    config.burn_token_amount,
+                )?;
+                msg!("🔥 Burned {} of {}", config.burn_token_amount, burn_mint.key());
+            }
+            (None, None, Some(burn_asset), Some(burn_collection), Some(burn_state)) => {
+                require!(
+                    config.burn_asset_collection != Pubkey::default()
+                        && burn_collection.key() == config.burn_asset_collection,
+                    NftError::InvalidBurnPayment
+                );
+                load_owned_asset(burn_asset, payer.key)?;
+                verify_collection_membership(burn_asset, &burn_collection.key())?;
+                let (expected, _) =
+                    Pubkey::find_program_address(&[b"nft_state", burn_asset.key.as_ref()], &crate::ID);
+                require_keys_eq!(burn_state.key(), expected, NftError::InvalidNftState);
+                let state = load_if_initialized::<NftState>(burn_state)?.unwrap_or_default();
+                require!(state.lock_state == LockState::Unlocked, NftError::AssetLocked);
+                burned_checkpoint = Some((burn_asset.key(), state, hash_attributes(&fetch_attributes(burn_asset)?)));
+                MplCore::new(&accounts.mpl_core_program, &accounts.system_program.to_account_info())
+                    .burn(AssetCall::new(burn_asset, burn_collection, &payer, &payer))?;
+                // 🧹 The burned asset's state goes with it, rent back to the payer
+                if burn_state.owner == &crate::ID {
+                    close_program_account(burn_state, &payer)?;
+                }
+                msg!("🔥 Burned asset {}", burn_asset.key());
+            }
+            _ => return err!(NftError::InvalidBurnPayment),
+        }
+        // 📸 A burned asset leaves its checkpoint in its history
+        if let Some((burned, state, attributes_hash)) = burned_checkpoint {
+            let history = ctx.accounts.burn_asset_history.as_mut().ok_or(NftError::AssetHistoryRequired)?;
+            require_keys_eq!(history.asset, burned, NftError::AssetHistoryRequired);
+            history.record(CheckpointOperation::Burn, &state, attributes_hash, now);
+        }
+
+        // ✍️ Collections handed to the program are signed for by their authority PDA
+        let collection_key = ctx.accounts.collection.key();
+        let authority_bump = [ctx.bumps.collection_authority];
+        let authority_seeds: &[&[u8]] = &[b"collection_authority", collection_key.as_ref(), &authority_bump];
+        let program_signer = program_signer(&ctx.accounts.collection, &ctx.accounts.collection_authority, authority_seeds)?;
+
+        let minted_rarity = mint_core_asset(
+            &MplCore::new(&ctx.accounts.mpl_core_program, &ctx.accounts.system_program.to_account_info()),
+            &ctx.accounts.asset,
+            &ctx.accounts.collection,
+            &payer,
+            name,
+            uri,
+            level,
+            &rarity,
+            fusion_potential,
+            lucky,
+            terms.plugins,
+            edition,
+            program_signer,
+            None,
+            None,
+            achievement_tiers.as_ref(),
+            &ctx.accounts.program_config,
+            &clock,
+        )?;
+        ctx.accounts.signal_board.record(
+            SignalKind::Mint,
+            ctx.accounts.asset.key(),
+            level,
+            &minted_rarity,
+            &program_clock(ctx.remaining_accounts)?,
+        );
+
+        Ok(())
+    }
+
+    /// 📦 Mint Several NFTs in One Transaction
+    /// Features: Up to MAX_BATCH_MINT assets, passed as (asset, nft_state)
+    /// remaining account pairs; each gets its NftState created alongside it
+    pub fn mint_nft_batch<'info>(
+        ctx: Context<'_, '_, 'info, 'info, MintNFTBatch<'info>>,
+        mints: Vec<BatchMintParams>,
+        region_attestation: Option<RegionAttestation>,
+    ) -> Result<()> {
+        let clock = program_clock(ctx.remaining_accounts)?;
+        enforce_single_mint(&ctx.accounts.collection_config, &ctx.accounts.instructions_sysvar)?;
+        let payer = ctx.accounts.payer.to_account_info();
+        let system_program = ctx.accounts.system_program.to_account_info();
+        let pairs = without_mock_clock(ctx.remaining_accounts);
+        require!(
+            !mints.is_empty() && mints.len() <= MAX_BATCH_MINT && pairs.len() == mints.len() * 2,
+            NftError::InvalidBatchMint
+        );
+
+        enforce_region_lock(
+            &ctx.accounts.region_lock,
+            &ctx.accounts.instructions_sysvar,
+            payer.key,
+            ctx.accounts.collection.key,
+            region_attestation,
+            clock.unix_timestamp,
+        )?;
+
+        // 🍀 A full charge of streak luck turns the mint Legendary
+        let lucky = ctx.accounts.player_profile.take_mint_luck(payer.key(), ctx.bumps.player_profile, clock.unix_timestamp);
+        // 🏛️ One supply, price and plugin check covers the whole batch
+        ctx.accounts.mint_receipt.bind(ctx.accounts.collection.key(), payer.key(), ctx.bumps.mint_receipt);
+        let plugins = charge_collection_mint(
+            &ctx.accounts.collection_config,
+            &ctx.accounts.mint_shard,
+            &mut ctx.accounts.mint_receipt,
+            &payer,
+            &ctx.accounts.treasury.to_account_info(),
+            &system_program,
+            &mut ctx.accounts.economy_ledger,
+            mints.len() as u32,
+            clock.unix_timestamp,
+            None,
+        )?;
+        let achievement_tiers = load_if_initialized::<AchievementTiers>(&ctx.accounts.achievement_tiers)?;
+        let edition = claim_editions(
+            &ctx.accounts.collection_config,
+            ctx.accounts.edition_counter.as_deref_mut(),
+            mints.len() as u32,
+        )?;
+
+        for (i, (params, pair)) in mints.into_iter().zip(pairs.chunks_exact(2)).enumerate() {
+            let (asset, state_info) = (&pair[0], &pair[1]);
+            require!(asset.is_signer, NftError::InvalidBatchMint);
+            let level = params.level;
+            let fusion_potential = params.fusion_potential;
+
+            let minted_rarity = mint_core_asset(
+                &MplCore::new(&ctx.accounts.mpl_core_program, &system_program),
+                asset,
+                &ctx.accounts.collection,
+                &payer,
+                params.name,
+                params.uri,
+                level,
+                &params.rarity,
+                fusion_potential,
+                lucky && i == 0,
+                plugins.clone(),
+                edition.map(|edition| edition.nth(i as u32)),
+                None,
+                None,
+                None,
+                achievement_tiers.as_ref(),
+                &ctx.accounts.program_config,
+                &clock,
+            )?;
+
+            let bump = create_nft_state_account(state_info, asset.key, &payer, &system_program)?;
+            let mut nft_state = NftState::default();
+            nft_state.bind_asset(asset.key(), bump)?;
+            nft_state.bind_collection(ctx.accounts.collection.key())?;
+            nft_state.attributes_hash = hash_attributes(&fetch_attributes(asset)?);
+            nft_state.level = level;
+            nft_state.rarity = Rarity::from_name(&minted_rarity).unwrap_or_default();
+            nft_state.fusion_potential = u32::try_from(fusion_potential).unwrap_or(u32::MAX);
+            nft_state.mint_date = clock.unix_timestamp;
+            nft_state.last_updated = clock.unix_timestamp;
+            nft_state.rule_versions = ctx.accounts.program_config.rule_versions;
+            nft_state.try_serialize(&mut &mut state_info.try_borrow_mut_data()?[..])?;
+
+            ctx.accounts.signal_board.record(SignalKind::Mint, asset.key(), level, &minted_rarity, &clock);
+        }
+
+        msg!("📦 Batch minted {} NFTs", pairs.len() / 2);
+
+        Ok(())
+    }
+
+    /// 🧾 Quote-Based Minting for Dynamic Pricing Experiments
+    /// Features: Backend-signed (wallet, collection, price, expiry, nonce), ed25519 verification, replay protection
+    #[allow(clippy::too_many_arguments)]
+    pub fn mint_nft_with_quote(
+        ctx: Context<MintNFTWithQuote>,
+        name: String,
+        uri: String,
+        level: u64,
+        rarity: String,
+        fusion_potential: u64,
+        price_lamports: u64,
+        expires_at: i64,
+        nonce: u64,
+    ) -> Result<()> {
+        let clock = program_clock(ctx.remaining_accounts)?;
+        enforce_single_mint(&ctx.accounts.collection_config, &ctx.accounts.instructions_sysvar)?;
+        let payer = ctx.accounts.payer.to_account_info();
+        let quote_signer = ctx.accounts.program_config.quote_signer;
+
+        require!(quote_signer != Pubkey::default(), NftError::QuotesDisabled);
+        require!(clock.unix_timestamp <= expires_at, NftError::QuoteExpired);
+
+        // ✍️ The preceding ed25519 instruction must sign exactly this quote
+        verify_ed25519_instruction(
+            &ctx.accounts.instructions_sysvar,
+            &quote_signer,
+            &quote_message(payer.key, ctx.accounts.collection.key, price_lamports, expires_at, nonce),
+        )?;
+
+        // 💸 Charge exactly the quoted price
+        if price_lamports > 0 {
+            system_program::transfer(
+                CpiContext::new(
+                    ctx.accounts.system_program.to_account_info(),
+                    system_program::Transfer {
+                        from: payer.clone(),
+                        to: ctx.accounts.treasury.to_account_info(),
+                    },
+                ),
+                price_lamports,
+            )?;
+        }
+
+        ctx.accounts.economy_ledger.record_collected(price_lamports)?;
+        ctx.accounts
+            .player_profile
+            .record_spend(payer.key(), ctx.bumps.player_profile, price_lamports)?;
+
+        // 🍀 A full charge of streak luck turns the mint Legendary
+        let lucky = ctx.accounts.player_profile.take_mint_luck(payer.key(), ctx.bumps.player_profile, clock.unix_timestamp);
+        // 🏛️ The quote replaces the collection's mint price, but not its supply cap or plugins
+        ctx.accounts.mint_receipt.bind(ctx.accounts.collection.key(), payer.key(), ctx.bumps.mint_receipt);
+        let plugins = apply_collection_config(
+            &ctx.accounts.collection_config,
+            &ctx.accounts.mint_shard,
+            &mut ctx.accounts.mint_receipt,
+            1,
+            clock.unix_timestamp,
+            None,
+        )?
+        .plugins;
+        let achievement_tiers = load_if_initialized::<AchievementTiers>(&ctx.accounts.achievement_tiers)?;
+        let edition = claim_editions(
+            &ctx.accounts.collection_config,
+            ctx.accounts.edition_counter.as_deref_mut(),
+            1,
+        )?;
+
+        let receipt = &mut ctx.accounts.quote_receipt;
+        receipt.wallet = payer.key();
+        receipt.nonce = nonce;
+        receipt.price_lamports = price_lamports;
+        receipt.used_at = clock.unix_timestamp;
+        receipt.bump = ctx.bumps.quote_receipt;
+
+        let minted_rarity = mint_core_asset(
+            &MplCore::new(&ctx.accounts.mpl_core_program, &ctx.accounts.system_program.to_account_info()),
+            &ctx.accounts.asset,
+            &ctx.accounts.collection,
+            &payer,
+            name,
+            uri,
+            level,
+            &rarity,
+            fusion_potential,
+            lucky,
+            plugins,
+            edition,
+            None,
+            None,
+            None,
+            achievement_tiers.as_ref(),
+            &ctx.accounts.program_config,
+            &clock,
+        )?;
+        ctx.accounts.signal_board.record(
+            SignalKind::Mint,
+            ctx.accounts.asset.key(),
+            level,
+            &minted_rarity,
+            &program_clock(ctx.remaining_accounts)?,
+        );
+
+        let max_pages = ctx.accounts.program_config.max_history_pages;
+        ctx.accounts.player_profile.record_action(
+            &mut ctx.accounts.player_history_page,
+            ctx.bumps.player_history_page,
+            max_pages,
+            PlayerAction::QuotedMint,
+            ctx.accounts.asset.key(),
+            clock.unix_timestamp,
+        )?;
+
+        msg!("🧾 Quote #{} honored at {} lamports", nonce, price_lamports);
+
+        Ok(())
+    }
+
+    /// 🎟️ Gasless Minting with a Signed Voucher
+    /// Features: Backend-signed (name, uri, recipient, collection, expiry, nonce)
+    /// voucher, ed25519 verification, replay protection; a relayer pays every
+    /// fee, including the collection's mint price, and the asset is minted
+    /// straight to the recipient
+    pub fn mint_with_voucher(ctx: Context<MintWithVoucher>, voucher: MintVoucher) -> Result<()> {
+        let clock = program_clock(ctx.remaining_accounts)?;
+        enforce_single_mint(&ctx.accounts.collection_config, &ctx.accounts.instructions_sysvar)?;
+        let voucher_signer = ctx.accounts.program_config.voucher_signer;
+
+        require!(voucher_signer != Pubkey::default(), NftError::VouchersDisabled);
+        require!(clock.unix_timestamp <= voucher.expires_at, NftError::VoucherExpired);
+
+        // ✍️ The preceding ed25519 instruction must sign exactly this voucher
+        verify_ed25519_instruction(
+            &ctx.accounts.instructions_sysvar,
+            &voucher_signer,
+            &voucher.message(),
+        )?;
+
+        // 🏛️ The recipient's limits apply; the relayer pays the collection's mint price
+        let recipient = ctx.accounts.recipient.to_account_info();
+        ctx.accounts.mint_receipt.bind(ctx.accounts.collection.key(), recipient.key(), ctx.bumps.mint_receipt);
+        let plugins = charge_collection_mint(
+            &ctx.accounts.collection_config,
+            &ctx.accounts.mint_shard,
+            &mut ctx.accounts.mint_receipt,
+            &ctx.accounts.payer.to_account_info(),
+            &ctx.accounts.treasury.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            &mut ctx.accounts.economy_ledger,
+            1,
+            clock.unix_timestamp,
+            None,
+        )?;
+        let achievement_tiers = load_if_initialized::<AchievementTiers>(&ctx.accounts.achievement_tiers)?;
+        let edition = claim_editions(
+            &ctx.accounts.collection_config,
+            ctx.accounts.edition_counter.as_deref_mut(),
+            1,
+        )?;
+
+        let receipt = &mut ctx.accounts.voucher_receipt;
+        receipt.wallet = recipient.key();
+        receipt.nonce = voucher.nonce;
+        receipt.price_lamports = 0;
+        receipt.used_at = clock.unix_timestamp;
+        receipt.bump = ctx.bumps.voucher_receipt;
+
+        // ✍️ Collections handed to the program are signed for by their authority PDA
+        let collection_key = ctx.accounts.collection.key();
+        let authority_bump = [ctx.bumps.collection_authority];
+        let authority_seeds: &[&[u8]] = &[b"collection_authority", collection_key.as_ref(), &authority_bump];
+        let program_signer = program_signer(&ctx.accounts.collection, &ctx.accounts.collection_authority, authority_seeds)?;
+
+        let minted_rarity = mint_core_asset(
+            &MplCore::new(&ctx.accounts.mpl_core_program, &ctx.accounts.system_program.to_account_info()),
+            &ctx.accounts.asset,
+            &ctx.accounts.collection,
+            &ctx.accounts.payer.to_account_info(),
+            voucher.name,
+            voucher.uri,
+            1,
+            "Common",
+            0,
+            false,
+            plugins,
+            edition,
+            program_signer,
+            None,
+            Some(&recipient),
+            achievement_tiers.as_ref(),
+            &ctx.accounts.program_config,
+            &clock,
+        )?;
+        ctx.accounts.signal_board.record(
+            SignalKind::Mint,
+            ctx.accounts.asset.key(),
+            1,
+            &minted_rarity,
+            &clock,
+        );
+
+        msg!("🎟️ Voucher #{} minted {} to {}", voucher.nonce, ctx.accounts.asset.key(), recipient.key());
+
+        Ok(())
+    }
+
+    /// 🎖️ Holder-Tier Mint Phase Setup (admin)
+    /// Features: Gate a drop on holding ≥N assets at ≥X level from a prior collection
+    pub fn create_holder_phase(
+        ctx: Context<CreateHolderPhase>,
+        prior_collection: Pubkey,
+        min_assets: u8,
+        min_level: u64,
+        starts_at: i64,
+        ends_at: i64,
+    ) -> Result<()> {
+        require!(min_assets > 0, NftError::InvalidPhaseConfig);
+        require!(ends_at > starts_at, NftError::InvalidPhaseConfig);
+
+        let phase = &mut ctx.accounts.holder_phase;
+        phase.collection = ctx.accounts.collection.key();
+        phase.prior_collection = prior_collection;
+        phase.min_assets = min_assets;
+        phase.min_level = min_level;
+        phase.starts_at = starts_at;
+        phase.ends_at = ends_at;
+        phase.minted = 0;
+        phase.bump = ctx.bumps.holder_phase;
+
+        msg!("🎖️ Holder phase: hold {} assets at level {}+ from {}",
+             min_assets, min_level, prior_collection);
+
+        Ok(())
+    }
+
+    /// 🎖️ Holder-Tier Minting
+    /// Features: Holdings proven via (asset, nft_state) pairs in remaining_accounts
+    pub fn mint_nft_holder_phase<'info>(
+        ctx: Context<'_, '_, 'info, 'info, MintNFTHolderPhase<'info>>,
+        name: String,
+        uri: String,
+        level: u64,
+        rarity: String,
+        fusion_potential: u64,
+    ) -> Result<()> {
+        let clock = program_clock(ctx.remaining_accounts)?;
+        enforce_single_mint(&ctx.accounts.collection_config, &ctx.accounts.instructions_sysvar)?;
+        let payer = ctx.accounts.payer.to_account_info();
+        let phase = &ctx.accounts.holder_phase;
+
+        require!(
+            clock.unix_timestamp >= phase.starts_at && clock.unix_timestamp < phase.ends_at,
+            NftError::PhaseNotActive
+        );
+
+        // 🔍 Count distinct qualifying assets from the prior collection
+        let holdings = without_mock_clock(ctx.remaining_accounts);
+        require!(holdings.len().is_multiple_of(2), NftError::InvalidRemainingAccounts);
+        let mut qualifying: Vec<Pubkey> = Vec::new();
+        for pair in holdings.chunks(2) {
+            let (asset, state_info) = (&pair[0], &pair[1]);
+            let base_asset = load_owned_asset(asset, payer.key)?;
+            if asset_collection(&base_asset) != Some(phase.prior_collection)
+                || qualifying.contains(asset.key)
+            {
+                continue;
+            }
+            let nft_state = load_nft_state(state_info, asset.key)?;
+            if nft_state.level >= phase.min_level {
+                qualifying.push(asset.key());
+            }
+        }
+        require!(
+            qualifying.len() >= phase.min_assets as usize,
+            NftError::HolderRequirementNotMet
+        );
+
+        // 🍀 A full charge of streak luck turns the mint Legendary
+        let lucky = ctx.accounts.player_profile.take_mint_luck(payer.key(), ctx.bumps.player_profile, clock.unix_timestamp);
+        // 🏛️ Program-created collections cap supply, price and plugin bundle
+        ctx.accounts.mint_receipt.bind(ctx.accounts.collection.key(), payer.key(), ctx.bumps.mint_receipt);
+        let plugins = charge_collection_mint(
+            &ctx.accounts.collection_config,
+            &ctx.accounts.mint_shard,
+            &mut ctx.accounts.mint_receipt,
+            &payer,
+            &ctx.accounts.treasury.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            &mut ctx.accounts.economy_ledger,
+            1,
+            clock.unix_timestamp,
+            None,
+        )?;
+        let achievement_tiers = load_if_initialized::<AchievementTiers>(&ctx.accounts.achievement_tiers)?;
+        let edition = claim_editions(
+            &ctx.accounts.collection_config,
+            ctx.accounts.edition_counter.as_deref_mut(),
+            1,
+        )?;
+
+        let minted_rarity = mint_core_asset(
+            &MplCore::new(&ctx.accounts.mpl_core_program, &ctx.accounts.system_program.to_account_info()),
+            &ctx.accounts.asset,
+            &ctx.accounts.collection,
+            &payer,
+            name,
+            uri,
+            level,
+            &rarity,
+            fusion_potential,
+            lucky,
+            plugins,
+            edition,
+            None,
+            None,
+            None,
+            achievement_tiers.as_ref(),
+            &ctx.accounts.program_config,
+            &clock,
+        )?;
+        ctx.accounts.signal_board.record(
+            SignalKind::Mint,
+            ctx.accounts.asset.key(),
+            level,
+            &minted_rarity,
+            &program_clock(ctx.remaining_accounts)?,
+        );
+
+        let phase = &mut ctx.accounts.holder_phase;
+        phase.minted += 1;
+
+        msg!("🎖️ Holder mint with {} qualifying assets", qualifying.len());
 
-        msg!("🎉 NFT minted with {} rarity at hour {}!", dynamic_rarity, hour);
-        msg!("🏆 Achievement: {} | Fusion Potential: {}", achievement_level, fusion_potential);
-        
         Ok(())
     }
 
@@ -80,42 +796,54 @@ pub mod nft_minter {
         min_time_elapsed: i64,
         new_rarity: Option<String>,
     ) -> Result<()> {
-        let clock = Clock::get()?;
+        let clock = program_clock(ctx.remaining_accounts)?;
         let current_time = clock.unix_timestamp;
+        let new_rarity = new_rarity
+            .map(|name| Rarity::from_name(&name).ok_or(NftError::InvalidRarity))
+            .transpose()?;
+        authorize_asset_action(
+            &ctx.accounts.asset,
+            &ctx.accounts.payer.key(),
+            ctx.accounts.delegation.as_ref(),
+            DelegatedAction::Update,
+        )?;
+        load_owned_asset(&ctx.accounts.asset, ctx.accounts.owner.key)?;
         let nft_state = &mut ctx.accounts.nft_state;
+        require!(nft_state.lock_state == LockState::Unlocked, NftError::AssetLocked);
 
-        // ⏰ Cooldown system with rarity-based timing
-        let cooldown_multiplier = match nft_state.rarity.as_str() {
-            "Common" => 1,
-            "Uncommon" => 2,
-            "Rare" => 3,
-            "Epic" => 4,
-            "Legendary" => 5,
-            _ => 6,
+        // 🏷️ Refuse assets from any collection other than this state's own
+        verify_collection_membership(&ctx.accounts.asset, &ctx.accounts.collection.key())?;
+        nft_state.bind_collection(ctx.accounts.collection.key())?;
+
+        // ⏰ Cooldown system with rarity-based timing, never below the collection's floor
+        let base_cooldown = match load_if_initialized::<CollectionConfig>(&ctx.accounts.collection_config)? {
+            Some(config) => min_time_elapsed.max(config.min_update_cooldown),
+            None => min_time_elapsed,
         };
-        
-        let required_cooldown = min_time_elapsed * cooldown_multiplier;
-        require!(
-            current_time >= nft_state.last_updated + required_cooldown,
-            NftError::UpdateTooSoon
-        );
+        let cooldown_multiplier = cooldown_multiplier(nft_state.rarity);
+        let mut required_cooldown = base_cooldown * cooldown_multiplier;
+        if let Some(event) = active_global_event(&ctx.accounts.global_event, current_time)? {
+            required_cooldown = event.shorten_cooldown(required_cooldown);
+            event.announce(ctx.accounts.asset.key());
+        }
+        let ready_at = nft_state.last_updated + required_cooldown;
+        if current_time < ready_at {
+            return Err(fail_with_context(NftError::UpdateTooSoon, ready_at, current_time));
+        }
 
         // 📈 Progressive level validation
-        require!(
-            new_level > nft_state.level,
-            NftError::InvalidLevelProgression
-        );
+        if new_level <= nft_state.level {
+            return Err(fail_with_context(NftError::InvalidLevelProgression, nft_state.level + 1, new_level));
+        }
 
         // 🎁 Rarity-based rewards
-        let reward_multiplier = match nft_state.rarity.as_str() {
-            "Common" => 1,
-            "Uncommon" => 2,
-            "Rare" => 3,
-            "Epic" => 4,
-            "Legendary" => 5,
-            _ => 6,
-        };
+        let reward_multiplier = nft_state.rarity.reward_multiplier();
 
+        // 🧢 Levels past the cap become the owner's prestige instead
+        let (new_level, excess_levels) = ctx.accounts.program_config.cap_level(nft_state.level, new_level);
+        if excess_levels > 0 {
+            checkpoint_prestige(&mut ctx.accounts.asset_history, nft_state, &ctx.accounts.asset, current_time)?;
+        }
         let level_gain = new_level - nft_state.level;
         let bonus_experience = level_gain * reward_multiplier;
 
@@ -129,25 +857,46 @@ pub mod nft_minter {
         if let Some(rarity) = new_rarity {
             new_attributes.push(Attribute {
                 key: "rarity".to_string(),
-                value: rarity,
+                value: rarity.to_string(),
             });
         }
+        let new_attributes = merge_attributes(&ctx.accounts.program_config, &ctx.accounts.asset, new_attributes)?;
 
-        UpdateV1CpiBuilder::new(&ctx.accounts.mpl_core_program)
-            .asset(&ctx.accounts.asset)
-            .authority(&ctx.accounts.payer)
-            .new_update_authority(None)
-            .plugins(vec![PluginAuthorityPair {
-                plugin: Plugin::Attributes { attributes: new_attributes },
-                authority: None,
-            }])
-            .invoke()?;
+        // ✍️ Collections handed to the program are signed for by their authority PDA
+        let collection_key = ctx.accounts.collection.key();
+        let authority_bump = [ctx.bumps.collection_authority];
+        let authority_seeds: &[&[u8]] = &[b"collection_authority", collection_key.as_ref(), &authority_bump];
+        let payer = ctx.accounts.payer.to_account_info();
+        let program_signer =
+            program_signer(&ctx.accounts.collection, &ctx.accounts.collection_authority, authority_seeds)?;
+        let authority = program_signer.map_or(&payer, |signer| signer.authority);
+        let signer_seeds: Vec<&[&[u8]]> = program_signer.iter().map(|signer| signer.seeds).collect();
+
+        if ctx.accounts.program_config.attribute_diff_events.enabled(RuleAction::Update) {
+            let previous_attributes = fetch_attributes(&ctx.accounts.asset)?;
+            emit_attribute_diff(ctx.accounts.asset.key(), RuleAction::Update, &previous_attributes, &new_attributes);
+        }
+
+        nft_state.attributes_hash = write_attributes_signed(
+            &MplCore::new(&ctx.accounts.mpl_core_program, &ctx.accounts.system_program.to_account_info()),
+            &ctx.accounts.asset,
+            &ctx.accounts.collection,
+            &payer,
+            authority,
+            AttributesAuthority::new(&ctx.accounts.attributes_authority, ctx.bumps.attributes_authority),
+            new_attributes,
+            &signer_seeds,
+        )?;
 
         nft_state.level = new_level;
         nft_state.last_updated = current_time;
         if let Some(rarity) = new_rarity {
             nft_state.rarity = rarity;
         }
+        nft_state.apply_rules(RuleAction::Update, ctx.accounts.program_config.rule_versions);
+
+        ctx.accounts.player_profile.bind(ctx.accounts.owner.key(), ctx.bumps.player_profile);
+        overflow_to_prestige(&mut ctx.accounts.player_profile, ctx.accounts.asset.key(), excess_levels);
 
         msg!("🚀 NFT updated! Level: {} | Bonus XP: {} | Cooldown: {}x", 
              new_level, bonus_experience, cooldown_multiplier);
@@ -155,74 +904,198 @@ pub mod nft_minter {
         Ok(())
     }
 
-    /// 🌟 Advanced NFT Evolution with Fusion Mechanics
-    /// Features: Time-based evolution, fusion potential, rarity progression
-    pub fn evolve_nft(ctx: Context<EvolveNFT>) -> Result<()> {
-        let clock = Clock::get()?;
+    /// ✏️ Rename an NFT or point it at new metadata
+    /// Features: Optional provenance lock once the NFT has evolved
+    pub fn rename_nft(
+        ctx: Context<RenameNFT>,
+        new_name: Option<String>,
+        new_uri: Option<String>,
+    ) -> Result<()> {
+        let payer = ctx.accounts.payer.to_account_info();
+        let nft_state = &ctx.accounts.nft_state;
+
+        require!(new_name.is_some() || new_uri.is_some(), NftError::NothingToUpdate);
+
+        // 🔏 Collectors value stable identifiers once an NFT has evolved
+        require!(
+            !(ctx.accounts.program_config.lock_identity_after_evolution
+                && nft_state.evolution_count > 0),
+            NftError::IdentityLocked
+        );
+
+        // ✍️ Collections handed to the program are signed for by their authority PDA,
+        // but only on behalf of the asset's owner or one of its delegates
+        let collection_key = ctx.accounts.collection.key();
+        let authority_bump = [ctx.bumps.collection_authority];
+        let authority_seeds: &[&[u8]] = &[b"collection_authority", collection_key.as_ref(), &authority_bump];
+        let program_signer = program_signer(&ctx.accounts.collection, &ctx.accounts.collection_authority, authority_seeds)?;
+        let signer_seeds = [authority_seeds];
+        let call = match program_signer {
+            Some(signer) => {
+                authorize_asset_action(
+                    &ctx.accounts.asset,
+                    &payer.key(),
+                    ctx.accounts.delegation.as_ref(),
+                    DelegatedAction::Update,
+                )?;
+                AssetCall::new(&ctx.accounts.asset, &ctx.accounts.collection, &payer, signer.authority)
+                    .signed(&signer_seeds)
+            }
+            None => AssetCall::new(&ctx.accounts.asset, &ctx.accounts.collection, &payer, &payer),
+        };
+
+        let system_program = ctx.accounts.system_program.to_account_info();
+        MplCore::new(&ctx.accounts.mpl_core_program, &system_program).update(
+            call,
+            new_name.clone(),
+            new_uri.clone(),
+        )?;
+
+        msg!("✏️ NFT renamed | Name: {:?} | URI: {:?}", new_name, new_uri);
+
+        Ok(())
+    }
+
+    /// 🎲 Evolution - Commit
+    /// Features: Time-based readiness check, locks the NFT and commits its
+    /// evolution roll to a future slot; `evolve_nft` reveals it once that
+    /// slot has passed
+    pub fn commit_evolution(ctx: Context<CommitEvolution>) -> Result<()> {
+        let clock = program_clock(ctx.remaining_accounts)?;
         let current_time = clock.unix_timestamp;
+        authorize_asset_action(
+            &ctx.accounts.asset,
+            &ctx.accounts.payer.key(),
+            ctx.accounts.delegation.as_ref(),
+            DelegatedAction::Evolve,
+        )?;
         let nft_state = &mut ctx.accounts.nft_state;
+        require!(nft_state.lock_state == LockState::Unlocked, NftError::AssetLocked);
+
+        verify_collection_membership(&ctx.accounts.asset, &ctx.accounts.collection.key())?;
+        nft_state.bind_collection(ctx.accounts.collection.key())?;
 
         // ⏱️ Time-based evolution with fusion bonus
         let base_evolution_time = nft_state.level * 86400; // 1 day per level
-        let fusion_bonus = nft_state.fusion_potential * 3600; // 1 hour per fusion point
-        let total_required_time = base_evolution_time - fusion_bonus;
+        let fusion_bonus = nft_state.fusion_potential as u64 * 3600; // 1 hour per fusion point
+        let total_required_time = (base_evolution_time - fusion_bonus) as i64;
         
         let time_since_mint = current_time - nft_state.mint_date;
-        require!(
-            time_since_mint >= total_required_time,
-            NftError::EvolutionNotReady
-        );
+        if time_since_mint < total_required_time {
+            return Err(fail_with_context(NftError::EvolutionNotReady, total_required_time, time_since_mint));
+        }
+
+        nft_state.lock_state = LockState::PendingEvolution;
+        let subject = evolution_subject(&ctx.accounts.asset.key(), nft_state.evolution_count);
+        let commitment = &mut ctx.accounts.roll_commitment;
+        commitment.open(ctx.accounts.payer.key(), RollAction::Evolve, subject, clock.slot, ctx.bumps.roll_commitment);
+
+        msg!("🎲 Evolution committed | Reveal after slot {}", commitment.reveal_slot);
+
+        Ok(())
+    }
+
+    /// 🌟 Advanced NFT Evolution with Fusion Mechanics
+    /// Features: Reveals the roll committed by `commit_evolution`, rarity
+    /// progression, fusion potential. A miss, or a reveal left until the
+    /// committed slot hash has aged out, unlocks the NFT unchanged
+    pub fn evolve_nft(ctx: Context<EvolveNFT>) -> Result<()> {
+        let clock = program_clock(ctx.remaining_accounts)?;
+        let current_time = clock.unix_timestamp;
+        let nft_state = &mut ctx.accounts.nft_state;
+        // 🚨 A recall or sunset since the commit keeps the NFT where it is
+        require!(nft_state.lock_state == LockState::PendingEvolution, NftError::AssetLocked);
+
+        load_owned_asset(&ctx.accounts.asset, ctx.accounts.owner.key)?;
+        verify_collection_membership(&ctx.accounts.asset, &ctx.accounts.collection.key())?;
+        nft_state.bind_collection(ctx.accounts.collection.key())?;
+        let fusion_bonus = nft_state.fusion_potential as u64 * 3600; // 1 hour per fusion point
 
         // 🎲 Rarity evolution with probability system
-        let evolution_chance = match nft_state.rarity.as_str() {
-            "Common" => 100,      // 100% chance to evolve
-            "Uncommon" => 85,     // 85% chance
-            "Rare" => 70,         // 70% chance
-            "Epic" => 50,         // 50% chance
-            "Legendary" => 25,    // 25% chance
-            _ => 10,             // 10% chance for Mythic
-        };
+        let mut evolution_chance = evolution_chance(nft_state.rarity);
+        if let Some(event) = active_global_event(&ctx.accounts.global_event, current_time)? {
+            evolution_chance = event.boost_evolution_chance(evolution_chance);
+            event.announce(ctx.accounts.asset.key());
+        }
 
-        // 🎯 Random evolution success check
-        let random_seed = current_time % 100;
-        require!(
-            random_seed <= evolution_chance,
-            NftError::EvolutionFailed
+        // 🎯 Random evolution success check against the committed roll
+        let randomness = Randomness::new(
+            ctx.accounts.program_config.randomness_provider,
+            &ctx.accounts.slot_hashes,
         );
-
-        let new_level = nft_state.level + 1;
-        let evolved_rarity = match nft_state.rarity.as_str() {
-            "Common" => "Uncommon",
-            "Uncommon" => "Rare",
-            "Rare" => "Epic",
-            "Epic" => "Legendary",
-            "Legendary" => "Mythic",
-            _ => "Divine",
+        let seed = ctx.accounts.roll_commitment.reveal(
+            &randomness,
+            RollAction::Evolve,
+            evolution_subject(&ctx.accounts.asset.key(), nft_state.evolution_count),
+            clock.slot,
+        )?;
+        nft_state.lock_state = LockState::Unlocked;
+        let Some(random_seed) = seed.map(|seed| roll_below(&seed, 0, 100) as i64) else {
+            msg!("💨 Evolution roll expired unrevealed - {} is unlocked unchanged", ctx.accounts.asset.key());
+            return Ok(());
         };
+        if random_seed > evolution_chance {
+            msg!("💨 Evolution failed (roll {} > {}%)", random_seed, evolution_chance);
+            return Ok(());
+        }
+
+        let (new_level, excess_levels) = ctx.accounts.program_config.cap_level(nft_state.level, nft_state.level + 1);
+        if excess_levels > 0 {
+            checkpoint_prestige(&mut ctx.accounts.asset_history, nft_state, &ctx.accounts.asset, current_time)?;
+        }
+        let evolved_rarity = next_rarity(nft_state.rarity);
 
-        let evolved_attributes = vec![
+        let evolved_attributes = merge_attributes(&ctx.accounts.program_config, &ctx.accounts.asset, vec![
             Attribute { key: "level".to_string(), value: new_level.to_string() },
             Attribute { key: "rarity".to_string(), value: evolved_rarity.to_string() },
             Attribute { key: "evolved_at".to_string(), value: current_time.to_string() },
             Attribute { key: "evolution_count".to_string(), value: (nft_state.evolution_count + 1).to_string() },
             Attribute { key: "fusion_bonus_used".to_string(), value: fusion_bonus.to_string() },
             Attribute { key: "evolution_chance".to_string(), value: evolution_chance.to_string() },
-        ];
+        ])?;
 
-        UpdateV1CpiBuilder::new(&ctx.accounts.mpl_core_program)
-            .asset(&ctx.accounts.asset)
-            .authority(&ctx.accounts.payer)
-            .new_update_authority(None)
-            .plugins(vec![PluginAuthorityPair {
-                plugin: Plugin::Attributes { attributes: evolved_attributes },
-                authority: None,
-            }])
-            .invoke()?;
+        if ctx.accounts.program_config.attribute_diff_events.enabled(RuleAction::Evolve) {
+            let previous_attributes = fetch_attributes(&ctx.accounts.asset)?;
+            emit_attribute_diff(ctx.accounts.asset.key(), RuleAction::Evolve, &previous_attributes, &evolved_attributes);
+        }
+
+        nft_state.attributes_hash = write_attributes(
+            &MplCore::new(&ctx.accounts.mpl_core_program, &ctx.accounts.system_program.to_account_info()),
+            &ctx.accounts.asset,
+            &ctx.accounts.collection,
+            &ctx.accounts.payer.to_account_info(),
+            AttributesAuthority::new(&ctx.accounts.attributes_authority, ctx.bumps.attributes_authority),
+            evolved_attributes,
+        )?;
 
         nft_state.level = new_level;
-        nft_state.rarity = evolved_rarity.to_string();
+        nft_state.rarity = evolved_rarity;
         nft_state.last_updated = current_time;
         nft_state.evolution_count += 1;
+        nft_state.apply_rules(RuleAction::Evolve, ctx.accounts.program_config.rule_versions);
+
+        // 📡 Broadcast top-tier evolutions for other programs to react to
+        if evolved_rarity >= Rarity::Legendary {
+            ctx.accounts.signal_board.record(
+                SignalKind::LegendaryEvolution,
+                ctx.accounts.asset.key(),
+                new_level,
+                evolved_rarity.as_str(),
+                &clock,
+            );
+        }
+
+        ctx.accounts.player_profile.bind(ctx.accounts.owner.key(), ctx.bumps.player_profile);
+        overflow_to_prestige(&mut ctx.accounts.player_profile, ctx.accounts.asset.key(), excess_levels);
+        let max_pages = ctx.accounts.program_config.max_history_pages;
+        ctx.accounts.player_profile.record_action(
+            &mut ctx.accounts.player_history_page,
+            ctx.bumps.player_history_page,
+            max_pages,
+            PlayerAction::Evolve,
+            ctx.accounts.asset.key(),
+            current_time,
+        )?;
 
         msg!("🌟 NFT evolved to {} rarity! Level: {} | Fusion bonus: {} hours", 
              evolved_rarity, new_level, fusion_bonus / 3600);
@@ -230,251 +1103,17235 @@ pub mod nft_minter {
         Ok(())
     }
 
-    /// 🔥 NFT Fusion - Combine two NFTs for enhanced attributes
-    /// Features: Fusion mechanics, attribute inheritance, rarity boost
-    pub fn fuse_nfts(
-        ctx: Context<FuseNFTs>,
+    /// 🎲 Fusion - Commit
+    /// Features: Checks and locks every input, then commits the fusion roll
+    /// to a future slot; `fuse_nfts` with the same type, inputs and result
+    /// reveals it. Inputs beyond the first two are passed as payer-owned,
+    /// writable (asset, nft_state) remaining account pairs
+    pub fn commit_fusion<'info>(
+        ctx: Context<'_, '_, 'info, 'info, CommitFusion<'info>>,
+        fusion_type: String,
+    ) -> Result<()> {
+        let clock = program_clock(ctx.remaining_accounts)?;
+        let extra_pairs = without_mock_clock(ctx.remaining_accounts);
+        let (input_keys, mut extra_states, generation) = gather_fusion_inputs(
+            &ctx.accounts.payer.key(),
+            [&ctx.accounts.asset_1, &ctx.accounts.asset_2],
+            [ctx.accounts.delegation_1.as_ref(), ctx.accounts.delegation_2.as_ref()],
+            extra_pairs,
+        )?;
+        require_generation_route(&ctx.accounts.generation_map, generation, &ctx.accounts.result_asset)?;
+
+        // 🔒 Inputs stay locked until the roll is revealed
+        let payer = ctx.accounts.payer.to_account_info();
+        let system_program = ctx.accounts.system_program.to_account_info();
+        for state in [&mut ctx.accounts.nft_state_1, &mut ctx.accounts.nft_state_2] {
+            require!(state.lock_state == LockState::Unlocked, NftError::AssetLocked);
+            state.lock_state = LockState::PendingFusion;
+        }
+        for (pair, state) in extra_pairs.chunks_exact(2).zip(extra_states.iter_mut()) {
+            require!(state.lock_state == LockState::Unlocked, NftError::AssetLocked);
+            state.lock_state = LockState::PendingFusion;
+            upgrade_nft_state(&pair[1], &payer, &system_program)?;
+            state.try_serialize(&mut &mut pair[1].try_borrow_mut_data()?[..])?;
+        }
+
+        let subject = fusion_subject(&fusion_type, &input_keys, &ctx.accounts.result_asset.key());
+        let commitment = &mut ctx.accounts.roll_commitment;
+        commitment.open(payer.key(), RollAction::Fuse, subject, clock.slot, ctx.bumps.roll_commitment);
+
+        msg!("🎲 Fusion of {} committed | Reveal after slot {}", input_keys.len(), commitment.reveal_slot);
+
+        Ok(())
+    }
+
+    /// 🔥 NFT Fusion - Combine two to MAX_FUSION_INPUTS NFTs for enhanced attributes
+    /// Features: Reveals the roll committed by `commit_fusion`, fusion
+    /// mechanics, attribute inheritance, rarity boost; inputs are passed as
+    /// they were committed, and higher output tiers need more inputs. A
+    /// reveal left until the committed slot hash has aged out is a downgrade
+    pub fn fuse_nfts<'info>(
+        ctx: Context<'_, '_, 'info, 'info, FuseNFTs<'info>>,
         fusion_type: String,
     ) -> Result<()> {
-        let clock = Clock::get()?;
+        let clock = program_clock(ctx.remaining_accounts)?;
         let current_time = clock.unix_timestamp;
         
+        let extra_pairs = without_mock_clock(ctx.remaining_accounts);
+        let (input_keys, mut extra_states, generation) = gather_fusion_inputs(
+            &ctx.accounts.payer.key(),
+            [&ctx.accounts.asset_1, &ctx.accounts.asset_2],
+            [ctx.accounts.delegation_1.as_ref(), ctx.accounts.delegation_2.as_ref()],
+            extra_pairs,
+        )?;
+        require_generation_route(&ctx.accounts.generation_map, generation, &ctx.accounts.result_asset)?;
+        load_owned_asset(&ctx.accounts.result_asset, ctx.accounts.result_owner.key)?;
+
+        // 🎲 The committed roll, for exactly these inputs
+        let randomness = Randomness::new(
+            ctx.accounts.program_config.randomness_provider,
+            &ctx.accounts.slot_hashes,
+        );
+        let seed = ctx.accounts.roll_commitment.reveal(
+            &randomness,
+            RollAction::Fuse,
+            fusion_subject(&fusion_type, &input_keys, &ctx.accounts.result_asset.key()),
+            clock.slot,
+        )?;
+
+        // 🔓 Unlock the inputs; a recall or sunset since the commit keeps them where they are
+        let payer_info = ctx.accounts.payer.to_account_info();
+        let system_program = ctx.accounts.system_program.to_account_info();
+        for state in [&mut ctx.accounts.nft_state_1, &mut ctx.accounts.nft_state_2] {
+            require!(state.lock_state == LockState::PendingFusion, NftError::AssetLocked);
+            state.lock_state = LockState::Unlocked;
+        }
+        for (pair, state) in extra_pairs.chunks_exact(2).zip(extra_states.iter_mut()) {
+            require!(state.lock_state == LockState::PendingFusion, NftError::AssetLocked);
+            state.lock_state = LockState::Unlocked;
+            upgrade_nft_state(&pair[1], &payer_info, &system_program)?;
+            state.try_serialize(&mut &mut pair[1].try_borrow_mut_data()?[..])?;
+        }
+
         let nft_state_1 = &ctx.accounts.nft_state_1;
         let nft_state_2 = &ctx.accounts.nft_state_2;
         let result_nft_state = &mut ctx.accounts.result_nft_state;
+        let inputs: Vec<&NftState> = [&**nft_state_1, &**nft_state_2]
+            .into_iter()
+            .chain(extra_states.iter())
+            .collect();
 
-        // 🔬 Fusion validation
-        require!(
-            nft_state_1.asset != nft_state_2.asset,
-            NftError::CannotFuseSameNFT
-        );
+        // 🧬 Fusion type and input stats determine the outcome
+        let outcome = fusion_outcome(&ctx.accounts.program_config.fusion_outcome_table, seed);
+        let (combined_level, fusion_potential, fusion_multiplier) = fusion_stats(&inputs, &fusion_type);
+        let rarity_bonus = fusion_rarity(&inputs, outcome);
+        // 🧢 Levels past the cap become the result owner's prestige instead
+        let (combined_level, excess_levels) =
+            ctx.accounts.program_config.cap_level(result_nft_state.level, combined_level);
 
-        // 🧬 Fusion type determines outcome
-        let fusion_multiplier = match fusion_type.as_str() {
-            "Power" => 2,
-            "Speed" => 3,
-            "Magic" => 4,
-            "Legendary" => 5,
-            _ => 1,
-        };
+        // 🔥 Fusion fee: burn share to the incinerator, the rest to the treasury
+        let (burned, to_treasury) = charge_fusion_fee(
+            ctx.accounts.program_config.fusion_fee,
+            &ctx.accounts.payer.to_account_info(),
+            &ctx.accounts.incinerator,
+            &ctx.accounts.treasury.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            &mut ctx.accounts.economy_ledger,
+        )?;
 
-        // 📊 Attribute fusion calculation
-        let combined_level = (nft_state_1.level + nft_state_2.level) * fusion_multiplier / 2;
-        let fusion_potential = nft_state_1.fusion_potential + nft_state_2.fusion_potential + 1;
-        
-        // 🎲 Rarity fusion with bonus chance
-        let rarity_bonus = match (nft_state_1.rarity.as_str(), nft_state_2.rarity.as_str()) {
-            ("Legendary", "Legendary") => "Divine",
-            ("Epic", "Epic") => "Legendary",
-            ("Rare", "Rare") => "Epic",
-            _ => "Rare",
-        };
+        // 📸 Checkpoint the result asset before its state is overwritten
+        let previous_attributes = fetch_attributes(&ctx.accounts.result_asset)?;
+        let attributes_hash = hash_attributes(&previous_attributes);
+        ctx.accounts.result_history.record(
+            CheckpointOperation::Fusion,
+            result_nft_state,
+            attributes_hash,
+            current_time,
+        );
 
-        let fused_attributes = vec![
+        let fused_attributes = merge_attributes(&ctx.accounts.program_config, &ctx.accounts.result_asset, vec![
             Attribute { key: "level".to_string(), value: combined_level.to_string() },
             Attribute { key: "rarity".to_string(), value: rarity_bonus.to_string() },
-            Attribute { key: "fusion_type".to_string(), value: fusion_type },
+            Attribute { key: "fusion_type".to_string(), value: fusion_type.clone() },
             Attribute { key: "fusion_potential".to_string(), value: fusion_potential.to_string() },
             Attribute { key: "fused_at".to_string(), value: current_time.to_string() },
             Attribute { key: "fusion_multiplier".to_string(), value: fusion_multiplier.to_string() },
-        ];
+            Attribute { key: "generation".to_string(), value: generation.to_string() },
+        ])?;
 
-        UpdateV1CpiBuilder::new(&ctx.accounts.mpl_core_program)
-            .asset(&ctx.accounts.result_asset)
-            .authority(&ctx.accounts.payer)
-            .new_update_authority(None)
-            .plugins(vec![PluginAuthorityPair {
-                plugin: Plugin::Attributes { attributes: fused_attributes },
-                authority: None,
-            }])
-            .invoke()?;
+        if ctx.accounts.program_config.attribute_diff_events.enabled(RuleAction::Fuse) {
+            emit_attribute_diff(ctx.accounts.result_asset.key(), RuleAction::Fuse, &previous_attributes, &fused_attributes);
+        }
+
+        result_nft_state.attributes_hash = write_attributes(
+            &MplCore::new(&ctx.accounts.mpl_core_program, &ctx.accounts.system_program.to_account_info()),
+            &ctx.accounts.result_asset,
+            &ctx.accounts.collection,
+            &ctx.accounts.payer.to_account_info(),
+            AttributesAuthority::new(&ctx.accounts.attributes_authority, ctx.bumps.attributes_authority),
+            fused_attributes,
+        )?;
 
         // Update result NFT state
         result_nft_state.level = combined_level;
-        result_nft_state.rarity = rarity_bonus.to_string();
+        result_nft_state.rarity = rarity_bonus;
+        result_nft_state.bind_asset(ctx.accounts.result_asset.key(), ctx.bumps.result_nft_state)?;
         result_nft_state.fusion_potential = fusion_potential;
+        result_nft_state.mint_date = current_time;
         result_nft_state.last_updated = current_time;
-        result_nft_state.evolution_count = nft_state_1.evolution_count + nft_state_2.evolution_count;
+        result_nft_state.evolution_count = inputs.iter().map(|state| state.evolution_count).sum();
+        result_nft_state.apply_rules(RuleAction::Fuse, ctx.accounts.program_config.rule_versions);
+
+        ctx.accounts.player_profile.bind(ctx.accounts.result_owner.key(), ctx.bumps.player_profile);
+        overflow_to_prestige(&mut ctx.accounts.player_profile, ctx.accounts.result_asset.key(), excess_levels);
+        let max_pages = ctx.accounts.program_config.max_history_pages;
+        ctx.accounts.player_profile.record_action(
+            &mut ctx.accounts.player_history_page,
+            ctx.bumps.player_history_page,
+            max_pages,
+            PlayerAction::Fuse,
+            ctx.accounts.result_asset.key(),
+            current_time,
+        )?;
 
-        msg!("🔥 Fusion successful! New level: {} | Rarity: {} | Type: {}", 
-             combined_level, rarity_bonus, fusion_type);
+        emit!(FusionCompleted {
+            owner: ctx.accounts.payer.key(),
+            result_asset: ctx.accounts.result_asset.key(),
+            outcome,
+            level: combined_level,
+            rarity: rarity_bonus,
+            fusion_type: fusion_type.clone(),
+        });
+
+        msg!("🔥 Fusion of {} successful! New level: {} | Rarity: {} | Type: {} | Fee burned: {} | To treasury: {}", 
+             inputs.len(), combined_level, rarity_bonus, fusion_type, burned, to_treasury);
         
         Ok(())
     }
-}
 
-/// 🎯 Enhanced Context for Advanced NFT Minting
-#[derive(Accounts)]
-#[instruction(name: String, uri: String, level: u64, rarity: String, fusion_potential: u64)]
-pub struct MintNFT<'info> {
-    #[account(mut)]
-    pub payer: Signer<'info>,
-    
-    /// CHECK: Handled by mpl-core
-    #[account(mut)]
-    pub asset: AccountInfo<'info>,
-    
-    /// CHECK: Handled by mpl-core
-    #[account(mut)]
-    pub collection: AccountInfo<'info>,
-    
-    /// CHECK: Metaplex Core program
-    pub mpl_core_program: AccountInfo<'info>,
-    
-    pub system_program: Program<'info, System>,
-}
+    /// ⚙️ Program Configuration Bootstrap
+    /// Features: Admin authority, treasury PDA funding, marketplace fee setup
+    pub fn initialize_program_config(
+        ctx: Context<InitializeProgramConfig>,
+        trait_fee_bps: u16,
+    ) -> Result<()> {
+        require!(trait_fee_bps <= MAX_BPS, NftError::InvalidFeeBps);
 
-/// 🚀 Enhanced Context for Advanced Metadata Updates
-#[derive(Accounts)]
-pub struct UpdateNFTMetadata<'info> {
-    #[account(mut)]
+        let config = &mut ctx.accounts.program_config;
+        config.authority = ctx.accounts.authority.key();
+        config.trait_fee_bps = trait_fee_bps;
+        config.max_attributes = DEFAULT_MAX_ATTRIBUTES;
+        config.max_attribute_bytes = DEFAULT_MAX_ATTRIBUTE_BYTES;
+        config.attribute_overflow_policy = AttributeOverflowPolicy::Reject;
+        config.quote_signer = Pubkey::default();
+        config.voucher_signer = Pubkey::default();
+        config.fusion_confirmation_window_seconds = DEFAULT_FUSION_CONFIRMATION_WINDOW;
+        config.dispute_window_seconds = DEFAULT_DISPUTE_WINDOW;
+        config.lock_identity_after_evolution = false;
+        config.vip_tiers = Vec::new();
+        config.fusion_outcome_table = FusionOutcomeTable::default();
+        config.successor_program = Pubkey::default();
+        config.royalty_mode = RoyaltyMode::Flat;
+        config.rarity_fee_bps = DEFAULT_RARITY_FEE_BPS;
+        config.evolution_downgrade_bps = 0;
+        config.rule_versions = RuleVersions::default();
+        config.subscription_plan = SubscriptionPlan::default();
+        config.randomness_provider = RandomnessProvider::default();
+        config.fusion_fee = FusionFee::default();
+        config.max_history_pages = DEFAULT_MAX_HISTORY_PAGES;
+        config.recall_compensation_lamports = 0;
+        config.split_retain_bps = DEFAULT_SPLIT_RETAIN_BPS;
+        config.transfer_restrictions = Vec::new();
+        config.xp_transfer_loss_bps = DEFAULT_XP_TRANSFER_LOSS_BPS;
+        config.xp_donor_min_level = 1;
+        config.attribute_diff_events = AttributeDiffEvents::default();
+        config.max_level = DEFAULT_MAX_LEVEL;
+        config.docs_base_uri = String::new();
+        config.bump = ctx.bumps.program_config;
+        config.treasury_bump = ctx.bumps.treasury;
+
+        // 🏦 Fund the treasury up to rent exemption so small fees can land in it
+        let rent_exempt = Rent::get()?.minimum_balance(0);
+        let shortfall = rent_exempt.saturating_sub(ctx.accounts.treasury.lamports());
+        if shortfall > 0 {
+            system_program::transfer(
+                CpiContext::new(
+                    ctx.accounts.system_program.to_account_info(),
+                    system_program::Transfer {
+                        from: ctx.accounts.authority.to_account_info(),
+                        to: ctx.accounts.treasury.to_account_info(),
+                    },
+                ),
+                shortfall,
+            )?;
+        }
+
+        msg!("⚙️ Program config initialized | Trait fee: {} bps", trait_fee_bps);
+
+        Ok(())
+    }
+
+    /// 💸 Update the trait marketplace fee routed to the treasury
+    pub fn set_trait_fee_bps(ctx: Context<UpdateProgramConfig>, trait_fee_bps: u16) -> Result<()> {
+        require!(trait_fee_bps <= MAX_BPS, NftError::InvalidFeeBps);
+        ctx.accounts.program_config.trait_fee_bps = trait_fee_bps;
+
+        msg!("💸 Trait fee updated to {} bps", trait_fee_bps);
+
+        Ok(())
+    }
+
+    /// 📏 Configure the per-asset attribute cap and overflow policy
+    pub fn set_attribute_limits(
+        ctx: Context<UpdateProgramConfig>,
+        max_attributes: u16,
+        max_attribute_bytes: u32,
+        attribute_overflow_policy: AttributeOverflowPolicy,
+    ) -> Result<()> {
+        // 🧱 Core attributes alone must always fit under the cap
+        require!(
+            max_attributes as usize >= CORE_ATTRIBUTE_KEYS.len(),
+            NftError::InvalidAttributeLimits
+        );
+        require!(max_attribute_bytes > 0, NftError::InvalidAttributeLimits);
+
+        let config = &mut ctx.accounts.program_config;
+        config.max_attributes = max_attributes;
+        config.max_attribute_bytes = max_attribute_bytes;
+        config.attribute_overflow_policy = attribute_overflow_policy;
+
+        msg!("📏 Attribute limits: {} entries | {} bytes | {:?}",
+             max_attributes, max_attribute_bytes, attribute_overflow_policy);
+
+        Ok(())
+    }
+
+    /// 🧾 Set the backend key trusted to sign mint price quotes
+    pub fn set_quote_signer(ctx: Context<UpdateProgramConfig>, quote_signer: Pubkey) -> Result<()> {
+        ctx.accounts.program_config.quote_signer = quote_signer;
+
+        msg!("🧾 Quote signer set to {}", quote_signer);
+
+        Ok(())
+    }
+
+    /// 🎟️ Set the backend key trusted to sign mint vouchers
+    pub fn set_voucher_signer(ctx: Context<UpdateProgramConfig>, voucher_signer: Pubkey) -> Result<()> {
+        ctx.accounts.program_config.voucher_signer = voucher_signer;
+
+        msg!("🎟️ Voucher signer set to {}", voucher_signer);
+
+        Ok(())
+    }
+
+    /// ⏳ Set how long owners have to confirm a two-phase fusion
+    pub fn set_fusion_confirmation_window(
+        ctx: Context<UpdateProgramConfig>,
+        window_seconds: i64,
+    ) -> Result<()> {
+        require!(window_seconds > 0, NftError::InvalidFusionWindow);
+        ctx.accounts.program_config.fusion_confirmation_window_seconds = window_seconds;
+
+        msg!("⏳ Fusion confirmation window set to {}s", window_seconds);
+
+        Ok(())
+    }
+
+    /// ⚖️ Set the dispute window for checkpoint restores
+    pub fn set_dispute_window(ctx: Context<UpdateProgramConfig>, window_seconds: i64) -> Result<()> {
+        require!(window_seconds > 0, NftError::InvalidDisputeWindow);
+        ctx.accounts.program_config.dispute_window_seconds = window_seconds;
+
+        msg!("⚖️ Dispute window set to {}s", window_seconds);
+
+        Ok(())
+    }
+
+    /// 🔏 Toggle name/URI immutability after the first evolution
+    pub fn set_identity_lock(ctx: Context<UpdateProgramConfig>, enabled: bool) -> Result<()> {
+        ctx.accounts.program_config.lock_identity_after_evolution = enabled;
+
+        msg!("🔏 Identity lock after evolution: {}", enabled);
+
+        Ok(())
+    }
+
+    /// 💎 Replace the VIP tier table
+    pub fn set_vip_tiers(ctx: Context<UpdateProgramConfig>, vip_tiers: Vec<VipTier>) -> Result<()> {
+        require!(vip_tiers.len() <= MAX_VIP_TIERS, NftError::InvalidVipTiers);
+        require!(
+            vip_tiers.iter().all(|tier| tier.fee_discount_bps <= MAX_BPS)
+                && vip_tiers
+                    .windows(2)
+                    .all(|pair| pair[0].min_lifetime_spend < pair[1].min_lifetime_spend),
+            NftError::InvalidVipTiers
+        );
+
+        msg!("💎 VIP tiers updated: {} tiers", vip_tiers.len());
+        ctx.accounts.program_config.vip_tiers = vip_tiers;
+
+        Ok(())
+    }
+
+    /// 🎰 Replace the fusion outcome odds
+    pub fn set_fusion_outcome_table(
+        ctx: Context<UpdateProgramConfig>,
+        table: FusionOutcomeTable,
+    ) -> Result<()> {
+        require!(table.is_valid(), NftError::InvalidFusionOutcomeTable);
+        let config = &mut ctx.accounts.program_config;
+        config.fusion_outcome_table = table;
+        config.rule_versions.rarity = config.rule_versions.rarity.saturating_add(1);
+
+        msg!("🎰 Fusion odds (bps) | Critical: {} | Upgraded: {} | Sideways: {} | Downgraded: {}",
+             table.critical_bps, table.upgraded_bps, table.sideways_bps, table.downgraded_bps);
+
+        Ok(())
+    }
+
+    /// 🚚 Name the program that may adopt this deployment's configs
+    pub fn set_successor_program(
+        ctx: Context<UpdateProgramConfig>,
+        successor_program: Pubkey,
+    ) -> Result<()> {
+        ctx.accounts.program_config.successor_program = successor_program;
+
+        msg!("🚚 Successor program: {}", successor_program);
+
+        Ok(())
+    }
+
+    /// 👑 Choose flat or rarity-scaled marketplace fees
+    pub fn set_royalty_mode(
+        ctx: Context<UpdateProgramConfig>,
+        royalty_mode: RoyaltyMode,
+        rarity_fee_bps: [u16; 7],
+    ) -> Result<()> {
+        require!(
+            rarity_fee_bps.iter().all(|&bps| bps <= MAX_BPS),
+            NftError::InvalidFeeBps
+        );
+
+        let config = &mut ctx.accounts.program_config;
+        config.royalty_mode = royalty_mode;
+        config.rarity_fee_bps = rarity_fee_bps;
+
+        msg!("👑 Royalty mode: {:?} | Rarity fees (bps): {:?}", royalty_mode, rarity_fee_bps);
+
+        Ok(())
+    }
+
+    /// 💀 Set the Endgame Downgrade Risk (admin)
+    /// Features: Failed Legendary → Mythic attempts in the evolution queue may
+    /// drop the asset back to Epic; 0 disables the rule
+    pub fn set_evolution_downgrade_bps(
+        ctx: Context<UpdateProgramConfig>,
+        downgrade_bps: u16,
+    ) -> Result<()> {
+        require!(downgrade_bps <= MAX_BPS, NftError::InvalidFeeBps);
+        let config = &mut ctx.accounts.program_config;
+        config.evolution_downgrade_bps = downgrade_bps;
+        config.rule_versions.evolution = config.rule_versions.evolution.saturating_add(1);
+
+        msg!("💀 Legendary evolution downgrade chance: {} bps", downgrade_bps);
+
+        Ok(())
+    }
+
+    /// 📜 Bump Rule Table Versions (admin)
+    /// Features: Records rule changes shipped in a program upgrade; versions
+    /// can only move forward
+    pub fn set_rule_versions(
+        ctx: Context<UpdateProgramConfig>,
+        rule_versions: RuleVersions,
+    ) -> Result<()> {
+        let config = &mut ctx.accounts.program_config;
+        require!(
+            rule_versions.evolution >= config.rule_versions.evolution
+                && rule_versions.cooldown >= config.rule_versions.cooldown
+                && rule_versions.rarity >= config.rule_versions.rarity,
+            NftError::RuleVersionRegressed
+        );
+        config.rule_versions = rule_versions;
+
+        msg!("📜 Rule versions | Evolution: {} | Cooldown: {} | Rarity: {}",
+             rule_versions.evolution, rule_versions.cooldown, rule_versions.rarity);
+
+        Ok(())
+    }
+
+    /// 🛒 Trait Marketplace - List a cosmetic attribute for sale
+    /// Features: Attribute detachment, escrowed listing, schema policy checks
+    pub fn list_trait(
+        ctx: Context<ListTrait>,
+        trait_key: String,
+        price_lamports: u64,
+    ) -> Result<()> {
+        let seller = &ctx.accounts.seller;
+        let asset = &ctx.accounts.asset;
+
+        load_owned_asset(asset, &seller.key())?;
+
+        // 📐 Schema policy: only cosmetic attributes can change hands
+        require!(is_tradable_trait(&trait_key), NftError::TraitNotTradable);
+        require!(price_lamports > 0, NftError::InvalidTraitPrice);
+
+        let mut attribute_list = fetch_attributes(asset)?;
+        let position = attribute_list
+            .iter()
+            .position(|attribute| attribute.key == trait_key)
+            .ok_or(NftError::TraitNotFound)?;
+        let detached = attribute_list.remove(position);
+        require!(
+            detached.value.len() <= MAX_TRAIT_VALUE_LEN,
+            NftError::TraitValueTooLong
+        );
+
+        let attributes_hash = write_attributes(
+            &MplCore::new(&ctx.accounts.mpl_core_program, &ctx.accounts.system_program.to_account_info()),
+            asset,
+            &ctx.accounts.collection,
+            &seller.to_account_info(),
+            AttributesAuthority::new(&ctx.accounts.attributes_authority, ctx.bumps.attributes_authority),
+            attribute_list,
+        )?;
+        stamp_attributes_hash(&ctx.accounts.nft_state, attributes_hash)?;
+
+        let listing = &mut ctx.accounts.trait_listing;
+        listing.seller = seller.key();
+        listing.asset = asset.key();
+        listing.trait_key = detached.key;
+        listing.trait_value = detached.value;
+        listing.price_lamports = price_lamports;
+        listing.listed_at = program_clock(ctx.remaining_accounts)?.unix_timestamp;
+        listing.bump = ctx.bumps.trait_listing;
+
+        msg!("🛒 Trait '{}' listed for {} lamports", listing.trait_key, price_lamports);
+
+        Ok(())
+    }
+
+    /// 🛍️ Trait Marketplace - Buy a listed attribute onto your own asset
+    /// Features: Treasury fee split, duplicate trait protection, listing settlement
+    pub fn buy_trait(ctx: Context<BuyTrait>) -> Result<()> {
+        let buyer = &ctx.accounts.buyer;
+        let buyer_asset = &ctx.accounts.buyer_asset;
+        let listing = &ctx.accounts.trait_listing;
+
+        load_owned_asset(buyer_asset, &buyer.key())?;
+
+        require!(
+            !fetch_attributes(buyer_asset)?.iter().any(|attribute| attribute.key == listing.trait_key),
+            NftError::TraitAlreadyPresent
+        );
+
+        // 💰 Settle payment: fee to the treasury, remainder to the seller
+        let Split { share: fee, rest: seller_proceeds } =
+            treasury_fee_split(listing.price_lamports, ctx.accounts.program_config.trait_fee_bps)?;
+
+        // 💎 VIP buyers pay a discounted fee; the seller's share is unchanged
+        let now = program_clock(ctx.remaining_accounts)?.unix_timestamp;
+        let perks = ctx.accounts.program_config.perks(&ctx.accounts.player_profile, now);
+        let fee = discounted(fee, perks.fee_discount_bps)?;
+
+        if fee > 0 {
+            system_program::transfer(
+                CpiContext::new(
+                    ctx.accounts.system_program.to_account_info(),
+                    system_program::Transfer {
+                        from: buyer.to_account_info(),
+                        to: ctx.accounts.treasury.to_account_info(),
+                    },
+                ),
+                fee,
+            )?;
+        }
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: buyer.to_account_info(),
+                    to: ctx.accounts.seller.to_account_info(),
+                },
+            ),
+            seller_proceeds,
+        )?;
+        ctx.accounts.economy_ledger.record_collected(fee)?;
+        ctx.accounts.player_profile.record_spend(
+            buyer.key(),
+            ctx.bumps.player_profile,
+            fee + seller_proceeds,
+        )?;
+
+        let attribute_list = merge_attributes(&ctx.accounts.program_config, buyer_asset, vec![Attribute {
+            key: listing.trait_key.clone(),
+            value: listing.trait_value.clone(),
+        }])?;
+        // 🛡️ Never let pruning discard the trait the buyer just paid for
+        require!(
+            attribute_list.iter().any(|attribute| attribute.key == listing.trait_key),
+            NftError::AttributeLimitExceeded
+        );
+
+        let attributes_hash = write_attributes(
+            &MplCore::new(&ctx.accounts.mpl_core_program, &ctx.accounts.system_program.to_account_info()),
+            buyer_asset,
+            &ctx.accounts.collection,
+            &buyer.to_account_info(),
+            AttributesAuthority::new(&ctx.accounts.attributes_authority, ctx.bumps.attributes_authority),
+            attribute_list,
+        )?;
+        stamp_attributes_hash(&ctx.accounts.buyer_nft_state, attributes_hash)?;
+
+        let max_pages = ctx.accounts.program_config.max_history_pages;
+        ctx.accounts.player_profile.record_action(
+            &mut ctx.accounts.player_history_page,
+            ctx.bumps.player_history_page,
+            max_pages,
+            PlayerAction::BuyTrait,
+            buyer_asset.key(),
+            now,
+        )?;
+
+        msg!("🛍️ Trait '{}' purchased for {} lamports | Treasury fee: {}",
+             listing.trait_key, listing.price_lamports, fee);
+
+        Ok(())
+    }
+
+    /// ↩️ Trait Marketplace - Cancel a listing and re-attach the trait
+    pub fn cancel_trait_listing(ctx: Context<CancelTraitListing>) -> Result<()> {
+        let seller = &ctx.accounts.seller;
+        let asset = &ctx.accounts.asset;
+        let listing = &ctx.accounts.trait_listing;
+
+        load_owned_asset(asset, &seller.key())?;
+
+        require!(
+            !fetch_attributes(asset)?.iter().any(|attribute| attribute.key == listing.trait_key),
+            NftError::TraitAlreadyPresent
+        );
+        let attribute_list = merge_attributes(&ctx.accounts.program_config, asset, vec![Attribute {
+            key: listing.trait_key.clone(),
+            value: listing.trait_value.clone(),
+        }])?;
+
+        let attributes_hash = write_attributes(
+            &MplCore::new(&ctx.accounts.mpl_core_program, &ctx.accounts.system_program.to_account_info()),
+            asset,
+            &ctx.accounts.collection,
+            &seller.to_account_info(),
+            AttributesAuthority::new(&ctx.accounts.attributes_authority, ctx.bumps.attributes_authority),
+            attribute_list,
+        )?;
+        stamp_attributes_hash(&ctx.accounts.nft_state, attributes_hash)?;
+
+        msg!("↩️ Trait listing '{}' cancelled", listing.trait_key);
+
+        Ok(())
+    }
+
+    /// 🏷️ NFT Marketplace - List an owned NFT, escrowed in the listing PDA
+    /// Features: Snapshot of level, rarity, power score and attributes for buyer protection
+    pub fn list_nft(ctx: Context<ListNFT>, price_lamports: u64) -> Result<()> {
+        let seller = ctx.accounts.seller.to_account_info();
+        let asset = &ctx.accounts.asset;
+
+        load_owned_asset(asset, seller.key)?;
+        require!(price_lamports > 0, NftError::InvalidListingPrice);
+        let nft_state = &ctx.accounts.nft_state;
+        ensure_transferable(&ctx.accounts.program_config, asset, nft_state)?;
+
+        let listing = &mut ctx.accounts.listing;
+        listing.seller = seller.key();
+        listing.asset = asset.key();
+        listing.price_lamports = price_lamports;
+        listing.level = nft_state.level;
+        listing.rarity = nft_state.rarity;
+        listing.power_score = battle_rating(nft_state);
+        listing.attributes_hash = hash_attributes(&fetch_attributes(asset)?);
+        listing.listed_at = program_clock(ctx.remaining_accounts)?.unix_timestamp;
+        listing.bump = ctx.bumps.listing;
+
+        let system_program = ctx.accounts.system_program.to_account_info();
+        MplCore::new(&ctx.accounts.mpl_core_program, &system_program).transfer(
+            AssetCall::new(asset, &ctx.accounts.collection, &seller, &seller),
+            &ctx.accounts.listing.to_account_info(),
+        )?;
+
+        msg!("🏷️ NFT listed for {} lamports | Level: {} | Power: {}",
+             price_lamports, ctx.accounts.listing.level, ctx.accounts.listing.power_score);
+
+        Ok(())
+    }
+
+    /// 🛍️ NFT Marketplace - Buy a listed NFT
+    /// Features: Fails with ListingStale if the NFT changed since it was listed
+    pub fn buy_nft(ctx: Context<BuyNFT>) -> Result<()> {
+        let buyer = ctx.accounts.buyer.to_account_info();
+        let listing = &ctx.accounts.listing;
+        let nft_state = &ctx.accounts.nft_state;
+
+        // 🛡️ Buyer protection: the NFT must be exactly what was advertised
+        require!(
+            nft_state.level == listing.level
+                && nft_state.rarity == listing.rarity
+                && battle_rating(nft_state) == listing.power_score
+                && hash_attributes(&fetch_attributes(&ctx.accounts.asset)?) == listing.attributes_hash,
+            NftError::ListingStale
+        );
+        ensure_transferable(&ctx.accounts.program_config, &ctx.accounts.asset, nft_state)?;
+
+        // 💰 Settle payment like trait sales: discounted fee to treasury, rest to seller
+        let fee_bps = ctx.accounts.program_config.marketplace_fee_bps(listing.rarity);
+        let Split { share: fee, rest: seller_proceeds } =
+            treasury_fee_split(listing.price_lamports, fee_bps)?;
+        let now = program_clock(ctx.remaining_accounts)?.unix_timestamp;
+        let perks = ctx.accounts.program_config.perks(&ctx.accounts.player_profile, now);
+        let fee = discounted(fee, perks.fee_discount_bps)?;
+
+        // 🤝 Registered market makers accrue the fee into their daily netted settlement
+        let deferred = defer_market_maker_fee(&ctx.accounts.market_maker, fee, now)?;
+        if fee > 0 && !deferred {
+            system_program::transfer(
+                CpiContext::new(
+                    ctx.accounts.system_program.to_account_info(),
+                    system_program::Transfer {
+                        from: buyer.clone(),
+                        to: ctx.accounts.treasury.to_account_info(),
+                    },
+                ),
+                fee,
+            )?;
+            ctx.accounts.economy_ledger.record_collected(fee)?;
+        }
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: buyer.clone(),
+                    to: ctx.accounts.seller.to_account_info(),
+                },
+            ),
+            seller_proceeds,
+        )?;
+        ctx.accounts.player_profile.record_spend(
+            buyer.key(),
+            ctx.bumps.player_profile,
+            fee + seller_proceeds,
+        )?;
+        ctx.accounts.floor_oracle.record_sale(listing.rarity, listing.price_lamports, now)?;
+
+        release_listed_asset(
+            &MplCore::new(&ctx.accounts.mpl_core_program, &ctx.accounts.system_program.to_account_info()),
+            &ctx.accounts.asset,
+            &ctx.accounts.collection,
+            &buyer,
+            &ctx.accounts.listing.to_account_info(),
+            &buyer,
+            ctx.accounts.listing.bump,
+        )?;
+
+        let max_pages = ctx.accounts.program_config.max_history_pages;
+        ctx.accounts.player_profile.record_action(
+            &mut ctx.accounts.player_history_page,
+            ctx.bumps.player_history_page,
+            max_pages,
+            PlayerAction::BuyNft,
+            ctx.accounts.asset.key(),
+            program_clock(ctx.remaining_accounts)?.unix_timestamp,
+        )?;
+
+        msg!("🛍️ NFT bought for {} lamports | Treasury fee: {} (deferred: {})",
+             ctx.accounts.listing.price_lamports, fee, deferred);
+
+        Ok(())
+    }
+
+    /// ↩️ NFT Marketplace - Cancel a listing and take the NFT back
+    pub fn cancel_nft_listing(ctx: Context<CancelNFTListing>) -> Result<()> {
+        let seller = ctx.accounts.seller.to_account_info();
+
+        release_listed_asset(
+            &MplCore::new(&ctx.accounts.mpl_core_program, &ctx.accounts.system_program.to_account_info()),
+            &ctx.accounts.asset,
+            &ctx.accounts.collection,
+            &seller,
+            &ctx.accounts.listing.to_account_info(),
+            &seller,
+            ctx.accounts.listing.bump,
+        )?;
+
+        msg!("↩️ NFT listing cancelled");
+
+        Ok(())
+    }
+
+    /// 🏟️ Weekly Tournament Creation
+    /// Features: Entry fee escrow, treasury-funded prize pool, oracle-reported
+    /// results; a tournament without a champion by `completion_deadline` can be
+    /// cancelled and refunded
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_tournament(
+        ctx: Context<CreateTournament>,
+        tournament_id: u64,
+        entry_fee_lamports: u64,
+        registration_closes_at: i64,
+        completion_deadline: i64,
+        max_entrants: u8,
+        oracle: Pubkey,
+        treasury_contribution: u64,
+    ) -> Result<()> {
+        let clock = program_clock(ctx.remaining_accounts)?;
+        require!(
+            max_entrants >= 2
+                && max_entrants as usize <= MAX_TOURNAMENT_ENTRANTS
+                && max_entrants.is_power_of_two(),
+            NftError::InvalidTournamentSize
+        );
+        require!(
+            registration_closes_at > clock.unix_timestamp && completion_deadline > registration_closes_at,
+            NftError::InvalidTournamentSchedule
+        );
+
+        // 🏦 Seed the prize pool from the treasury without dipping below rent exemption
+        if treasury_contribution > 0 {
+            let rent_exempt = Rent::get()?.minimum_balance(0);
+            require!(
+                ctx.accounts.treasury.lamports() >= treasury_contribution + rent_exempt,
+                NftError::InsufficientTreasuryBalance
+            );
+            pay_from_treasury(
+                &ctx.accounts.treasury,
+                &ctx.accounts.tournament.to_account_info(),
+                &ctx.accounts.system_program,
+                ctx.accounts.program_config.treasury_bump,
+                treasury_contribution,
+            )?;
+        }
+        let ledger = &mut ctx.accounts.economy_ledger;
+        ledger.record_treasury_spent(treasury_contribution)?;
+        ledger.record_escrow_deposit(treasury_contribution)?;
+
+        let tournament = &mut ctx.accounts.tournament;
+        tournament.tournament_id = tournament_id;
+        tournament.authority = ctx.accounts.authority.key();
+        tournament.oracle = oracle;
+        tournament.entry_fee_lamports = entry_fee_lamports;
+        tournament.prize_pool = treasury_contribution;
+        tournament.treasury_contribution = treasury_contribution;
+        tournament.registration_closes_at = registration_closes_at;
+        tournament.completion_deadline = completion_deadline;
+        tournament.max_entrants = max_entrants;
+        tournament.round = 0;
+        tournament.champion = None;
+        tournament.prize_claimed = false;
+        tournament.cancelled = false;
+        tournament.bump = ctx.bumps.tournament;
+
+        msg!("🏟️ Tournament #{} created | Entry: {} lamports | Pool: {} lamports",
+             tournament_id, entry_fee_lamports, treasury_contribution);
+
+        Ok(())
+    }
+
+    /// 🎟️ Enter a Tournament with an owned NFT
+    /// Features: Battle rating snapshot for seeding, entry fee into the prize pool
+    pub fn enter_tournament(ctx: Context<EnterTournament>) -> Result<()> {
+        let clock = program_clock(ctx.remaining_accounts)?;
+        let entrant = &ctx.accounts.entrant;
+        let asset = &ctx.accounts.asset;
+
+        load_owned_asset(asset, &entrant.key())?;
+
+        let tournament = &ctx.accounts.tournament;
+        require!(
+            clock.unix_timestamp < tournament.registration_closes_at,
+            NftError::TournamentRegistrationClosed
+        );
+        require!(
+            tournament.entrants.len() < tournament.max_entrants as usize,
+            NftError::TournamentFull
+        );
+        require!(
+            !tournament.entrants.iter().any(|e| e.asset == asset.key()),
+            NftError::AlreadyEnteredTournament
+        );
+
+        let entry_fee = tournament.entry_fee_lamports;
+        if entry_fee > 0 {
+            system_program::transfer(
+                CpiContext::new(
+                    ctx.accounts.system_program.to_account_info(),
+                    system_program::Transfer {
+                        from: entrant.to_account_info(),
+                        to: ctx.accounts.tournament.to_account_info(),
+                    },
+                ),
+                entry_fee,
+            )?;
+        }
+        ctx.accounts.economy_ledger.record_escrow_deposit(entry_fee)?;
+        ctx.accounts
+            .player_profile
+            .record_spend(entrant.key(), ctx.bumps.player_profile, entry_fee)?;
+
+        let max_pages = ctx.accounts.program_config.max_history_pages;
+        ctx.accounts.player_profile.record_action(
+            &mut ctx.accounts.player_history_page,
+            ctx.bumps.player_history_page,
+            max_pages,
+            PlayerAction::EnterTournament,
+            asset.key(),
+            clock.unix_timestamp,
+        )?;
+
+        let rating = battle_rating(&ctx.accounts.nft_state);
+        let tournament = &mut ctx.accounts.tournament;
+        tournament.prize_pool = tournament
+            .prize_pool
+            .checked_add(entry_fee)
+            .ok_or(NftError::MathOverflow)?;
+        tournament.entrants.push(TournamentEntrant {
+            asset: asset.key(),
+            owner: entrant.key(),
+            battle_rating: rating,
+            refunded: false,
+        });
+
+        msg!("🎟️ Entered tournament #{} | Battle rating: {} | Entrants: {}/{}",
+             tournament.tournament_id, rating, tournament.entrants.len(), tournament.max_entrants);
+
+        Ok(())
+    }
+
+    /// ⚔️ Oracle-Signed Match Reporting
+    /// Features: Lazy bracket seeding by battle rating, byes, round advancement
+    pub fn report_match(
+        ctx: Context<ReportMatch>,
+        match_index: u8,
+        winning_asset: Pubkey,
+    ) -> Result<()> {
+        let clock = program_clock(ctx.remaining_accounts)?;
+        let tournament = &mut ctx.accounts.tournament;
+
+        require!(
+            clock.unix_timestamp >= tournament.registration_closes_at,
+            NftError::TournamentNotStarted
+        );
+        require!(tournament.champion.is_none(), NftError::TournamentFinished);
+        require!(
+            !tournament.cancelled && clock.unix_timestamp < tournament.completion_deadline,
+            NftError::TournamentExpired
+        );
+
+        // 🌱 First report locks the bracket: seeds ordered by battle rating
+        if tournament.bracket.is_empty() {
+            require!(tournament.entrants.len() >= 2, NftError::NotEnoughEntrants);
+            tournament.bracket = seed_bracket(&tournament.entrants);
+            tournament.match_winners = resolve_byes(&tournament.bracket);
+            tournament.round = 1;
+        }
+
+        let match_slot = match_index as usize;
+        require!(
+            match_slot < tournament.match_winners.len(),
+            NftError::InvalidMatch
+        );
+        require!(
+            tournament.match_winners[match_slot] == BRACKET_UNDECIDED,
+            NftError::MatchAlreadyReported
+        );
+
+        let winner = [tournament.bracket[2 * match_slot], tournament.bracket[2 * match_slot + 1]]
+            .into_iter()
+            .find(|&slot| {
+                slot != BRACKET_BYE && tournament.entrants[slot as usize].asset == winning_asset
+            })
+            .ok_or(NftError::InvalidMatch)?;
+        tournament.match_winners[match_slot] = winner;
+
+        msg!("⚔️ Tournament #{} round {} match {} won by {}",
+             tournament.tournament_id, tournament.round, match_index, winning_asset);
+
+        // 🏁 Advance the bracket once every match in the round is decided
+        if tournament.match_winners.iter().all(|&w| w != BRACKET_UNDECIDED) {
+            if tournament.match_winners.len() == 1 {
+                tournament.champion = Some(winner);
+                msg!("🏆 Tournament #{} champion: {}", tournament.tournament_id, winning_asset);
+            } else {
+                tournament.bracket = tournament.match_winners.clone();
+                tournament.match_winners = resolve_byes(&tournament.bracket);
+                tournament.round += 1;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// 💰 Claim the Tournament Prize Pool as Champion
+    /// Features: Escrow payout, champion results written as asset attributes
+    pub fn claim_tournament_prize(ctx: Context<ClaimTournamentPrize>) -> Result<()> {
+        let claimant = &ctx.accounts.claimant;
+        let asset = &ctx.accounts.asset;
+        let tournament = &ctx.accounts.tournament;
+
+        let champion = tournament.champion.ok_or(NftError::TournamentNotFinished)?;
+        require!(!tournament.prize_claimed, NftError::PrizeAlreadyClaimed);
+        require_keys_eq!(
+            tournament.entrants[champion as usize].asset,
+            asset.key(),
+            NftError::NotTournamentChampion
+        );
+        load_owned_asset(asset, &claimant.key())?;
+
+        let prize = tournament.prize_pool;
+        let tournament_id = tournament.tournament_id;
+        transfer_lamports_from_program_account(
+            &ctx.accounts.tournament.to_account_info(),
+            &claimant.to_account_info(),
+            prize,
+        )?;
+        ctx.accounts.economy_ledger.record_reward(prize)?;
+
+        // 🏅 Record the result on the asset itself
+        let wins = fetch_attributes(asset)?
+            .iter()
+            .find(|attribute| attribute.key == "tournament_wins")
+            .and_then(|attribute| attribute.value.parse::<u64>().ok())
+            .unwrap_or(0);
+        let attribute_list = merge_attributes(&ctx.accounts.program_config, asset, vec![
+            Attribute { key: "tournament_wins".to_string(), value: (wins + 1).to_string() },
+            Attribute { key: "last_tournament_won".to_string(), value: tournament_id.to_string() },
+        ])?;
+
+        let attributes_hash = write_attributes(
+            &MplCore::new(&ctx.accounts.mpl_core_program, &ctx.accounts.system_program.to_account_info()),
+            asset,
+            &ctx.accounts.collection,
+            &claimant.to_account_info(),
+            AttributesAuthority::new(&ctx.accounts.attributes_authority, ctx.bumps.attributes_authority),
+            attribute_list,
+        )?;
+        stamp_attributes_hash(&ctx.accounts.nft_state, attributes_hash)?;
+
+        let tournament = &mut ctx.accounts.tournament;
+        tournament.prize_pool = 0;
+        tournament.prize_claimed = true;
+
+        msg!("💰 Tournament #{} prize of {} lamports claimed | Total wins: {}",
+             tournament_id, prize, wins + 1);
+
+        Ok(())
+    }
+
+    /// 🛑 Cancel a Stalled Tournament
+    /// Features: Permissionless once the completion deadline passes without a
+    /// champion, or registration closed with fewer than two entrants; the
+    /// treasury's contribution goes back to the treasury and entrants can then
+    /// reclaim their fees with `refund_tournament_entry`
+    pub fn cancel_tournament(ctx: Context<CancelTournament>) -> Result<()> {
+        let now = program_clock(ctx.remaining_accounts)?.unix_timestamp;
+        let tournament = &ctx.accounts.tournament;
+        require!(tournament.champion.is_none(), NftError::TournamentFinished);
+        require!(!tournament.cancelled, NftError::TournamentExpired);
+        let stalled = now >= tournament.completion_deadline
+            || (now >= tournament.registration_closes_at && tournament.entrants.len() < 2);
+        require!(stalled, NftError::TournamentNotStalled);
+
+        let contribution = tournament.treasury_contribution;
+        if contribution > 0 {
+            transfer_lamports_from_program_account(
+                &ctx.accounts.tournament.to_account_info(),
+                &ctx.accounts.treasury.to_account_info(),
+                contribution,
+            )?;
+        }
+        let ledger = &mut ctx.accounts.economy_ledger;
+        ledger.record_escrow_release(contribution)?;
+        ledger.record_collected(contribution)?;
+
+        let tournament = &mut ctx.accounts.tournament;
+        tournament.prize_pool = tournament.prize_pool.saturating_sub(contribution);
+        tournament.treasury_contribution = 0;
+        tournament.cancelled = true;
+
+        msg!("🛑 Tournament #{} cancelled | {} lamports back to the treasury",
+             tournament.tournament_id, contribution);
+
+        Ok(())
+    }
+
+    /// 💸 Refund Tournament Entries
+    /// Features: After a cancellation, returns the entry fee of every entry the
+    /// caller paid for
+    pub fn refund_tournament_entry(ctx: Context<RefundTournamentEntry>) -> Result<()> {
+        let entrant = ctx.accounts.entrant.key();
+        let tournament = &mut ctx.accounts.tournament;
+        require!(tournament.cancelled, NftError::TournamentNotCancelled);
+
+        let mut entries = 0u64;
+        for entry in tournament.entrants.iter_mut().filter(|entry| entry.owner == entrant && !entry.refunded) {
+            entry.refunded = true;
+            entries += 1;
+        }
+        require!(entries > 0, NftError::NothingToClaim);
+        let refund = tournament
+            .entry_fee_lamports
+            .checked_mul(entries)
+            .ok_or(NftError::MathOverflow)?;
+        tournament.prize_pool = tournament.prize_pool.checked_sub(refund).ok_or(NftError::MathOverflow)?;
+        let tournament_id = tournament.tournament_id;
+
+        if refund > 0 {
+            transfer_lamports_from_program_account(
+                &ctx.accounts.tournament.to_account_info(),
+                &ctx.accounts.entrant.to_account_info(),
+                refund,
+            )?;
+        }
+        ctx.accounts.economy_ledger.record_escrow_release(refund)?;
+
+        msg!("💸 Tournament #{} refunded {} entries ({} lamports) to {}",
+             tournament_id, entries, refund, entrant);
+
+        Ok(())
+    }
+
+    /// 🏠 Rental Offer - list an owned NFT for time-based rental
+    /// Features: Per-second pricing, duration cap, early-termination penalty
+    /// terms. The owner hands the asset's FreezeDelegate (thawed) to the
+    /// program's `["stake_authority"]` PDA, so a rental can freeze it for its term
+    pub fn offer_rental(
+        ctx: Context<OfferRental>,
+        rate_lamports_per_second: u64,
+        max_duration_seconds: i64,
+        early_termination_penalty_bps: u16,
+    ) -> Result<()> {
+        let owner = ctx.accounts.owner.to_account_info();
+        let asset = &ctx.accounts.asset;
+
+        load_owned_asset(asset, owner.key)?;
+        require!(
+            rate_lamports_per_second > 0 && max_duration_seconds > 0,
+            NftError::InvalidRentalTerms
+        );
+        require!(early_termination_penalty_bps <= MAX_BPS, NftError::InvalidFeeBps);
+
+        let system_program = ctx.accounts.system_program.to_account_info();
+        let backend = MplCore::new(&ctx.accounts.mpl_core_program, &system_program);
+        let call = AssetCall::new(asset, &ctx.accounts.collection, &owner, &owner);
+        let freeze_authority = PluginAuthority::Address { address: ctx.accounts.stake_authority.key() };
+        match fetch_plugin::<BaseAssetV1, FreezeDelegate>(asset, PluginType::FreezeDelegate) {
+            Ok((plugin_authority, freeze, _)) => {
+                require!(!freeze.frozen, NftError::AssetAlreadyFrozen);
+                if plugin_authority != freeze_authority {
+                    backend.approve_plugin_authority(call, PluginType::FreezeDelegate, freeze_authority)?;
+                }
+            }
+            Err(_) => {
+                backend.add_plugin(call, Plugin::FreezeDelegate(FreezeDelegate { frozen: false }), Some(freeze_authority))?;
+            }
+        }
+
+        let rental = &mut ctx.accounts.rental;
+        rental.owner = owner.key();
+        rental.asset = asset.key();
+        rental.borrower = Pubkey::default();
+        rental.rate_lamports_per_second = rate_lamports_per_second;
+        rental.max_duration_seconds = max_duration_seconds;
+        rental.early_termination_penalty_bps = early_termination_penalty_bps;
+        rental.started_at = 0;
+        rental.ends_at = 0;
+        rental.escrowed = 0;
+        rental.claimed = 0;
+        rental.bump = ctx.bumps.rental;
+
+        msg!("🏠 Rental offered at {} lamports/s for up to {}s",
+             rate_lamports_per_second, max_duration_seconds);
+
+        Ok(())
+    }
+
+    /// 🔑 Start a Rental - borrower prepays the full term into escrow
+    /// Features: Escrowed prepayment, income streams to the owner as time
+    /// elapses. The asset is frozen until the rental ends, so the owner can't
+    /// transfer or list it mid-term
+    pub fn start_rental(ctx: Context<StartRental>, duration_seconds: i64) -> Result<()> {
+        let clock = program_clock(ctx.remaining_accounts)?;
+        let rental = &ctx.accounts.rental;
+
+        require!(!rental.is_active(), NftError::RentalActive);
+        require!(
+            duration_seconds > 0 && duration_seconds <= rental.max_duration_seconds,
+            NftError::InvalidRentalTerms
+        );
+
+        // 🧊 The offer's thawed FreezeDelegate must still be ours; a transfer
+        // hands it back to the new owner, and staking or `freeze_asset` froze it
+        load_owned_asset(&ctx.accounts.asset, &rental.owner)?;
+        let (plugin_authority, freeze, _) =
+            fetch_plugin::<BaseAssetV1, FreezeDelegate>(&ctx.accounts.asset, PluginType::FreezeDelegate)
+                .map_err(|_| error!(NftError::FreezeAuthorityMismatch))?;
+        require!(
+            plugin_authority == PluginAuthority::Address { address: ctx.accounts.stake_authority.key() },
+            NftError::FreezeAuthorityMismatch
+        );
+        require!(!freeze.frozen, NftError::AssetAlreadyFrozen);
+        set_stake_freeze(
+            &MplCore::new(&ctx.accounts.mpl_core_program, &ctx.accounts.system_program.to_account_info()),
+            &ctx.accounts.asset,
+            &ctx.accounts.collection,
+            &ctx.accounts.borrower.to_account_info(),
+            &ctx.accounts.stake_authority,
+            ctx.bumps.stake_authority,
+            true,
+        )?;
+
+        let prepayment = rental
+            .rate_lamports_per_second
+            .checked_mul(duration_seconds as u64)
+            .ok_or(NftError::MathOverflow)?;
+
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: ctx.accounts.borrower.to_account_info(),
+                    to: ctx.accounts.rental.to_account_info(),
+                },
+            ),
+            prepayment,
+        )?;
+        ctx.accounts.economy_ledger.record_escrow_deposit(prepayment)?;
+
+        let rental = &mut ctx.accounts.rental;
+        rental.borrower = ctx.accounts.borrower.key();
+        rental.started_at = clock.unix_timestamp;
+        rental.ends_at = clock.unix_timestamp + duration_seconds;
+        rental.escrowed = prepayment;
+        rental.claimed = 0;
+
+        msg!("🔑 Rental started for {}s | Escrowed: {} lamports", duration_seconds, prepayment);
+
+        Ok(())
+    }
+
+    /// 💵 Claim Rental Income - owner withdraws what has streamed so far
+    /// Features: Per-second accrual, automatic reset once the term is fully paid out
+    pub fn claim_rental_income(ctx: Context<ClaimRentalIncome>) -> Result<()> {
+        let clock = program_clock(ctx.remaining_accounts)?;
+        let rental = &ctx.accounts.rental;
+
+        require!(rental.is_active(), NftError::NoActiveRental);
+
+        let accrued = rental.accrued_at(clock.unix_timestamp)?;
+        let claimable = accrued - rental.claimed;
+        let term_complete = clock.unix_timestamp >= rental.ends_at;
+        require!(claimable > 0 || term_complete, NftError::NothingToClaim);
+
+        if claimable > 0 {
+            transfer_lamports_from_program_account(
+                &ctx.accounts.rental.to_account_info(),
+                &ctx.accounts.owner.to_account_info(),
+                claimable,
+            )?;
+        }
+        ctx.accounts.economy_ledger.record_escrow_release(claimable)?;
+
+        let rental = &mut ctx.accounts.rental;
+        rental.claimed += claimable;
+
+        // 🔄 Term complete and fully paid out - the asset thaws and the offer is open again
+        if term_complete {
+            rental.reset();
+            set_stake_freeze(
+                &MplCore::new(&ctx.accounts.mpl_core_program, &ctx.accounts.system_program.to_account_info()),
+                &ctx.accounts.asset,
+                &ctx.accounts.collection,
+                &ctx.accounts.owner.to_account_info(),
+                &ctx.accounts.stake_authority,
+                ctx.bumps.stake_authority,
+                false,
+            )?;
+        }
+
+        msg!("💵 Claimed {} lamports of rental income", claimable);
+
+        Ok(())
+    }
+
+    /// ⏹️ Terminate a Rental Early - borrower exits before the term ends
+    /// Features: Owner keeps accrued income plus a penalty on the unused remainder
+    pub fn terminate_rental_early(ctx: Context<TerminateRentalEarly>) -> Result<()> {
+        let clock = program_clock(ctx.remaining_accounts)?;
+        let rental = &ctx.accounts.rental;
+
+        require!(rental.is_active(), NftError::NoActiveRental);
+        require!(clock.unix_timestamp < rental.ends_at, NftError::RentalExpired);
+
+        let accrued = rental.accrued_at(clock.unix_timestamp)?;
+        let unused = rental.escrowed - accrued;
+        let Split { share: penalty, rest: refund } =
+            split_bps(unused, rental.early_termination_penalty_bps, Rounding::Floor)?;
+        let owner_payout = accrued - rental.claimed + penalty;
+
+        let rental_info = ctx.accounts.rental.to_account_info();
+        transfer_lamports_from_program_account(
+            &rental_info,
+            &ctx.accounts.owner.to_account_info(),
+            owner_payout,
+        )?;
+        transfer_lamports_from_program_account(
+            &rental_info,
+            &ctx.accounts.borrower.to_account_info(),
+            refund,
+        )?;
+        ctx.accounts.economy_ledger.record_escrow_release(owner_payout + refund)?;
+
+        ctx.accounts.rental.reset();
+        set_stake_freeze(
+            &MplCore::new(&ctx.accounts.mpl_core_program, &ctx.accounts.system_program.to_account_info()),
+            &ctx.accounts.asset,
+            &ctx.accounts.collection,
+            &ctx.accounts.borrower.to_account_info(),
+            &ctx.accounts.stake_authority,
+            ctx.bumps.stake_authority,
+            false,
+        )?;
+
+        msg!("⏹️ Rental terminated early | Owner: {} lamports (penalty {}) | Refund: {}",
+             owner_payout, penalty, refund);
+
+        Ok(())
+    }
+
+    /// 🚪 Withdraw a Rental Offer while no rental is running
+    pub fn close_rental_offer(ctx: Context<CloseRentalOffer>) -> Result<()> {
+        require!(!ctx.accounts.rental.is_active(), NftError::RentalActive);
+
+        msg!("🚪 Rental offer closed");
+
+        Ok(())
+    }
+
+    /// 🧪 Two-Phase Fusion - Initiate
+    /// Features: Input locking, the fusion fee charged up front, level and
+    /// potential preview stored on-chain, confirmation deadline. The rarity roll is committed to a future slot
+    /// and only revealed by `confirm_fusion`, so no one can see it while the
+    /// fusion can still be cancelled for free
+    pub fn initiate_fusion(ctx: Context<InitiateFusion>, fusion_type: String) -> Result<()> {
+        let clock = program_clock(ctx.remaining_accounts)?;
+        let owner = ctx.accounts.owner.key();
+
+        load_owned_asset(&ctx.accounts.asset_1, &owner)?;
+        load_owned_asset(&ctx.accounts.asset_2, &owner)?;
+        load_owned_asset(&ctx.accounts.result_asset, &owner)?;
+        require!(fusion_type.len() <= MAX_FUSION_TYPE_LEN, NftError::FusionRequirementsNotMet);
+        let generation = asset_generation(&ctx.accounts.asset_1)?
+            .max(asset_generation(&ctx.accounts.asset_2)?)
+            .saturating_add(1);
+        require_generation_route(&ctx.accounts.generation_map, generation, &ctx.accounts.result_asset)?;
+
+        let nft_state_1 = &mut ctx.accounts.nft_state_1;
+        let nft_state_2 = &mut ctx.accounts.nft_state_2;
+
+        // 🔬 Fusion validation; the result is a third asset, never an input
+        let result_key = ctx.accounts.result_asset.key();
+        require!(
+            ctx.accounts.asset_1.key() != ctx.accounts.asset_2.key()
+                && result_key != ctx.accounts.asset_1.key()
+                && result_key != ctx.accounts.asset_2.key(),
+            NftError::CannotFuseSameNFT
+        );
+        require!(
+            nft_state_1.lock_state == LockState::Unlocked
+                && nft_state_2.lock_state == LockState::Unlocked,
+            NftError::AssetLocked
+        );
+
+        let (level, fusion_potential, fusion_multiplier) =
+            fusion_stats(&[&**nft_state_1, &**nft_state_2], &fusion_type);
+        nft_state_1.lock_state = LockState::PendingFusion;
+        nft_state_2.lock_state = LockState::PendingFusion;
+
+        // 🔥 Fusion fee, as fuse_nfts charges it; kept if the fusion is cancelled
+        let (burned, to_treasury) = charge_fusion_fee(
+            ctx.accounts.program_config.fusion_fee,
+            &ctx.accounts.owner.to_account_info(),
+            &ctx.accounts.incinerator,
+            &ctx.accounts.treasury.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            &mut ctx.accounts.economy_ledger,
+        )?;
+
+        let window = ctx.accounts.program_config.fusion_confirmation_window_seconds;
+        let pending = &mut ctx.accounts.pending_fusion;
+        pending.owner = owner;
+        pending.asset_1 = ctx.accounts.asset_1.key();
+        pending.asset_2 = ctx.accounts.asset_2.key();
+        pending.result_asset = ctx.accounts.result_asset.key();
+        pending.fusion_type = fusion_type;
+        pending.level = level;
+        pending.fusion_potential = fusion_potential;
+        pending.fusion_multiplier = fusion_multiplier;
+        pending.reveal_slot = clock.slot + FUSION_REVEAL_DELAY_SLOTS;
+        pending.evolution_count = nft_state_1.evolution_count + nft_state_2.evolution_count;
+        pending.rule_versions = ctx.accounts.program_config.rule_versions;
+        pending.generation = generation;
+        pending.initiated_at = clock.unix_timestamp;
+        pending.confirm_deadline = clock.unix_timestamp + window;
+        pending.bump = ctx.bumps.pending_fusion;
+
+        msg!("🧪 Fusion initiated → level {} | Rarity revealed after slot {} | Confirm within {}s | Fee burned: {} | To treasury: {}",
+             level, pending.reveal_slot, window, burned, to_treasury);
+
+        Ok(())
+    }
+
+    /// ✅ Two-Phase Fusion - Confirm within the window
+    /// Features: Rolls the rarity from the slot committed at initiate, applies
+    /// the result, unlocks inputs. The owner must still hold both inputs and
+    /// the result. A confirm left until the committed slot hash has aged out
+    /// resolves as a downgrade, so waiting can't buy a fresh roll. A result
+    /// asset that already has an NftState keeps its points, energy and mint
+    /// date and must not be locked; otherwise its state is created here
+    pub fn confirm_fusion(ctx: Context<ConfirmFusion>) -> Result<()> {
+        let clock = program_clock(ctx.remaining_accounts)?;
+        let current_time = clock.unix_timestamp;
+        let pending = &ctx.accounts.pending_fusion;
+
+        require!(current_time <= pending.confirm_deadline, NftError::FusionWindowExpired);
+        require!(clock.slot > pending.reveal_slot, NftError::FusionRevealPending);
+
+        // 🔍 The inputs' states are locked, but the assets themselves can
+        // change hands while the fusion is pending
+        load_owned_asset(&ctx.accounts.asset_1, &pending.owner)?;
+        load_owned_asset(&ctx.accounts.asset_2, &pending.owner)?;
+        load_owned_asset(&ctx.accounts.result_asset, &pending.owner)?;
+        verify_collection_membership(&ctx.accounts.result_asset, &ctx.accounts.collection.key())?;
+        require_generation_route(&ctx.accounts.generation_map, pending.generation, &ctx.accounts.result_asset)?;
+
+        // 🎲 Reveal the committed roll
+        let randomness = Randomness::new(
+            ctx.accounts.program_config.randomness_provider,
+            &ctx.accounts.slot_hashes,
+        );
+        let seed = randomness.revealed(pending.reveal_slot, &[pending.asset_1.as_ref(), pending.asset_2.as_ref()])?;
+        let outcome = fusion_outcome(&ctx.accounts.program_config.fusion_outcome_table, seed);
+        let rarity = fusion_rarity(&[&*ctx.accounts.nft_state_1, &*ctx.accounts.nft_state_2], outcome);
+
+        let owner = ctx.accounts.owner.to_account_info();
+        let system_program = ctx.accounts.system_program.to_account_info();
+        let result_info = &ctx.accounts.result_nft_state;
+        let mut result_nft_state = if result_info.owner == &crate::ID {
+            let state = NftState::try_deserialize(&mut &result_info.try_borrow_data()?[..])?;
+            require!(state.lock_state == LockState::Unlocked, NftError::AssetLocked);
+            upgrade_nft_state(result_info, &owner, &system_program)?;
+            state
+        } else {
+            create_nft_state_account(result_info, &pending.result_asset, &owner, &system_program)?;
+            NftState { mint_date: current_time, ..NftState::default() }
+        };
+        result_nft_state.bind_asset(pending.result_asset, ctx.bumps.result_nft_state)?;
+        result_nft_state.bind_collection(ctx.accounts.collection.key())?;
+        // 🧢 Levels past the cap become the owner's prestige instead
+        let (level, excess_levels) = ctx.accounts.program_config.cap_level(result_nft_state.level, pending.level);
+
+        // 📸 Checkpoint the result asset before fusion rewrites it
+        ctx.accounts.result_history.record(
+            CheckpointOperation::Fusion,
+            &result_nft_state,
+            hash_attributes(&fetch_attributes(&ctx.accounts.result_asset)?),
+            current_time,
+        );
+
+        let attribute_list = merge_attributes(&ctx.accounts.program_config, &ctx.accounts.result_asset, vec![
+            Attribute { key: "level".to_string(), value: level.to_string() },
+            Attribute { key: "rarity".to_string(), value: rarity.to_string() },
+            Attribute { key: "fusion_type".to_string(), value: pending.fusion_type.clone() },
+            Attribute { key: "fusion_potential".to_string(), value: pending.fusion_potential.to_string() },
+            Attribute { key: "fused_at".to_string(), value: current_time.to_string() },
+            Attribute { key: "fusion_multiplier".to_string(), value: pending.fusion_multiplier.to_string() },
+            Attribute { key: "generation".to_string(), value: pending.generation.to_string() },
+        ])?;
+
+        let attributes_hash = write_attributes(
+            &MplCore::new(&ctx.accounts.mpl_core_program, &ctx.accounts.system_program.to_account_info()),
+            &ctx.accounts.result_asset,
+            &ctx.accounts.collection,
+            &ctx.accounts.owner.to_account_info(),
+            AttributesAuthority::new(&ctx.accounts.attributes_authority, ctx.bumps.attributes_authority),
+            attribute_list,
+        )?;
+
+        result_nft_state.attributes_hash = attributes_hash;
+        result_nft_state.level = level;
+        result_nft_state.rarity = rarity;
+        result_nft_state.fusion_potential = pending.fusion_potential;
+        result_nft_state.evolution_count = pending.evolution_count;
+        result_nft_state.apply_rules(RuleAction::Fuse, pending.rule_versions);
+        result_nft_state.last_updated = current_time;
+        result_nft_state.try_serialize(&mut &mut ctx.accounts.result_nft_state.try_borrow_mut_data()?[..])?;
+
+        ctx.accounts.nft_state_1.lock_state = LockState::Unlocked;
+        ctx.accounts.nft_state_2.lock_state = LockState::Unlocked;
+        ctx.accounts.player_profile.bind(pending.owner, ctx.bumps.player_profile);
+        overflow_to_prestige(&mut ctx.accounts.player_profile, pending.result_asset, excess_levels);
+
+        emit!(FusionCompleted {
+            owner: pending.owner,
+            result_asset: pending.result_asset,
+            outcome,
+            level,
+            rarity,
+            fusion_type: pending.fusion_type.clone(),
+        });
+
+        msg!("✅ Fusion confirmed! New level: {} | Rarity: {} | Type: {}",
+             level, rarity, pending.fusion_type);
+
+        Ok(())
+    }
+
+    /// ❌ Two-Phase Fusion - Cancel and unlock the inputs
+    /// Features: Allowed until the committed roll is revealed; after that only
+    /// once the confirmation window has closed, so a bad roll can't be
+    /// cancelled straight away and tried again. The fee paid at initiate is kept
+    pub fn cancel_fusion(ctx: Context<CancelFusion>) -> Result<()> {
+        let clock = program_clock(ctx.remaining_accounts)?;
+        let pending = &ctx.accounts.pending_fusion;
+        require!(
+            clock.slot <= pending.reveal_slot || clock.unix_timestamp > pending.confirm_deadline,
+            NftError::FusionRevealed
+        );
+
+        ctx.accounts.nft_state_1.lock_state = LockState::Unlocked;
+        ctx.accounts.nft_state_2.lock_state = LockState::Unlocked;
+
+        msg!("❌ Fusion cancelled - inputs unlocked");
+
+        Ok(())
+    }
+
+    /// 🧩 Register a Cross-Asset Set (admin)
+    /// Features: Trait/collection combo requirements, points or attribute bonus
+    pub fn register_set(
+        ctx: Context<RegisterSet>,
+        set_id: u64,
+        name: String,
+        requirements: Vec<SetRequirement>,
+        bonus_points: u64,
+        bonus_attribute_key: String,
+        bonus_attribute_value: String,
+    ) -> Result<()> {
+        require!(
+            !requirements.is_empty() && requirements.len() <= MAX_SET_REQUIREMENTS,
+            NftError::InvalidSetDefinition
+        );
+        require!(name.len() <= 32, NftError::InvalidSetDefinition);
+        require!(
+            requirements.iter().all(|r| {
+                r.trait_key.len() <= MAX_TRAIT_KEY_LEN && r.trait_value.len() <= MAX_TRAIT_VALUE_LEN
+            }),
+            NftError::InvalidSetDefinition
+        );
+        require!(
+            bonus_attribute_key.is_empty() || is_tradable_trait(&bonus_attribute_key),
+            NftError::InvalidSetDefinition
+        );
+        require!(
+            bonus_attribute_value.len() <= MAX_TRAIT_VALUE_LEN,
+            NftError::TraitValueTooLong
+        );
+        require!(
+            bonus_points > 0 || !bonus_attribute_key.is_empty(),
+            NftError::InvalidSetDefinition
+        );
+
+        let set = &mut ctx.accounts.set_definition;
+        set.set_id = set_id;
+        set.name = name;
+        set.requirements = requirements;
+        set.bonus_points = bonus_points;
+        set.bonus_attribute_key = bonus_attribute_key;
+        set.bonus_attribute_value = bonus_attribute_value;
+        set.claims = 0;
+        set.bump = ctx.bumps.set_definition;
+
+        msg!("🧩 Set #{} '{}' registered with {} pieces",
+             set_id, set.name, set.requirements.len());
+
+        Ok(())
+    }
+
+    /// 🎁 Claim a Set Bonus by proving ownership of every piece
+    /// Features: Set pieces via remaining_accounts, one claim per asset per
+    /// set. The target asset is itself one of the pieces, so a set's pieces
+    /// can back at most as many claims as there are pieces
+    pub fn claim_set_bonus<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ClaimSetBonus<'info>>,
+    ) -> Result<()> {
+        let claimant = &ctx.accounts.claimant;
+        let set = &ctx.accounts.set_definition;
+
+        load_owned_asset(&ctx.accounts.target_asset, &claimant.key())?;
+
+        // 🔍 Candidate pieces: the target first, then each distinct asset the
+        // claimant owns; anything else passed in is skipped
+        let mut pieces = vec![&ctx.accounts.target_asset];
+        for piece in without_mock_clock(ctx.remaining_accounts) {
+            if pieces.iter().any(|seen| seen.key() == piece.key()) {
+                continue;
+            }
+            if load_owned_asset(piece, &claimant.key()).is_ok() {
+                pieces.push(piece);
+            }
+        }
+
+        let mut eligible = vec![vec![false; pieces.len()]; set.requirements.len()];
+        for (index, piece) in pieces.iter().enumerate() {
+            let collection = asset_collection(&load_core_asset(piece)?);
+            let attributes = fetch_attributes(piece)?;
+            for (requirement, row) in set.requirements.iter().zip(eligible.iter_mut()) {
+                row[index] = requirement.is_met_by(collection, &attributes);
+            }
+        }
+
+        // 🧩 Each requirement needs its own piece, and the target must be one
+        // of them. Any complete matching can hand a requirement the target
+        // fits over to it, so fitting one requirement is enough
+        require!(
+            match_set_pieces(&eligible).is_some() && eligible.iter().any(|row| row[0]),
+            NftError::SetIncomplete
+        );
+
+        // 🏅 Apply the bonus to the chosen target asset
+        let target_state = &mut ctx.accounts.target_nft_state;
+        target_state.achievement_points = target_state
+            .achievement_points
+            .checked_add(set.bonus_points)
+            .ok_or(NftError::MathOverflow)?;
+
+        if !set.bonus_attribute_key.is_empty() {
+            let attribute_list = merge_attributes(&ctx.accounts.program_config, &ctx.accounts.target_asset, vec![
+                Attribute { key: set.bonus_attribute_key.clone(), value: set.bonus_attribute_value.clone() },
+            ])?;
+
+            ctx.accounts.target_nft_state.attributes_hash = write_attributes(
+                &MplCore::new(&ctx.accounts.mpl_core_program, &ctx.accounts.system_program.to_account_info()),
+                &ctx.accounts.target_asset,
+                &ctx.accounts.collection,
+                &claimant.to_account_info(),
+                AttributesAuthority::new(&ctx.accounts.attributes_authority, ctx.bumps.attributes_authority),
+                attribute_list,
+            )?;
+        }
+
+        let claim = &mut ctx.accounts.set_claim;
+        claim.set_id = set.set_id;
+        claim.target_asset = ctx.accounts.target_asset.key();
+        claim.wallet = claimant.key();
+        claim.claimed_at = program_clock(ctx.remaining_accounts)?.unix_timestamp;
+        claim.bump = ctx.bumps.set_claim;
+
+        ctx.accounts.economy_ledger.record_points_issued(set.bonus_points)?;
+
+        let set = &mut ctx.accounts.set_definition;
+        set.claims += 1;
+
+        msg!("🎁 Set '{}' bonus claimed | +{} achievement points", set.name, set.bonus_points);
+
+        Ok(())
+    }
+
+    /// 📚 Create the checkpoint history for an asset
+    /// Features: Required before destructive operations can run on the asset
+    pub fn initialize_asset_history(ctx: Context<InitializeAssetHistory>) -> Result<()> {
+        let history = &mut ctx.accounts.asset_history;
+        history.asset = ctx.accounts.asset.key();
+        history.next_index = 0;
+        history.bump = ctx.bumps.asset_history;
+
+        msg!("📚 Asset history created for {}", history.asset);
+
+        Ok(())
+    }
+
+    /// ⏪ Restore an asset from a checkpoint (admin, within the dispute window)
+    /// Features: Undo program-bug damage, one restore per checkpoint. The
+    /// checkpoint only keeps the attributes' hash, so the admin passes the
+    /// attribute list it hashes (e.g. from the AttributesChanged events) and
+    /// it replaces the asset's attributes. Burned assets can't be restored;
+    /// their checkpoints stay as a record.
+    pub fn restore_from_checkpoint(
+        ctx: Context<RestoreFromCheckpoint>,
+        checkpoint_index: u8,
+        attribute_list: Vec<Attribute>,
+    ) -> Result<()> {
+        let clock = program_clock(ctx.remaining_accounts)?;
+        let dispute_window = ctx.accounts.program_config.dispute_window_seconds;
+        let history = &mut ctx.accounts.asset_history;
+
+        let checkpoint = history
+            .checkpoints
+            .get_mut(checkpoint_index as usize)
+            .ok_or(NftError::CheckpointNotFound)?;
+        require!(!checkpoint.restored, NftError::CheckpointAlreadyRestored);
+        require!(
+            clock.unix_timestamp <= checkpoint.taken_at + dispute_window,
+            NftError::DisputeWindowClosed
+        );
+        require!(load_core_asset(&ctx.accounts.asset).is_ok(), NftError::CheckpointNotRestorable);
+        require!(
+            hash_attributes(&attribute_list) == checkpoint.attributes_hash,
+            NftError::CheckpointAttributesMismatch
+        );
+        verify_collection_membership(&ctx.accounts.asset, ctx.accounts.collection.key)?;
+
+        let nft_state = &mut ctx.accounts.nft_state;
+        nft_state.attributes_hash = write_attributes(
+            &MplCore::new(&ctx.accounts.mpl_core_program, &ctx.accounts.system_program.to_account_info()),
+            &ctx.accounts.asset,
+            &ctx.accounts.collection,
+            &ctx.accounts.authority.to_account_info(),
+            AttributesAuthority::new(&ctx.accounts.attributes_authority, ctx.bumps.attributes_authority),
+            attribute_list,
+        )?;
+        nft_state.level = checkpoint.level;
+        nft_state.rarity = checkpoint.rarity;
+        nft_state.evolution_count = checkpoint.evolution_count;
+        nft_state.fusion_potential = checkpoint.fusion_potential;
+        nft_state.achievement_points = checkpoint.achievement_points;
+        nft_state.lock_state = LockState::Unlocked;
+        nft_state.last_updated = clock.unix_timestamp;
+        checkpoint.restored = true;
+
+        msg!("⏪ Restored {:?} checkpoint #{} | Level: {} | Rarity: {}",
+             checkpoint.operation, checkpoint_index, checkpoint.level, checkpoint.rarity);
+
+        Ok(())
+    }
+
+    /// 🏪 Open the Energy Shop (admin)
+    /// Features: Base refill price, per-refill escalation, daily refill cap
+    pub fn initialize_energy_shop(
+        ctx: Context<InitializeEnergyShop>,
+        refill_base_price_lamports: u64,
+        refill_price_step_bps: u16,
+        refill_energy_amount: u64,
+        max_refills_per_day: u8,
+        max_energy: u64,
+    ) -> Result<()> {
+        let shop = &mut ctx.accounts.shop_config;
+        shop.bump = ctx.bumps.shop_config;
+        shop.configure(
+            refill_base_price_lamports,
+            refill_price_step_bps,
+            refill_energy_amount,
+            max_refills_per_day,
+            max_energy,
+        )
+    }
+
+    /// 🏪 Reprice the Energy Shop (admin)
+    pub fn configure_energy_shop(
+        ctx: Context<ConfigureEnergyShop>,
+        refill_base_price_lamports: u64,
+        refill_price_step_bps: u16,
+        refill_energy_amount: u64,
+        max_refills_per_day: u8,
+        max_energy: u64,
+    ) -> Result<()> {
+        ctx.accounts.shop_config.configure(
+            refill_base_price_lamports,
+            refill_price_step_bps,
+            refill_energy_amount,
+            max_refills_per_day,
+            max_energy,
+        )
+    }
+
+    /// ⚡ Buy an Energy Refill
+    /// Features: Escalating price per refill within a UTC day, daily reset, energy cap
+    pub fn buy_energy_refill(ctx: Context<BuyEnergyRefill>) -> Result<()> {
+        let clock = program_clock(ctx.remaining_accounts)?;
+        let shop = &ctx.accounts.shop_config;
+
+        load_owned_asset(&ctx.accounts.asset, &ctx.accounts.owner.key())?;
+
+        // 📅 Allowance resets at each UTC day boundary
+        let today = clock.unix_timestamp.div_euclid(86400);
+        let nft_state = &mut ctx.accounts.nft_state;
+        if nft_state.last_refill_day != today {
+            nft_state.last_refill_day = today;
+            nft_state.refills_today = 0;
+        }
+        require!(
+            nft_state.refills_today < shop.max_refills_per_day,
+            NftError::DailyRefillLimitReached
+        );
+        require!(nft_state.energy < shop.max_energy, NftError::EnergyFull);
+
+        let perks = ctx
+            .accounts
+            .program_config
+            .perks(&ctx.accounts.player_profile, clock.unix_timestamp);
+        let price = shop.refill_price(nft_state.refills_today)?;
+        let price = discounted(price, perks.fee_discount_bps)?;
+        if price > 0 {
+            system_program::transfer(
+                CpiContext::new(
+                    ctx.accounts.system_program.to_account_info(),
+                    system_program::Transfer {
+                        from: ctx.accounts.owner.to_account_info(),
+                        to: ctx.accounts.treasury.to_account_info(),
+                    },
+                ),
+                price,
+            )?;
+        }
+        ctx.accounts.economy_ledger.record_collected(price)?;
+        ctx.accounts
+            .player_profile
+            .record_spend(ctx.accounts.owner.key(), ctx.bumps.player_profile, price)?;
+
+        let max_pages = ctx.accounts.program_config.max_history_pages;
+        ctx.accounts.player_profile.record_action(
+            &mut ctx.accounts.player_history_page,
+            ctx.bumps.player_history_page,
+            max_pages,
+            PlayerAction::EnergyRefill,
+            ctx.accounts.asset.key(),
+            clock.unix_timestamp,
+        )?;
+
+        let nft_state = &mut ctx.accounts.nft_state;
+        nft_state.energy = nft_state
+            .energy
+            .saturating_add(shop.refill_energy_amount)
+            .saturating_add(perks.bonus_energy)
+            .min(shop.max_energy);
+        nft_state.refills_today += 1;
+
+        msg!("⚡ Energy refilled to {} | Paid {} lamports | Refill {}/{} today",
+             nft_state.energy, price, nft_state.refills_today, shop.max_refills_per_day);
+
+        Ok(())
+    }
+
+    /// 🌳 Publish a Merkle Points Distribution (admin)
+    /// Features: Off-chain computed rewards granted on-chain at scale
+    pub fn publish_points_distribution(
+        ctx: Context<PublishPointsDistribution>,
+        distribution_id: u64,
+        merkle_root: [u8; 32],
+    ) -> Result<()> {
+        let distribution = &mut ctx.accounts.points_distribution;
+        distribution.distribution_id = distribution_id;
+        distribution.merkle_root = merkle_root;
+        distribution.published_at = program_clock(ctx.remaining_accounts)?.unix_timestamp;
+        distribution.total_claimed = 0;
+        distribution.claims = 0;
+        distribution.bump = ctx.bumps.points_distribution;
+
+        msg!("🌳 Points distribution #{} published", distribution_id);
+
+        Ok(())
+    }
+
+    /// 🎯 Claim Achievement Points from a Merkle Distribution
+    /// Features: Proof of (wallet, amount), one claim per (distribution, wallet)
+    pub fn claim_points(
+        ctx: Context<ClaimPoints>,
+        proof: Vec<[u8; 32]>,
+        amount: u64,
+    ) -> Result<()> {
+        let claimant = &ctx.accounts.claimant;
+        let distribution = &ctx.accounts.points_distribution;
+
+        load_owned_asset(&ctx.accounts.asset, &claimant.key())?;
+
+        let leaf = hashv(&[claimant.key().as_ref(), &amount.to_le_bytes()]).to_bytes();
+        require!(
+            verify_merkle_proof(&proof, distribution.merkle_root, leaf),
+            NftError::InvalidMerkleProof
+        );
+
+        let nft_state = &mut ctx.accounts.nft_state;
+        nft_state.achievement_points = nft_state
+            .achievement_points
+            .checked_add(amount)
+            .ok_or(NftError::MathOverflow)?;
+
+        let receipt = &mut ctx.accounts.points_claim;
+        receipt.distribution_id = distribution.distribution_id;
+        receipt.wallet = claimant.key();
+        receipt.amount = amount;
+        receipt.bump = ctx.bumps.points_claim;
+
+        let distribution = &mut ctx.accounts.points_distribution;
+        distribution.total_claimed = distribution
+            .total_claimed
+            .checked_add(amount)
+            .ok_or(NftError::MathOverflow)?;
+        distribution.claims += 1;
+        ctx.accounts.economy_ledger.record_points_issued(amount)?;
+
+        msg!("🎯 Claimed {} achievement points | Total: {}", amount, nft_state.achievement_points);
+
+        Ok(())
+    }
+
+    /// 📡 Create the Signal Board (admin)
+    /// Features: Fixed-layout ring of recent significant events for composability
+    pub fn initialize_signal_board(ctx: Context<InitializeSignalBoard>) -> Result<()> {
+        let board = &mut ctx.accounts.signal_board;
+        board.head = 0;
+        board.total_signals = 0;
+        board.bump = ctx.bumps.signal_board;
+
+        msg!("📡 Signal board initialized with {} slots", SIGNAL_BOARD_SLOTS);
+
+        Ok(())
+    }
+
+    /// 🪂 Create a Funded Airdrop Campaign
+    /// Features: Merkle root of (index, recipient) leaves, SOL escrowed up front
+    pub fn create_airdrop_campaign(
+        ctx: Context<CreateAirdropCampaign>,
+        campaign_id: u64,
+        merkle_root: [u8; 32],
+        total_recipients: u32,
+        amount_per_recipient: u64,
+    ) -> Result<()> {
+        require!(
+            total_recipients > 0 && total_recipients <= MAX_AIRDROP_RECIPIENTS,
+            NftError::InvalidAirdropTerms
+        );
+        require!(amount_per_recipient > 0, NftError::InvalidAirdropTerms);
+
+        let funding = amount_per_recipient
+            .checked_mul(total_recipients as u64)
+            .ok_or(NftError::MathOverflow)?;
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: ctx.accounts.creator.to_account_info(),
+                    to: ctx.accounts.airdrop_campaign.to_account_info(),
+                },
+            ),
+            funding,
+        )?;
+        ctx.accounts.economy_ledger.record_escrow_deposit(funding)?;
+
+        let campaign = &mut ctx.accounts.airdrop_campaign;
+        campaign.creator = ctx.accounts.creator.key();
+        campaign.campaign_id = campaign_id;
+        campaign.merkle_root = merkle_root;
+        campaign.total_recipients = total_recipients;
+        campaign.amount_per_recipient = amount_per_recipient;
+        campaign.delivered = 0;
+        campaign.cursor = 0;
+        campaign.bump = ctx.bumps.airdrop_campaign;
+        campaign.delivered_bitmap = vec![0; AirdropCampaign::bitmap_len(total_recipients)];
+
+        msg!("🪂 Airdrop #{} created | Recipients: {} | Funded: {} lamports",
+             campaign_id, total_recipients, funding);
+
+        Ok(())
+    }
+
+    /// 🎁 Pull-Claim an Airdrop Allocation
+    /// Features: Shares the delivery bitmap with the crank, so no double-sends
+    pub fn claim_airdrop(
+        ctx: Context<ClaimAirdrop>,
+        index: u32,
+        proof: Vec<[u8; 32]>,
+    ) -> Result<()> {
+        let recipient = ctx.accounts.recipient.to_account_info();
+        let campaign_info = ctx.accounts.airdrop_campaign.to_account_info();
+        let campaign = &mut ctx.accounts.airdrop_campaign;
+
+        campaign.verify_recipient(index, recipient.key, &proof)?;
+        require!(!campaign.is_delivered(index), NftError::AirdropAlreadyDelivered);
+
+        transfer_lamports_from_program_account(
+            &campaign_info,
+            &recipient,
+            campaign.amount_per_recipient,
+        )?;
+        campaign.mark_delivered(index);
+        ctx.accounts
+            .economy_ledger
+            .record_reward(campaign.amount_per_recipient)?;
+
+        msg!("🎁 Airdrop #{} claimed by index {} | Delivered: {}/{}",
+             campaign.campaign_id, index, campaign.delivered, campaign.total_recipients);
+
+        Ok(())
+    }
+
+    /// ⚙️ Push-Deliver an Airdrop Batch (permissionless crank)
+    /// Features: Recipients in remaining_accounts, already-delivered entries skipped,
+    /// cursor advances past every contiguous delivered index so batches can resume
+    pub fn crank_airdrop<'info>(
+        ctx: Context<'_, '_, 'info, 'info, CrankAirdrop<'info>>,
+        deliveries: Vec<AirdropDelivery>,
+    ) -> Result<()> {
+        require!(
+            deliveries.len() == ctx.remaining_accounts.len(),
+            NftError::InvalidRemainingAccounts
+        );
+
+        let campaign_info = ctx.accounts.airdrop_campaign.to_account_info();
+        let campaign = &mut ctx.accounts.airdrop_campaign;
+        let mut sent = 0u32;
+
+        for (delivery, recipient) in deliveries.iter().zip(ctx.remaining_accounts.iter()) {
+            campaign.verify_recipient(delivery.index, recipient.key, &delivery.proof)?;
+            // ♻️ A retried batch may overlap work that already landed
+            if campaign.is_delivered(delivery.index) {
+                continue;
+            }
+            require!(recipient.is_writable, NftError::InvalidRemainingAccounts);
+
+            transfer_lamports_from_program_account(
+                &campaign_info,
+                recipient,
+                campaign.amount_per_recipient,
+            )?;
+            campaign.mark_delivered(delivery.index);
+            sent += 1;
+        }
+
+        campaign.advance_cursor();
+        let delivered_lamports = campaign
+            .amount_per_recipient
+            .checked_mul(sent as u64)
+            .ok_or(NftError::MathOverflow)?;
+        ctx.accounts.economy_ledger.record_reward(delivered_lamports)?;
+
+        msg!("⚙️ Airdrop #{} batch: {} sent | Delivered: {}/{} | Cursor: {}",
+             campaign.campaign_id, sent, campaign.delivered, campaign.total_recipients, campaign.cursor);
+
+        Ok(())
+    }
+
+    /// 📒 Create the Economy Ledger (admin)
+    /// Features: Running on-chain totals of every SOL and points flow
+    pub fn initialize_economy_ledger(ctx: Context<InitializeEconomyLedger>) -> Result<()> {
+        let ledger = &mut ctx.accounts.economy_ledger;
+        ledger.sol_collected = 0;
+        ledger.sol_treasury_spent = 0;
+        ledger.sol_rewards_paid = 0;
+        ledger.sol_escrow_deposited = 0;
+        ledger.sol_escrow_released = 0;
+        ledger.points_issued = 0;
+        ledger.sol_burned = 0;
+        ledger.tokens_collected = 0;
+        ledger.bump = ctx.bumps.economy_ledger;
+
+        msg!("📒 Economy ledger initialized");
+
+        Ok(())
+    }
+
+    /// ⏳ Create the Evolution Queue (admin)
+    /// Features: Crank incentive escrowed per request, paid to whoever finalizes it
+    pub fn initialize_evolution_queue(
+        ctx: Context<InitializeEvolutionQueue>,
+        crank_incentive_lamports: u64,
+    ) -> Result<()> {
+        let queue = &mut ctx.accounts.evolution_queue;
+        queue.crank_incentive_lamports = crank_incentive_lamports;
+        queue.processed = 0;
+        queue.bump = ctx.bumps.evolution_queue;
+
+        msg!("⏳ Evolution queue initialized | Crank incentive: {} lamports", crank_incentive_lamports);
+
+        Ok(())
+    }
+
+    /// 📥 Queue an Evolution for Automatic Finalization
+    /// Features: Commits to a future slot hash, locks the NFT until the crank runs
+    pub fn queue_evolution(ctx: Context<QueueEvolution>) -> Result<()> {
+        let clock = program_clock(ctx.remaining_accounts)?;
+        let owner = &ctx.accounts.owner;
+
+        load_owned_asset(&ctx.accounts.asset, &owner.key())?;
+        // ✍️ The crank writes the result, so only the program may hold the Attributes plugin
+        require!(
+            attributes_held_by(&ctx.accounts.asset, ctx.accounts.attributes_authority.key),
+            NftError::AttributesNotMigrated
+        );
+
+        let nft_state = &mut ctx.accounts.nft_state;
+        require!(nft_state.lock_state == LockState::Unlocked, NftError::AssetLocked);
+
+        let required_time = (nft_state.level * 86400)
+            .saturating_sub(nft_state.fusion_potential as u64 * 3600) as i64;
+        let time_since_mint = clock.unix_timestamp - nft_state.mint_date;
+        if time_since_mint < required_time {
+            return Err(fail_with_context(NftError::EvolutionNotReady, required_time, time_since_mint));
+        }
+        require!(
+            ctx.accounts.evolution_queue.entries.len() < MAX_QUEUED_EVOLUTIONS,
+            NftError::EvolutionQueueFull
+        );
+
+        let incentive = ctx.accounts.evolution_queue.crank_incentive_lamports;
+        if incentive > 0 {
+            system_program::transfer(
+                CpiContext::new(
+                    ctx.accounts.system_program.to_account_info(),
+                    system_program::Transfer {
+                        from: owner.to_account_info(),
+                        to: ctx.accounts.evolution_queue.to_account_info(),
+                    },
+                ),
+                incentive,
+            )?;
+        }
+        ctx.accounts.economy_ledger.record_escrow_deposit(incentive)?;
+
+        nft_state.lock_state = LockState::PendingEvolution;
+
+        let reveal_slot = clock.slot + EVOLUTION_REVEAL_DELAY_SLOTS;
+        ctx.accounts.evolution_queue.entries.push(QueuedEvolution {
+            asset: ctx.accounts.asset.key(),
+            owner: owner.key(),
+            reveal_slot,
+            queued_at: clock.unix_timestamp,
+            incentive_lamports: incentive,
+        });
+
+        msg!("📥 Evolution queued | Reveal slot: {} | Queue depth: {}",
+             reveal_slot, ctx.accounts.evolution_queue.entries.len());
+
+        Ok(())
+    }
+
+    /// ⚙️ Finalize Queued Evolutions (permissionless crank)
+    /// Features: (nft_state, asset, collection, owner's player_profile)
+    /// quadruples in remaining_accounts, rolls from the committed slot hash,
+    /// writes the result to the asset's attributes, levels past the cap go to
+    /// the owner's prestige, crank incentive paid per finalized entry. Bonded crankers get
+    /// the first CRANKER_PRIORITY_SLOTS after each reveal slot to themselves;
+    /// a registered cranker passing unqueued or unrevealed states is slashed
+    /// instead of reverted
+    pub fn process_queue<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ProcessEvolutionQueue<'info>>,
+    ) -> Result<()> {
+        let clock = program_clock(ctx.remaining_accounts)?;
+        let queue_info = ctx.accounts.evolution_queue.to_account_info();
+        let cranker_info = ctx.accounts.cranker.to_account_info();
+        let mut finalized: Vec<Pubkey> = Vec::new();
+        let mut incentives = 0u64;
+        let mut rescheduled = 0usize;
+        let mut registration = load_if_initialized::<Cranker>(&ctx.accounts.cranker_registration)?;
+        let priority = registration.as_ref().is_some_and(Cranker::is_bonded);
+        let mut faults = 0u32;
+        let global_event = active_global_event(&ctx.accounts.global_event, clock.unix_timestamp)?;
+        let randomness = Randomness::new(
+            ctx.accounts.program_config.randomness_provider,
+            &ctx.accounts.slot_hashes,
+        );
+
+        let system_program = ctx.accounts.system_program.to_account_info();
+        let quadruples = without_mock_clock(ctx.remaining_accounts);
+        require!(quadruples.len().is_multiple_of(4), NftError::InvalidRemainingAccounts);
+        for quadruple in quadruples.chunks_exact(4) {
+            let (state_info, asset, collection, profile_info) =
+                (&quadruple[0], &quadruple[1], &quadruple[2], &quadruple[3]);
+            let queue = &mut ctx.accounts.evolution_queue;
+            let Some(entry) = queue
+                .entries
+                .iter_mut()
+                .find(|entry| !finalized.contains(&entry.asset) && {
+                    let (expected, _) = Pubkey::find_program_address(
+                        &[b"nft_state", entry.asset.as_ref()],
+                        &crate::ID,
+                    );
+                    expected == *state_info.key
+                })
+            else {
+                // 🪓 Registered crankers pay for bad work rather than reverting it
+                require!(registration.is_some(), NftError::InvalidNftState);
+                faults += 1;
+                continue;
+            };
+
+            if clock.slot <= entry.reveal_slot {
+                if registration.is_some() {
+                    faults += 1;
+                }
+                continue;
+            }
+            if !priority && clock.slot <= entry.reveal_slot + CRANKER_PRIORITY_SLOTS {
+                continue;
+            }
+            // 🔁 Commitment can no longer be revealed - commit to a fresh slot instead
+            let Some(roll_hash) = randomness.revealed(entry.reveal_slot, &[entry.asset.as_ref()])? else {
+                entry.reveal_slot = clock.slot + EVOLUTION_REVEAL_DELAY_SLOTS;
+                rescheduled += 1;
+                continue;
+            };
+
+            let mut nft_state: Account<NftState> = Account::try_from(state_info)?;
+            require_keys_eq!(nft_state.asset, entry.asset, NftError::NftStateAssetMismatch);
+            require_keys_eq!(asset.key(), entry.asset, NftError::NftStateAssetMismatch);
+            if nft_state.lock_state == LockState::PendingEvolution {
+                let mut chance = evolution_chance(nft_state.rarity);
+                if let Some(event) = &global_event {
+                    chance = event.boost_evolution_chance(chance);
+                    event.announce(entry.asset);
+                }
+                verify_collection_membership(asset, collection.key)?;
+                let (_, excess_levels) = resolve_queued_evolution(
+                    &MplCore::new(&ctx.accounts.mpl_core_program, &system_program),
+                    &ctx.accounts.program_config,
+                    &mut nft_state,
+                    asset,
+                    collection,
+                    &cranker_info,
+                    AttributesAuthority::new(&ctx.accounts.attributes_authority, ctx.bumps.attributes_authority),
+                    &roll_hash,
+                    chance,
+                    clock.unix_timestamp,
+                )?;
+                nft_state.exit(&crate::ID)?;
+                // 🎖️ Levels past the cap go to whoever holds the asset now
+                credit_prestige(
+                    profile_info,
+                    &load_core_asset(asset)?.owner,
+                    entry.asset,
+                    excess_levels,
+                    &cranker_info,
+                    &system_program,
+                )?;
+            }
+
+            incentives = incentives
+                .checked_add(entry.incentive_lamports)
+                .ok_or(NftError::MathOverflow)?;
+            finalized.push(entry.asset);
+        }
+
+        require!(
+            !finalized.is_empty() || rescheduled > 0 || faults > 0,
+            NftError::NothingToProcess
+        );
+
+        if incentives > 0 {
+            transfer_lamports_from_program_account(&queue_info, &cranker_info, incentives)?;
+        }
+        ctx.accounts.economy_ledger.record_escrow_release(incentives)?;
+
+        if let Some(cranker) = registration.as_mut() {
+            cranker.jobs_completed += finalized.len() as u64;
+            let slashed = cranker.slash(faults)?;
+            let registration_info = &ctx.accounts.cranker_registration;
+            cranker.try_serialize(&mut &mut registration_info.try_borrow_mut_data()?[..])?;
+            if slashed > 0 {
+                transfer_lamports_from_program_account(
+                    registration_info,
+                    &ctx.accounts.treasury.to_account_info(),
+                    slashed,
+                )?;
+                let ledger = &mut ctx.accounts.economy_ledger;
+                ledger.record_escrow_release(slashed)?;
+                ledger.record_collected(slashed)?;
+                emit!(CrankerSlashed {
+                    wallet: cranker.wallet,
+                    faults,
+                    slashed_lamports: slashed,
+                    remaining_bond: cranker.bond_lamports,
+                });
+            }
+        }
+
+        let queue = &mut ctx.accounts.evolution_queue;
+        queue.entries.retain(|entry| !finalized.contains(&entry.asset));
+        queue.processed += finalized.len() as u64;
+
+        msg!("⚙️ Finalized {} queued evolutions | Crank paid {} lamports | Remaining: {}",
+             finalized.len(), incentives, queue.entries.len());
+
+        Ok(())
+    }
+
+    /// 🌐 Register a Collection's Metadata Base URI (admin)
+    /// Features: Content-hash commitment recorded alongside every base URI
+    pub fn initialize_collection_uri(
+        ctx: Context<InitializeCollectionUri>,
+        base_uri: String,
+        proof_hash: [u8; 32],
+    ) -> Result<()> {
+        require!(
+            !base_uri.is_empty() && base_uri.len() <= MAX_BASE_URI_LEN,
+            NftError::InvalidBaseUri
+        );
+
+        let uri_config = &mut ctx.accounts.collection_uri;
+        uri_config.collection = ctx.accounts.collection.key();
+        uri_config.base_uri = base_uri;
+        uri_config.proof_hash = proof_hash;
+        uri_config.version = 1;
+        uri_config.pending_base_uri = String::new();
+        uri_config.pending_proof_hash = [0; 32];
+        uri_config.pending_effective_at = 0;
+        uri_config.history = Vec::new();
+        uri_config.bump = ctx.bumps.collection_uri;
+
+        msg!("🌐 Base URI registered: {}", uri_config.base_uri);
+
+        Ok(())
+    }
+
+    /// 🔄 Schedule a Base URI Rotation (admin, timelocked)
+    /// Features: Takes effect after BASE_URI_ROTATION_TIMELOCK, old base kept for provenance
+    pub fn rotate_base_uri(
+        ctx: Context<RotateBaseUri>,
+        new_base: String,
+        proof_hash: [u8; 32],
+    ) -> Result<()> {
+        let now = program_clock(ctx.remaining_accounts)?.unix_timestamp;
+        require!(
+            !new_base.is_empty() && new_base.len() <= MAX_BASE_URI_LEN,
+            NftError::InvalidBaseUri
+        );
+
+        let uri_config = &mut ctx.accounts.collection_uri;
+        uri_config.promote_pending(now);
+        require!(uri_config.pending_effective_at == 0, NftError::UriRotationPending);
+        require!(new_base != uri_config.base_uri, NftError::InvalidBaseUri);
+
+        uri_config.pending_base_uri = new_base;
+        uri_config.pending_proof_hash = proof_hash;
+        uri_config.pending_effective_at = now + BASE_URI_ROTATION_TIMELOCK;
+
+        msg!("🔄 Base URI rotation to {} scheduled for {}",
+             uri_config.pending_base_uri, uri_config.pending_effective_at);
+
+        Ok(())
+    }
+
+    /// 🔁 Migrate One Asset to the Current Base URI (permissionless)
+    /// Features: Keeps the asset's path suffix, only rewrites a recorded previous base
+    pub fn resync_asset_uri(ctx: Context<ResyncAssetUri>) -> Result<()> {
+        let now = program_clock(ctx.remaining_accounts)?.unix_timestamp;
+        let uri_config = &mut ctx.accounts.collection_uri;
+        uri_config.promote_pending(now);
+
+        let asset = &ctx.accounts.asset;
+        verify_collection_membership(asset, &uri_config.collection)?;
+        let current_uri = BaseAssetV1::from_bytes(&asset.try_borrow_data()?)
+            .map_err(|_| error!(NftError::InvalidAsset))?
+            .uri;
+
+        require!(!current_uri.starts_with(&uri_config.base_uri), NftError::AssetUriCurrent);
+        let previous = uri_config
+            .history
+            .iter()
+            .filter(|retired| current_uri.starts_with(&retired.base_uri))
+            .max_by_key(|retired| retired.base_uri.len())
+            .ok_or(NftError::UnknownBaseUri)?;
+        let new_uri = format!("{}{}", uri_config.base_uri, &current_uri[previous.base_uri.len()..]);
+
+        let payer = ctx.accounts.payer.to_account_info();
+        let uri_authority = ctx.accounts.uri_authority.to_account_info();
+        let system_program = ctx.accounts.system_program.to_account_info();
+        MplCore::new(&ctx.accounts.mpl_core_program, &system_program).update(
+            AssetCall::new(asset, &ctx.accounts.collection, &payer, &uri_authority)
+                .signed(&[&[b"uri_authority", &[ctx.bumps.uri_authority]]]),
+            None,
+            Some(new_uri.clone()),
+        )?;
+
+        msg!("🔁 Asset URI v{} → v{}: {}", previous.version, uri_config.version, new_uri);
+
+        Ok(())
+    }
+
+    /// 🔒 Mint and Stake in One Instruction
+    /// Features: Asset created already frozen under the stake authority, no freeze race
+    pub fn mint_and_stake(
+        ctx: Context<MintAndStake>,
+        name: String,
+        uri: String,
+        level: u64,
+        rarity: String,
+        fusion_potential: u64,
+    ) -> Result<()> {
+        let clock = program_clock(ctx.remaining_accounts)?;
+        enforce_single_mint(&ctx.accounts.collection_config, &ctx.accounts.instructions_sysvar)?;
+        let payer = ctx.accounts.payer.to_account_info();
+
+        // 🍀 A full charge of streak luck turns the mint Legendary
+        let lucky = ctx.accounts.player_profile.take_mint_luck(payer.key(), ctx.bumps.player_profile, clock.unix_timestamp);
+        // 🏛️ Program-created collections cap supply, price and plugin bundle
+        ctx.accounts.mint_receipt.bind(ctx.accounts.collection.key(), payer.key(), ctx.bumps.mint_receipt);
+        let plugins = charge_collection_mint(
+            &ctx.accounts.collection_config,
+            &ctx.accounts.mint_shard,
+            &mut ctx.accounts.mint_receipt,
+            &payer,
+            &ctx.accounts.treasury.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            &mut ctx.accounts.economy_ledger,
+            1,
+            clock.unix_timestamp,
+            None,
+        )?;
+        let achievement_tiers = load_if_initialized::<AchievementTiers>(&ctx.accounts.achievement_tiers)?;
+        let edition = claim_editions(
+            &ctx.accounts.collection_config,
+            ctx.accounts.edition_counter.as_deref_mut(),
+            1,
+        )?;
+
+        let minted_rarity = mint_core_asset(
+            &MplCore::new(&ctx.accounts.mpl_core_program, &ctx.accounts.system_program.to_account_info()),
+            &ctx.accounts.asset,
+            &ctx.accounts.collection,
+            &payer,
+            name,
+            uri,
+            level,
+            &rarity,
+            fusion_potential,
+            lucky,
+            // 🔒 Our own frozen FreezeDelegate replaces any thawed one in the bundle
+            plugins
+                .into_iter()
+                .filter(|pair| !matches!(pair.plugin, Plugin::FreezeDelegate(_)))
+                .chain([PluginAuthorityPair {
+                    plugin: Plugin::FreezeDelegate(FreezeDelegate { frozen: true }),
+                    authority: Some(PluginAuthority::Address {
+                        address: ctx.accounts.stake_authority.key(),
+                    }),
+                }])
+                .collect(),
+            edition,
+            None,
+            None,
+            None,
+            achievement_tiers.as_ref(),
+            &ctx.accounts.program_config,
+            &clock,
+        )?;
+        ctx.accounts.signal_board.record(
+            SignalKind::Mint,
+            ctx.accounts.asset.key(),
+            level,
+            &minted_rarity,
+            &clock,
+        );
+
+        let stake = &mut ctx.accounts.stake_record;
+        stake.owner = payer.key();
+        stake.asset = ctx.accounts.asset.key();
+        stake.staked_at = clock.unix_timestamp;
+        stake.bump = ctx.bumps.stake_record;
+        ctx.accounts.staked_index.insert(stake.asset, stake.owner)?;
+
+        msg!("🔒 Minted and staked at {}", stake.staked_at);
+
+        Ok(())
+    }
+
+    /// 🥩 Stake an Owned NFT
+    /// Features: Freeze delegated to the program's stake authority while staked
+    pub fn stake_nft(ctx: Context<StakeNFT>) -> Result<()> {
+        let owner = ctx.accounts.owner.to_account_info();
+        let stake_authority = ctx.accounts.stake_authority.to_account_info();
+        let system_program = ctx.accounts.system_program.to_account_info();
+
+        load_owned_asset(&ctx.accounts.asset, owner.key)?;
+
+        // ♻️ Previously staked or frozen assets keep a freeze plugin we still
+        // control; one frozen by `freeze_asset` stays out of staking until thawed
+        let freeze_plugin =
+            fetch_plugin::<BaseAssetV1, FreezeDelegate>(&ctx.accounts.asset, PluginType::FreezeDelegate).ok();
+        if let Some((_, freeze, _)) = freeze_plugin {
+            require!(!freeze.frozen, NftError::AssetAlreadyFrozen);
+            set_stake_freeze(
+                &MplCore::new(&ctx.accounts.mpl_core_program, &system_program),
+                &ctx.accounts.asset,
+                &ctx.accounts.collection,
+                &owner,
+                &stake_authority,
+                ctx.bumps.stake_authority,
+                true,
+            )?;
+        } else {
+            MplCore::new(&ctx.accounts.mpl_core_program, &system_program).add_plugin(
+                AssetCall::new(&ctx.accounts.asset, &ctx.accounts.collection, &owner, &owner),
+                Plugin::FreezeDelegate(FreezeDelegate { frozen: true }),
+                Some(PluginAuthority::Address { address: stake_authority.key() }),
+            )?;
+        }
+
+        let stake = &mut ctx.accounts.stake_record;
+        stake.owner = owner.key();
+        stake.asset = ctx.accounts.asset.key();
+        stake.staked_at = program_clock(ctx.remaining_accounts)?.unix_timestamp;
+        stake.idle_claimed_at = stake.staked_at;
+        stake.bump = ctx.bumps.stake_record;
+        ctx.accounts.staked_index.insert(stake.asset, stake.owner)?;
+
+        msg!("🥩 NFT staked at {}", stake.staked_at);
+
+        Ok(())
+    }
+
+    /// 🔓 Unstake an NFT
+    /// Features: Thaws the asset and closes the stake record back to the owner
+    pub fn unstake_nft(ctx: Context<UnstakeNFT>) -> Result<()> {
+        let staked_for = program_clock(ctx.remaining_accounts)?.unix_timestamp - ctx.accounts.stake_record.staked_at;
+
+        set_stake_freeze(
+            &MplCore::new(&ctx.accounts.mpl_core_program, &ctx.accounts.system_program.to_account_info()),
+            &ctx.accounts.asset,
+            &ctx.accounts.collection,
+            &ctx.accounts.owner.to_account_info(),
+            &ctx.accounts.stake_authority,
+            ctx.bumps.stake_authority,
+            false,
+        )?;
+        ctx.accounts.staked_index.remove(&ctx.accounts.asset.key())?;
+
+        msg!("🔓 NFT unstaked after {}s", staked_for);
+
+        Ok(())
+    }
+
+    /// 📤 Export Configs to the Successor Program
+    /// Features: Callable only via CPI signed by the successor's migration PDA,
+    /// returns a StateExport as return data
+    pub fn export_state(ctx: Context<ExportState>) -> Result<StateExport> {
+        let config = &ctx.accounts.program_config;
+        require!(config.successor_program != Pubkey::default(), NftError::NoSuccessorProgram);
+        let (expected_authority, _) =
+            Pubkey::find_program_address(&[b"migration"], &config.successor_program);
+        require_keys_eq!(
+            ctx.accounts.migration_authority.key(),
+            expected_authority,
+            NftError::Unauthorized
+        );
+
+        let shop = &ctx.accounts.shop_config;
+        let uri_config = &ctx.accounts.collection_uri;
+
+        msg!("📤 Exporting configs for collection {} to {}",
+             uri_config.collection, config.successor_program);
+
+        Ok(StateExport {
+            trait_fee_bps: config.trait_fee_bps,
+            max_attributes: config.max_attributes,
+            max_attribute_bytes: config.max_attribute_bytes,
+            attribute_overflow_policy: config.attribute_overflow_policy,
+            quote_signer: config.quote_signer,
+            fusion_confirmation_window_seconds: config.fusion_confirmation_window_seconds,
+            dispute_window_seconds: config.dispute_window_seconds,
+            lock_identity_after_evolution: config.lock_identity_after_evolution,
+            vip_tiers: config.vip_tiers.clone(),
+            fusion_outcome_table: config.fusion_outcome_table,
+            royalty_mode: config.royalty_mode,
+            rarity_fee_bps: config.rarity_fee_bps,
+            evolution_downgrade_bps: config.evolution_downgrade_bps,
+            rule_versions: config.rule_versions,
+            subscription_plan: config.subscription_plan,
+            randomness_provider: config.randomness_provider,
+            fusion_fee: config.fusion_fee,
+            max_history_pages: config.max_history_pages,
+            recall_compensation_lamports: config.recall_compensation_lamports,
+            split_retain_bps: config.split_retain_bps,
+            transfer_restrictions: config.transfer_restrictions.clone(),
+            xp_transfer_loss_bps: config.xp_transfer_loss_bps,
+            xp_donor_min_level: config.xp_donor_min_level,
+            attribute_diff_events: config.attribute_diff_events,
+            max_level: config.max_level,
+            docs_base_uri: config.docs_base_uri.clone(),
+            refill_base_price_lamports: shop.refill_base_price_lamports,
+            refill_price_step_bps: shop.refill_price_step_bps,
+            refill_energy_amount: shop.refill_energy_amount,
+            max_refills_per_day: shop.max_refills_per_day,
+            max_energy: shop.max_energy,
+            collection: uri_config.collection,
+            base_uri: uri_config.base_uri.clone(),
+            base_uri_proof_hash: uri_config.proof_hash,
+            base_uri_version: uri_config.version,
+        })
+    }
+
+    /// 📥 Adopt a Predecessor Deployment's Configs (admin)
+    /// Features: CPIs into the predecessor's export_state and recreates the configs here
+    pub fn import_state(ctx: Context<ImportState>) -> Result<()> {
+        let predecessor = ctx.accounts.predecessor_program.key();
+
+        let export_ix = Instruction {
+            program_id: predecessor,
+            accounts: vec![
+                AccountMeta::new_readonly(ctx.accounts.migration_authority.key(), true),
+                AccountMeta::new_readonly(ctx.accounts.predecessor_program_config.key(), false),
+                AccountMeta::new_readonly(ctx.accounts.predecessor_shop_config.key(), false),
+                AccountMeta::new_readonly(ctx.accounts.predecessor_collection_uri.key(), false),
+            ],
+            data: instruction::ExportState::DISCRIMINATOR.to_vec(),
+        };
+        invoke_signed(
+            &export_ix,
+            &[
+                ctx.accounts.migration_authority.to_account_info(),
+                ctx.accounts.predecessor_program_config.to_account_info(),
+                ctx.accounts.predecessor_shop_config.to_account_info(),
+                ctx.accounts.predecessor_collection_uri.to_account_info(),
+            ],
+            &[&[b"migration", &[ctx.bumps.migration_authority]]],
+        )?;
+
+        let (returning_program, data) = get_return_data().ok_or(NftError::InvalidStateExport)?;
+        require_keys_eq!(returning_program, predecessor, NftError::InvalidStateExport);
+        let export = StateExport::deserialize(&mut &data[..])
+            .map_err(|_| error!(NftError::InvalidStateExport))?;
+        require_keys_eq!(
+            export.collection,
+            ctx.accounts.collection.key(),
+            NftError::InvalidStateExport
+        );
+        require!(export.vip_tiers.len() <= MAX_VIP_TIERS, NftError::InvalidStateExport);
+        require!(export.docs_base_uri.len() <= MAX_BASE_URI_LEN, NftError::InvalidStateExport);
+
+        let config = &mut ctx.accounts.program_config;
+        config.trait_fee_bps = export.trait_fee_bps;
+        config.max_attributes = export.max_attributes;
+        config.max_attribute_bytes = export.max_attribute_bytes;
+        config.attribute_overflow_policy = export.attribute_overflow_policy;
+        config.quote_signer = export.quote_signer;
+        config.fusion_confirmation_window_seconds = export.fusion_confirmation_window_seconds;
+        config.dispute_window_seconds = export.dispute_window_seconds;
+        config.lock_identity_after_evolution = export.lock_identity_after_evolution;
+        config.vip_tiers = export.vip_tiers;
+        config.fusion_outcome_table = export.fusion_outcome_table;
+        config.royalty_mode = export.royalty_mode;
+        config.rarity_fee_bps = export.rarity_fee_bps;
+        config.evolution_downgrade_bps = export.evolution_downgrade_bps;
+        config.rule_versions = export.rule_versions;
+        config.subscription_plan = export.subscription_plan;
+        config.randomness_provider = export.randomness_provider;
+        config.fusion_fee = export.fusion_fee;
+        config.max_history_pages = export.max_history_pages;
+        config.recall_compensation_lamports = export.recall_compensation_lamports;
+        config.split_retain_bps = export.split_retain_bps;
+        config.transfer_restrictions = export.transfer_restrictions;
+        config.xp_transfer_loss_bps = export.xp_transfer_loss_bps;
+        config.xp_donor_min_level = export.xp_donor_min_level;
+        config.attribute_diff_events = export.attribute_diff_events;
+        config.max_level = export.max_level;
+        config.docs_base_uri = export.docs_base_uri;
+
+        let shop = &mut ctx.accounts.shop_config;
+        shop.bump = ctx.bumps.shop_config;
+        shop.configure(
+            export.refill_base_price_lamports,
+            export.refill_price_step_bps,
+            export.refill_energy_amount,
+            export.max_refills_per_day,
+            export.max_energy,
+        )?;
+
+        let uri_config = &mut ctx.accounts.collection_uri;
+        uri_config.collection = export.collection;
+        uri_config.base_uri = export.base_uri;
+        uri_config.proof_hash = export.base_uri_proof_hash;
+        uri_config.version = export.base_uri_version;
+        uri_config.pending_base_uri = String::new();
+        uri_config.pending_proof_hash = [0; 32];
+        uri_config.pending_effective_at = 0;
+        uri_config.history = Vec::new();
+        uri_config.bump = ctx.bumps.collection_uri;
+
+        msg!("📥 Imported configs for collection {} from {}", export.collection, predecessor);
+
+        Ok(())
+    }
+
+    /// 🕰️ Pin the Program Clock for Tests (admin, `test-clock` builds only)
+    /// Features: Handlers read this timestamp when the MockClock PDA is passed last
+    /// in remaining_accounts; zero hands control back to the real clock
+    pub fn set_mock_clock(ctx: Context<SetMockClock>, unix_timestamp: i64) -> Result<()> {
+        require!(cfg!(feature = "test-clock"), NftError::TestClockDisabled);
+
+        let mock_clock = &mut ctx.accounts.mock_clock;
+        mock_clock.unix_timestamp = unix_timestamp;
+        mock_clock.bump = ctx.bumps.mock_clock;
+
+        msg!("🕰️ Mock clock set to {}", unix_timestamp);
+
+        Ok(())
+    }
+
+    /// 🤝 Grant a Scoped Delegation
+    /// Features: Lets a game server act on one asset within a single scope,
+    /// re-granting overwrites the previous scope
+    pub fn grant_delegation(ctx: Context<GrantDelegation>, scope: DelegationScope) -> Result<()> {
+        load_owned_asset(&ctx.accounts.asset, &ctx.accounts.owner.key())?;
+
+        let delegation = &mut ctx.accounts.delegation;
+        delegation.asset = ctx.accounts.asset.key();
+        delegation.owner = ctx.accounts.owner.key();
+        delegation.delegate = ctx.accounts.delegate.key();
+        delegation.scope = scope;
+        delegation.bump = ctx.bumps.delegation;
+
+        msg!("🤝 Delegated {:?} on {} to {}", scope, delegation.asset, delegation.delegate);
+
+        Ok(())
+    }
+
+    /// ✂️ Revoke a Delegation
+    /// Features: Closes the delegation PDA back to the granting owner
+    pub fn revoke_delegation(ctx: Context<RevokeDelegation>) -> Result<()> {
+        msg!("✂️ Delegation revoked for {}", ctx.accounts.delegation.delegate);
+
+        Ok(())
+    }
+
+    /// 🗂️ Staked Index Bootstrap
+    /// Features: Enumerable list of every staked asset, kept in sync by stake/unstake
+    pub fn initialize_staked_index(ctx: Context<InitializeStakedIndex>) -> Result<()> {
+        let index = &mut ctx.accounts.staked_index;
+        index.entries = Vec::new();
+        index.draw_pending = false;
+        index.bump = ctx.bumps.staked_index;
+
+        msg!("🗂️ Staked index initialized");
+
+        Ok(())
+    }
+
+    /// 🎟️ Holder Lottery Bootstrap
+    /// Features: Epoch-aligned draws, fixed treasury-funded prize per epoch
+    pub fn initialize_holder_lottery(
+        ctx: Context<InitializeHolderLottery>,
+        epoch_seconds: i64,
+        prize_lamports: u64,
+    ) -> Result<()> {
+        let now = program_clock(ctx.remaining_accounts)?.unix_timestamp;
+        let lottery = &mut ctx.accounts.holder_lottery;
+        lottery.configure(epoch_seconds, prize_lamports)?;
+        lottery.epoch = 0;
+        lottery.next_draw_at = lottery.next_boundary(now);
+        lottery.winner = Pubkey::default();
+        lottery.winner_asset = Pubkey::default();
+        lottery.unclaimed_prize = 0;
+        lottery.reveal_slot = 0;
+        lottery.bump = ctx.bumps.holder_lottery;
+
+        msg!("🎟️ Holder lottery opens | First draw at {}", lottery.next_draw_at);
+
+        Ok(())
+    }
+
+    /// 🎟️ Retune the Holder Lottery (admin)
+    pub fn configure_holder_lottery(
+        ctx: Context<ConfigureHolderLottery>,
+        epoch_seconds: i64,
+        prize_lamports: u64,
+    ) -> Result<()> {
+        ctx.accounts.holder_lottery.configure(epoch_seconds, prize_lamports)
+    }
+
+    /// 🎰 Draw the Holder Lottery
+    /// Features: Permissionless once the epoch boundary passes. The first call
+    /// commits to a future slot and freezes the staked index; a call after that
+    /// slot reveals its hash and picks the winner uniformly from the frozen
+    /// index, prize escrowed until the winner claims. An unclaimed prize from
+    /// the previous epoch rolls into this one.
+    pub fn draw_lottery(ctx: Context<DrawLottery>) -> Result<()> {
+        let clock = program_clock(ctx.remaining_accounts)?;
+        let now = clock.unix_timestamp;
+        let lottery = &ctx.accounts.holder_lottery;
+        require!(now >= lottery.next_draw_at, NftError::LotteryNotDue);
+        require!(!ctx.accounts.staked_index.entries.is_empty(), NftError::NoStakedAssets);
+
+        // 🔒 Commit first, so no one knows the seed while the population can still change
+        if lottery.reveal_slot == 0 {
+            ctx.accounts.holder_lottery.reveal_slot = clock.slot + LOTTERY_REVEAL_DELAY_SLOTS;
+            ctx.accounts.staked_index.draw_pending = true;
+            msg!("🎰 Epoch {} draw committed to slot {}",
+                 ctx.accounts.holder_lottery.epoch + 1, ctx.accounts.holder_lottery.reveal_slot);
+            return Ok(());
+        }
+        require!(clock.slot > lottery.reveal_slot, NftError::LotteryRevealPending);
+        let randomness = Randomness::new(
+            ctx.accounts.program_config.randomness_provider,
+            &ctx.accounts.slot_hashes,
+        );
+        // 🔁 Commitment can no longer be revealed - commit to a fresh slot instead
+        let Some(seed) = randomness.revealed(lottery.reveal_slot, &[&lottery.epoch.to_le_bytes()])? else {
+            ctx.accounts.holder_lottery.reveal_slot = clock.slot + LOTTERY_REVEAL_DELAY_SLOTS;
+            msg!("🔁 Epoch {} draw recommitted to slot {}",
+                 ctx.accounts.holder_lottery.epoch + 1, ctx.accounts.holder_lottery.reveal_slot);
+            return Ok(());
+        };
+
+        // 🏦 Fund this epoch's prize without dipping below rent exemption
+        let prize = lottery.prize_lamports;
+        let rent_exempt = Rent::get()?.minimum_balance(0);
+        require!(
+            ctx.accounts.treasury.lamports() >= prize + rent_exempt,
+            NftError::InsufficientTreasuryBalance
+        );
+        pay_from_treasury(
+            &ctx.accounts.treasury,
+            &ctx.accounts.holder_lottery.to_account_info(),
+            &ctx.accounts.system_program,
+            ctx.accounts.program_config.treasury_bump,
+            prize,
+        )?;
+        let ledger = &mut ctx.accounts.economy_ledger;
+        ledger.record_treasury_spent(prize)?;
+        ledger.record_escrow_deposit(prize)?;
+
+        // 🎲 Uniform pick over the staked population frozen at commit
+        let index = &mut ctx.accounts.staked_index;
+        let winner = index.entries[roll_below(&seed, 0, index.entries.len() as u64) as usize];
+        index.draw_pending = false;
+
+        let lottery = &mut ctx.accounts.holder_lottery;
+        lottery.reveal_slot = 0;
+        lottery.epoch += 1;
+        lottery.next_draw_at = lottery.next_boundary(now);
+        lottery.winner = winner.owner;
+        lottery.winner_asset = winner.asset;
+        lottery.unclaimed_prize = lottery
+            .unclaimed_prize
+            .checked_add(prize)
+            .ok_or(NftError::MathOverflow)?;
+
+        msg!("🎰 Epoch {} lottery won by {} | Prize: {} lamports",
+             lottery.epoch, winner.asset, lottery.unclaimed_prize);
+
+        Ok(())
+    }
+
+    /// 💰 Claim the Holder Lottery Prize
+    /// Features: Only the staker of the winning asset, before the next draw
+    pub fn claim_lottery_prize(ctx: Context<ClaimLotteryPrize>) -> Result<()> {
+        let prize = ctx.accounts.holder_lottery.unclaimed_prize;
+        require!(prize > 0, NftError::NothingToClaim);
+
+        transfer_lamports_from_program_account(
+            &ctx.accounts.holder_lottery.to_account_info(),
+            &ctx.accounts.winner.to_account_info(),
+            prize,
+        )?;
+        ctx.accounts.economy_ledger.record_reward(prize)?;
+        ctx.accounts.holder_lottery.unclaimed_prize = 0;
+
+        msg!("💰 Lottery prize of {} lamports claimed", prize);
+
+        Ok(())
+    }
+
+    /// 🌍 Configure a Collection's Region Lock (admin)
+    /// Features: Per-collection toggle, KYC oracle key whose ed25519 attestations
+    /// `mint_nft` requires while the lock is enabled
+    pub fn set_region_lock(
+        ctx: Context<SetRegionLock>,
+        collection: Pubkey,
+        kyc_oracle: Pubkey,
+        enabled: bool,
+    ) -> Result<()> {
+        require!(
+            !enabled || kyc_oracle != Pubkey::default(),
+            NftError::RegionAttestationRequired
+        );
+
+        let region_lock = &mut ctx.accounts.region_lock;
+        region_lock.collection = collection;
+        region_lock.kyc_oracle = kyc_oracle;
+        region_lock.enabled = enabled;
+        region_lock.bump = ctx.bumps.region_lock;
+
+        msg!("🌍 Region lock for {} {} | Oracle: {}",
+             collection, if enabled { "enabled" } else { "disabled" }, kyc_oracle);
+
+        Ok(())
+    }
+
+    /// 🔮 Simulate a Mint
+    /// Features: Runs the full mint attribute pipeline with no CPI and no state
+    /// change, returning the resulting attributes as return data. Defaults to
+    /// the current time; pass `unix_timestamp` to preview a mint at another time.
+    pub fn simulate_mint(
+        ctx: Context<SimulateMint>,
+        level: u64,
+        rarity: String,
+        fusion_potential: u64,
+        unix_timestamp: Option<i64>,
+    ) -> Result<MintPreview> {
+        let unix_timestamp = match unix_timestamp {
+            Some(unix_timestamp) => unix_timestamp,
+            None => program_clock(ctx.remaining_accounts)?.unix_timestamp,
+        };
+        let roll = roll_mint(level, &rarity, fusion_potential, unix_timestamp, false, None);
+
+        Ok(MintPreview {
+            rarity: roll.rarity,
+            attributes: roll
+                .attributes
+                .into_iter()
+                .map(|attribute| PreviewAttribute { key: attribute.key, value: attribute.value })
+                .collect(),
+        })
+    }
+
+    /// 🪪 Describe an Asset for Wallets
+    /// Features: Read-only; gathers lock status, cooldown inputs, the live
+    /// buff and any pending listing, rental, stake or recall into one
+    /// versioned AssetStatus returned as return data, for a single simulated call
+    pub fn describe_asset(ctx: Context<DescribeAsset>) -> Result<AssetStatus> {
+        let now = program_clock(ctx.remaining_accounts)?.unix_timestamp;
+        let nft_state = &ctx.accounts.nft_state;
+        let today = now.div_euclid(86400);
+
+        let listing = load_if_initialized::<Listing>(&ctx.accounts.listing)?;
+        let rental = load_if_initialized::<RentalAgreement>(&ctx.accounts.rental)?;
+        let forced_recall = load_if_initialized::<ForcedRecall>(&ctx.accounts.forced_recall)?;
+
+        Ok(AssetStatus {
+            version: ASSET_STATUS_VERSION,
+            asset: nft_state.asset,
+            level: nft_state.level,
+            rarity: nft_state.rarity,
+            lock_state: nft_state.lock_state,
+            energy: nft_state.energy,
+            last_updated: nft_state.last_updated,
+            cooldown_multiplier: cooldown_multiplier(nft_state.rarity) as u8,
+            refills_today: if nft_state.last_refill_day == today { nft_state.refills_today } else { 0 },
+            active_event: active_global_event(&ctx.accounts.global_event, now)?.map(|event| ActiveBuff {
+                name: event.name,
+                evolution_chance_bonus_bps: event.evolution_chance_bonus_bps,
+                cooldown_reduction_bps: event.cooldown_reduction_bps,
+                ends_at: event.ends_at,
+            }),
+            listed_price_lamports: listing.map(|listing| listing.price_lamports),
+            staked: ctx.accounts.stake_record.owner == &crate::ID,
+            rented_until: rental.filter(RentalAgreement::is_active).map(|rental| rental.ends_at),
+            recall_executable_at: forced_recall
+                .filter(|recall| recall.executed_at == 0)
+                .map(|recall| recall.executable_at),
+        })
+    }
+
+    /// 🔗 Bind an NftState to its Asset
+    /// Features: Permissionless; records the asset key and PDA bump on state
+    /// accounts created before they were stored, and verifies them otherwise
+    pub fn bind_nft_state(ctx: Context<BindNftState>) -> Result<()> {
+        let asset = ctx.accounts.asset.key();
+        ctx.accounts.nft_state.bind_asset(asset, ctx.bumps.nft_state)?;
+
+        msg!("🔗 NftState bound to {}", asset);
+
+        Ok(())
+    }
+
+    /// 📊 Snapshot a Page of NftStates
+    /// Features: Permissionless crank; folds NftState accounts passed as
+    /// remaining accounts into the current epoch's running totals. Pages must
+    /// be passed in strictly ascending address order, across calls as well,
+    /// so no state can be counted twice.
+    pub fn snapshot_states<'info>(
+        ctx: Context<'_, '_, 'info, 'info, SnapshotStates<'info>>,
+        epoch: u64,
+    ) -> Result<()> {
+        let clock = program_clock(ctx.remaining_accounts)?;
+        require!(epoch == clock.epoch, NftError::SnapshotEpochClosed);
+
+        let page = &mut ctx.accounts.snapshot_page;
+        if page.states_counted == 0 {
+            page.epoch = epoch;
+            page.bump = ctx.bumps.snapshot_page;
+        }
+
+        let mut counted = 0u32;
+        for state_info in without_mock_clock(ctx.remaining_accounts) {
+            require!(*state_info.key > page.last_state, NftError::SnapshotOutOfOrder);
+            require_keys_eq!(*state_info.owner, crate::ID, NftError::InvalidNftState);
+            let nft_state = NftState::try_deserialize(&mut &state_info.try_borrow_data()?[..])?;
+            let expected = Pubkey::create_program_address(
+                &[b"nft_state", nft_state.asset.as_ref(), &[nft_state.bump]],
+                &crate::ID,
+            )
+            .map_err(|_| error!(NftError::InvalidNftState))?;
+            require_keys_eq!(*state_info.key, expected, NftError::InvalidNftState);
+
+            page.record(&nft_state)?;
+            page.last_state = *state_info.key;
+            counted += 1;
+        }
+        require!(counted > 0, NftError::NothingToProcess);
+
+        msg!("📊 Epoch {} snapshot: +{} states | Total: {} | Average level: {}",
+             epoch, counted, page.states_counted, page.average_level());
+
+        Ok(())
+    }
+
+    /// 🎨 Create a Community Bounty
+    /// Features: Reward escrowed up front against a hash of the contest rules,
+    /// optional commemorative NFT for the winner
+    pub fn create_bounty(
+        ctx: Context<CreateBounty>,
+        bounty_id: u64,
+        requirements_hash: [u8; 32],
+        reward_lamports: u64,
+        commemorative_uri: Option<String>,
+    ) -> Result<()> {
+        require!(reward_lamports > 0, NftError::InvalidBountyTerms);
+        require!(
+            commemorative_uri.as_ref().is_none_or(|uri| uri.len() <= MAX_BASE_URI_LEN),
+            NftError::InvalidBountyTerms
+        );
+
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: ctx.accounts.creator.to_account_info(),
+                    to: ctx.accounts.bounty.to_account_info(),
+                },
+            ),
+            reward_lamports,
+        )?;
+        ctx.accounts.economy_ledger.record_escrow_deposit(reward_lamports)?;
+
+        let bounty = &mut ctx.accounts.bounty;
+        bounty.creator = ctx.accounts.creator.key();
+        bounty.bounty_id = bounty_id;
+        bounty.requirements_hash = requirements_hash;
+        bounty.reward_lamports = reward_lamports;
+        bounty.commemorative_uri = commemorative_uri;
+        bounty.bump = ctx.bumps.bounty;
+
+        msg!("🎨 Bounty #{} opened | Reward: {} lamports", bounty_id, reward_lamports);
+
+        Ok(())
+    }
+
+    /// 🏅 Award a Community Bounty
+    /// Features: The bounty's creator picks the winner; escrow pays out in full,
+    /// mints the commemorative NFT if one was promised, and closes the bounty
+    pub fn award_bounty(ctx: Context<AwardBounty>) -> Result<()> {
+        let clock = program_clock(ctx.remaining_accounts)?;
+        let bounty = &ctx.accounts.bounty;
+        let reward = bounty.reward_lamports;
+        let bounty_id = bounty.bounty_id;
+
+        transfer_lamports_from_program_account(
+            &bounty.to_account_info(),
+            &ctx.accounts.winner,
+            reward,
+        )?;
+        ctx.accounts.economy_ledger.record_reward(reward)?;
+
+        // 🖼️ Commemorative NFT is minted to the creator, then handed to the winner
+        if let Some(uri) = bounty.commemorative_uri.clone() {
+            let (Some(asset), Some(collection), Some(mpl_core_program)) = (
+                ctx.accounts.commemorative_asset.as_ref(),
+                ctx.accounts.collection.as_ref(),
+                ctx.accounts.mpl_core_program.as_ref(),
+            ) else {
+                return err!(NftError::CommemorativeAccountsMissing);
+            };
+            let creator = ctx.accounts.creator.to_account_info();
+            let system_program = ctx.accounts.system_program.to_account_info();
+
+            mint_core_asset(
+                &MplCore::new(mpl_core_program, &system_program),
+                &asset.to_account_info(),
+                collection,
+                &creator,
+                format!("Bounty #{} Winner", bounty_id),
+                uri,
+                1,
+                "Common",
+                0,
+                false,
+                vec![],
+                None,
+                None,
+                None,
+                None,
+                None,
+                &ctx.accounts.program_config,
+                &clock,
+            )?;
+            MplCore::new(mpl_core_program, &system_program).transfer(
+                AssetCall::new(&asset.to_account_info(), collection, &creator, &creator),
+                &ctx.accounts.winner,
+            )?;
+        }
+
+        msg!("🏅 Bounty #{} awarded to {} | {} lamports", bounty_id, ctx.accounts.winner.key(), reward);
+
+        Ok(())
+    }
+
+    /// 🌱 Initialize an Asset's NftState
+    /// Features: Seeds level, rarity and fusion potential from the asset's own
+    /// attributes. Idempotent: an already-initialized state (mint_date != 0)
+    /// is left untouched, so progress can never be reset.
+    pub fn initialize_nft_state(ctx: Context<InitializeNftState>) -> Result<()> {
+        let nft_state = &mut ctx.accounts.nft_state;
+        if nft_state.mint_date != 0 {
+            msg!("🌱 NftState already initialized");
+            return Ok(());
+        }
+
+        let asset = &ctx.accounts.asset;
+        require_keys_eq!(*asset.owner, mpl_core::ID, NftError::InvalidAsset);
+        verify_collection_membership(asset, &ctx.accounts.collection.key())?;
+        let attribute_list = fetch_attributes(asset)?;
+        let attribute = |key: &str| {
+            attribute_list
+                .iter()
+                .find(|attribute| attribute.key == key)
+                .map(|attribute| attribute.value.as_str())
+        };
+
+        let now = program_clock(ctx.remaining_accounts)?.unix_timestamp;
+        nft_state.bind_asset(asset.key(), ctx.bumps.nft_state)?;
+        nft_state.bind_collection(ctx.accounts.collection.key())?;
+        nft_state.attributes_hash = hash_attributes(&attribute_list);
+        nft_state.level = attribute("level").and_then(|level| level.parse().ok()).unwrap_or(1);
+        nft_state.rarity = attribute("rarity").and_then(Rarity::from_name).unwrap_or_default();
+        nft_state.fusion_potential = attribute("fusion_potential")
+            .and_then(|potential| potential.parse().ok())
+            .unwrap_or(0);
+        nft_state.mint_date = attribute("mint_date")
+            .and_then(|mint_date| mint_date.parse().ok())
+            .filter(|&mint_date: &i64| mint_date != 0)
+            .unwrap_or(now);
+        nft_state.last_updated = now;
+        nft_state.lock_state = LockState::Unlocked;
+
+        msg!("🌱 NftState initialized | Level: {} | Rarity: {}", nft_state.level, nft_state.rarity);
+
+        Ok(())
+    }
+
+    /// 🎉 Start a Global Event (admin)
+    /// Features: Collection-wide evolution and cooldown modifiers inside a
+    /// start/end window; replaces any previous event
+    pub fn start_global_event(
+        ctx: Context<StartGlobalEvent>,
+        name: String,
+        evolution_chance_bonus_bps: u16,
+        cooldown_reduction_bps: u16,
+        starts_at: i64,
+        ends_at: i64,
+    ) -> Result<()> {
+        require!(
+            name.len() <= MAX_GLOBAL_EVENT_NAME_LEN
+                && cooldown_reduction_bps <= MAX_BPS
+                && starts_at < ends_at,
+            NftError::InvalidGlobalEvent
+        );
+
+        let event = &mut ctx.accounts.global_event;
+        event.name = name;
+        event.evolution_chance_bonus_bps = evolution_chance_bonus_bps;
+        event.cooldown_reduction_bps = cooldown_reduction_bps;
+        event.starts_at = starts_at;
+        event.ends_at = ends_at;
+        event.bump = ctx.bumps.global_event;
+
+        msg!("🎉 Global event '{}' | Evolution +{} bps | Cooldown -{} bps | {} → {}",
+             event.name, evolution_chance_bonus_bps, cooldown_reduction_bps, starts_at, ends_at);
+
+        Ok(())
+    }
+
+    /// 📅 Set the Subscription Plan (admin)
+    /// Features: Price per period and perks; existing subscribers keep the
+    /// price they signed up at until they resubscribe
+    pub fn set_subscription_plan(
+        ctx: Context<UpdateProgramConfig>,
+        plan: SubscriptionPlan,
+    ) -> Result<()> {
+        require!(
+            plan.period_seconds > 0 && plan.fee_discount_bps <= MAX_BPS,
+            NftError::InvalidSubscriptionPlan
+        );
+        ctx.accounts.program_config.subscription_plan = plan;
+
+        msg!("📅 Subscription: {} lamports / {}s | Discount: {} bps | Bonus energy: {}",
+             plan.price_per_period_lamports, plan.period_seconds, plan.fee_discount_bps, plan.bonus_energy);
+
+        Ok(())
+    }
+
+    /// 🔥 Set the Fusion Fee (admin)
+    /// Features: Flat per-fusion charge with a burn / treasury split
+    pub fn set_fusion_fee(ctx: Context<UpdateProgramConfig>, fee: FusionFee) -> Result<()> {
+        require!(fee.burn_bps <= MAX_BPS, NftError::InvalidFeeBps);
+        ctx.accounts.program_config.fusion_fee = fee;
+
+        msg!("🔥 Fusion fee: {} lamports | Burn: {} bps", fee.lamports, fee.burn_bps);
+
+        Ok(())
+    }
+
+    /// 📜 Set the Player History Page Cap (admin)
+    /// Features: Bounds the rent each wallet's action log can grow to; past the
+    /// cap the oldest page is recycled
+    pub fn set_max_history_pages(ctx: Context<UpdateProgramConfig>, max_pages: u32) -> Result<()> {
+        require!(max_pages > 0, NftError::InvalidHistoryCap);
+        ctx.accounts.program_config.max_history_pages = max_pages;
+
+        msg!("📜 Player history capped at {} pages of {} actions", max_pages, MAX_HISTORY_PAGE_ENTRIES);
+
+        Ok(())
+    }
+
+    /// 📅 Subscribe (or Top Up) for Perks
+    /// Features: Prepays `periods` into an escrow that streams to the treasury
+    /// per period; perks lapse automatically once the escrow runs dry
+    pub fn subscribe(ctx: Context<Subscribe>, periods: u32) -> Result<()> {
+        let now = program_clock(ctx.remaining_accounts)?.unix_timestamp;
+        let plan = ctx.accounts.program_config.subscription_plan;
+        require!(plan.price_per_period_lamports > 0, NftError::SubscriptionsDisabled);
+        require!(periods > 0, NftError::InvalidSubscriptionPlan);
+
+        // ♻️ A lapsed subscription is settled in full and restarted at today's price
+        let subscription = &ctx.accounts.subscription;
+        if subscription.wallet != Pubkey::default() && subscription.paid_through() <= now {
+            let owed = subscription.escrowed - subscription.settled;
+            if owed > 0 {
+                transfer_lamports_from_program_account(
+                    &subscription.to_account_info(),
+                    &ctx.accounts.treasury.to_account_info(),
+                    owed,
+                )?;
+            }
+            let ledger = &mut ctx.accounts.economy_ledger;
+            ledger.record_escrow_release(owed)?;
+            ledger.record_collected(owed)?;
+            let subscription = &mut ctx.accounts.subscription;
+            subscription.wallet = Pubkey::default();
+            subscription.escrowed = 0;
+            subscription.settled = 0;
+        }
+
+        let subscription = &ctx.accounts.subscription;
+        let price = if subscription.wallet == Pubkey::default() {
+            plan.price_per_period_lamports
+        } else {
+            subscription.price_per_period_lamports
+        };
+        let deposit = price.checked_mul(periods as u64).ok_or(NftError::MathOverflow)?;
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: ctx.accounts.wallet.to_account_info(),
+                    to: ctx.accounts.subscription.to_account_info(),
+                },
+            ),
+            deposit,
+        )?;
+        ctx.accounts.economy_ledger.record_escrow_deposit(deposit)?;
+
+        let subscription = &mut ctx.accounts.subscription;
+        if subscription.wallet == Pubkey::default() {
+            subscription.wallet = ctx.accounts.wallet.key();
+            subscription.price_per_period_lamports = plan.price_per_period_lamports;
+            subscription.period_seconds = plan.period_seconds;
+            subscription.started_at = now;
+            subscription.bump = ctx.bumps.subscription;
+        }
+        subscription.escrowed = subscription
+            .escrowed
+            .checked_add(deposit)
+            .ok_or(NftError::MathOverflow)?;
+        let paid_through = subscription.paid_through();
+
+        let profile = &mut ctx.accounts.player_profile;
+        profile.record_spend(ctx.accounts.wallet.key(), ctx.bumps.player_profile, 0)?;
+        profile.subscribed_until = paid_through;
+
+        msg!("📅 Subscribed through {} | Escrowed: {} lamports", paid_through, subscription.escrowed);
+
+        Ok(())
+    }
+
+    /// 💧 Settle a Subscription Stream
+    /// Features: Permissionless crank moving every started period's payment
+    /// from the escrow to the treasury
+    pub fn settle_subscription(ctx: Context<SettleSubscription>) -> Result<()> {
+        let now = program_clock(ctx.remaining_accounts)?.unix_timestamp;
+        let subscription = &ctx.accounts.subscription;
+        let due = subscription.accrued_at(now) - subscription.settled;
+        require!(due > 0, NftError::NothingToClaim);
+
+        transfer_lamports_from_program_account(
+            &subscription.to_account_info(),
+            &ctx.accounts.treasury.to_account_info(),
+            due,
+        )?;
+        let ledger = &mut ctx.accounts.economy_ledger;
+        ledger.record_escrow_release(due)?;
+        ledger.record_collected(due)?;
+        ctx.accounts.subscription.settled += due;
+
+        msg!("💧 Settled {} lamports of subscription", due);
+
+        Ok(())
+    }
+
+    /// 🛑 Cancel a Subscription
+    /// Features: Pays the treasury for every started period, refunds the rest,
+    /// and keeps perks until the current period ends
+    pub fn cancel_subscription(ctx: Context<CancelSubscription>) -> Result<()> {
+        let now = program_clock(ctx.remaining_accounts)?.unix_timestamp;
+        let subscription = &ctx.accounts.subscription;
+        let due = subscription.accrued_at(now) - subscription.settled;
+        let refund = subscription.escrowed - subscription.accrued_at(now);
+        let perks_until = subscription.current_period_end(now);
+
+        let subscription_info = subscription.to_account_info();
+        if due > 0 {
+            transfer_lamports_from_program_account(
+                &subscription_info,
+                &ctx.accounts.treasury.to_account_info(),
+                due,
+            )?;
+        }
+        if refund > 0 {
+            transfer_lamports_from_program_account(
+                &subscription_info,
+                &ctx.accounts.wallet.to_account_info(),
+                refund,
+            )?;
+        }
+        let ledger = &mut ctx.accounts.economy_ledger;
+        ledger.record_escrow_release(due + refund)?;
+        ledger.record_collected(due)?;
+
+        ctx.accounts.player_profile.subscribed_until = perks_until;
+
+        msg!("🛑 Subscription cancelled | Refunded: {} lamports | Perks until {}", refund, perks_until);
+
+        Ok(())
+    }
+
+    /// 🎲 Starter Pack - Commit
+    /// Features: Charges the collection's price for three mints and commits
+    /// the pack's rarity rolls to a future slot; `mint_starter_pack` reveals
+    /// them and mints. One open pack per wallet at a time
+    pub fn commit_starter_pack(ctx: Context<CommitStarterPack>) -> Result<()> {
+        let clock = program_clock(ctx.remaining_accounts)?;
+        enforce_single_mint(&ctx.accounts.collection_config, &ctx.accounts.instructions_sysvar)?;
+        let payer = ctx.accounts.payer.to_account_info();
+
+        // 🏛️ Program-created collections cap supply, price and plugin bundle
+        ctx.accounts.mint_receipt.bind(ctx.accounts.collection.key(), payer.key(), ctx.bumps.mint_receipt);
+        let plugins = charge_collection_mint(
+            &ctx.accounts.collection_config,
+            &ctx.accounts.mint_shard,
+            &mut ctx.accounts.mint_receipt,
+            &payer,
+            &ctx.accounts.treasury.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            &mut ctx.accounts.economy_ledger,
+            3,
+            clock.unix_timestamp,
+            None,
+        )?;
+
+        let action = RollAction::StarterPack { beta: plugins.contains(&beta_plugin()) };
+        let commitment = &mut ctx.accounts.roll_commitment;
+        commitment.open(payer.key(), action, ctx.accounts.collection.key().to_bytes(), clock.slot, ctx.bumps.roll_commitment);
+
+        msg!("🎲 Starter pack committed | Reveal after slot {}", commitment.reveal_slot);
+
+        Ok(())
+    }
+
+    /// 🎁 Starter Pack Mint
+    /// Features: Three assets in one call, rarities rolled from the slot
+    /// committed by `commit_starter_pack`, at least one Uncommon or better
+    /// guaranteed. A reveal left until the committed slot hash has aged out
+    /// mints the floor pack
+    pub fn mint_starter_pack(ctx: Context<MintStarterPack>, name: String, uri: String) -> Result<()> {
+        let clock = program_clock(ctx.remaining_accounts)?;
+        enforce_single_mint(&ctx.accounts.collection_config, &ctx.accounts.instructions_sysvar)?;
+        let payer = ctx.accounts.payer.to_account_info();
+        let system_program = ctx.accounts.system_program.to_account_info();
+
+        // 🎲 One committed roll, sliced into one roll per asset
+        let RollAction::StarterPack { beta } = ctx.accounts.roll_commitment.action else {
+            return err!(NftError::RollCommitmentMismatch);
+        };
+        let randomness = Randomness::new(
+            ctx.accounts.program_config.randomness_provider,
+            &ctx.accounts.slot_hashes,
+        );
+        let seed = ctx.accounts.roll_commitment.reveal(
+            &randomness,
+            RollAction::StarterPack { beta },
+            ctx.accounts.collection.key().to_bytes(),
+            clock.slot,
+        )?;
+        let mut rarities = [0, 1, 2].map(|i| {
+            seed.map_or(Rarity::Common, |seed| starter_pack_rarity(roll_below(&seed, i, MAX_BPS as u64) as u16))
+        });
+        // 🛡️ Rarity floor: promote the last card if the pack came up all Common
+        if rarities.iter().all(|&rarity| rarity == Rarity::Common) {
+            rarities[2] = Rarity::Uncommon;
+        }
+
+        // 🏛️ Paid for at commit; the collection's bundle, plus beta if bought in a beta phase
+        let mut plugins = load_if_initialized::<CollectionConfig>(&ctx.accounts.collection_config)?
+            .map(|config| config.mint_plugins())
+            .unwrap_or_default();
+        if beta {
+            plugins.push(beta_plugin());
+        }
+        let achievement_tiers = load_if_initialized::<AchievementTiers>(&ctx.accounts.achievement_tiers)?;
+        let edition = claim_editions(
+            &ctx.accounts.collection_config,
+            ctx.accounts.edition_counter.as_deref_mut(),
+            3,
+        )?;
+
+        let assets = [
+            ctx.accounts.asset_1.to_account_info(),
+            ctx.accounts.asset_2.to_account_info(),
+            ctx.accounts.asset_3.to_account_info(),
+        ];
+        for (index, (asset, rarity)) in assets.iter().zip(rarities).enumerate() {
+            let minted_rarity = mint_core_asset(
+                &MplCore::new(&ctx.accounts.mpl_core_program, &system_program),
+                asset,
+                &ctx.accounts.collection,
+                &payer,
+                format!("{} #{}", name, index + 1),
+                uri.clone(),
+                1,
+                rarity.as_str(),
+                0,
+                false,
+                plugins.clone(),
+                edition.map(|edition| edition.nth(index as u32)),
+                None,
+                None,
+                None,
+                achievement_tiers.as_ref(),
+                &ctx.accounts.program_config,
+                &clock,
+            )?;
+            ctx.accounts.signal_board.record(SignalKind::Mint, asset.key(), 1, &minted_rarity, &clock);
+        }
+
+        msg!("🎁 Starter pack minted | Rarities: {} / {} / {}", rarities[0], rarities[1], rarities[2]);
+
+        Ok(())
+    }
+
+    /// 🚨 Set the Forced Recall Compensation (admin)
+    /// Features: Lamports paid to the holder of every asset recalled from now on
+    pub fn set_recall_compensation(ctx: Context<UpdateProgramConfig>, lamports: u64) -> Result<()> {
+        ctx.accounts.program_config.recall_compensation_lamports = lamports;
+
+        msg!("🚨 Recall compensation: {} lamports", lamports);
+
+        Ok(())
+    }
+
+    /// 🚨 Schedule a Forced Recall (admin)
+    /// Features: Public, timelocked notice before an asset is burned or frozen
+    /// (e.g. art IP takedowns); compensation is fixed at scheduling time
+    pub fn schedule_forced_recall(
+        ctx: Context<ScheduleForcedRecall>,
+        mode: RecallMode,
+        reason_hash: [u8; 32],
+        voucher_uri: String,
+    ) -> Result<()> {
+        require!(
+            !voucher_uri.is_empty() && voucher_uri.len() <= MAX_BASE_URI_LEN,
+            NftError::InvalidRecallTerms
+        );
+        let now = program_clock(ctx.remaining_accounts)?.unix_timestamp;
+
+        let recall = &mut ctx.accounts.forced_recall;
+        recall.asset = ctx.accounts.asset.key();
+        recall.mode = mode;
+        recall.reason_hash = reason_hash;
+        recall.compensation_lamports = ctx.accounts.program_config.recall_compensation_lamports;
+        recall.voucher_uri = voucher_uri;
+        recall.scheduled_at = now;
+        recall.executable_at = now + FORCED_RECALL_TIMELOCK;
+        recall.executed_at = 0;
+        recall.holder = Pubkey::default();
+        recall.voucher = Pubkey::default();
+        recall.bump = ctx.bumps.forced_recall;
+
+        emit!(ForcedRecallScheduled {
+            asset: recall.asset,
+            mode,
+            reason_hash,
+            compensation_lamports: recall.compensation_lamports,
+            executable_at: recall.executable_at,
+        });
+
+        msg!("🚨 Forced recall ({:?}) scheduled for {} | Executable at {}",
+             mode, recall.asset, recall.executable_at);
+
+        Ok(())
+    }
+
+    /// 🚨 Cancel a Pending Forced Recall (admin)
+    pub fn cancel_forced_recall(ctx: Context<CancelForcedRecall>) -> Result<()> {
+        let recall = &ctx.accounts.forced_recall;
+        require!(recall.executed_at == 0, NftError::RecallAlreadyExecuted);
+
+        emit!(ForcedRecallCancelled { asset: recall.asset });
+
+        msg!("🚨 Forced recall of {} cancelled", recall.asset);
+
+        Ok(())
+    }
+
+    /// 🚨 Execute a Forced Recall (admin)
+    /// Features: Burns or freezes the asset, compensates its current holder from
+    /// the treasury and mints them a replacement voucher. The authority must be
+    /// the collection's update authority, with a permanent burn / freeze
+    /// delegate on the collection or asset.
+    pub fn execute_forced_recall(ctx: Context<ExecuteForcedRecall>) -> Result<()> {
+        let clock = program_clock(ctx.remaining_accounts)?;
+        let now = clock.unix_timestamp;
+        let recall = &ctx.accounts.forced_recall;
+        require!(recall.executed_at == 0, NftError::RecallAlreadyExecuted);
+        require!(now >= recall.executable_at, NftError::RecallTimelockActive);
+
+        // 👤 Whoever holds the asset at execution time is compensated
+        let holder = ctx.accounts.holder.key();
+        load_owned_asset(&ctx.accounts.asset, &holder)?;
+        let authority = ctx.accounts.authority.to_account_info();
+        let system_program = ctx.accounts.system_program.to_account_info();
+        let mpl_core_program = &ctx.accounts.mpl_core_program;
+
+        // 📸 Checkpoint the asset before it is burned or frozen
+        ctx.accounts.asset_history.record(
+            CheckpointOperation::Recall,
+            &ctx.accounts.nft_state,
+            hash_attributes(&fetch_attributes(&ctx.accounts.asset)?),
+            now,
+        );
+
+        let backend = MplCore::new(mpl_core_program, &system_program);
+        let call = AssetCall::new(&ctx.accounts.asset, &ctx.accounts.collection, &authority, &authority);
+        match recall.mode {
+            RecallMode::Burn => backend.burn(call)?,
+            RecallMode::Freeze => backend.update_plugin(
+                call,
+                Plugin::PermanentFreezeDelegate(PermanentFreezeDelegate { frozen: true }),
+            )?,
+        }
+        ctx.accounts.nft_state.lock_state = LockState::Recalled;
+
+        // 💸 Compensation without dipping the treasury below rent exemption
+        let compensation = recall.compensation_lamports;
+        if compensation > 0 {
+            let rent_exempt = Rent::get()?.minimum_balance(0);
+            require!(
+                ctx.accounts.treasury.lamports() >= compensation + rent_exempt,
+                NftError::InsufficientTreasuryBalance
+            );
+            pay_from_treasury(
+                &ctx.accounts.treasury,
+                &ctx.accounts.holder,
+                &ctx.accounts.system_program,
+                ctx.accounts.program_config.treasury_bump,
+                compensation,
+            )?;
+        }
+        ctx.accounts.economy_ledger.record_treasury_spent(compensation)?;
+
+        // 🎟️ Replacement voucher, minted to the authority and handed to the holder
+        let voucher = ctx.accounts.voucher_asset.to_account_info();
+        mint_core_asset(
+            &MplCore::new(mpl_core_program, &system_program),
+            &voucher,
+            &ctx.accounts.collection,
+            &authority,
+            "Recall Voucher".to_string(),
+            recall.voucher_uri.clone(),
+            1,
+            "Common",
+            0,
+            false,
+            vec![],
+            None,
+            None,
+            None,
+            None,
+            None,
+            &ctx.accounts.program_config,
+            &clock,
+        )?;
+        MplCore::new(mpl_core_program, &system_program).transfer(
+            AssetCall::new(&voucher, &ctx.accounts.collection, &authority, &authority),
+            &ctx.accounts.holder,
+        )?;
+
+        let recall = &mut ctx.accounts.forced_recall;
+        recall.executed_at = now;
+        recall.holder = holder;
+        recall.voucher = voucher.key();
+
+        emit!(ForcedRecallExecuted {
+            asset: recall.asset,
+            holder,
+            mode: recall.mode,
+            compensation_lamports: compensation,
+            voucher: recall.voucher,
+        });
+
+        msg!("🚨 Forced recall ({:?}) of {} executed | Holder {} compensated {} lamports",
+             recall.mode, recall.asset, holder, compensation);
+
+        Ok(())
+    }
+
+    /// ✂️ Set the Split Retention (admin)
+    /// Features: Fraction of the original's level and fusion potential that
+    /// survives a split, shared between both children
+    pub fn set_split_retain_bps(ctx: Context<UpdateProgramConfig>, split_retain_bps: u16) -> Result<()> {
+        require!(
+            split_retain_bps > 0 && split_retain_bps <= MAX_BPS,
+            NftError::InvalidFeeBps
+        );
+        ctx.accounts.program_config.split_retain_bps = split_retain_bps;
+
+        msg!("✂️ Splits keep {} bps of the original's stats", split_retain_bps);
+
+        Ok(())
+    }
+
+    /// 🚫 Set Attribute-Conditional Transfer Restrictions (admin)
+    /// Features: Replaces the whole list; an empty list lifts all restrictions
+    pub fn set_transfer_restrictions(
+        ctx: Context<UpdateProgramConfig>,
+        restrictions: Vec<TransferRestriction>,
+    ) -> Result<()> {
+        require!(
+            restrictions.len() <= MAX_TRANSFER_RESTRICTIONS
+                && restrictions.iter().all(|restriction| {
+                    !restriction.key.is_empty()
+                        && restriction.key.len() <= MAX_TRAIT_KEY_LEN
+                        && restriction.value.len() <= MAX_TRAIT_VALUE_LEN
+                }),
+            NftError::InvalidAttributeLimits
+        );
+        ctx.accounts.program_config.transfer_restrictions = restrictions;
+
+        msg!("🚫 {} transfer restrictions in force",
+             ctx.accounts.program_config.transfer_restrictions.len());
+
+        Ok(())
+    }
+
+    /// 🎓 Set the Experience Transfer Rules (admin)
+    /// Features: Loss in transit and the level floor donors can't go below
+    pub fn set_xp_transfer_rules(
+        ctx: Context<UpdateProgramConfig>,
+        loss_bps: u16,
+        donor_min_level: u64,
+    ) -> Result<()> {
+        require!(loss_bps < MAX_BPS, NftError::InvalidFeeBps);
+        require!(donor_min_level > 0, NftError::InvalidLevelProgression);
+        let config = &mut ctx.accounts.program_config;
+        config.xp_transfer_loss_bps = loss_bps;
+        config.xp_donor_min_level = donor_min_level;
+
+        msg!("🎓 XP transfers lose {} bps | Donor floor: level {}", loss_bps, donor_min_level);
+
+        Ok(())
+    }
+
+    /// 🔀 Choose Which Paths Emit Attribute Diffs (admin)
+    /// Features: Each diff costs a read of the asset's current attributes, so
+    /// it can be switched off per path where compute is tight
+    pub fn set_attribute_diff_events(
+        ctx: Context<UpdateProgramConfig>,
+        events: AttributeDiffEvents,
+    ) -> Result<()> {
+        ctx.accounts.program_config.attribute_diff_events = events;
+
+        msg!("🔀 Attribute diffs | Update: {} | Evolve: {} | Fuse: {}",
+             events.update, events.evolve, events.fuse);
+
+        Ok(())
+    }
+
+    /// 🧢 Set the Program-Wide Level Cap (admin)
+    /// Features: Update and evolution stop at `max_level`; levels gained past
+    /// it are paid to the acting wallet as PlayerProfile prestige
+    pub fn set_max_level(ctx: Context<UpdateProgramConfig>, max_level: u64) -> Result<()> {
+        require!(max_level > 0, NftError::InvalidLevelProgression);
+        ctx.accounts.program_config.max_level = max_level;
+
+        msg!("🧢 Level cap set to {}", max_level);
+
+        Ok(())
+    }
+
+    /// 🌐 Set the Error Docs Base URI (admin)
+    /// Features: Clients append an error's short code to this to deep-link
+    /// its localized explanation; empty clears it
+    pub fn set_docs_base_uri(ctx: Context<UpdateProgramConfig>, docs_base_uri: String) -> Result<()> {
+        require!(docs_base_uri.len() <= MAX_BASE_URI_LEN, NftError::InvalidBaseUri);
+        ctx.accounts.program_config.docs_base_uri = docs_base_uri;
+
+        msg!("🌐 Error docs at {:?}", ctx.accounts.program_config.docs_base_uri);
+
+        Ok(())
+    }
+
+    /// ✂️ Split NFT - the inverse of fusion
+    /// Features: Burns the asset and mints two children one rarity tier lower
+    /// whose combined level and fusion potential are `split_retain_bps` of the
+    /// original; the parent → children link is kept in a SplitRecord
+    pub fn split_nft(ctx: Context<SplitNFT>, name: String, uri: String) -> Result<()> {
+        let now = program_clock(ctx.remaining_accounts)?.unix_timestamp;
+        let owner = ctx.accounts.owner.to_account_info();
+        let system_program = ctx.accounts.system_program.to_account_info();
+        let mpl_core_program = &ctx.accounts.mpl_core_program;
+
+        load_owned_asset(&ctx.accounts.asset, owner.key)?;
+        verify_collection_membership(&ctx.accounts.asset, &ctx.accounts.collection.key())?;
+        let parent = &mut ctx.accounts.nft_state;
+        parent.bind_collection(ctx.accounts.collection.key())?;
+        require!(parent.lock_state == LockState::Unlocked, NftError::AssetLocked);
+        require!(parent.level >= 2, NftError::SplitRequirementsNotMet);
+
+        // 📉 Children share the retained stats, the first taking any odd unit
+        let retain_bps = ctx.accounts.program_config.split_retain_bps;
+        let level = bps_of(parent.level, retain_bps)?;
+        let fusion_potential = bps_of(parent.fusion_potential as u64, retain_bps)? as u32;
+        let levels = [level.div_ceil(2).max(1), (level / 2).max(1)];
+        let potentials = [fusion_potential.div_ceil(2), fusion_potential / 2];
+        let rarity = previous_rarity(parent.rarity);
+        let parent_key = ctx.accounts.asset.key();
+        let (parent_level, parent_rarity) = (parent.level, parent.rarity);
+
+        // 📸 Checkpoint the parent before it is burned
+        ctx.accounts.asset_history.record(
+            CheckpointOperation::Burn,
+            parent,
+            hash_attributes(&fetch_attributes(&ctx.accounts.asset)?),
+            now,
+        );
+
+        MplCore::new(mpl_core_program, &system_program)
+            .burn(AssetCall::new(&ctx.accounts.asset, &ctx.accounts.collection, &owner, &owner))?;
+
+        let children = [ctx.accounts.child_asset_1.key(), ctx.accounts.child_asset_2.key()];
+        let child_assets = [&ctx.accounts.child_asset_1, &ctx.accounts.child_asset_2];
+        let mut attribute_hashes = [[0u8; 32]; 2];
+        for (i, child) in child_assets.into_iter().enumerate() {
+            let attributes = vec![
+                Attribute { key: "level".to_string(), value: levels[i].to_string() },
+                Attribute { key: "rarity".to_string(), value: rarity.to_string() },
+                Attribute { key: "mint_date".to_string(), value: now.to_string() },
+                Attribute { key: "fusion_potential".to_string(), value: potentials[i].to_string() },
+                Attribute { key: "split_from".to_string(), value: parent_key.to_string() },
+            ];
+            attribute_hashes[i] = hash_attributes(&attributes);
+            create_core_asset(
+                &MplCore::new(mpl_core_program, &system_program),
+                &child.to_account_info(),
+                &ctx.accounts.collection,
+                &owner,
+                name.clone(),
+                uri.clone(),
+                attributes,
+                vec![],
+                None,
+                None,
+                None,
+                &ctx.accounts.program_config,
+            )?;
+        }
+
+        for (i, (state, bump)) in [
+            (&mut ctx.accounts.child_state_1, ctx.bumps.child_state_1),
+            (&mut ctx.accounts.child_state_2, ctx.bumps.child_state_2),
+        ]
+        .into_iter()
+        .enumerate()
+        {
+            state.bind_asset(children[i], bump)?;
+            state.bind_collection(ctx.accounts.collection.key())?;
+            state.attributes_hash = attribute_hashes[i];
+            state.level = levels[i];
+            state.rarity = rarity;
+            state.fusion_potential = potentials[i];
+            state.mint_date = now;
+            state.last_updated = now;
+            state.lock_state = LockState::Unlocked;
+            state.rule_versions = ctx.accounts.program_config.rule_versions;
+        }
+
+        let record = &mut ctx.accounts.split_record;
+        record.parent = parent_key;
+        record.children = children;
+        record.parent_level = parent_level;
+        record.parent_rarity = parent_rarity;
+        record.split_at = now;
+        record.bump = ctx.bumps.split_record;
+
+        emit!(NftSplit {
+            owner: owner.key(),
+            parent: parent_key,
+            children,
+            levels,
+            rarity,
+        });
+
+        msg!("✂️ {} (level {} {}) split into two level {}/{} {} assets",
+             parent_key, parent_level, parent_rarity, levels[0], levels[1], rarity);
+
+        Ok(())
+    }
+
+    /// 🏛️ Create an mpl-core Collection and its CollectionConfig
+    /// Features: Royalties plugin paying the creator split (up to
+    /// MAX_ROYALTY_CREATORS shares summing to 100; empty = the authority), optional max supply
+    /// (0 = uncapped), mint price and per-asset plugin bundle that every
+    /// program mint into it enforces
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_collection(
+        ctx: Context<CreateCollection>,
+        name: String,
+        uri: String,
+        max_supply: u32,
+        mint_price_lamports: u64,
+        royalty_bps: u16,
+        royalty_creators: Vec<RoyaltyCreator>,
+        plugin_bundle: MintPluginBundle,
+    ) -> Result<()> {
+        require!(royalty_bps <= MAX_BPS, NftError::InvalidFeeBps);
+        require!(uri.len() <= MAX_BASE_URI_LEN, NftError::InvalidBaseUri);
+        require!(valid_royalty_split(&royalty_creators), NftError::InvalidRoyaltySplit);
+
+        let authority = ctx.accounts.authority.to_account_info();
+        CreateCollectionV1CpiBuilder::new(&ctx.accounts.mpl_core_program)
+            .collection(&ctx.accounts.collection.to_account_info())
+            .update_authority(Some(&authority))
+            .payer(&authority)
+            .system_program(&ctx.accounts.system_program.to_account_info())
+            .name(name)
+            .uri(uri)
+            .plugins(vec![PluginAuthorityPair {
+                plugin: Plugin::Royalties(royalties(royalty_bps, &royalty_creators, authority.key())),
+                authority: None,
+            }])
+            .invoke()?;
+
+        let config = &mut ctx.accounts.collection_config;
+        config.collection = ctx.accounts.collection.key();
+        config.authority = authority.key();
+        config.max_supply = max_supply;
+        config.total_minted = 0;
+        config.total_burned = 0;
+        config.reserved_supply = 0;
+        config.team_allocation = 0;
+        config.team_minted = 0;
+        config.max_per_wallet = 0;
+        config.phases = vec![];
+        config.allowlist_root = [0; 32];
+        config.mint_price_lamports = mint_price_lamports;
+        config.price_curve = PriceCurve::Flat;
+        config.payment_mint = Pubkey::default();
+        config.token_price = 0;
+        config.burn_token_mint = Pubkey::default();
+        config.burn_token_amount = 0;
+        config.burn_asset_collection = Pubkey::default();
+        config.numbered_editions = false;
+        config.anti_bot = false;
+        config.proceeds_lamports = 0;
+        config.refund_deadline = 0;
+        config.drop_finalized = false;
+        config.reward_split_bps = 0;
+        config.unrebalanced_proceeds = 0;
+        config.rebalanced_epoch = -1;
+        config.royalty_bps = royalty_bps;
+        config.royalty_creators = royalty_creators;
+        config.plugin_bundle = plugin_bundle;
+        config.beta_sunset_at = 0;
+        config.beta_sunset_mode = BetaSunsetMode::Graduate;
+        config.beta_sunset_reveal_slot = 0;
+        config.beta_sunset_seed = [0; 32];
+        config.min_update_cooldown = 0;
+        config.bump = ctx.bumps.collection_config;
+
+        msg!("🏛️ Collection {} created | Max supply: {} | Price: {} lamports | Royalty: {} bps",
+             config.collection, max_supply, mint_price_lamports, royalty_bps);
+
+        Ok(())
+    }
+
+    /// 🧩 Change a Collection's Mint Plugin Bundle (collection authority)
+    /// Features: Applies to assets minted from now on; existing assets keep theirs
+    pub fn set_mint_plugin_bundle(
+        ctx: Context<UpdateCollectionConfig>,
+        plugin_bundle: MintPluginBundle,
+    ) -> Result<()> {
+        ctx.accounts.collection_config.plugin_bundle = plugin_bundle;
+
+        msg!("🧩 Mint plugins | Royalties: {} | Freeze delegate: {} | Burn delegate: {}",
+             plugin_bundle.royalties, plugin_bundle.freeze_delegate, plugin_bundle.burn_delegate);
+
+        Ok(())
+    }
+
+    /// 🧾 Limit Mints per Wallet (collection authority)
+    /// Features: Counts every program mint a wallet has made into the
+    /// collection, including ones before the limit was set; 0 lifts it
+    pub fn set_max_per_wallet(ctx: Context<UpdateCollectionConfig>, max_per_wallet: u32) -> Result<()> {
+        ctx.accounts.collection_config.max_per_wallet = max_per_wallet;
+
+        msg!("🧾 Max mints per wallet: {}", max_per_wallet);
+
+        Ok(())
+    }
+
+    /// 🪙 Price Mints in an SPL Token (collection authority)
+    /// Features: Enables `mint_nft_with_token` for `payment_mint`;
+    /// the default pubkey disables it again
+    pub fn set_token_payment(
+        ctx: Context<UpdateCollectionConfig>,
+        payment_mint: Pubkey,
+        token_price: u64,
+    ) -> Result<()> {
+        let config = &mut ctx.accounts.collection_config;
+        config.payment_mint = payment_mint;
+        config.token_price = token_price;
+
+        msg!("🪙 Token payment: {} units of {}", token_price, payment_mint);
+
+        Ok(())
+    }
+
+    /// 🔥 Configure Burn-to-Mint (collection authority)
+    /// Features: Enables `mint_with_burn` for a whitelist token, a burn
+    /// collection or both; default pubkeys disable either
+    pub fn set_burn_to_mint(
+        ctx: Context<UpdateCollectionConfig>,
+        burn_token_mint: Pubkey,
+        burn_token_amount: u64,
+        burn_asset_collection: Pubkey,
+    ) -> Result<()> {
+        require!(
+            burn_token_mint == Pubkey::default() || burn_token_amount > 0,
+            NftError::InvalidBurnPayment
+        );
+        let config = &mut ctx.accounts.collection_config;
+        config.burn_token_mint = burn_token_mint;
+        config.burn_token_amount = burn_token_amount;
+        config.burn_asset_collection = burn_asset_collection;
+
+        msg!("🔥 Burn-to-mint | Token: {} x {} | Asset collection: {}",
+             burn_token_amount, burn_token_mint, burn_asset_collection);
+
+        Ok(())
+    }
+
+    /// 👥 Set a Collection's Team Allocation (collection authority)
+    /// Features: Carves supply out of `max_supply` that only `mint_reserved`
+    /// can mint; it can't drop below what the team has already minted
+    pub fn set_team_allocation(ctx: Context<UpdateCollectionConfig>, team_allocation: u32) -> Result<()> {
+        let config = &mut ctx.accounts.collection_config;
+        require!(team_allocation >= config.team_minted, NftError::InvalidTeamAllocation);
+        config.team_allocation = team_allocation;
+        require!(
+            config.max_supply == 0 || config.committed_supply() <= config.max_supply,
+            NftError::InvalidTeamAllocation
+        );
+
+        msg!("👥 Team allocation: {} | Minted: {}", team_allocation, config.team_minted);
+
+        Ok(())
+    }
+
+    /// 🔢 Number a Collection's Editions (collection authority)
+    /// Features: Creates the EditionCounter; every program mint from now on
+    /// is stamped "#0001 / max" in order. Numbered mints write the counter,
+    /// so they no longer spread across the mint shards.
+    pub fn enable_editions(ctx: Context<EnableEditions>) -> Result<()> {
+        let counter = &mut ctx.accounts.edition_counter;
+        counter.collection = ctx.accounts.collection_config.collection;
+        counter.next_edition = 1;
+        counter.bump = ctx.bumps.edition_counter;
+        ctx.accounts.collection_config.numbered_editions = true;
+
+        msg!("🔢 Editions numbered for {}", counter.collection);
+
+        Ok(())
+    }
+
+    /// ✍️ Hand a Collection's Update Authority to the Program
+    /// Features: Makes the collection's program authority PDA its update
+    /// authority, so `mint_nft` and `update_nft_metadata` sign for it and
+    /// any wallet can mint without the creator co-signing
+    pub fn assign_program_authority(ctx: Context<AssignProgramAuthority>) -> Result<()> {
+        let authority = ctx.accounts.authority.to_account_info();
+        UpdateCollectionV1CpiBuilder::new(&ctx.accounts.mpl_core_program)
+            .collection(&ctx.accounts.collection)
+            .payer(&authority)
+            .authority(Some(&authority))
+            .new_update_authority(Some(&ctx.accounts.collection_authority))
+            .system_program(&ctx.accounts.system_program.to_account_info())
+            .invoke()?;
+
+        msg!("✍️ {} is now signed for by {}",
+             ctx.accounts.collection.key(), ctx.accounts.collection_authority.key());
+
+        Ok(())
+    }
+
+    /// 🗓️ Stage a Collection's Launch (collection authority)
+    /// Features: Up to MAX_MINT_PHASES time-ordered, non-overlapping windows,
+    /// each with its own price and optional allowlist; an empty list mints at
+    /// the base price any time
+    pub fn set_mint_phases(
+        ctx: Context<UpdateCollectionConfig>,
+        phases: Vec<MintPhase>,
+        allowlist_root: [u8; 32],
+    ) -> Result<()> {
+        require!(phases.len() <= MAX_MINT_PHASES, NftError::InvalidMintPhases);
+        require!(
+            phases.iter().all(|phase| phase.starts_at < phase.ends_at),
+            NftError::InvalidMintPhases
+        );
+        require!(
+            phases.windows(2).all(|pair| pair[0].ends_at <= pair[1].starts_at),
+            NftError::InvalidMintPhases
+        );
+        require!(
+            allowlist_root != [0; 32] || phases.iter().all(|phase| !phase.allowlist_required),
+            NftError::InvalidMintPhases
+        );
+
+        let config = &mut ctx.accounts.collection_config;
+        config.phases = phases;
+        config.allowlist_root = allowlist_root;
+
+        msg!("🗓️ {} mint phases set for {}", config.phases.len(), config.collection);
+
+        Ok(())
+    }
+
+    /// 🎓 Transfer Experience Between Two Owned Assets
+    /// Features: Moves `amount` levels from a donor to a recipient in the same
+    /// collection, minus the configured loss; the donor can't drop below the
+    /// configured level floor
+    pub fn transfer_xp(ctx: Context<TransferXp>, amount: u64) -> Result<()> {
+        let owner = ctx.accounts.owner.to_account_info();
+        let collection = ctx.accounts.collection.key();
+        require!(
+            ctx.accounts.from_asset.key() != ctx.accounts.to_asset.key(),
+            NftError::InvalidXpTransfer
+        );
+        for asset in [&ctx.accounts.from_asset, &ctx.accounts.to_asset] {
+            load_owned_asset(asset, owner.key)?;
+            verify_collection_membership(asset, &collection)?;
+        }
+
+        let config = &ctx.accounts.program_config;
+        let received = amount - bps_of(amount, config.xp_transfer_loss_bps)?;
+        require!(received > 0, NftError::InvalidXpTransfer);
+
+        let now = program_clock(ctx.remaining_accounts)?.unix_timestamp;
+        let from_state = &mut ctx.accounts.from_nft_state;
+        let to_state = &mut ctx.accounts.to_nft_state;
+        require!(
+            from_state.lock_state == LockState::Unlocked && to_state.lock_state == LockState::Unlocked,
+            NftError::AssetLocked
+        );
+        from_state.bind_collection(collection)?;
+        to_state.bind_collection(collection)?;
+        let donor_level = from_state
+            .level
+            .checked_sub(amount)
+            .filter(|&level| level >= config.xp_donor_min_level)
+            .ok_or(NftError::DonorBelowLevelFloor)?;
+        let recipient_level = to_state.level.checked_add(received).ok_or(NftError::MathOverflow)?;
+        // 🧢 Levels past the cap become the owner's prestige instead
+        let (recipient_level, excess_levels) = config.cap_level(to_state.level, recipient_level);
+
+        from_state.level = donor_level;
+        from_state.last_updated = now;
+        to_state.level = recipient_level;
+        to_state.last_updated = now;
+
+        // 🪞 Mirror the new levels onto both assets' attributes
+        for (asset, level, state) in [
+            (&ctx.accounts.from_asset, donor_level, &mut ctx.accounts.from_nft_state),
+            (&ctx.accounts.to_asset, recipient_level, &mut ctx.accounts.to_nft_state),
+        ] {
+            let attribute_list = merge_attributes(&ctx.accounts.program_config, asset, vec![
+                Attribute { key: "level".to_string(), value: level.to_string() },
+            ])?;
+            state.attributes_hash = write_attributes(
+                &MplCore::new(&ctx.accounts.mpl_core_program, &ctx.accounts.system_program.to_account_info()),
+                asset,
+                &ctx.accounts.collection,
+                &owner,
+                AttributesAuthority::new(&ctx.accounts.attributes_authority, ctx.bumps.attributes_authority),
+                attribute_list,
+            )?;
+        }
+
+        ctx.accounts.player_profile.bind(owner.key(), ctx.bumps.player_profile);
+        overflow_to_prestige(&mut ctx.accounts.player_profile, ctx.accounts.to_asset.key(), excess_levels);
+
+        msg!("🎓 Transferred {} levels ({} after loss) | Donor: {} | Recipient: {}",
+             amount, received, donor_level, recipient_level);
+
+        Ok(())
+    }
+
+    /// 🏛️ Cap a Collection's Supply (collection update authority)
+    /// Features: Attaches a CollectionConfig to collections created outside
+    /// this program on first call; the cap can be raised, lowered to what has
+    /// already been minted, reserved by mint shards or allocated to the team,
+    /// or lifted (0)
+    pub fn set_collection_max_supply(ctx: Context<SetCollectionMaxSupply>, max_supply: u32) -> Result<()> {
+        let config = &mut ctx.accounts.collection_config;
+        let authority = ctx.accounts.authority.key();
+        if config.collection == Pubkey::default() {
+            // 🔑 Only the collection's own update authority may attach a config
+            require_keys_eq!(*ctx.accounts.collection.owner, mpl_core::ID, NftError::InvalidAsset);
+            let collection = BaseCollectionV1::from_bytes(&ctx.accounts.collection.try_borrow_data()?)
+                .map_err(|_| error!(NftError::InvalidAsset))?;
+            require_keys_eq!(collection.update_authority, authority, NftError::Unauthorized);
+
+            config.collection = ctx.accounts.collection.key();
+            config.authority = authority;
+            config.total_minted = 0;
+            config.total_burned = 0;
+            config.reserved_supply = 0;
+            config.team_allocation = 0;
+            config.team_minted = 0;
+            config.max_per_wallet = 0;
+            config.phases = vec![];
+            config.allowlist_root = [0; 32];
+            config.mint_price_lamports = 0;
+            config.price_curve = PriceCurve::Flat;
+            config.payment_mint = Pubkey::default();
+            config.token_price = 0;
+            config.burn_token_mint = Pubkey::default();
+            config.burn_token_amount = 0;
+            config.burn_asset_collection = Pubkey::default();
+            config.numbered_editions = false;
+            config.anti_bot = false;
+            config.proceeds_lamports = 0;
+            config.refund_deadline = 0;
+            config.drop_finalized = false;
+            config.reward_split_bps = 0;
+            config.unrebalanced_proceeds = 0;
+            config.rebalanced_epoch = -1;
+            config.royalty_bps = 0;
+            config.royalty_creators = vec![];
+            config.plugin_bundle = MintPluginBundle::default();
+            config.beta_sunset_at = 0;
+            config.beta_sunset_mode = BetaSunsetMode::Graduate;
+            config.beta_sunset_reveal_slot = 0;
+            config.beta_sunset_seed = [0; 32];
+            config.min_update_cooldown = 0;
+            config.bump = ctx.bumps.collection_config;
+        }
+        require_keys_eq!(config.authority, authority, NftError::Unauthorized);
+        let committed = config.committed_supply();
+        require!(max_supply == 0 || max_supply >= committed, NftError::InvalidMaxSupply);
+        config.max_supply = max_supply;
+
+        msg!("🏛️ Collection {} max supply: {} | Minted: {}",
+             config.collection, max_supply, config.total_minted);
+
+        Ok(())
+    }
+
+    /// 🧮 Reconcile a Mint Counter Shard
+    /// Features: Permissionless crank; creates the shard on first call, folds
+    /// its pending mints and proceeds into the collection's totals and, for capped
+    /// collections, tops its quota up with an even share of the unreserved supply
+    pub fn reconcile_counters(ctx: Context<ReconcileCounters>, shard_index: u8) -> Result<()> {
+        require!(shard_index < MINT_COUNTER_SHARDS, NftError::InvalidMintShard);
+        let config = &mut ctx.accounts.collection_config;
+        let shard = &mut ctx.accounts.mint_shard;
+        if shard.collection == Pubkey::default() {
+            shard.collection = config.collection;
+            shard.index = shard_index;
+            shard.bump = ctx.bumps.mint_shard;
+        }
+
+        let pending = shard.pending;
+        config.total_minted = config.total_minted.checked_add(pending).ok_or(NftError::MathOverflow)?;
+        config.proceeds_lamports = config
+            .proceeds_lamports
+            .checked_add(shard.pending_proceeds)
+            .ok_or(NftError::MathOverflow)?;
+        config.unrebalanced_proceeds = config
+            .unrebalanced_proceeds
+            .checked_add(shard.pending_proceeds)
+            .ok_or(NftError::MathOverflow)?;
+        shard.pending_proceeds = 0;
+        // Uncapped mints never draw on quota, so there may be less reserved than pending
+        config.reserved_supply = config.reserved_supply.saturating_sub(pending);
+        shard.pending = 0;
+
+        if config.max_supply != 0 {
+            let unreserved = config.max_supply.saturating_sub(config.committed_supply());
+            let top_up = unreserved.div_ceil(MINT_COUNTER_SHARDS as u32);
+            shard.quota = shard.quota.checked_add(top_up).ok_or(NftError::MathOverflow)?;
+            config.reserved_supply = config.reserved_supply.checked_add(top_up).ok_or(NftError::MathOverflow)?;
+        }
+
+        msg!("🧮 Shard {} reconciled | +{} minted | Total: {} | Shard quota: {}",
+             shard_index, pending, config.total_minted, shard.quota);
+
+        Ok(())
+    }
+
+    /// 🔍 Audit an Asset's Attributes Against Its NftState
+    /// Features: Permissionless; recomputes the Attributes plugin hash and
+    /// flags edits made directly through mpl-core. With `repair`, the signer
+    /// (who must hold the plugin's update authority) rewrites the fields
+    /// NftState tracks and the result becomes the new baseline. A state with
+    /// no hash yet adopts the current attributes as its baseline.
+    pub fn audit_attributes(ctx: Context<AuditAttributes>, repair: bool) -> Result<()> {
+        let asset = &ctx.accounts.asset;
+        let collection = ctx.accounts.collection.key();
+        require_keys_eq!(*asset.owner, mpl_core::ID, NftError::InvalidAsset);
+        verify_collection_membership(asset, &collection)?;
+        let nft_state = &mut ctx.accounts.nft_state;
+        nft_state.bind_collection(collection)?;
+
+        let found = hash_attributes(&fetch_attributes(asset)?);
+        let expected = nft_state.attributes_hash;
+        if expected == [0; 32] {
+            nft_state.attributes_hash = found;
+            msg!("🔍 No attribute baseline yet; adopted the current attributes");
+            return Ok(());
+        }
+
+        let tampered = found != expected;
+        let repaired = tampered && repair;
+        if repaired {
+            let attribute_list = merge_attributes(&ctx.accounts.program_config, asset, vec![
+                Attribute { key: "level".to_string(), value: nft_state.level.to_string() },
+                Attribute { key: "rarity".to_string(), value: nft_state.rarity.to_string() },
+                Attribute { key: "fusion_potential".to_string(), value: nft_state.fusion_potential.to_string() },
+                Attribute { key: "evolution_count".to_string(), value: nft_state.evolution_count.to_string() },
+            ])?;
+            nft_state.attributes_hash = write_attributes(
+                &MplCore::new(&ctx.accounts.mpl_core_program, &ctx.accounts.system_program.to_account_info()),
+                asset,
+                &ctx.accounts.collection,
+                &ctx.accounts.authority.to_account_info(),
+                AttributesAuthority::new(&ctx.accounts.attributes_authority, ctx.bumps.attributes_authority),
+                attribute_list,
+            )?;
+        }
+
+        emit!(AttributesAudited { asset: asset.key(), expected, found, tampered, repaired });
+
+        msg!("🔍 Attribute audit | Tampered: {} | Repaired: {}", tampered, repaired);
+
+        Ok(())
+    }
+
+    /// ⚔️ Open a Guild Objective
+    /// Features: The creator escrows a reward pool that contributors split
+    /// pro rata if the combined power target is reached before the deadline
+    pub fn create_objective(
+        ctx: Context<CreateObjective>,
+        objective_id: u64,
+        target_power: u64,
+        deadline: i64,
+        reward_lamports: u64,
+    ) -> Result<()> {
+        let now = program_clock(ctx.remaining_accounts)?.unix_timestamp;
+        require!(target_power > 0 && deadline > now, NftError::InvalidObjectiveTerms);
+
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: ctx.accounts.creator.to_account_info(),
+                    to: ctx.accounts.objective.to_account_info(),
+                },
+            ),
+            reward_lamports,
+        )?;
+        ctx.accounts.economy_ledger.record_escrow_deposit(reward_lamports)?;
+
+        let objective = &mut ctx.accounts.objective;
+        objective.creator = ctx.accounts.creator.key();
+        objective.objective_id = objective_id;
+        objective.target_power = target_power;
+        objective.deadline = deadline;
+        objective.reward_lamports = reward_lamports;
+        objective.bump = ctx.bumps.objective;
+
+        msg!("⚔️ Objective #{} opened | Target: {} power by {} | Reward: {} lamports",
+             objective_id, target_power, deadline, reward_lamports);
+
+        Ok(())
+    }
+
+    /// ⚔️ Contribute to a Guild Objective
+    /// Features: Spends achievement points from an owned asset and/or
+    /// commits the asset itself (adding its battle rating) until settlement;
+    /// one contribution per asset per objective
+    pub fn contribute_to_objective(
+        ctx: Context<ContributeToObjective>,
+        points: u64,
+        commit_asset: bool,
+    ) -> Result<()> {
+        let now = program_clock(ctx.remaining_accounts)?.unix_timestamp;
+        let contributor = ctx.accounts.contributor.key();
+        load_owned_asset(&ctx.accounts.asset, &contributor)?;
+
+        let objective = &mut ctx.accounts.objective;
+        require!(now < objective.deadline, NftError::ObjectiveClosed);
+
+        let nft_state = &mut ctx.accounts.nft_state;
+        let mut power = points;
+        if points > 0 {
+            nft_state.achievement_points = nft_state
+                .achievement_points
+                .checked_sub(points)
+                .ok_or(NftError::InsufficientAchievementPoints)?;
+        }
+        if commit_asset {
+            require!(nft_state.lock_state == LockState::Unlocked, NftError::AssetLocked);
+            nft_state.lock_state = LockState::Committed;
+            power = power.checked_add(battle_rating(nft_state)).ok_or(NftError::MathOverflow)?;
+        }
+        require!(power > 0, NftError::InvalidObjectiveTerms);
+
+        objective.total_power = objective.total_power.checked_add(power).ok_or(NftError::MathOverflow)?;
+        objective.open_contributions += 1;
+        if objective.met_at == 0 && objective.total_power >= objective.target_power {
+            objective.met_at = now;
+            msg!("🏁 Objective #{} met!", objective.objective_id);
+        }
+
+        let contribution = &mut ctx.accounts.contribution;
+        contribution.objective = objective.key();
+        contribution.contributor = contributor;
+        contribution.asset = ctx.accounts.asset.key();
+        contribution.points_spent = points;
+        contribution.power = power;
+        contribution.committed = commit_asset;
+        contribution.bump = ctx.bumps.contribution;
+
+        msg!("⚔️ +{} power to objective #{} | Total: {}/{}",
+             power, objective.objective_id, objective.total_power, objective.target_power);
+
+        Ok(())
+    }
+
+    /// ⚔️ Settle a Contribution After the Deadline
+    /// Features: Releases the committed asset; on success pays the pro-rata
+    /// share of the reward pool and stamps a guild badge attribute, on
+    /// failure refunds the spent points
+    pub fn settle_contribution(ctx: Context<SettleContribution>) -> Result<()> {
+        let now = program_clock(ctx.remaining_accounts)?.unix_timestamp;
+        let objective = &mut ctx.accounts.objective;
+        require!(now >= objective.deadline, NftError::ObjectiveStillOpen);
+
+        let contribution = &ctx.accounts.contribution;
+        let nft_state = &mut ctx.accounts.nft_state;
+        if contribution.committed && nft_state.lock_state == LockState::Committed {
+            nft_state.lock_state = LockState::Unlocked;
+        }
+        objective.open_contributions -= 1;
+
+        let succeeded = objective.met_at != 0;
+        if succeeded {
+            let share = mul_div(
+                objective.reward_lamports,
+                contribution.power,
+                objective.total_power,
+                Rounding::Floor,
+            )?;
+            transfer_lamports_from_program_account(
+                &objective.to_account_info(),
+                &ctx.accounts.contributor.to_account_info(),
+                share,
+            )?;
+            ctx.accounts.economy_ledger.record_reward(share)?;
+
+            let attribute_list = merge_attributes(&ctx.accounts.program_config, &ctx.accounts.asset, vec![
+                Attribute { key: "guild_badge".to_string(), value: objective.objective_id.to_string() },
+            ])?;
+            nft_state.attributes_hash = write_attributes(
+                &MplCore::new(&ctx.accounts.mpl_core_program, &ctx.accounts.system_program.to_account_info()),
+                &ctx.accounts.asset,
+                &ctx.accounts.collection,
+                &ctx.accounts.contributor.to_account_info(),
+                AttributesAuthority::new(&ctx.accounts.attributes_authority, ctx.bumps.attributes_authority),
+                attribute_list,
+            )?;
+
+            msg!("🏆 Objective #{} share: {} lamports + badge", objective.objective_id, share);
+        } else {
+            nft_state.achievement_points = nft_state
+                .achievement_points
+                .checked_add(contribution.points_spent)
+                .ok_or(NftError::MathOverflow)?;
+
+            msg!("↩️ Objective #{} failed | {} points refunded",
+                 objective.objective_id, contribution.points_spent);
+        }
+
+        Ok(())
+    }
+
+    /// ⚔️ Close a Guild Objective (creator)
+    /// Features: Once the deadline has passed and every contribution is
+    /// settled, returns what's left of the pool - all of it on failure,
+    /// rounding dust on success - along with the rent
+    pub fn close_objective(ctx: Context<CloseObjective>) -> Result<()> {
+        let now = program_clock(ctx.remaining_accounts)?.unix_timestamp;
+        let objective = &ctx.accounts.objective;
+        require!(
+            now >= objective.deadline && objective.open_contributions == 0,
+            NftError::ObjectiveStillOpen
+        );
+
+        let rent = Rent::get()?.minimum_balance(objective.to_account_info().data_len());
+        let remaining = objective.to_account_info().lamports().saturating_sub(rent);
+        ctx.accounts.economy_ledger.record_escrow_release(remaining)?;
+
+        msg!("⚔️ Objective #{} closed | {} lamports returned", objective.objective_id, remaining);
+
+        Ok(())
+    }
+
+    /// 💰 Withdraw Mint Proceeds (collection authority)
+    /// Features: Pays out of the treasury PDA up to the collection's
+    /// reconciled mint-price proceeds; run `reconcile_counters` first to
+    /// fold in recent mints. Escrowed drops must be finalized first.
+    pub fn withdraw_treasury(ctx: Context<WithdrawTreasury>, amount: u64) -> Result<()> {
+        let config = &mut ctx.accounts.collection_config;
+        require!(!config.escrow_active(), NftError::DropNotFinalized);
+        config.proceeds_lamports = config
+            .proceeds_lamports
+            .checked_sub(amount)
+            .ok_or(NftError::InsufficientTreasuryBalance)?;
+
+        pay_from_treasury(
+            &ctx.accounts.treasury,
+            &ctx.accounts.authority.to_account_info(),
+            &ctx.accounts.system_program,
+            ctx.bumps.treasury,
+            amount,
+        )?;
+        ctx.accounts.economy_ledger.record_treasury_spent(amount)?;
+
+        msg!("💰 Withdrew {} lamports of {} proceeds | Remaining: {}",
+             amount, config.collection, config.proceeds_lamports);
+
+        Ok(())
+    }
+
+    /// 🌳 Append a Page of NftStates to the Epoch's State Root
+    /// Features: Permissionless crank; hashes each NftState passed as a
+    /// remaining account into an `hashv([asset, level, rarity])` leaf and
+    /// appends it to the epoch's incremental merkle tree. States must be
+    /// passed in strictly ascending address order, across calls as well.
+    pub fn build_state_root<'info>(
+        ctx: Context<'_, '_, 'info, 'info, BuildStateRoot<'info>>,
+        epoch: u64,
+    ) -> Result<()> {
+        let clock = program_clock(ctx.remaining_accounts)?;
+        require!(epoch == clock.epoch, NftError::SnapshotEpochClosed);
+
+        let state_root = &mut ctx.accounts.state_root;
+        if state_root.leaf_count == 0 {
+            state_root.epoch = epoch;
+            state_root.bump = ctx.bumps.state_root;
+        }
+        require!(!state_root.finalized, NftError::StateRootFinalized);
+
+        let mut appended = 0u32;
+        for state_info in without_mock_clock(ctx.remaining_accounts) {
+            require!(*state_info.key > state_root.last_state, NftError::SnapshotOutOfOrder);
+            require_keys_eq!(*state_info.owner, crate::ID, NftError::InvalidNftState);
+            let nft_state = NftState::try_deserialize(&mut &state_info.try_borrow_data()?[..])?;
+            let expected = Pubkey::create_program_address(
+                &[b"nft_state", nft_state.asset.as_ref(), &[nft_state.bump]],
+                &crate::ID,
+            )
+            .map_err(|_| error!(NftError::InvalidNftState))?;
+            require_keys_eq!(*state_info.key, expected, NftError::InvalidNftState);
+
+            state_root.append(state_leaf(&nft_state.asset, nft_state.level, nft_state.rarity))?;
+            state_root.last_state = *state_info.key;
+            appended += 1;
+        }
+        require!(appended > 0, NftError::NothingToProcess);
+
+        msg!("🌳 Epoch {} state root: +{} leaves | Total: {}",
+             epoch, appended, state_root.leaf_count);
+
+        Ok(())
+    }
+
+    /// 🌳 Finalize an Epoch's State Root (admin only)
+    /// Features: Seals the tree once every NftState has been appended and
+    /// publishes the root that asset proofs verify against
+    pub fn finalize_state_root(ctx: Context<FinalizeStateRoot>, epoch: u64) -> Result<()> {
+        let state_root = &mut ctx.accounts.state_root;
+        require!(!state_root.finalized, NftError::StateRootFinalized);
+        require!(state_root.leaf_count > 0, NftError::NothingToProcess);
+
+        state_root.root = state_root.compute_root();
+        state_root.finalized = true;
+
+        emit!(StateRootPublished {
+            epoch,
+            root: state_root.root,
+            leaf_count: state_root.leaf_count,
+        });
+
+        msg!("🌳 Epoch {} state root finalized over {} assets", epoch, state_root.leaf_count);
+
+        Ok(())
+    }
+
+    /// 🤝 Register as a Market Maker
+    /// Features: Escrows collateral in the MarketMaker PDA; `buy_nft` then
+    /// accrues the buyer's marketplace fees against it instead of charging
+    /// them per trade, for one netted settlement per day
+    pub fn register_market_maker(ctx: Context<RegisterMarketMaker>, collateral_lamports: u64) -> Result<()> {
+        require!(
+            collateral_lamports >= MIN_MARKET_MAKER_COLLATERAL,
+            NftError::InsufficientCollateral
+        );
+
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: ctx.accounts.wallet.to_account_info(),
+                    to: ctx.accounts.market_maker.to_account_info(),
+                },
+            ),
+            collateral_lamports,
+        )?;
+        ctx.accounts.economy_ledger.record_escrow_deposit(collateral_lamports)?;
+
+        let market_maker = &mut ctx.accounts.market_maker;
+        market_maker.wallet = ctx.accounts.wallet.key();
+        market_maker.collateral_lamports = collateral_lamports;
+        market_maker.accrued_fees = 0;
+        market_maker.deferred_trades = 0;
+        market_maker.period_start = 0;
+        market_maker.defaulted = false;
+        market_maker.bump = ctx.bumps.market_maker;
+
+        msg!("🤝 Market maker {} registered | Collateral: {} lamports",
+             market_maker.wallet, collateral_lamports);
+
+        Ok(())
+    }
+
+    /// 🤝 Settle a Market Maker's Accrued Fees
+    /// Features: One transfer to the treasury for every trade deferred since
+    /// the last settlement; due within a day of the first one
+    pub fn settle_market_maker(ctx: Context<SettleMarketMaker>) -> Result<()> {
+        let accrued = ctx.accounts.market_maker.accrued_fees;
+        require!(accrued > 0, NftError::NothingToProcess);
+
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: ctx.accounts.wallet.to_account_info(),
+                    to: ctx.accounts.treasury.to_account_info(),
+                },
+            ),
+            accrued,
+        )?;
+        ctx.accounts.economy_ledger.record_collected(accrued)?;
+
+        let market_maker = &mut ctx.accounts.market_maker;
+        let trades = market_maker.deferred_trades;
+        market_maker.accrued_fees = 0;
+        market_maker.deferred_trades = 0;
+
+        msg!("🤝 Settled {} lamports of fees over {} trades", accrued, trades);
+
+        Ok(())
+    }
+
+    /// 🚨 Claim a Market Maker Default
+    /// Features: Permissionless once a settlement is past due plus the grace
+    /// period; the accrued fees are seized from collateral and the maker
+    /// loses fee deferral
+    pub fn claim_market_maker_default(ctx: Context<ClaimMarketMakerDefault>) -> Result<()> {
+        let now = program_clock(ctx.remaining_accounts)?.unix_timestamp;
+        let market_maker = &ctx.accounts.market_maker;
+        require!(market_maker.accrued_fees > 0, NftError::NothingToProcess);
+        require!(
+            now >= market_maker.settlement_due() + MARKET_MAKER_GRACE_PERIOD,
+            NftError::MarketMakerNotInDefault
+        );
+
+        let seized = market_maker.accrued_fees;
+        transfer_lamports_from_program_account(
+            &ctx.accounts.market_maker.to_account_info(),
+            &ctx.accounts.treasury.to_account_info(),
+            seized,
+        )?;
+        let ledger = &mut ctx.accounts.economy_ledger;
+        ledger.record_escrow_release(seized)?;
+        ledger.record_collected(seized)?;
+
+        let market_maker = &mut ctx.accounts.market_maker;
+        market_maker.collateral_lamports -= seized;
+        market_maker.accrued_fees = 0;
+        market_maker.deferred_trades = 0;
+        market_maker.defaulted = true;
+
+        emit!(MarketMakerDefaulted {
+            wallet: market_maker.wallet,
+            seized_lamports: seized,
+            remaining_collateral: market_maker.collateral_lamports,
+        });
+
+        msg!("🚨 Market maker {} defaulted | Seized: {} lamports", market_maker.wallet, seized);
+
+        Ok(())
+    }
+
+    /// 🤝 Deregister as a Market Maker
+    /// Features: Returns the remaining collateral once nothing is owed
+    pub fn close_market_maker(ctx: Context<CloseMarketMaker>) -> Result<()> {
+        let market_maker = &ctx.accounts.market_maker;
+        require!(market_maker.accrued_fees == 0, NftError::MarketMakerUnsettled);
+        ctx.accounts.economy_ledger.record_escrow_release(market_maker.collateral_lamports)?;
+
+        msg!("🤝 Market maker {} closed | Collateral returned: {} lamports",
+             market_maker.wallet, market_maker.collateral_lamports);
+
+        Ok(())
+    }
+
+    /// 🪪 Show the Owner's .sol Name on an Asset
+    /// Features: Reads the Solana Name Service record for `name` (without
+    /// ".sol") passed as the first remaining account, checks it is owned by
+    /// the signing asset owner and writes `owner_handle` and
+    /// `owner_handle_wallet`, so readers can confirm the handle still
+    /// matches the asset's owner
+    pub fn set_owner_handle<'info>(
+        ctx: Context<'_, '_, 'info, 'info, SetOwnerHandle<'info>>,
+        name: String,
+    ) -> Result<()> {
+        let owner = ctx.accounts.owner.to_account_info();
+        let asset = &ctx.accounts.asset;
+        load_owned_asset(asset, owner.key)?;
+
+        let name_record = without_mock_clock(ctx.remaining_accounts)
+            .first()
+            .ok_or(NftError::InvalidNameRecord)?;
+        verify_sns_owner(name_record, &name, owner.key)?;
+        let handle = format!("{}.sol", name);
+        require!(handle.len() <= MAX_TRAIT_VALUE_LEN, NftError::InvalidNameRecord);
+
+        let attribute_list = merge_attributes(&ctx.accounts.program_config, asset, vec![
+            Attribute { key: "owner_handle".to_string(), value: handle.clone() },
+            Attribute { key: "owner_handle_wallet".to_string(), value: owner.key.to_string() },
+        ])?;
+
+        let attributes_hash = write_attributes(
+            &MplCore::new(&ctx.accounts.mpl_core_program, &ctx.accounts.system_program.to_account_info()),
+            asset,
+            &ctx.accounts.collection,
+            &owner,
+            AttributesAuthority::new(&ctx.accounts.attributes_authority, ctx.bumps.attributes_authority),
+            attribute_list,
+        )?;
+        stamp_attributes_hash(&ctx.accounts.nft_state, attributes_hash)?;
+
+        msg!("🪪 {} now shows owner {}", asset.key(), handle);
+
+        Ok(())
+    }
+
+    /// 🩺 Validate a Collection's Configuration
+    /// Features: Read-only; cross-checks the program, collection, shop and
+    /// edition config accounts for consistency and returns every problem
+    /// found as a ConfigReport, so operators can simulate it after an update
+    /// instead of discovering a bricked mint
+    pub fn validate_config(ctx: Context<ValidateConfig>) -> Result<ConfigReport> {
+        let collection_config = &ctx.accounts.collection_config;
+        let mut issues = ctx.accounts.program_config.config_issues();
+        issues.extend(collection_config.config_issues());
+        if let Some(shop_config) = load_if_initialized::<ShopConfig>(&ctx.accounts.shop_config)? {
+            issues.extend(shop_config.config_issues());
+        }
+        if collection_config.numbered_editions && ctx.accounts.edition_counter.owner != &crate::ID {
+            issues.push(ConfigIssue::EditionCounterMissing);
+        }
+
+        msg!("🩺 {} config issues for {}", issues.len(), collection_config.collection);
+
+        Ok(ConfigReport {
+            version: CONFIG_REPORT_VERSION,
+            collection: collection_config.collection,
+            valid: issues.is_empty(),
+            issues,
+        })
+    }
+
+    /// 🛡️ Configure a Collection's Evolution Insurance (collection authority)
+    /// Features: Creates the collection's InsurancePool on first call; sets the
+    /// share of a stake paid back when an insured evolution fails (above
+    /// 10 000 bps pays a bonus out of the pool), the pity points granted and
+    /// the largest stake accepted
+    pub fn configure_insurance(
+        ctx: Context<ConfigureInsurance>,
+        refund_bps: u16,
+        pity_points: u64,
+        max_stake_lamports: u64,
+    ) -> Result<()> {
+        require!(max_stake_lamports > 0, NftError::InvalidInsuranceTerms);
+
+        let pool = &mut ctx.accounts.insurance_pool;
+        if pool.collection == Pubkey::default() {
+            pool.collection = ctx.accounts.collection_config.collection;
+            pool.bump = ctx.bumps.insurance_pool;
+        }
+        pool.refund_bps = refund_bps;
+        pool.pity_points = pity_points;
+        pool.max_stake_lamports = max_stake_lamports;
+
+        msg!("🛡️ Insurance for {} | Refund: {} bps | Pity: {} points | Max stake: {} lamports",
+             pool.collection, refund_bps, pity_points, max_stake_lamports);
+
+        Ok(())
+    }
+
+    /// 🛡️ Insure a Queued Evolution
+    /// Features: Stakes SOL into the collection's InsurancePool against the
+    /// asset's pending queued evolution; settle once the crank has finalized it
+    pub fn insure_evolution(ctx: Context<InsureEvolution>, stake_lamports: u64) -> Result<()> {
+        let owner = &ctx.accounts.owner;
+        load_owned_asset(&ctx.accounts.asset, &owner.key())?;
+        let nft_state = &ctx.accounts.nft_state;
+        require!(
+            nft_state.lock_state == LockState::PendingEvolution,
+            NftError::EvolutionNotQueued
+        );
+        let pool = &ctx.accounts.insurance_pool;
+        require!(
+            stake_lamports > 0 && stake_lamports <= pool.max_stake_lamports,
+            NftError::InvalidInsuranceTerms
+        );
+
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: owner.to_account_info(),
+                    to: ctx.accounts.insurance_pool.to_account_info(),
+                },
+            ),
+            stake_lamports,
+        )?;
+        ctx.accounts.economy_ledger.record_escrow_deposit(stake_lamports)?;
+
+        let pool = &mut ctx.accounts.insurance_pool;
+        pool.outstanding_stakes = pool
+            .outstanding_stakes
+            .checked_add(stake_lamports)
+            .ok_or(NftError::MathOverflow)?;
+
+        let insurance = &mut ctx.accounts.insurance;
+        insurance.asset = ctx.accounts.asset.key();
+        insurance.owner = owner.key();
+        insurance.stake_lamports = stake_lamports;
+        insurance.evolution_count = nft_state.evolution_count;
+        insurance.bump = ctx.bumps.insurance;
+
+        msg!("🛡️ Evolution of {} insured for {} lamports", insurance.asset, stake_lamports);
+
+        Ok(())
+    }
+
+    /// 🛡️ Settle Evolution Insurance
+    /// Features: Once the insured evolution is finalized, a success rolls the
+    /// stake into the pool; a failure pays back the configured share (capped
+    /// by what the pool holds) and grants pity points to the asset. If the
+    /// asset was queued again before settling, any success since insuring
+    /// counts as a success.
+    pub fn settle_insurance(ctx: Context<SettleInsurance>) -> Result<()> {
+        let nft_state = &mut ctx.accounts.nft_state;
+        require!(
+            nft_state.lock_state != LockState::PendingEvolution,
+            NftError::EvolutionStillPending
+        );
+
+        let insurance = &ctx.accounts.insurance;
+        let stake = insurance.stake_lamports;
+        let evolved = nft_state.evolution_count > insurance.evolution_count;
+        let pool = &mut ctx.accounts.insurance_pool;
+        pool.outstanding_stakes -= stake;
+        let available = pool.balance_lamports.checked_add(stake).ok_or(NftError::MathOverflow)?;
+
+        if evolved {
+            pool.balance_lamports = available;
+            msg!("🛡️ Evolution succeeded | {} lamports rolled into the pool", stake);
+            return Ok(());
+        }
+
+        let payout = mul_div(stake, pool.refund_bps as u64, MAX_BPS as u64, Rounding::Floor)?.min(available);
+        pool.balance_lamports = available - payout;
+        let pity_points = pool.pity_points;
+        if payout > 0 {
+            transfer_lamports_from_program_account(
+                &ctx.accounts.insurance_pool.to_account_info(),
+                &ctx.accounts.owner.to_account_info(),
+                payout,
+            )?;
+        }
+        nft_state.achievement_points = nft_state
+            .achievement_points
+            .checked_add(pity_points)
+            .ok_or(NftError::MathOverflow)?;
+        let ledger = &mut ctx.accounts.economy_ledger;
+        ledger.record_escrow_release(payout)?;
+        ledger.record_points_issued(pity_points)?;
+
+        msg!("🛡️ Evolution failed | Paid out {} lamports | +{} pity points", payout, pity_points);
+
+        Ok(())
+    }
+
+    /// 👥 Mint from the Team Allocation (collection authority)
+    /// Features: Mints to the authority without price, phases, wallet limits
+    /// or mint shards; counted against `team_allocation` instead of public supply
+    pub fn mint_reserved(
+        ctx: Context<MintReserved>,
+        name: String,
+        uri: String,
+        level: u64,
+        rarity: String,
+        fusion_potential: u64,
+    ) -> Result<()> {
+        let clock = program_clock(ctx.remaining_accounts)?;
+        enforce_single_mint(&ctx.accounts.collection_config.to_account_info(), &ctx.accounts.instructions_sysvar)?;
+        let config = &mut ctx.accounts.collection_config;
+        require!(config.team_minted < config.team_allocation, NftError::TeamAllocationExhausted);
+        config.team_minted += 1;
+        let plugins = config.mint_plugins();
+        let team_minted = config.team_minted;
+        let team_allocation = config.team_allocation;
+
+        // ✍️ Collections handed to the program are signed for by their authority PDA
+        let collection_key = ctx.accounts.collection.key();
+        let authority_bump = [ctx.bumps.collection_authority];
+        let authority_seeds: &[&[u8]] = &[b"collection_authority", collection_key.as_ref(), &authority_bump];
+        let program_signer = program_signer(&ctx.accounts.collection, &ctx.accounts.collection_authority, authority_seeds)?;
+        let achievement_tiers = load_if_initialized::<AchievementTiers>(&ctx.accounts.achievement_tiers)?;
+        let edition = claim_editions(
+            &ctx.accounts.collection_config.to_account_info(),
+            ctx.accounts.edition_counter.as_deref_mut(),
+            1,
+        )?;
+
+        mint_core_asset(
+            &MplCore::new(&ctx.accounts.mpl_core_program, &ctx.accounts.system_program.to_account_info()),
+            &ctx.accounts.asset,
+            &ctx.accounts.collection,
+            &ctx.accounts.authority.to_account_info(),
+            name,
+            uri,
+            level,
+            &rarity,
+            fusion_potential,
+            false,
+            plugins,
+            edition,
+            program_signer,
+            None,
+            None,
+            achievement_tiers.as_ref(),
+            &ctx.accounts.program_config,
+            &clock,
+        )?;
+
+        msg!("👥 Team mint {} / {} for {}", team_minted, team_allocation, collection_key);
+
+        Ok(())
+    }
+
+    /// 🪓 Register as a Bonded Cranker
+    /// Features: Escrows CRANKER_BOND in the Cranker PDA for priority on paid
+    /// crank work; calling again tops a slashed bond back up
+    pub fn register_cranker(ctx: Context<RegisterCranker>) -> Result<()> {
+        let cranker = &mut ctx.accounts.cranker;
+        if cranker.wallet == Pubkey::default() {
+            cranker.wallet = ctx.accounts.wallet.key();
+            cranker.bump = ctx.bumps.cranker;
+        }
+        let deposit = CRANKER_BOND.saturating_sub(cranker.bond_lamports);
+        require!(deposit > 0, NftError::NothingToProcess);
+        cranker.bond_lamports = CRANKER_BOND;
+
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: ctx.accounts.wallet.to_account_info(),
+                    to: ctx.accounts.cranker.to_account_info(),
+                },
+            ),
+            deposit,
+        )?;
+        ctx.accounts.economy_ledger.record_escrow_deposit(deposit)?;
+
+        msg!("🪓 Cranker {} bonded | Deposited: {} lamports", ctx.accounts.wallet.key(), deposit);
+
+        Ok(())
+    }
+
+    /// 🪓 Deregister as a Cranker
+    /// Features: Returns whatever bond is left; faults are slashed in the
+    /// same transaction as the bad work, so nothing can be pending
+    pub fn close_cranker(ctx: Context<CloseCranker>) -> Result<()> {
+        let cranker = &ctx.accounts.cranker;
+        ctx.accounts.economy_ledger.record_escrow_release(cranker.bond_lamports)?;
+
+        msg!("🪓 Cranker {} closed | Jobs: {} | Bond returned: {} lamports",
+             cranker.wallet, cranker.jobs_completed, cranker.bond_lamports);
+
+        Ok(())
+    }
+
+    /// 🏆 Name a Collection's Achievement Ladder (collection authority)
+    /// Features: Up to MAX_ACHIEVEMENT_TIERS (min_level, name) tiers replacing
+    /// "Novice" … "Grandmaster" on this collection's mints; the first tier
+    /// starts at level 0 and each later one at a higher level
+    pub fn set_achievement_tiers(
+        ctx: Context<SetAchievementTiers>,
+        tiers: Vec<AchievementTier>,
+    ) -> Result<()> {
+        require!(
+            (1..=MAX_ACHIEVEMENT_TIERS).contains(&tiers.len())
+                && tiers[0].min_level == 0
+                && tiers.windows(2).all(|pair| pair[0].min_level < pair[1].min_level)
+                && tiers
+                    .iter()
+                    .all(|tier| !tier.name.is_empty() && tier.name.len() <= MAX_ACHIEVEMENT_NAME_LEN),
+            NftError::InvalidAchievementTiers
+        );
+
+        let achievement_tiers = &mut ctx.accounts.achievement_tiers;
+        achievement_tiers.collection = ctx.accounts.collection_config.collection;
+        achievement_tiers.tiers = tiers;
+        achievement_tiers.bump = ctx.bumps.achievement_tiers;
+
+        msg!("🏆 {} achievement tiers for {}", achievement_tiers.tiers.len(), achievement_tiers.collection);
+
+        Ok(())
+    }
+
+    /// 🧪 Schedule a Collection's Beta Sunset (collection authority)
+    /// Features: From `sunset_at`, anyone can crank `sunset_beta_assets` to
+    /// graduate or freeze every asset minted during a beta phase; 0 unschedules
+    pub fn set_beta_sunset(
+        ctx: Context<UpdateCollectionConfig>,
+        sunset_at: i64,
+        mode: BetaSunsetMode,
+    ) -> Result<()> {
+        let config = &mut ctx.accounts.collection_config;
+        require!(
+            sunset_at == 0
+                || config.phases.iter().filter(|phase| phase.beta).all(|phase| phase.ends_at <= sunset_at),
+            NftError::InvalidBetaSunset
+        );
+        config.beta_sunset_at = sunset_at;
+        config.beta_sunset_mode = mode;
+        config.beta_sunset_reveal_slot = 0;
+        config.beta_sunset_seed = [0; 32];
+
+        msg!("🧪 Beta sunset for {}: {:?} at {}", config.collection, mode, sunset_at);
+
+        Ok(())
+    }
+
+    /// 🧪 Sunset Beta Assets (permissionless crank)
+    /// Features: Once the collection's beta sunset has passed, retires each
+    /// (asset, nft_state, owner's player_profile) triple in the remaining
+    /// accounts per its sunset mode: Graduate adds BETA_GRADUATION_LEVELS
+    /// levels (past the cap, prestige) with a BETA_UPGRADE_BPS shot at the next
+    /// rarity, Freeze locks the asset for good. Graduation rolls
+    /// come from one committed seed, so the first Graduate crank only commits
+    /// it and the first one past its reveal slot reveals it. Assets that aren't
+    /// live beta assets, or whose state is locked, are skipped. The collection
+    /// must have been handed to its program authority, which signs every rewrite.
+    pub fn sunset_beta_assets<'info>(
+        ctx: Context<'_, '_, 'info, 'info, SunsetBetaAssets<'info>>,
+    ) -> Result<()> {
+        let clock = program_clock(ctx.remaining_accounts)?;
+        let now = clock.unix_timestamp;
+        let config = &ctx.accounts.collection_config;
+        require!(
+            config.beta_sunset_at != 0 && now >= config.beta_sunset_at,
+            NftError::BetaSunsetNotReached
+        );
+        let mode = config.beta_sunset_mode;
+
+        let collection_key = ctx.accounts.collection.key();
+        let authority_bump = [ctx.bumps.collection_authority];
+        let authority_seeds: &[&[u8]] = &[b"collection_authority", collection_key.as_ref(), &authority_bump];
+        require!(
+            program_signer(&ctx.accounts.collection, &ctx.accounts.collection_authority, authority_seeds)?.is_some(),
+            NftError::ProgramAuthorityRequired
+        );
+
+        let triples = without_mock_clock(ctx.remaining_accounts);
+        require!(
+            !triples.is_empty() && triples.len().is_multiple_of(3),
+            NftError::InvalidRemainingAccounts
+        );
+        // 🎲 Graduations share one seed: the first crank commits it to a future
+        // slot and the first crank after that slot reveals it, so no holder can
+        // simulate their own asset's roll and crank only when it upgrades
+        if mode == BetaSunsetMode::Graduate && ctx.accounts.collection_config.beta_sunset_seed == [0; 32] {
+            let config = &mut ctx.accounts.collection_config;
+            let randomness = Randomness::new(
+                ctx.accounts.program_config.randomness_provider,
+                &ctx.accounts.slot_hashes,
+            );
+            let revealed = match config.beta_sunset_reveal_slot {
+                0 => None,
+                slot => {
+                    require!(clock.slot > slot, NftError::RollRevealPending);
+                    randomness.revealed(slot, &[b"beta_sunset", collection_key.as_ref()])?
+                }
+            };
+            // 🔁 Not committed yet, or the commitment can no longer be revealed
+            let Some(seed) = revealed else {
+                config.beta_sunset_reveal_slot = clock.slot + ROLL_REVEAL_DELAY_SLOTS;
+                msg!("🎲 Beta graduation roll committed | Reveal after slot {}", config.beta_sunset_reveal_slot);
+                return Ok(());
+            };
+            config.beta_sunset_seed = seed;
+        }
+        let sunset_seed = ctx.accounts.collection_config.beta_sunset_seed;
+        let payer = ctx.accounts.payer.to_account_info();
+        let system_program = ctx.accounts.system_program.to_account_info();
+        let mpl_core_program = &ctx.accounts.mpl_core_program;
+        let collection = &ctx.accounts.collection;
+        let collection_authority = &ctx.accounts.collection_authority;
+
+        let mut retired = 0u32;
+        for triple in triples.chunks_exact(3) {
+            let (asset, state_info, profile_info) = (&triple[0], &triple[1], &triple[2]);
+            verify_collection_membership(asset, &collection_key)?;
+            let (expected_state, _) = Pubkey::find_program_address(&[b"nft_state", asset.key.as_ref()], &crate::ID);
+            require_keys_eq!(*state_info.key, expected_state, NftError::InvalidNftState);
+
+            let attribute_list = fetch_attributes(asset)?;
+            let attribute = |key: &str| {
+                attribute_list.iter().find(|attribute| attribute.key == key).map(|attribute| attribute.value.clone())
+            };
+            if attribute("beta").as_deref() != Some(BETA_LIVE) {
+                continue;
+            }
+            let mut nft_state = load_if_initialized::<NftState>(state_info)?;
+            if nft_state.as_ref().is_some_and(|state| state.lock_state != LockState::Unlocked) {
+                continue;
+            }
+
+            let mut level = attribute("level").and_then(|value| value.parse().ok()).unwrap_or(1u64);
+            let mut rarity = attribute("rarity").and_then(|value| Rarity::from_name(&value)).unwrap_or_default();
+            let mut excess_levels = 0;
+            let changes = match mode {
+                BetaSunsetMode::Graduate => {
+                    let seed = hashv(&[&sunset_seed, asset.key.as_ref()]).to_bytes();
+                    (level, excess_levels) = ctx
+                        .accounts
+                        .program_config
+                        .cap_level(level, level.saturating_add(BETA_GRADUATION_LEVELS));
+                    if roll_below(&seed, 0, MAX_BPS as u64) < BETA_UPGRADE_BPS as u64 {
+                        rarity = next_rarity(rarity);
+                    }
+                    vec![
+                        Attribute { key: "level".to_string(), value: level.to_string() },
+                        Attribute { key: "rarity".to_string(), value: rarity.as_str().to_string() },
+                        Attribute { key: "beta".to_string(), value: BETA_GRADUATED.to_string() },
+                    ]
+                }
+                BetaSunsetMode::Freeze => vec![Attribute { key: "beta".to_string(), value: BETA_FROZEN.to_string() }],
+            };
+            let attribute_list = merge_attributes(&ctx.accounts.program_config, asset, changes)?;
+
+            let attributes_hash = write_attributes_signed(
+                &MplCore::new(mpl_core_program, &system_program),
+                asset,
+                collection,
+                &payer,
+                collection_authority,
+                AttributesAuthority::new(&ctx.accounts.attributes_authority, ctx.bumps.attributes_authority),
+                attribute_list,
+                &[authority_seeds],
+            )?;
+            if mode == BetaSunsetMode::Freeze {
+                MplCore::new(mpl_core_program, &system_program).update_plugin(
+                    AssetCall::new(asset, collection, &payer, collection_authority).signed(&[authority_seeds]),
+                    Plugin::PermanentFreezeDelegate(PermanentFreezeDelegate { frozen: true }),
+                )?;
+            }
+            if let Some(state) = nft_state.as_mut() {
+                match mode {
+                    BetaSunsetMode::Graduate => {
+                        state.level = level;
+                        state.rarity = rarity;
+                    }
+                    BetaSunsetMode::Freeze => state.lock_state = LockState::Sunset,
+                }
+                state.attributes_hash = attributes_hash;
+                upgrade_nft_state(state_info, &payer, &system_program)?;
+                state.try_serialize(&mut &mut state_info.try_borrow_mut_data()?[..])?;
+            }
+
+            credit_prestige(
+                profile_info,
+                &load_core_asset(asset)?.owner,
+                asset.key(),
+                excess_levels,
+                &payer,
+                &system_program,
+            )?;
+
+            emit!(BetaAssetSunset { collection: collection_key, asset: asset.key(), mode, level, rarity });
+            retired += 1;
+        }
+        require!(retired > 0, NftError::NothingToProcess);
+
+        msg!("🧪 {} beta assets of {} sunset ({:?})", retired, collection_key, mode);
+
+        Ok(())
+    }
+
+    /// 📈 Set a Collection's Price Curve (collection authority)
+    /// Features: Prices each lamport mint off the supply minted before it, on
+    /// top of the base or live phase price, so early minters pay less; Flat
+    /// turns the curve off. Supply counts reconciled mints plus the payer's
+    /// shard's pending ones; while a curve is set a shard takes at most
+    /// `CURVE_SHARD_PENDING_LIMIT` mints between reconciles, so a price lags
+    /// the true supply by at most 15 × that many mints.
+    pub fn set_price_curve(ctx: Context<UpdateCollectionConfig>, price_curve: PriceCurve) -> Result<()> {
+        if let PriceCurve::Exponential { step_bps, every } = price_curve {
+            require!(
+                step_bps > 0 && step_bps <= MAX_BPS && every > 0,
+                NftError::InvalidPriceCurve
+            );
+        }
+        let config = &mut ctx.accounts.collection_config;
+        config.price_curve = price_curve;
+
+        msg!("📈 Price curve for {}: {:?}", config.collection, price_curve);
+
+        Ok(())
+    }
+
+    /// ✅ Daily Check-In
+    /// Features: Counts today toward the wallet's streak and adds CHECK_IN_LUCK
+    /// luck, once per CHECK_IN_COOLDOWN per wallet
+    pub fn check_in(ctx: Context<CheckIn>) -> Result<()> {
+        let now = program_clock(ctx.remaining_accounts)?.unix_timestamp;
+        let profile = &mut ctx.accounts.player_profile;
+        profile.bind(ctx.accounts.wallet.key(), ctx.bumps.player_profile);
+        profile.claim_reward(WalletReward::CheckIn, now)?;
+        profile.record_activity(now);
+        profile.luck = profile.luck.saturating_add(CHECK_IN_LUCK);
+
+        msg!("✅ Checked in | Streak: {} days | Luck: {}", profile.streak_days, profile.luck);
+
+        Ok(())
+    }
+
+    /// 💤 Claim Idle XP on a Staked Asset
+    /// Features: One level per IDLE_XP_SECONDS staked since the asset's last
+    /// claim, banking at most IDLE_XP_MAX_LEVELS. The wallet claims once per
+    /// IDLE_XP_WALLET_COOLDOWN across all its staked assets, so rotating
+    /// claims through many assets doesn't multiply the reward.
+    pub fn claim_idle_xp(ctx: Context<ClaimIdleXp>) -> Result<()> {
+        let now = program_clock(ctx.remaining_accounts)?.unix_timestamp;
+        let owner = ctx.accounts.owner.to_account_info();
+        let stake = &mut ctx.accounts.stake_record;
+        let levels = (now.saturating_sub(stake.idle_claimed_at) / IDLE_XP_SECONDS).max(0) as u64;
+        let levels = levels.min(IDLE_XP_MAX_LEVELS);
+        require!(levels > 0, NftError::NothingToProcess);
+
+        let nft_state = &mut ctx.accounts.nft_state;
+        require!(nft_state.lock_state == LockState::Unlocked, NftError::AssetLocked);
+        let profile = &mut ctx.accounts.player_profile;
+        profile.bind(owner.key(), ctx.bumps.player_profile);
+        profile.claim_reward(WalletReward::IdleXp, now)?;
+        stake.idle_claimed_at = now;
+
+        let (level, excess_levels) = ctx
+            .accounts
+            .program_config
+            .cap_level(nft_state.level, nft_state.level.checked_add(levels).ok_or(NftError::MathOverflow)?);
+        nft_state.level = level;
+        overflow_to_prestige(profile, ctx.accounts.asset.key(), excess_levels);
+        let attribute_list = merge_attributes(&ctx.accounts.program_config, &ctx.accounts.asset, vec![
+            Attribute { key: "level".to_string(), value: nft_state.level.to_string() },
+        ])?;
+        nft_state.attributes_hash = write_attributes(
+            &MplCore::new(&ctx.accounts.mpl_core_program, &ctx.accounts.system_program.to_account_info()),
+            &ctx.accounts.asset,
+            &ctx.accounts.collection,
+            &owner,
+            AttributesAuthority::new(&ctx.accounts.attributes_authority, ctx.bumps.attributes_authority),
+            attribute_list,
+        )?;
+
+        msg!("💤 +{} idle levels | Level: {}", levels, nft_state.level);
+
+        Ok(())
+    }
+
+    /// 🤖 Toggle a Collection's Anti-Bot Checks (collection authority)
+    /// Features: Public mints must then be the transaction's only program mint
+    /// and come straight from the transaction, not a bundler program's CPI
+    pub fn set_anti_bot(ctx: Context<UpdateCollectionConfig>, enabled: bool) -> Result<()> {
+        let config = &mut ctx.accounts.collection_config;
+        config.anti_bot = enabled;
+
+        msg!("🤖 Anti-bot checks for {}: {}", config.collection, enabled);
+
+        Ok(())
+    }
+
+    /// 🌱 Set the Staking Reward Split (collection authority)
+    /// Features: Share of the collection's mint proceeds that each epoch's
+    /// `rebalance_treasury` moves into the staking reward pool
+    pub fn set_reward_split(ctx: Context<UpdateCollectionConfig>, reward_split_bps: u16) -> Result<()> {
+        require!(reward_split_bps <= MAX_BPS, NftError::InvalidFeeBps);
+        let config = &mut ctx.accounts.collection_config;
+        config.reward_split_bps = reward_split_bps;
+
+        msg!("🌱 {} sends {} bps of proceeds to staking rewards", config.collection, reward_split_bps);
+
+        Ok(())
+    }
+
+    /// 📨 Mint into Custody (collection authority)
+    /// Features: For fiat and card onboarding partners - mints the asset to a
+    /// PendingClaim PDA keyed by `claim_key`, the ed25519 public key whose seed
+    /// is `hashv(["custodial_claim", code])`, where the code reaches the buyer
+    /// off-chain (e.g. by email). Counts toward max supply and charges no price.
+    pub fn mint_custodial(
+        ctx: Context<MintCustodial>,
+        name: String,
+        uri: String,
+        level: u64,
+        rarity: String,
+        fusion_potential: u64,
+        claim_key: Pubkey,
+    ) -> Result<()> {
+        let clock = program_clock(ctx.remaining_accounts)?;
+        enforce_single_mint(&ctx.accounts.collection_config.to_account_info(), &ctx.accounts.instructions_sysvar)?;
+        let now = clock.unix_timestamp;
+        let config = &mut ctx.accounts.collection_config;
+        if config.max_supply > 0 && config.committed_supply() >= config.max_supply {
+            return Err(fail_with_context(NftError::SupplyExhausted, config.max_supply, config.committed_supply()));
+        }
+        config.total_minted += 1;
+        let plugins = config.mint_plugins();
+
+        let claim = &mut ctx.accounts.pending_claim;
+        claim.claim_key = claim_key;
+        claim.asset = ctx.accounts.asset.key();
+        claim.collection = ctx.accounts.collection.key();
+        claim.partner = ctx.accounts.authority.key();
+        claim.created_at = now;
+        claim.bump = ctx.bumps.pending_claim;
+
+        // ✍️ Collections handed to the program are signed for by their authority PDA
+        let collection_key = ctx.accounts.collection.key();
+        let authority_bump = [ctx.bumps.collection_authority];
+        let authority_seeds: &[&[u8]] = &[b"collection_authority", collection_key.as_ref(), &authority_bump];
+        let program_signer = program_signer(&ctx.accounts.collection, &ctx.accounts.collection_authority, authority_seeds)?;
+        let achievement_tiers = load_if_initialized::<AchievementTiers>(&ctx.accounts.achievement_tiers)?;
+        let edition = claim_editions(
+            &ctx.accounts.collection_config.to_account_info(),
+            ctx.accounts.edition_counter.as_deref_mut(),
+            1,
+        )?;
+
+        let custodian = ctx.accounts.pending_claim.to_account_info();
+        mint_core_asset(
+            &MplCore::new(&ctx.accounts.mpl_core_program, &ctx.accounts.system_program.to_account_info()),
+            &ctx.accounts.asset,
+            &ctx.accounts.collection,
+            &ctx.accounts.authority.to_account_info(),
+            name,
+            uri,
+            level,
+            &rarity,
+            fusion_potential,
+            false,
+            plugins,
+            edition,
+            program_signer,
+            None,
+            Some(&custodian),
+            achievement_tiers.as_ref(),
+            &ctx.accounts.program_config,
+            &clock,
+        )?;
+
+        msg!("📨 {} minted into custody for {}", ctx.accounts.asset.key(), collection_key);
+
+        Ok(())
+    }
+
+    /// 📨 Claim a Custodial Asset
+    /// Features: The claim code's key signs (pending claim, destination) in a
+    /// preceding ed25519 instruction, so the code itself never goes on chain
+    /// and a copied signature can only deliver the asset to the same
+    /// destination. The PendingClaim's rent returns to the partner.
+    pub fn claim_custodial_asset(ctx: Context<ClaimCustodialAsset>) -> Result<()> {
+        let claim = &ctx.accounts.pending_claim;
+        let destination = ctx.accounts.destination.to_account_info();
+        verify_ed25519_instruction(
+            &ctx.accounts.instructions_sysvar,
+            &claim.claim_key,
+            &custodial_claim_message(&claim.key(), destination.key),
+        )
+        .map_err(|_| error!(NftError::InvalidClaimCode))?;
+
+        let claimer = ctx.accounts.claimer.to_account_info();
+        let claim_key = claim.claim_key;
+        let claim_bump = [claim.bump];
+        MplCore::new(&ctx.accounts.mpl_core_program, &ctx.accounts.system_program.to_account_info()).transfer(
+            AssetCall::new(
+                &ctx.accounts.asset,
+                &ctx.accounts.collection,
+                &claimer,
+                &ctx.accounts.pending_claim.to_account_info(),
+            )
+            .signed(&[&[b"pending_claim", claim_key.as_ref(), &claim_bump]]),
+            &destination,
+        )?;
+
+        emit!(CustodialAssetClaimed {
+            asset: ctx.accounts.asset.key(),
+            claim_key,
+            wallet: destination.key(),
+        });
+
+        msg!("📨 {} claimed to {}", ctx.accounts.asset.key(), destination.key());
+
+        Ok(())
+    }
+
+    /// 📉 Create the Floor Oracle (admin)
+    /// Features: Per-rarity floor estimates fed by every marketplace sale
+    pub fn initialize_floor_oracle(ctx: Context<InitializeFloorOracle>) -> Result<()> {
+        let oracle = &mut ctx.accounts.floor_oracle;
+        oracle.floors = [FloorEstimate::default(); 7];
+        oracle.bump = ctx.bumps.floor_oracle;
+
+        msg!("📉 Floor oracle initialized");
+
+        Ok(())
+    }
+
+    /// 📉 Read a Rarity's Floor Estimate
+    /// Features: Read-only; returns the rarity's FloorEstimate as return data
+    /// so lending, insurance and partner programs can value assets by CPI
+    pub fn floor_price(ctx: Context<ReadFloorOracle>, rarity: Rarity) -> Result<FloorEstimate> {
+        Ok(ctx.accounts.floor_oracle.floors[rarity as usize])
+    }
+
+    /// 🔐 Escrow a Collection's Drop (collection authority)
+    /// Features: Mint-price proceeds stay locked in the treasury until
+    /// `finalize_drop`; if `reveal_deadline` passes first, minters can
+    /// `refund_mint`. Enable before the drop opens - earlier mints aren't refundable.
+    pub fn enable_mint_escrow(ctx: Context<UpdateCollectionConfig>, reveal_deadline: i64) -> Result<()> {
+        let now = program_clock(ctx.remaining_accounts)?.unix_timestamp;
+        let config = &mut ctx.accounts.collection_config;
+        require!(
+            !config.escrow_active() && !config.drop_finalized && reveal_deadline > now,
+            NftError::InvalidRefundDeadline
+        );
+        config.refund_deadline = reveal_deadline;
+
+        msg!("🔐 Drop for {} escrowed until {}", config.collection, reveal_deadline);
+
+        Ok(())
+    }
+
+    /// 🔐 Finalize an Escrowed Drop (collection authority)
+    /// Features: Releases the proceeds for withdrawal and ends refunds; must
+    /// happen before the reveal deadline
+    pub fn finalize_drop(ctx: Context<UpdateCollectionConfig>) -> Result<()> {
+        let now = program_clock(ctx.remaining_accounts)?.unix_timestamp;
+        let config = &mut ctx.accounts.collection_config;
+        require!(config.escrow_active(), NftError::DropNotEscrowed);
+        require!(now < config.refund_deadline, NftError::RefundDeadlinePassed);
+        config.drop_finalized = true;
+
+        emit!(DropFinalized { collection: config.collection, proceeds_lamports: config.proceeds_lamports });
+
+        msg!("🔐 Drop for {} finalized | Proceeds: {}", config.collection, config.proceeds_lamports);
+
+        Ok(())
+    }
+
+    /// 🔐 Refund an Escrowed Mint
+    /// Features: Once an escrowed drop's reveal deadline passes unfinalized,
+    /// burns one of the minter's assets from the collection and refunds their
+    /// average escrowed price per mint from the treasury
+    pub fn refund_mint(ctx: Context<RefundMint>) -> Result<()> {
+        let now = program_clock(ctx.remaining_accounts)?.unix_timestamp;
+        let config = &mut ctx.accounts.collection_config;
+        require!(config.escrow_active(), NftError::DropNotEscrowed);
+        require!(now >= config.refund_deadline, NftError::RefundDeadlineNotReached);
+        let receipt = &mut ctx.accounts.mint_receipt;
+        require!(receipt.escrowed_mints > 0, NftError::NothingToRefund);
+
+        let minter = ctx.accounts.minter.to_account_info();
+        load_owned_asset(&ctx.accounts.asset, minter.key)?;
+        verify_collection_membership(&ctx.accounts.asset, &config.collection)?;
+        MplCore::new(&ctx.accounts.mpl_core_program, &ctx.accounts.system_program.to_account_info())
+            .burn(AssetCall::new(&ctx.accounts.asset, &ctx.accounts.collection, &minter, &minter))?;
+
+        // 💸 Average share, so the last refund takes any rounding remainder
+        let refund = receipt.escrowed_lamports / receipt.escrowed_mints as u64;
+        receipt.escrowed_mints -= 1;
+        receipt.escrowed_lamports -= refund;
+        config.proceeds_lamports = config.proceeds_lamports.saturating_sub(refund);
+        config.total_burned = config.total_burned.saturating_add(1);
+        if refund > 0 {
+            pay_from_treasury(
+                &ctx.accounts.treasury,
+                &minter,
+                &ctx.accounts.system_program,
+                ctx.bumps.treasury,
+                refund,
+            )?;
+        }
+        ctx.accounts.economy_ledger.record_treasury_spent(refund)?;
+
+        msg!("🔐 Refunded {} lamports for burned {} | {} escrowed mints left",
+             refund, ctx.accounts.asset.key(), receipt.escrowed_mints);
+
+        Ok(())
+    }
+
+    /// 🧊 Freeze an Asset
+    /// Features: Locks transfers with a FreezeDelegate plugin held by the
+    /// program's `["stake_authority"]` PDA. The owner may freeze their own
+    /// asset (adding the plugin if needed); the admin may freeze one whose
+    /// plugin the program already holds. Only the freezer or the admin thaws it
+    pub fn freeze_asset(ctx: Context<FreezeAsset>) -> Result<()> {
+        let authority = ctx.accounts.authority.to_account_info();
+        let freeze_authority = ctx.accounts.stake_authority.to_account_info();
+        let system_program = ctx.accounts.system_program.to_account_info();
+
+        let base_asset = load_core_asset(&ctx.accounts.asset)?;
+        let is_owner = base_asset.owner == authority.key();
+        require!(
+            is_owner || ctx.accounts.program_config.authority == authority.key(),
+            NftError::Unauthorized
+        );
+
+        match fetch_plugin::<BaseAssetV1, FreezeDelegate>(&ctx.accounts.asset, PluginType::FreezeDelegate) {
+            Ok((plugin_authority, freeze, _)) => {
+                require!(!freeze.frozen, NftError::AssetAlreadyFrozen);
+                require!(
+                    plugin_authority == PluginAuthority::Address { address: freeze_authority.key() },
+                    NftError::FreezeAuthorityMismatch
+                );
+                set_stake_freeze(
+                    &MplCore::new(&ctx.accounts.mpl_core_program, &system_program),
+                    &ctx.accounts.asset,
+                    &ctx.accounts.collection,
+                    &authority,
+                    &freeze_authority,
+                    ctx.bumps.stake_authority,
+                    true,
+                )?;
+            }
+            Err(_) => {
+                // ✍️ Adding an owner-managed plugin takes the owner's signature
+                require!(is_owner, NftError::NotAssetOwner);
+                MplCore::new(&ctx.accounts.mpl_core_program, &system_program).add_plugin(
+                    AssetCall::new(&ctx.accounts.asset, &ctx.accounts.collection, &authority, &authority),
+                    Plugin::FreezeDelegate(FreezeDelegate { frozen: true }),
+                    Some(PluginAuthority::Address { address: freeze_authority.key() }),
+                )?;
+            }
+        }
+
+        let record = &mut ctx.accounts.freeze_record;
+        record.asset = ctx.accounts.asset.key();
+        record.frozen_by = authority.key();
+        record.frozen_at = program_clock(ctx.remaining_accounts)?.unix_timestamp;
+        record.bump = ctx.bumps.freeze_record;
+
+        emit!(AssetFreezeChanged { asset: record.asset, authority: record.frozen_by, frozen: true });
+
+        msg!("🧊 Asset {} frozen by {}", record.asset, record.frozen_by);
+
+        Ok(())
+    }
+
+    /// 🌡️ Thaw an Asset
+    /// Features: Undoes `freeze_asset`; the freezer or the admin signs, and
+    /// the FreezeRecord's rent goes back to the freezer
+    pub fn thaw_asset(ctx: Context<ThawAsset>) -> Result<()> {
+        let authority = ctx.accounts.authority.to_account_info();
+        let record = &ctx.accounts.freeze_record;
+        require!(
+            authority.key() == record.frozen_by || authority.key() == ctx.accounts.program_config.authority,
+            NftError::Unauthorized
+        );
+
+        set_stake_freeze(
+            &MplCore::new(&ctx.accounts.mpl_core_program, &ctx.accounts.system_program.to_account_info()),
+            &ctx.accounts.asset,
+            &ctx.accounts.collection,
+            &authority,
+            &ctx.accounts.stake_authority,
+            ctx.bumps.stake_authority,
+            false,
+        )?;
+
+        emit!(AssetFreezeChanged { asset: record.asset, authority: authority.key(), frozen: false });
+
+        msg!("🌡️ Asset {} thawed by {}", record.asset, authority.key());
+
+        Ok(())
+    }
+
+    /// 🌱 Staking Reward Pool Bootstrap (admin)
+    /// Features: Escrow that collection proceeds compound into, at most once
+    /// per collection every `epoch_seconds`
+    pub fn initialize_staking_reward_pool(
+        ctx: Context<InitializeStakingRewardPool>,
+        epoch_seconds: i64,
+    ) -> Result<()> {
+        require!(epoch_seconds > 0, NftError::InvalidRewardEpoch);
+        let pool = &mut ctx.accounts.staking_reward_pool;
+        pool.epoch_seconds = epoch_seconds;
+        pool.total_topped_up = 0;
+        pool.bump = ctx.bumps.staking_reward_pool;
+
+        msg!("🌱 Staking reward pool opens | Epoch: {}s", epoch_seconds);
+
+        Ok(())
+    }
+
+    /// 🌱 Rebalance a Collection's Treasury Share
+    /// Features: Permissionless crank, once per collection per reward pool
+    /// epoch; moves `reward_split_bps` of the proceeds reconciled since the
+    /// last rebalance from the treasury into the staking reward pool. Waits
+    /// while the collection's drop escrow is active
+    pub fn rebalance_treasury(ctx: Context<RebalanceTreasury>) -> Result<()> {
+        let now = program_clock(ctx.remaining_accounts)?.unix_timestamp;
+        let epoch = ctx.accounts.staking_reward_pool.epoch_at(now);
+        let config = &mut ctx.accounts.collection_config;
+        require!(epoch > config.rebalanced_epoch, NftError::RebalanceNotDue);
+        require!(!config.escrow_active(), NftError::DropNotFinalized);
+
+        // 💸 Refunds may have shrunk proceeds below what was reconciled
+        let inflow = config.unrebalanced_proceeds.min(config.proceeds_lamports);
+        let top_up = bps_of(inflow, config.reward_split_bps)?;
+        config.unrebalanced_proceeds = 0;
+        config.rebalanced_epoch = epoch;
+        config.proceeds_lamports -= top_up;
+
+        if top_up > 0 {
+            pay_from_treasury(
+                &ctx.accounts.treasury,
+                &ctx.accounts.staking_reward_pool.to_account_info(),
+                &ctx.accounts.system_program,
+                ctx.accounts.program_config.treasury_bump,
+                top_up,
+            )?;
+            let ledger = &mut ctx.accounts.economy_ledger;
+            ledger.record_treasury_spent(top_up)?;
+            ledger.record_escrow_deposit(top_up)?;
+            let pool = &mut ctx.accounts.staking_reward_pool;
+            pool.total_topped_up = pool.total_topped_up.checked_add(top_up).ok_or(NftError::MathOverflow)?;
+        }
+
+        emit!(TreasuryRebalanced { collection: config.collection, epoch, inflow_lamports: inflow, top_up_lamports: top_up });
+
+        msg!("🌱 {} rebalanced for epoch {} | Inflow: {} | To rewards: {}",
+             config.collection, epoch, inflow, top_up);
+
+        Ok(())
+    }
+
+    /// 🤝 Delegate Transfers to the Program
+    /// Features: The owner makes the program's `["transfer_authority"]` PDA the
+    /// asset's TransferDelegate (adding the plugin or re-approving it, as
+    /// mpl-core hands it back to each new owner), enabling `program_transfer`
+    pub fn set_transfer_delegate(ctx: Context<SetTransferDelegate>) -> Result<()> {
+        let owner = ctx.accounts.owner.to_account_info();
+        let system_program = ctx.accounts.system_program.to_account_info();
+        load_owned_asset(&ctx.accounts.asset, owner.key)?;
+
+        let backend = MplCore::new(&ctx.accounts.mpl_core_program, &system_program);
+        let call = AssetCall::new(&ctx.accounts.asset, &ctx.accounts.collection, &owner, &owner);
+        let delegate = PluginAuthority::Address { address: ctx.accounts.transfer_authority.key() };
+        if fetch_plugin::<BaseAssetV1, TransferDelegate>(&ctx.accounts.asset, PluginType::TransferDelegate).is_ok() {
+            backend.approve_plugin_authority(call, PluginType::TransferDelegate, delegate)?;
+        } else {
+            backend.add_plugin(call, Plugin::TransferDelegate(TransferDelegate {}), Some(delegate))?;
+        }
+
+        msg!("🤝 Transfers of {} delegated to the program", ctx.accounts.asset.key());
+
+        Ok(())
+    }
+
+    /// 🤝 Revoke the Program's Transfer Delegation
+    /// Features: The owner removes the asset's TransferDelegate plugin
+    pub fn revoke_transfer_delegate(ctx: Context<SetTransferDelegate>) -> Result<()> {
+        let owner = ctx.accounts.owner.to_account_info();
+        let system_program = ctx.accounts.system_program.to_account_info();
+        load_owned_asset(&ctx.accounts.asset, owner.key)?;
+
+        MplCore::new(&ctx.accounts.mpl_core_program, &system_program).remove_plugin(
+            AssetCall::new(&ctx.accounts.asset, &ctx.accounts.collection, &owner, &owner),
+            PluginType::TransferDelegate,
+        )?;
+
+        msg!("🤝 Transfer delegation of {} revoked", ctx.accounts.asset.key());
+
+        Ok(())
+    }
+
+    /// ✍️ Approve an Update Delegate
+    /// Features: The owner names a key (e.g. a game server) that may sign
+    /// `update_nft_metadata` and `evolve_nft` for the asset, recorded as its
+    /// UpdateDelegate plugin. That plugin is authority-managed, so the
+    /// collection's program authority PDA adds or re-approves it
+    pub fn approve_update_delegate(ctx: Context<SetUpdateDelegate>, delegate: Pubkey) -> Result<()> {
+        let owner = ctx.accounts.owner.to_account_info();
+        let system_program = ctx.accounts.system_program.to_account_info();
+        load_owned_asset(&ctx.accounts.asset, owner.key)?;
+
+        let collection_key = ctx.accounts.collection.key();
+        let authority_bump = [ctx.bumps.collection_authority];
+        let authority_seeds: &[&[u8]] = &[b"collection_authority", collection_key.as_ref(), &authority_bump];
+        require!(
+            program_signer(&ctx.accounts.collection, &ctx.accounts.collection_authority, authority_seeds)?.is_some(),
+            NftError::ProgramAuthorityRequired
+        );
+
+        let backend = MplCore::new(&ctx.accounts.mpl_core_program, &system_program);
+        let signer_seeds = [authority_seeds];
+        let call = AssetCall::new(&ctx.accounts.asset, &ctx.accounts.collection, &owner, &ctx.accounts.collection_authority)
+            .signed(&signer_seeds);
+        let authority = PluginAuthority::Address { address: delegate };
+        if fetch_plugin::<BaseAssetV1, UpdateDelegate>(&ctx.accounts.asset, PluginType::UpdateDelegate).is_ok() {
+            backend.approve_plugin_authority(call, PluginType::UpdateDelegate, authority)?;
+        } else {
+            backend.add_plugin(
+                call,
+                Plugin::UpdateDelegate(UpdateDelegate { additional_delegates: vec![] }),
+                Some(authority),
+            )?;
+        }
+
+        emit!(UpdateDelegateChanged { asset: ctx.accounts.asset.key(), owner: owner.key(), delegate: Some(delegate) });
+
+        msg!("✍️ Updates of {} delegated to {}", ctx.accounts.asset.key(), delegate);
+
+        Ok(())
+    }
+
+    /// ✍️ Revoke the Update Delegate
+    /// Features: The owner removes the asset's UpdateDelegate plugin; a new
+    /// owner uses this to drop a delegate the previous owner approved
+    pub fn revoke_update_delegate(ctx: Context<SetUpdateDelegate>) -> Result<()> {
+        let owner = ctx.accounts.owner.to_account_info();
+        let system_program = ctx.accounts.system_program.to_account_info();
+        load_owned_asset(&ctx.accounts.asset, owner.key)?;
+
+        let collection_key = ctx.accounts.collection.key();
+        let authority_bump = [ctx.bumps.collection_authority];
+        let authority_seeds: &[&[u8]] = &[b"collection_authority", collection_key.as_ref(), &authority_bump];
+        require!(
+            program_signer(&ctx.accounts.collection, &ctx.accounts.collection_authority, authority_seeds)?.is_some(),
+            NftError::ProgramAuthorityRequired
+        );
+
+        MplCore::new(&ctx.accounts.mpl_core_program, &system_program).remove_plugin(
+            AssetCall::new(&ctx.accounts.asset, &ctx.accounts.collection, &owner, &ctx.accounts.collection_authority)
+                .signed(&[authority_seeds]),
+            PluginType::UpdateDelegate,
+        )?;
+
+        emit!(UpdateDelegateChanged { asset: ctx.accounts.asset.key(), owner: owner.key(), delegate: None });
+
+        msg!("✍️ Update delegation of {} revoked", ctx.accounts.asset.key());
+
+        Ok(())
+    }
+
+    /// 🚚 Program-Controlled Transfer (admin)
+    /// Features: Moves an asset whose owner ran `set_transfer_delegate` to
+    /// `new_owner`, signed by the `["transfer_authority"]` PDA; the base for
+    /// escrowed fusion, marketplace and rental flows. mpl-core resets the
+    /// delegation on transfer, so each move needs a fresh opt-in
+    pub fn program_transfer(ctx: Context<ProgramTransfer>) -> Result<()> {
+        let from = load_core_asset(&ctx.accounts.asset)?.owner;
+        let new_owner = ctx.accounts.new_owner.key();
+        transfer_as_program(
+            &MplCore::new(&ctx.accounts.mpl_core_program, &ctx.accounts.system_program.to_account_info()),
+            &ctx.accounts.asset,
+            &ctx.accounts.collection,
+            &ctx.accounts.authority.to_account_info(),
+            &ctx.accounts.transfer_authority,
+            &ctx.accounts.new_owner,
+            ctx.bumps.transfer_authority,
+        )?;
+
+        emit!(AssetProgramTransferred { asset: ctx.accounts.asset.key(), from, to: new_owner });
+
+        msg!("🚚 {} moved from {} to {}", ctx.accounts.asset.key(), from, new_owner);
+
+        Ok(())
+    }
+
+    /// 🔥 Burn an NFT
+    /// Features: Burns the asset, signed by its owner or the holder of its
+    /// BurnDelegate (mpl-core checks which), closes its NftState with the
+    /// rent going to the owner, and counts the burn on the collection
+    pub fn burn_nft(ctx: Context<BurnNFT>) -> Result<()> {
+        let asset_key = ctx.accounts.asset.key();
+        let owner = load_core_asset(&ctx.accounts.asset)?.owner;
+        require_keys_eq!(ctx.accounts.owner.key(), owner, NftError::NotAssetOwner);
+
+        let state_info = &ctx.accounts.nft_state;
+        let state = load_if_initialized::<NftState>(state_info)?.unwrap_or_default();
+        require!(state.lock_state == LockState::Unlocked, NftError::AssetLocked);
+
+        // 📸 The history outlives the asset and its state, as the burn's record
+        ctx.accounts.asset_history.record(
+            CheckpointOperation::Burn,
+            &state,
+            hash_attributes(&fetch_attributes(&ctx.accounts.asset)?),
+            program_clock(ctx.remaining_accounts)?.unix_timestamp,
+        );
+
+        let authority = ctx.accounts.authority.to_account_info();
+        MplCore::new(&ctx.accounts.mpl_core_program, &ctx.accounts.system_program.to_account_info())
+            .burn(AssetCall::new(&ctx.accounts.asset, &ctx.accounts.collection, &authority, &authority))?;
+
+        if state_info.owner == &crate::ID {
+            close_program_account(state_info, &ctx.accounts.owner)?;
+        }
+
+        let config_info = &ctx.accounts.collection_config;
+        let mut total_burned = 0;
+        if let Some(mut config) = load_if_initialized::<CollectionConfig>(config_info)? {
+            config.total_burned = config.total_burned.saturating_add(1);
+            total_burned = config.total_burned;
+            config.try_serialize(&mut &mut config_info.try_borrow_mut_data()?[..])?;
+        }
+
+        emit!(AssetBurned {
+            asset: asset_key,
+            collection: ctx.accounts.collection.key(),
+            owner,
+            burned_by: authority.key(),
+            total_burned,
+        });
+
+        msg!("🔥 Burned {} | Collection burns: {}", asset_key, total_burned);
+
+        Ok(())
+    }
+
+    /// 🤝 OTC Deal Rooms - Offer a bundle of assets for an asking price
+    /// Features: Escrows the offered assets in the `["deal", seller, deal_id]`
+    /// PDA against an ask of lamports, SPL tokens and/or specific assets.
+    /// `counterparty` reserves the deal for one wallet; `Pubkey::default()`
+    /// leaves it open. Remaining accounts: an (asset, collection, nft_state)
+    /// triple per offered asset, nft_state may be uninitialized
+    pub fn create_deal<'info>(
+        ctx: Context<'_, '_, 'info, 'info, CreateDeal<'info>>,
+        deal_id: u64,
+        counterparty: Pubkey,
+        ask: DealAsk,
+    ) -> Result<()> {
+        let triples = without_mock_clock(ctx.remaining_accounts);
+        require!(
+            !triples.is_empty() && triples.len().is_multiple_of(3) && triples.len() / 3 <= MAX_DEAL_ASSETS,
+            NftError::InvalidDealBundle
+        );
+        require!(ask.assets.len() <= MAX_DEAL_ASSETS, NftError::InvalidDealBundle);
+        require!(
+            ask.token_amount == 0 || ask.token_mint != Pubkey::default(),
+            NftError::InvalidPaymentMint
+        );
+
+        let seller = ctx.accounts.seller.to_account_info();
+        let deal_info = ctx.accounts.deal.to_account_info();
+        let system_program = ctx.accounts.system_program.to_account_info();
+        let mut offered = Vec::with_capacity(triples.len() / 3);
+        for triple in triples.chunks_exact(3) {
+            let (asset, collection) = (&triple[0], &triple[1]);
+            ensure_deal_asset_transferable(&ctx.accounts.program_config, asset, &triple[2], seller.key)?;
+            MplCore::new(&ctx.accounts.mpl_core_program, &system_program)
+                .transfer(AssetCall::new(asset, collection, &seller, &seller), &deal_info)?;
+            offered.push(asset.key());
+        }
+
+        let deal = &mut ctx.accounts.deal;
+        deal.seller = seller.key();
+        deal.deal_id = deal_id;
+        deal.counterparty = counterparty;
+        deal.offered_assets = offered;
+        deal.ask = ask;
+        deal.created_at = program_clock(ctx.remaining_accounts)?.unix_timestamp;
+        deal.bump = ctx.bumps.deal;
+
+        emit!(DealCreated {
+            deal: deal.key(),
+            seller: deal.seller,
+            counterparty,
+            offered_assets: deal.offered_assets.len() as u8,
+        });
+
+        msg!("🤝 Deal {} opened with {} assets | Ask: {} lamports, {} tokens, {} assets",
+             deal_id, deal.offered_assets.len(), deal.ask.lamports, deal.ask.token_amount, deal.ask.assets.len());
+
+        Ok(())
+    }
+
+    /// ✅ OTC Deal Rooms - Accept a deal
+    /// Features: Atomically pays the ask to the seller (lamports, tokens and
+    /// the asked assets) and releases the escrowed bundle to the buyer.
+    /// Remaining accounts: an (asset, collection) pair per offered asset in
+    /// deal order, then an (asset, collection, nft_state) triple per asked asset
+    pub fn accept_deal<'info>(ctx: Context<'_, '_, 'info, 'info, AcceptDeal<'info>>) -> Result<()> {
+        let deal = &ctx.accounts.deal;
+        let buyer = ctx.accounts.buyer.to_account_info();
+        require!(
+            deal.counterparty == Pubkey::default() || deal.counterparty == buyer.key(),
+            NftError::NotDealCounterparty
+        );
+        let accounts = without_mock_clock(ctx.remaining_accounts);
+        let offered_len = deal.offered_assets.len() * 2;
+        require!(
+            accounts.len() == offered_len + deal.ask.assets.len() * 3,
+            NftError::InvalidRemainingAccounts
+        );
+        let (offered, asked) = accounts.split_at(offered_len);
+
+        // 💰 The ask, straight from the buyer to the seller
+        let seller = ctx.accounts.seller.to_account_info();
+        if deal.ask.lamports > 0 {
+            system_program::transfer(
+                CpiContext::new(
+                    ctx.accounts.system_program.to_account_info(),
+                    system_program::Transfer { from: buyer.clone(), to: seller.clone() },
+                ),
+                deal.ask.lamports,
+            )?;
+        }
+        if deal.ask.token_amount > 0 {
+            let (Some(buyer_tokens), Some(seller_tokens)) =
+                (&ctx.accounts.buyer_token_account, &ctx.accounts.seller_token_account)
+            else {
+                return err!(NftError::InvalidPaymentMint);
+            };
+            require!(
+                buyer_tokens.mint == deal.ask.token_mint
+                    && seller_tokens.mint == deal.ask.token_mint
+                    && seller_tokens.owner == deal.seller,
+                NftError::InvalidPaymentMint
+            );
+            token::transfer(
+                CpiContext::new(
+                    ctx.accounts.token_program.to_account_info(),
+                    token::Transfer {
+                        from: buyer_tokens.to_account_info(),
+                        to: seller_tokens.to_account_info(),
+                        authority: buyer.clone(),
+                    },
+                ),
+                deal.ask.token_amount,
+            )?;
+        }
+
+        let system_program = ctx.accounts.system_program.to_account_info();
+        for (triple, wanted) in asked.chunks_exact(3).zip(&deal.ask.assets) {
+            let (asset, collection) = (&triple[0], &triple[1]);
+            require_keys_eq!(asset.key(), *wanted, NftError::DealAssetMismatch);
+            ensure_deal_asset_transferable(&ctx.accounts.program_config, asset, &triple[2], buyer.key)?;
+            MplCore::new(&ctx.accounts.mpl_core_program, &system_program)
+                .transfer(AssetCall::new(asset, collection, &buyer, &buyer), &seller)?;
+        }
+
+        // 📦 Then the bundle, out of escrow
+        release_deal_assets(
+            &MplCore::new(&ctx.accounts.mpl_core_program, &system_program),
+            deal,
+            &deal.to_account_info(),
+            offered,
+            &buyer,
+            &buyer,
+        )?;
+
+        emit!(DealAccepted { deal: deal.key(), seller: deal.seller, buyer: buyer.key() });
+
+        msg!("✅ Deal {} accepted by {}", deal.deal_id, buyer.key());
+
+        Ok(())
+    }
+
+    /// ↩️ OTC Deal Rooms - Cancel a deal before it is accepted
+    /// Features: The seller, or the counterparty a deal was reserved for, can
+    /// call it off; the bundle goes back to the seller either way. Remaining
+    /// accounts: an (asset, collection) pair per offered asset in deal order
+    pub fn cancel_deal<'info>(ctx: Context<'_, '_, 'info, 'info, CancelDeal<'info>>) -> Result<()> {
+        let deal = &ctx.accounts.deal;
+        let authority = ctx.accounts.authority.to_account_info();
+        require!(
+            authority.key() == deal.seller
+                || (deal.counterparty != Pubkey::default() && authority.key() == deal.counterparty),
+            NftError::NotDealParty
+        );
+        let offered = without_mock_clock(ctx.remaining_accounts);
+        require!(offered.len() == deal.offered_assets.len() * 2, NftError::InvalidRemainingAccounts);
+
+        release_deal_assets(
+            &MplCore::new(&ctx.accounts.mpl_core_program, &ctx.accounts.system_program.to_account_info()),
+            deal,
+            &deal.to_account_info(),
+            offered,
+            &authority,
+            &ctx.accounts.seller,
+        )?;
+
+        emit!(DealCancelled { deal: deal.key(), seller: deal.seller, cancelled_by: authority.key() });
+
+        msg!("↩️ Deal {} cancelled by {}", deal.deal_id, authority.key());
+
+        Ok(())
+    }
+
+    /// ✍️ Move Existing Assets' Attributes to the Program
+    /// Features: Hands the Attributes plugin of assets minted before attribute
+    /// writes moved to the `["attributes_authority"]` PDA over to it, so only
+    /// the program can rewrite their stats. Signed by the collection's update
+    /// authority: its program authority PDA if the collection was handed over,
+    /// otherwise `authority` itself. Remaining accounts: assets of `collection`;
+    /// already migrated ones are skipped
+    pub fn migrate_attributes_authority<'info>(
+        ctx: Context<'_, '_, 'info, 'info, MigrateAttributesAuthority<'info>>,
+    ) -> Result<()> {
+        let assets = without_mock_clock(ctx.remaining_accounts);
+        require!(!assets.is_empty(), NftError::InvalidRemainingAccounts);
+
+        let collection_key = ctx.accounts.collection.key();
+        let authority_bump = [ctx.bumps.collection_authority];
+        let authority_seeds: &[&[u8]] = &[b"collection_authority", collection_key.as_ref(), &authority_bump];
+        let payer = ctx.accounts.authority.to_account_info();
+        let program_signer =
+            program_signer(&ctx.accounts.collection, &ctx.accounts.collection_authority, authority_seeds)?;
+        let authority = program_signer.map_or(&payer, |signer| signer.authority);
+        let signer_seeds: Vec<&[&[u8]]> = program_signer.iter().map(|signer| signer.seeds).collect();
+
+        let system_program = ctx.accounts.system_program.to_account_info();
+        let attributes_authority = ctx.accounts.attributes_authority.key();
+        let mut migrated = 0u32;
+        for asset in assets {
+            verify_collection_membership(asset, &collection_key)?;
+            if attributes_held_by(asset, &attributes_authority) {
+                continue;
+            }
+            MplCore::new(&ctx.accounts.mpl_core_program, &system_program).approve_plugin_authority(
+                AssetCall::new(asset, &ctx.accounts.collection, &payer, authority).signed(&signer_seeds),
+                PluginType::Attributes,
+                PluginAuthority::Address { address: attributes_authority },
+            )?;
+            migrated += 1;
+        }
+        require!(migrated > 0, NftError::NothingToProcess);
+
+        msg!("✍️ Attributes of {} assets in {} moved to the program", migrated, collection_key);
+
+        Ok(())
+    }
+
+    /// 🧬 Route Fusion Outputs by Generation (admin)
+    /// Features: Each route names the collection fusion outputs of that
+    /// generation must be in, e.g. Gen1 inputs → the Gen2 collection;
+    /// generations without a route stay unrestricted. Replaces the whole map
+    pub fn set_generation_map(ctx: Context<SetGenerationMap>, routes: Vec<GenerationRoute>) -> Result<()> {
+        require!(
+            routes.len() <= MAX_GENERATION_ROUTES
+                && routes.iter().all(|route| route.generation >= 2)
+                && routes
+                    .iter()
+                    .enumerate()
+                    .all(|(i, route)| routes[..i].iter().all(|other| other.generation != route.generation)),
+            NftError::InvalidGenerationMap
+        );
+
+        let generation_map = &mut ctx.accounts.generation_map;
+        generation_map.routes = routes;
+        generation_map.bump = ctx.bumps.generation_map;
+
+        msg!("🧬 {} fusion generation routes set", generation_map.routes.len());
+
+        Ok(())
+    }
+
+    /// 🏛️ Add a Plugin to the Collection Itself (collection authority)
+    /// Features: Royalties, MasterEdition or Attributes on the collection
+    /// account, signed by the collection's program authority PDA if it was
+    /// handed over, otherwise by the authority. Collection Royalties also
+    /// become the CollectionConfig's royalty split
+    pub fn add_collection_plugin(ctx: Context<ManageCollectionPlugin>, plugin: CollectionPlugin) -> Result<()> {
+        let authority = ctx.accounts.authority.to_account_info();
+        let plugin_name = plugin.name();
+        let core_plugin = plugin.to_plugin(authority.key())?;
+
+        let collection_key = ctx.accounts.collection.key();
+        let authority_bump = [ctx.bumps.collection_authority];
+        let authority_seeds: &[&[u8]] = &[b"collection_authority", collection_key.as_ref(), &authority_bump];
+        let program_signer =
+            program_signer(&ctx.accounts.collection, &ctx.accounts.collection_authority, authority_seeds)?;
+        let signer = program_signer.map_or(&authority, |signer| signer.authority);
+        let signer_seeds: Vec<&[&[u8]]> = program_signer.iter().map(|signer| signer.seeds).collect();
+
+        AddCollectionPluginV1CpiBuilder::new(&ctx.accounts.mpl_core_program)
+            .collection(&ctx.accounts.collection)
+            .payer(&authority)
+            .authority(Some(signer))
+            .system_program(&ctx.accounts.system_program.to_account_info())
+            .plugin(core_plugin)
+            .invoke_signed(&signer_seeds)?;
+        ctx.accounts.collection_config.sync_royalties(&plugin);
+
+        msg!("🏛️ {} added to collection {}", plugin_name, collection_key);
+
+        Ok(())
+    }
+
+    /// 🏛️ Update a Plugin on the Collection Itself (collection authority)
+    /// Features: Replaces the data of a Royalties, MasterEdition or Attributes
+    /// plugin the collection already has; signed like `add_collection_plugin`
+    pub fn update_collection_plugin(ctx: Context<ManageCollectionPlugin>, plugin: CollectionPlugin) -> Result<()> {
+        let authority = ctx.accounts.authority.to_account_info();
+        let plugin_name = plugin.name();
+        let core_plugin = plugin.to_plugin(authority.key())?;
+
+        let collection_key = ctx.accounts.collection.key();
+        let authority_bump = [ctx.bumps.collection_authority];
+        let authority_seeds: &[&[u8]] = &[b"collection_authority", collection_key.as_ref(), &authority_bump];
+        let program_signer =
+            program_signer(&ctx.accounts.collection, &ctx.accounts.collection_authority, authority_seeds)?;
+        let signer = program_signer.map_or(&authority, |signer| signer.authority);
+        let signer_seeds: Vec<&[&[u8]]> = program_signer.iter().map(|signer| signer.seeds).collect();
+
+        UpdateCollectionPluginV1CpiBuilder::new(&ctx.accounts.mpl_core_program)
+            .collection(&ctx.accounts.collection)
+            .payer(&authority)
+            .authority(Some(signer))
+            .system_program(&ctx.accounts.system_program.to_account_info())
+            .plugin(core_plugin)
+            .invoke_signed(&signer_seeds)?;
+        ctx.accounts.collection_config.sync_royalties(&plugin);
+
+        msg!("🏛️ {} updated on collection {}", plugin_name, collection_key);
+
+        Ok(())
+    }
+
+    /// 🗳️ Holder Polls - Open a poll (collection authority)
+    /// Features: Holders choose between 2-MAX_POLL_OPTIONS values for one
+    /// CollectionConfig parameter until `ends_at`; the result only counts
+    /// once at least `quorum` assets have voted
+    pub fn create_poll(
+        ctx: Context<CreatePoll>,
+        poll_id: u64,
+        parameter: PollParameter,
+        options: Vec<u64>,
+        quorum: u64,
+        ends_at: i64,
+    ) -> Result<()> {
+        let now = program_clock(ctx.remaining_accounts)?.unix_timestamp;
+        require!(
+            (2..=MAX_POLL_OPTIONS).contains(&options.len())
+                && options.iter().enumerate().all(|(i, value)| {
+                    parameter.accepts(*value) && !options[..i].contains(value)
+                })
+                && quorum > 0
+                && ends_at > now,
+            NftError::InvalidPoll
+        );
+
+        let poll = &mut ctx.accounts.poll;
+        poll.collection = ctx.accounts.collection_config.collection;
+        poll.poll_id = poll_id;
+        poll.parameter = parameter;
+        poll.tallies = vec![0; options.len()];
+        poll.options = options;
+        poll.quorum = quorum;
+        poll.ends_at = ends_at;
+        poll.applied = false;
+        poll.bump = ctx.bumps.poll;
+
+        emit!(PollCreated {
+            poll: poll.key(),
+            collection: poll.collection,
+            parameter,
+            options: poll.options.len() as u8,
+            ends_at,
+        });
+
+        msg!("🗳️ Poll {} on {:?} for {} | {} options, quorum {}, ends {}",
+             poll_id, parameter, poll.collection, poll.options.len(), quorum, ends_at);
+
+        Ok(())
+    }
+
+    /// 🗳️ Holder Polls - Vote
+    /// Features: One vote per asset of the poll's collection the voter owns.
+    /// Each asset's vote is recorded in its own PollVote PDA, so an asset
+    /// can't vote twice, even after a transfer.
+    /// Remaining accounts: an (asset, poll_vote) pair per voting asset
+    pub fn vote<'info>(ctx: Context<'_, '_, 'info, 'info, Vote<'info>>, option: u8) -> Result<()> {
+        let now = program_clock(ctx.remaining_accounts)?.unix_timestamp;
+        let pairs = without_mock_clock(ctx.remaining_accounts);
+        let poll = &ctx.accounts.poll;
+        require!(now < poll.ends_at, NftError::PollClosed);
+        require!((option as usize) < poll.options.len(), NftError::InvalidPollOption);
+        require!(!pairs.is_empty() && pairs.len().is_multiple_of(2), NftError::InvalidPollVote);
+
+        let voter = ctx.accounts.voter.to_account_info();
+        let system_program = ctx.accounts.system_program.to_account_info();
+        for pair in pairs.chunks_exact(2) {
+            let asset = &pair[0];
+            load_owned_asset(asset, voter.key)?;
+            verify_collection_membership(asset, &poll.collection)?;
+            record_poll_vote(&pair[1], &poll.key(), asset.key, option, &voter, &system_program)?;
+        }
+
+        let weight = (pairs.len() / 2) as u64;
+        let poll = &mut ctx.accounts.poll;
+        let tally = &mut poll.tallies[option as usize];
+        *tally = tally.checked_add(weight).ok_or(NftError::MathOverflow)?;
+
+        emit!(PollVoted {
+            poll: poll.key(),
+            voter: voter.key(),
+            option,
+            weight,
+        });
+
+        msg!("🗳️ {} cast {} votes for option {} ({})", voter.key(), weight, option, poll.options[option as usize]);
+
+        Ok(())
+    }
+
+    /// 🏁 Holder Polls - Apply the result (anyone)
+    /// Features: Once voting has closed with quorum, writes the most-voted
+    /// value into the collection's config; ties go to the earlier option.
+    /// A poll's result can only be applied once
+    pub fn apply_poll_result(ctx: Context<ApplyPollResult>) -> Result<()> {
+        let now = program_clock(ctx.remaining_accounts)?.unix_timestamp;
+        let poll = &mut ctx.accounts.poll;
+        require!(now >= poll.ends_at, NftError::PollStillOpen);
+        require!(!poll.applied, NftError::PollAlreadyApplied);
+        let total_votes = poll.total_votes();
+        if total_votes < poll.quorum {
+            return Err(fail_with_context(NftError::PollQuorumNotReached, poll.quorum, total_votes));
+        }
+
+        let value = poll.winning_value();
+        poll.parameter.apply(&mut ctx.accounts.collection_config, value);
+        poll.applied = true;
+
+        emit!(PollApplied {
+            poll: poll.key(),
+            collection: poll.collection,
+            parameter: poll.parameter,
+            value,
+            total_votes,
+        });
+
+        msg!("🏁 Poll {} set {:?} = {} on {} | {} votes", poll.poll_id, poll.parameter, value, poll.collection, total_votes);
+
+        Ok(())
+    }
+}
+
+/// 🎯 Enhanced Context for Advanced NFT Minting
+#[derive(Accounts)]
+#[instruction(name: String, uri: String, level: u64, rarity: String, fusion_potential: u64, mint_index: u64)]
+pub struct MintNFT<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    
+    /// CHECK: Only receives the asset; omitted = the payer
+    pub recipient: Option<AccountInfo<'info>>,
+    
+    /// CHECK: Created by mpl-core; the program signs for this PDA
+    #[account(mut, seeds = [b"asset", collection.key().as_ref(), &mint_index.to_le_bytes()], bump)]
+    pub asset: AccountInfo<'info>,
+    
+    /// CHECK: Handled by mpl-core
+    #[account(mut)]
+    pub collection: AccountInfo<'info>,
+    
+    /// CHECK: This collection's RegionLock PDA; may be uninitialized (no lock)
+    #[account(seeds = [b"region_lock", collection.key().as_ref()], bump)]
+    pub region_lock: AccountInfo<'info>,
+    
+    /// CHECK: This collection's program authority PDA; signs the mint if it is
+    /// the collection's update authority
+    #[account(seeds = [b"collection_authority", collection.key().as_ref()], bump)]
+    pub collection_authority: AccountInfo<'info>,
+    
+    /// CHECK: This collection's CollectionConfig PDA; may be uninitialized (created externally)
+    #[account(seeds = [b"collection_config", collection.key().as_ref()], bump)]
+    pub collection_config: AccountInfo<'info>,
+    
+    /// CHECK: The payer's MintCounterShard PDA; must be initialized if the config is
+    #[account(
+        mut,
+        seeds = [b"mint_shard", collection.key().as_ref(), &[mint_shard_index(&payer.key())]],
+        bump
+    )]
+    pub mint_shard: AccountInfo<'info>,
+
+    /// This collection's EditionCounter; required once editions are numbered
+    #[account(mut, seeds = [b"edition", collection.key().as_ref()], bump = edition_counter.bump)]
+    pub edition_counter: Option<Account<'info, EditionCounter>>,
+
+    /// CHECK: This collection's AchievementTiers PDA; may be uninitialized (default ladder)
+    #[account(seeds = [b"achievement_tiers", collection.key().as_ref()], bump)]
+    pub achievement_tiers: AccountInfo<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + MintReceipt::INIT_SPACE,
+        seeds = [b"receipt", collection.key().as_ref(), payer.key().as_ref()],
+        bump
+    )]
+    pub mint_receipt: Account<'info, MintReceipt>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + PlayerProfile::INIT_SPACE,
+        seeds = [b"player_profile", payer.key().as_ref()],
+        bump
+    )]
+    pub player_profile: Account<'info, PlayerProfile>,
+    
+    #[account(mut, seeds = [b"treasury"], bump)]
+    pub treasury: SystemAccount<'info>,
+    
+    #[account(mut, seeds = [b"economy_ledger"], bump = economy_ledger.bump)]
+    pub economy_ledger: Account<'info, EconomyLedger>,
+    
+    /// CHECK: Instructions sysvar, address-constrained
+    #[account(address = sysvar::instructions::ID)]
+    pub instructions_sysvar: AccountInfo<'info>,
+    
+    #[account(mut, seeds = [b"signal_board"], bump = signal_board.bump)]
+    pub signal_board: Account<'info, SignalBoard>,
+    
+    #[account(seeds = [b"program_config"], bump = program_config.bump)]
+    pub program_config: Account<'info, ProgramConfig>,
+    
+    /// CHECK: Metaplex Core program
+    pub mpl_core_program: AccountInfo<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+/// 🪙 Context for Minting Paid in an SPL Token
+#[derive(Accounts)]
+pub struct MintNFTWithToken<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: Handled by mpl-core
+    #[account(mut)]
+    pub asset: AccountInfo<'info>,
+
+    /// CHECK: Handled by mpl-core
+    #[account(mut)]
+    pub collection: AccountInfo<'info>,
+
+    /// CHECK: This collection's program authority PDA; signs the mint if it is
+    /// the collection's update authority
+    #[account(seeds = [b"collection_authority", collection.key().as_ref()], bump)]
+    pub collection_authority: AccountInfo<'info>,
+
+    /// CHECK: This collection's RegionLock PDA; may be uninitialized (no lock)
+    #[account(seeds = [b"region_lock", collection.key().as_ref()], bump)]
+    pub region_lock: AccountInfo<'info>,
+
+    #[account(
+        seeds = [b"collection_config", collection.key().as_ref()],
+        bump = collection_config.bump,
+        constraint = collection_config.payment_mint != Pubkey::default() @ NftError::TokenPaymentDisabled
+    )]
+    pub collection_config: Account<'info, CollectionConfig>,
+
+    /// CHECK: The payer's MintCounterShard PDA; must be initialized
+    #[account(
+        mut,
+        seeds = [b"mint_shard", collection.key().as_ref(), &[mint_shard_index(&payer.key())]],
+        bump
+    )]
+    pub mint_shard: AccountInfo<'info>,
+
+    /// This collection's EditionCounter; required once editions are numbered
+    #[account(mut, seeds = [b"edition", collection.key().as_ref()], bump = edition_counter.bump)]
+    pub edition_counter: Option<Account<'info, EditionCounter>>,
+
+    /// CHECK: This collection's AchievementTiers PDA; may be uninitialized (default ladder)
+    #[account(seeds = [b"achievement_tiers", collection.key().as_ref()], bump)]
+    pub achievement_tiers: AccountInfo<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + MintReceipt::INIT_SPACE,
+        seeds = [b"receipt", collection.key().as_ref(), payer.key().as_ref()],
+        bump
+    )]
+    pub mint_receipt: Account<'info, MintReceipt>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + PlayerProfile::INIT_SPACE,
+        seeds = [b"player_profile", payer.key().as_ref()],
+        bump
+    )]
+    pub player_profile: Account<'info, PlayerProfile>,
+
+    #[account(seeds = [b"treasury"], bump)]
+    pub treasury: SystemAccount<'info>,
+
+    #[account(mut, seeds = [b"economy_ledger"], bump = economy_ledger.bump)]
+    pub economy_ledger: Account<'info, EconomyLedger>,
+
+    #[account(
+        mut,
+        token::authority = payer,
+        constraint = payer_token_account.mint == collection_config.payment_mint @ NftError::InvalidPaymentMint
+    )]
+    pub payer_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        token::authority = treasury,
+        constraint = treasury_token_account.mint == collection_config.payment_mint @ NftError::InvalidPaymentMint
+    )]
+    pub treasury_token_account: Account<'info, TokenAccount>,
+
+    /// CHECK: Instructions sysvar, address-constrained
+    #[account(address = sysvar::instructions::ID)]
+    pub instructions_sysvar: AccountInfo<'info>,
+
+    #[account(mut, seeds = [b"signal_board"], bump = signal_board.bump)]
+    pub signal_board: Account<'info, SignalBoard>,
+
+    #[account(seeds = [b"program_config"], bump = program_config.bump)]
+    pub program_config: Account<'info, ProgramConfig>,
+
+    /// CHECK: Metaplex Core program
+    pub mpl_core_program: AccountInfo<'info>,
+
+    pub token_program: Program<'info, Token>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// 🔥 Context for Burn-to-Mint; pass the token pair or the asset triple
+#[derive(Accounts)]
+pub struct MintWithBurn<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: Handled by mpl-core
+    #[account(mut)]
+    pub asset: AccountInfo<'info>,
+
+    /// CHECK: Handled by mpl-core
+    #[account(mut)]
+    pub collection: AccountInfo<'info>,
+
+    /// CHECK: This collection's program authority PDA; signs the mint if it is
+    /// the collection's update authority
+    #[account(seeds = [b"collection_authority", collection.key().as_ref()], bump)]
+    pub collection_authority: AccountInfo<'info>,
+
+    /// CHECK: This collection's RegionLock PDA; may be uninitialized (no lock)
+    #[account(seeds = [b"region_lock", collection.key().as_ref()], bump)]
+    pub region_lock: AccountInfo<'info>,
+
+    #[account(
+        seeds = [b"collection_config", collection.key().as_ref()],
+        bump = collection_config.bump
+    )]
+    pub collection_config: Account<'info, CollectionConfig>,
+
+    /// CHECK: The payer's MintCounterShard PDA; must be initialized
+    #[account(
+        mut,
+        seeds = [b"mint_shard", collection.key().as_ref(), &[mint_shard_index(&payer.key())]],
+        bump
+    )]
+    pub mint_shard: AccountInfo<'info>,
+
+    /// This collection's EditionCounter; required once editions are numbered
+    #[account(mut, seeds = [b"edition", collection.key().as_ref()], bump = edition_counter.bump)]
+    pub edition_counter: Option<Account<'info, EditionCounter>>,
+
+    /// CHECK: This collection's AchievementTiers PDA; may be uninitialized (default ladder)
+    #[account(seeds = [b"achievement_tiers", collection.key().as_ref()], bump)]
+    pub achievement_tiers: AccountInfo<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + MintReceipt::INIT_SPACE,
+        seeds = [b"receipt", collection.key().as_ref(), payer.key().as_ref()],
+        bump
+    )]
+    pub mint_receipt: Account<'info, MintReceipt>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + PlayerProfile::INIT_SPACE,
+        seeds = [b"player_profile", payer.key().as_ref()],
+        bump
+    )]
+    pub player_profile: Account<'info, PlayerProfile>,
+
+    /// The collection's whitelist token mint
+    #[account(mut)]
+    pub burn_token_mint: Option<Account<'info, Mint>>,
+
+    /// The payer's whitelist token account
+    #[account(mut)]
+    pub burn_token_account: Option<Account<'info, TokenAccount>>,
+
+    /// CHECK: Asset burned as the cost; ownership and collection checked in the handler
+    #[account(mut)]
+    pub burn_asset: Option<AccountInfo<'info>>,
+
+    /// CHECK: The burn asset's collection, checked against the config
+    #[account(mut)]
+    pub burn_asset_collection: Option<AccountInfo<'info>>,
+
+    /// CHECK: The burn asset's NftState PDA; may be uninitialized, closed to the payer otherwise
+    #[account(mut)]
+    pub burn_asset_state: Option<AccountInfo<'info>>,
+
+    /// The burn asset's checkpoint history; required when burning an asset
+    #[account(mut)]
+    pub burn_asset_history: Option<Account<'info, AssetHistory>>,
+
+    /// CHECK: Instructions sysvar, address-constrained
+    #[account(address = sysvar::instructions::ID)]
+    pub instructions_sysvar: AccountInfo<'info>,
+
+    #[account(mut, seeds = [b"signal_board"], bump = signal_board.bump)]
+    pub signal_board: Account<'info, SignalBoard>,
+
+    #[account(seeds = [b"program_config"], bump = program_config.bump)]
+    pub program_config: Account<'info, ProgramConfig>,
+
+    /// CHECK: Metaplex Core program
+    pub mpl_core_program: AccountInfo<'info>,
+
+    pub token_program: Program<'info, Token>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// 📦 Context for Batch Minting; the assets and their NftState PDAs follow as
+/// (asset, nft_state) remaining account pairs
+#[derive(Accounts)]
+pub struct MintNFTBatch<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: Handled by mpl-core
+    #[account(mut)]
+    pub collection: AccountInfo<'info>,
+
+    /// CHECK: This collection's RegionLock PDA; may be uninitialized (no lock)
+    #[account(seeds = [b"region_lock", collection.key().as_ref()], bump)]
+    pub region_lock: AccountInfo<'info>,
+
+    /// CHECK: This collection's CollectionConfig PDA; may be uninitialized (created externally)
+    #[account(seeds = [b"collection_config", collection.key().as_ref()], bump)]
+    pub collection_config: AccountInfo<'info>,
+
+    /// CHECK: The payer's MintCounterShard PDA; must be initialized if the config is
+    #[account(
+        mut,
+        seeds = [b"mint_shard", collection.key().as_ref(), &[mint_shard_index(&payer.key())]],
+        bump
+    )]
+    pub mint_shard: AccountInfo<'info>,
+
+    /// This collection's EditionCounter; required once editions are numbered
+    #[account(mut, seeds = [b"edition", collection.key().as_ref()], bump = edition_counter.bump)]
+    pub edition_counter: Option<Account<'info, EditionCounter>>,
+
+    /// CHECK: This collection's AchievementTiers PDA; may be uninitialized (default ladder)
+    #[account(seeds = [b"achievement_tiers", collection.key().as_ref()], bump)]
+    pub achievement_tiers: AccountInfo<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + MintReceipt::INIT_SPACE,
+        seeds = [b"receipt", collection.key().as_ref(), payer.key().as_ref()],
+        bump
+    )]
+    pub mint_receipt: Account<'info, MintReceipt>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + PlayerProfile::INIT_SPACE,
+        seeds = [b"player_profile", payer.key().as_ref()],
+        bump
+    )]
+    pub player_profile: Account<'info, PlayerProfile>,
+
+    #[account(mut, seeds = [b"treasury"], bump)]
+    pub treasury: SystemAccount<'info>,
+
+    #[account(mut, seeds = [b"economy_ledger"], bump = economy_ledger.bump)]
+    pub economy_ledger: Account<'info, EconomyLedger>,
+
+    #[account(seeds = [b"program_config"], bump = program_config.bump)]
+    pub program_config: Account<'info, ProgramConfig>,
+
+    /// CHECK: Instructions sysvar, address-constrained
+    #[account(address = sysvar::instructions::ID)]
+    pub instructions_sysvar: AccountInfo<'info>,
+
+    #[account(mut, seeds = [b"signal_board"], bump = signal_board.bump)]
+    pub signal_board: Account<'info, SignalBoard>,
+
+    /// CHECK: Metaplex Core program
+    #[account(address = mpl_core::ID)]
+    pub mpl_core_program: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// 🧾 Context for Quote-Based Minting
+#[derive(Accounts)]
+#[instruction(
+    name: String,
+    uri: String,
+    level: u64,
+    rarity: String,
+    fusion_potential: u64,
+    price_lamports: u64,
+    expires_at: i64,
+    nonce: u64
+)]
+pub struct MintNFTWithQuote<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: Handled by mpl-core
+    #[account(mut)]
+    pub asset: AccountInfo<'info>,
+
+    /// CHECK: Handled by mpl-core
+    #[account(mut)]
+    pub collection: AccountInfo<'info>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + QuoteReceipt::INIT_SPACE,
+        seeds = [b"quote_receipt", payer.key().as_ref(), nonce.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub quote_receipt: Account<'info, QuoteReceipt>,
+
+    /// CHECK: This collection's CollectionConfig PDA; may be uninitialized (created externally)
+    #[account(seeds = [b"collection_config", collection.key().as_ref()], bump)]
+    pub collection_config: AccountInfo<'info>,
+    
+    /// CHECK: The payer's MintCounterShard PDA; must be initialized if the config is
+    #[account(
+        mut,
+        seeds = [b"mint_shard", collection.key().as_ref(), &[mint_shard_index(&payer.key())]],
+        bump
+    )]
+    pub mint_shard: AccountInfo<'info>,
+
+    /// This collection's EditionCounter; required once editions are numbered
+    #[account(mut, seeds = [b"edition", collection.key().as_ref()], bump = edition_counter.bump)]
+    pub edition_counter: Option<Account<'info, EditionCounter>>,
+
+    /// CHECK: This collection's AchievementTiers PDA; may be uninitialized (default ladder)
+    #[account(seeds = [b"achievement_tiers", collection.key().as_ref()], bump)]
+    pub achievement_tiers: AccountInfo<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + MintReceipt::INIT_SPACE,
+        seeds = [b"receipt", collection.key().as_ref(), payer.key().as_ref()],
+        bump
+    )]
+    pub mint_receipt: Account<'info, MintReceipt>,
+
+    #[account(seeds = [b"program_config"], bump = program_config.bump)]
+    pub program_config: Account<'info, ProgramConfig>,
+
+    #[account(mut, seeds = [b"treasury"], bump = program_config.treasury_bump)]
+    pub treasury: SystemAccount<'info>,
+
+    /// CHECK: Instructions sysvar, address-constrained
+    #[account(address = sysvar::instructions::ID)]
+    pub instructions_sysvar: AccountInfo<'info>,
+
+    #[account(mut, seeds = [b"signal_board"], bump = signal_board.bump)]
+    pub signal_board: Account<'info, SignalBoard>,
+
+    /// CHECK: Metaplex Core program
+    pub mpl_core_program: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    #[account(mut, seeds = [b"economy_ledger"], bump = economy_ledger.bump)]
+    pub economy_ledger: Account<'info, EconomyLedger>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + PlayerProfile::INIT_SPACE,
+        seeds = [b"player_profile", payer.key().as_ref()],
+        bump
+    )]
+    pub player_profile: Account<'info, PlayerProfile>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + PlayerHistoryPage::INIT_SPACE,
+        seeds = [
+            b"player_history",
+            payer.key().as_ref(),
+            player_profile.history_page(program_config.max_history_pages).to_le_bytes().as_ref()
+        ],
+        bump
+    )]
+    pub player_history_page: Account<'info, PlayerHistoryPage>,
+}
+
+/// 🎟️ Context for Minting with a Signed Voucher; `payer` is the relayer
+#[derive(Accounts)]
+#[instruction(voucher: MintVoucher)]
+pub struct MintWithVoucher<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: Only receives the asset; must be the voucher's recipient
+    #[account(address = voucher.recipient @ NftError::VoucherRecipientMismatch)]
+    pub recipient: AccountInfo<'info>,
+
+    /// CHECK: Handled by mpl-core
+    #[account(mut)]
+    pub asset: AccountInfo<'info>,
+
+    /// CHECK: Handled by mpl-core; must be the voucher's collection
+    #[account(mut, address = voucher.collection @ NftError::VoucherCollectionMismatch)]
+    pub collection: AccountInfo<'info>,
+
+    /// CHECK: This collection's program authority PDA; signs the mint if it is
+    /// the collection's update authority
+    #[account(seeds = [b"collection_authority", collection.key().as_ref()], bump)]
+    pub collection_authority: AccountInfo<'info>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + QuoteReceipt::INIT_SPACE,
+        seeds = [b"voucher_receipt", voucher.recipient.as_ref(), voucher.nonce.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub voucher_receipt: Account<'info, QuoteReceipt>,
+
+    /// CHECK: This collection's CollectionConfig PDA; may be uninitialized (created externally)
+    #[account(seeds = [b"collection_config", collection.key().as_ref()], bump)]
+    pub collection_config: AccountInfo<'info>,
+
+    /// CHECK: The recipient's MintCounterShard PDA; must be initialized if the config is
+    #[account(
+        mut,
+        seeds = [b"mint_shard", collection.key().as_ref(), &[mint_shard_index(&voucher.recipient)]],
+        bump
+    )]
+    pub mint_shard: AccountInfo<'info>,
+
+    /// This collection's EditionCounter; required once editions are numbered
+    #[account(mut, seeds = [b"edition", collection.key().as_ref()], bump = edition_counter.bump)]
+    pub edition_counter: Option<Account<'info, EditionCounter>>,
+
+    /// CHECK: This collection's AchievementTiers PDA; may be uninitialized (default ladder)
+    #[account(seeds = [b"achievement_tiers", collection.key().as_ref()], bump)]
+    pub achievement_tiers: AccountInfo<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + MintReceipt::INIT_SPACE,
+        seeds = [b"receipt", collection.key().as_ref(), voucher.recipient.as_ref()],
+        bump
+    )]
+    pub mint_receipt: Account<'info, MintReceipt>,
+
+    #[account(mut, seeds = [b"treasury"], bump)]
+    pub treasury: SystemAccount<'info>,
+
+    #[account(mut, seeds = [b"economy_ledger"], bump = economy_ledger.bump)]
+    pub economy_ledger: Account<'info, EconomyLedger>,
+
+    #[account(seeds = [b"program_config"], bump = program_config.bump)]
+    pub program_config: Account<'info, ProgramConfig>,
+
+    /// CHECK: Instructions sysvar, address-constrained
+    #[account(address = sysvar::instructions::ID)]
+    pub instructions_sysvar: AccountInfo<'info>,
+
+    #[account(mut, seeds = [b"signal_board"], bump = signal_board.bump)]
+    pub signal_board: Account<'info, SignalBoard>,
+
+    /// CHECK: Metaplex Core program
+    pub mpl_core_program: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// 🎖️ Context for Holder Phase Setup
+#[derive(Accounts)]
+pub struct CreateHolderPhase<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"program_config"],
+        bump = program_config.bump,
+        has_one = authority @ NftError::Unauthorized
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+
+    /// CHECK: Collection the gated drop mints into, used as a PDA seed
+    pub collection: AccountInfo<'info>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + HolderPhase::INIT_SPACE,
+        seeds = [b"holder_phase", collection.key().as_ref()],
+        bump
+    )]
+    pub holder_phase: Account<'info, HolderPhase>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// 🎖️ Context for Holder-Tier Minting
+#[derive(Accounts)]
+pub struct MintNFTHolderPhase<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: Handled by mpl-core
+    #[account(mut)]
+    pub asset: AccountInfo<'info>,
+
+    /// CHECK: Handled by mpl-core
+    #[account(mut)]
+    pub collection: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"holder_phase", collection.key().as_ref()],
+        bump = holder_phase.bump
+    )]
+    pub holder_phase: Account<'info, HolderPhase>,
+
+    /// CHECK: This collection's CollectionConfig PDA; may be uninitialized (created externally)
+    #[account(seeds = [b"collection_config", collection.key().as_ref()], bump)]
+    pub collection_config: AccountInfo<'info>,
+    
+    /// CHECK: The payer's MintCounterShard PDA; must be initialized if the config is
+    #[account(
+        mut,
+        seeds = [b"mint_shard", collection.key().as_ref(), &[mint_shard_index(&payer.key())]],
+        bump
+    )]
+    pub mint_shard: AccountInfo<'info>,
+
+    /// This collection's EditionCounter; required once editions are numbered
+    #[account(mut, seeds = [b"edition", collection.key().as_ref()], bump = edition_counter.bump)]
+    pub edition_counter: Option<Account<'info, EditionCounter>>,
+
+    /// CHECK: This collection's AchievementTiers PDA; may be uninitialized (default ladder)
+    #[account(seeds = [b"achievement_tiers", collection.key().as_ref()], bump)]
+    pub achievement_tiers: AccountInfo<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + MintReceipt::INIT_SPACE,
+        seeds = [b"receipt", collection.key().as_ref(), payer.key().as_ref()],
+        bump
+    )]
+    pub mint_receipt: Account<'info, MintReceipt>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + PlayerProfile::INIT_SPACE,
+        seeds = [b"player_profile", payer.key().as_ref()],
+        bump
+    )]
+    pub player_profile: Account<'info, PlayerProfile>,
+
+    #[account(mut, seeds = [b"treasury"], bump)]
+    pub treasury: SystemAccount<'info>,
+
+    #[account(mut, seeds = [b"economy_ledger"], bump = economy_ledger.bump)]
+    pub economy_ledger: Account<'info, EconomyLedger>,
+
+    #[account(mut, seeds = [b"signal_board"], bump = signal_board.bump)]
+    pub signal_board: Account<'info, SignalBoard>,
+
+    #[account(seeds = [b"program_config"], bump = program_config.bump)]
+    pub program_config: Account<'info, ProgramConfig>,
+
+    /// CHECK: Instructions sysvar, address-constrained
+    #[account(address = sysvar::instructions::ID)]
+    pub instructions_sysvar: AccountInfo<'info>,
+
+    /// CHECK: Metaplex Core program
+    pub mpl_core_program: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// 🚀 Enhanced Context for Advanced Metadata Updates
+#[derive(Accounts)]
+pub struct UpdateNFTMetadata<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    
+    /// CHECK: Handled by mpl-core
+    #[account(mut)]
+    pub asset: AccountInfo<'info>,
+    
+    /// CHECK: Must be the asset's owner; credited with prestige
+    pub owner: AccountInfo<'info>,
+    
+    /// CHECK: Membership verified against the asset's update authority
+    #[account(mut)]
+    pub collection: AccountInfo<'info>,
+    
+    /// CHECK: This collection's program authority PDA; signs the update if it
+    /// is the collection's update authority
+    #[account(seeds = [b"collection_authority", collection.key().as_ref()], bump)]
+    pub collection_authority: AccountInfo<'info>,
+    
+    /// CHECK: This collection's CollectionConfig PDA; may be uninitialized (no cooldown floor)
+    #[account(seeds = [b"collection_config", collection.key().as_ref()], bump)]
+    pub collection_config: AccountInfo<'info>,
+    
+    #[account(
+        mut,
+        has_one = asset @ NftError::NftStateAssetMismatch,
+        seeds = [b"nft_state", asset.key().as_ref()],
+        bump = nft_state.bump,
+        realloc = 8 + NftState::INIT_SPACE,
+        realloc::payer = payer,
+        realloc::zero = false
+    )]
+    pub nft_state: Account<'info, NftState>,
+    
+    /// Required only when the payer is a delegate rather than the owner
+    #[account(
+        seeds = [b"delegation", asset.key().as_ref(), payer.key().as_ref()],
+        bump = delegation.bump
+    )]
+    pub delegation: Option<Account<'info, Delegation>>,
+    
+    #[account(seeds = [b"program_config"], bump = program_config.bump)]
+    pub program_config: Account<'info, ProgramConfig>,
+    
+    /// CHECK: GlobalEvent PDA; may be uninitialized (no live event)
+    #[account(seeds = [b"global_event"], bump)]
+    pub global_event: AccountInfo<'info>,
+    
+    /// Credited with prestige for levels past the cap
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + PlayerProfile::INIT_SPACE,
+        seeds = [b"player_profile", owner.key().as_ref()],
+        bump
+    )]
+    pub player_profile: Account<'info, PlayerProfile>,
+    
+    /// This asset's checkpoint history; required when levels overflow into prestige
+    #[account(mut, seeds = [b"asset_history", asset.key().as_ref()], bump = asset_history.bump)]
+    pub asset_history: Option<Account<'info, AssetHistory>>,
+
+    /// CHECK: PDA holding the Attributes plugin of assets minted or migrated
+    /// since attribute writes moved to the program
+    #[account(seeds = [b"attributes_authority"], bump)]
+    pub attributes_authority: AccountInfo<'info>,
+
+    /// CHECK: Metaplex Core program
+    pub mpl_core_program: AccountInfo<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+/// ✏️ Context for Renaming an NFT
+#[derive(Accounts)]
+pub struct RenameNFT<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: Handled by mpl-core
+    #[account(mut)]
+    pub asset: AccountInfo<'info>,
+
+    /// CHECK: Handled by mpl-core
+    #[account(mut)]
+    pub collection: AccountInfo<'info>,
+
+    /// CHECK: This collection's program authority PDA; signs the update if it
+    /// is the collection's update authority
+    #[account(seeds = [b"collection_authority", collection.key().as_ref()], bump)]
+    pub collection_authority: AccountInfo<'info>,
+
+    /// Required only when the collection is program-held and the payer is a
+    /// delegate rather than the owner
+    #[account(
+        seeds = [b"delegation", asset.key().as_ref(), payer.key().as_ref()],
+        bump = delegation.bump
+    )]
+    pub delegation: Option<Account<'info, Delegation>>,
+
+    #[account(
+        has_one = asset @ NftError::NftStateAssetMismatch,
+        seeds = [b"nft_state", asset.key().as_ref()],
+        bump = nft_state.bump
+    )]
+    pub nft_state: Account<'info, NftState>,
+
+    #[account(seeds = [b"program_config"], bump = program_config.bump)]
+    pub program_config: Account<'info, ProgramConfig>,
+
+    /// CHECK: Metaplex Core program
+    #[account(address = mpl_core::ID)]
+    pub mpl_core_program: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// 🎲 Context for Committing an Evolution Roll
+#[derive(Accounts)]
+pub struct CommitEvolution<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: Ownership verified against the mpl-core asset data
+    pub asset: AccountInfo<'info>,
+
+    /// CHECK: Membership verified against the asset's update authority
+    pub collection: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        has_one = asset @ NftError::NftStateAssetMismatch,
+        seeds = [b"nft_state", asset.key().as_ref()],
+        bump = nft_state.bump,
+        realloc = 8 + NftState::INIT_SPACE,
+        realloc::payer = payer,
+        realloc::zero = false
+    )]
+    pub nft_state: Account<'info, NftState>,
+
+    /// Required only when the payer is a delegate rather than the owner
+    #[account(
+        seeds = [b"delegation", asset.key().as_ref(), payer.key().as_ref()],
+        bump = delegation.bump
+    )]
+    pub delegation: Option<Account<'info, Delegation>>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + RollCommitment::INIT_SPACE,
+        seeds = [b"roll_commitment", asset.key().as_ref()],
+        bump
+    )]
+    pub roll_commitment: Account<'info, RollCommitment>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// 🌟 Enhanced Context for Advanced NFT Evolution
+#[derive(Accounts)]
+pub struct EvolveNFT<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    
+    /// CHECK: Handled by mpl-core
+    #[account(mut)]
+    pub asset: AccountInfo<'info>,
+    
+    /// CHECK: Must be the asset's owner; credited with prestige and the evolution
+    pub owner: AccountInfo<'info>,
+    
+    /// CHECK: Membership verified against the asset's update authority
+    #[account(mut)]
+    pub collection: AccountInfo<'info>,
+    
+    #[account(
+        mut,
+        has_one = asset @ NftError::NftStateAssetMismatch,
+        seeds = [b"nft_state", asset.key().as_ref()],
+        bump = nft_state.bump,
+        realloc = 8 + NftState::INIT_SPACE,
+        realloc::payer = payer,
+        realloc::zero = false
+    )]
+    pub nft_state: Account<'info, NftState>,
+    
+    /// The roll opened by `commit_evolution`; closed back to its opener
+    #[account(
+        mut,
+        close = payer,
+        constraint = roll_commitment.owner == payer.key() @ NftError::Unauthorized,
+        seeds = [b"roll_commitment", asset.key().as_ref()],
+        bump = roll_commitment.bump
+    )]
+    pub roll_commitment: Account<'info, RollCommitment>,
+    
+    #[account(seeds = [b"program_config"], bump = program_config.bump)]
+    pub program_config: Account<'info, ProgramConfig>,
+    
+    /// CHECK: GlobalEvent PDA; may be uninitialized (no live event)
+    #[account(seeds = [b"global_event"], bump)]
+    pub global_event: AccountInfo<'info>,
+    
+    #[account(mut, seeds = [b"signal_board"], bump = signal_board.bump)]
+    pub signal_board: Account<'info, SignalBoard>,
+    
+    /// CHECK: SlotHashes sysvar, address-constrained and parsed manually
+    #[account(address = sysvar::slot_hashes::ID)]
+    pub slot_hashes: AccountInfo<'info>,
+    
+    /// This asset's checkpoint history; required when levels overflow into prestige
+    #[account(mut, seeds = [b"asset_history", asset.key().as_ref()], bump = asset_history.bump)]
+    pub asset_history: Option<Account<'info, AssetHistory>>,
+
+    /// CHECK: PDA holding the Attributes plugin of assets minted or migrated
+    /// since attribute writes moved to the program
+    #[account(seeds = [b"attributes_authority"], bump)]
+    pub attributes_authority: AccountInfo<'info>,
+
+    /// CHECK: Metaplex Core program
+    pub mpl_core_program: AccountInfo<'info>,
+    
+    pub system_program: Program<'info, System>,
+    
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + PlayerProfile::INIT_SPACE,
+        seeds = [b"player_profile", owner.key().as_ref()],
+        bump
+    )]
+    pub player_profile: Account<'info, PlayerProfile>,
+    
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + PlayerHistoryPage::INIT_SPACE,
+        seeds = [
+            b"player_history",
+            owner.key().as_ref(),
+            player_profile.history_page(program_config.max_history_pages).to_le_bytes().as_ref()
+        ],
+        bump
+    )]
+    pub player_history_page: Account<'info, PlayerHistoryPage>,
+}
+
+/// 🎲 Context for Committing a Fusion Roll
+#[derive(Accounts)]
+pub struct CommitFusion<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: Ownership verified against the mpl-core asset data
+    pub asset_1: AccountInfo<'info>,
+
+    /// CHECK: Ownership verified against the mpl-core asset data
+    pub asset_2: AccountInfo<'info>,
+
+    /// CHECK: Generation route checked against the mpl-core asset data
+    pub result_asset: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        constraint = nft_state_1.asset == asset_1.key() @ NftError::NftStateAssetMismatch,
+        seeds = [b"nft_state", asset_1.key().as_ref()],
+        bump = nft_state_1.bump,
+        realloc = 8 + NftState::INIT_SPACE,
+        realloc::payer = payer,
+        realloc::zero = false
+    )]
+    pub nft_state_1: Account<'info, NftState>,
+
+    #[account(
+        mut,
+        constraint = nft_state_2.asset == asset_2.key() @ NftError::NftStateAssetMismatch,
+        seeds = [b"nft_state", asset_2.key().as_ref()],
+        bump = nft_state_2.bump,
+        realloc = 8 + NftState::INIT_SPACE,
+        realloc::payer = payer,
+        realloc::zero = false
+    )]
+    pub nft_state_2: Account<'info, NftState>,
+
+    /// Required only when the payer is a delegate rather than the owner of asset_1
+    #[account(
+        seeds = [b"delegation", asset_1.key().as_ref(), payer.key().as_ref()],
+        bump = delegation_1.bump
+    )]
+    pub delegation_1: Option<Account<'info, Delegation>>,
+
+    /// Required only when the payer is a delegate rather than the owner of asset_2
+    #[account(
+        seeds = [b"delegation", asset_2.key().as_ref(), payer.key().as_ref()],
+        bump = delegation_2.bump
+    )]
+    pub delegation_2: Option<Account<'info, Delegation>>,
+
+    /// CHECK: The result's NftState PDA; `fuse_nfts` creates it, so it must not exist yet
+    #[account(
+        seeds = [b"nft_state", result_asset.key().as_ref()],
+        bump,
+        constraint = result_nft_state.data_is_empty() @ NftError::FusionResultHasState
+    )]
+    pub result_nft_state: AccountInfo<'info>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + RollCommitment::INIT_SPACE,
+        seeds = [b"roll_commitment", asset_1.key().as_ref()],
+        bump
+    )]
+    pub roll_commitment: Account<'info, RollCommitment>,
+
+    /// CHECK: GenerationMap PDA; may be uninitialized (outputs unrouted)
+    #[account(seeds = [b"generation_map"], bump)]
+    pub generation_map: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// 🔥 Context for NFT Fusion
+#[derive(Accounts)]
+pub struct FuseNFTs<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    
+    /// CHECK: Handled by mpl-core
+    #[account(mut)]
+    pub asset_1: AccountInfo<'info>,
+    
+    /// CHECK: Handled by mpl-core
+    #[account(mut)]
+    pub asset_2: AccountInfo<'info>,
+    
+    /// CHECK: Handled by mpl-core
+    #[account(mut)]
+    pub result_asset: AccountInfo<'info>,
+
+    /// CHECK: Must be the result asset's owner; credited with prestige and the fusion
+    pub result_owner: AccountInfo<'info>,
+
+    /// CHECK: Handled by mpl-core
+    #[account(mut)]
+    pub collection: AccountInfo<'info>,
+    
+    #[account(
+        mut,
+        constraint = nft_state_1.asset == asset_1.key() @ NftError::NftStateAssetMismatch,
+        seeds = [b"nft_state", asset_1.key().as_ref()],
+        bump = nft_state_1.bump
+    )]
+    pub nft_state_1: Account<'info, NftState>,
+    
+    #[account(
+        mut,
+        constraint = nft_state_2.asset == asset_2.key() @ NftError::NftStateAssetMismatch,
+        seeds = [b"nft_state", asset_2.key().as_ref()],
+        bump = nft_state_2.bump
+    )]
+    pub nft_state_2: Account<'info, NftState>,
+    
+    /// Required only when the payer is a delegate rather than the owner of asset_1
+    #[account(
+        seeds = [b"delegation", asset_1.key().as_ref(), payer.key().as_ref()],
+        bump = delegation_1.bump
+    )]
+    pub delegation_1: Option<Account<'info, Delegation>>,
+    
+    /// Required only when the payer is a delegate rather than the owner of asset_2
+    #[account(
+        seeds = [b"delegation", asset_2.key().as_ref(), payer.key().as_ref()],
+        bump = delegation_2.bump
+    )]
+    pub delegation_2: Option<Account<'info, Delegation>>,
+    
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + NftState::INIT_SPACE,
+        seeds = [b"nft_state", result_asset.key().as_ref()],
+        bump
+    )]
+    pub result_nft_state: Account<'info, NftState>,
+
+    /// The roll opened by `commit_fusion`; closed back to its opener
+    #[account(
+        mut,
+        close = payer,
+        constraint = roll_commitment.owner == payer.key() @ NftError::Unauthorized,
+        seeds = [b"roll_commitment", asset_1.key().as_ref()],
+        bump = roll_commitment.bump
+    )]
+    pub roll_commitment: Account<'info, RollCommitment>,
+    
+    #[account(
+        mut,
+        seeds = [b"asset_history", result_asset.key().as_ref()],
+        bump = result_history.bump
+    )]
+    pub result_history: Account<'info, AssetHistory>,
+    
+    #[account(seeds = [b"program_config"], bump = program_config.bump)]
+    pub program_config: Account<'info, ProgramConfig>,
+
+    /// CHECK: GenerationMap PDA; may be uninitialized (outputs unrouted)
+    #[account(seeds = [b"generation_map"], bump)]
+    pub generation_map: AccountInfo<'info>,
+    
+    #[account(mut, seeds = [b"treasury"], bump = program_config.treasury_bump)]
+    pub treasury: SystemAccount<'info>,
+    
+    /// CHECK: Incinerator; lamports sent here are burned
+    #[account(mut, address = incinerator::ID)]
+    pub incinerator: AccountInfo<'info>,
+    
+    #[account(mut, seeds = [b"economy_ledger"], bump = economy_ledger.bump)]
+    pub economy_ledger: Account<'info, EconomyLedger>,
+    
+    /// CHECK: SlotHashes sysvar, address-constrained and parsed manually
+    #[account(address = sysvar::slot_hashes::ID)]
+    pub slot_hashes: AccountInfo<'info>,
+    
+    /// CHECK: PDA holding the Attributes plugin of assets minted or migrated
+    /// since attribute writes moved to the program
+    #[account(seeds = [b"attributes_authority"], bump)]
+    pub attributes_authority: AccountInfo<'info>,
+
+    /// CHECK: Metaplex Core program
+    pub mpl_core_program: AccountInfo<'info>,
+    
+    pub system_program: Program<'info, System>,
+    
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + PlayerProfile::INIT_SPACE,
+        seeds = [b"player_profile", result_owner.key().as_ref()],
+        bump
+    )]
+    pub player_profile: Account<'info, PlayerProfile>,
+    
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + PlayerHistoryPage::INIT_SPACE,
+        seeds = [
+            b"player_history",
+            result_owner.key().as_ref(),
+            player_profile.history_page(program_config.max_history_pages).to_le_bytes().as_ref()
+        ],
+        bump
+    )]
+    pub player_history_page: Account<'info, PlayerHistoryPage>,
+}
+
+/// ⚙️ Context for Program Configuration Bootstrap
+#[derive(Accounts)]
+pub struct InitializeProgramConfig<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + ProgramConfig::INIT_SPACE,
+        seeds = [b"program_config"],
+        bump
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+
+    #[account(mut, seeds = [b"treasury"], bump)]
+    pub treasury: SystemAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// 🔐 Context for Admin-Gated Program Configuration Updates
+#[derive(Accounts)]
+pub struct UpdateProgramConfig<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"program_config"],
+        bump = program_config.bump,
+        has_one = authority @ NftError::Unauthorized
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+}
+
+/// 🛒 Context for Listing a Trait
+#[derive(Accounts)]
+#[instruction(trait_key: String)]
+pub struct ListTrait<'info> {
+    #[account(mut)]
+    pub seller: Signer<'info>,
+
+    /// CHECK: Ownership verified against the mpl-core asset data
+    #[account(mut)]
+    pub asset: AccountInfo<'info>,
+
+    /// CHECK: Handled by mpl-core
+    #[account(mut)]
+    pub collection: AccountInfo<'info>,
+
+    /// CHECK: This asset's NftState PDA; may be uninitialized (never stamped)
+    #[account(mut, seeds = [b"nft_state", asset.key().as_ref()], bump)]
+    pub nft_state: AccountInfo<'info>,
+
+    #[account(
+        init,
+        payer = seller,
+        space = 8 + TraitListing::INIT_SPACE,
+        seeds = [b"trait_listing", asset.key().as_ref(), trait_key.as_bytes()],
+        bump
+    )]
+    pub trait_listing: Account<'info, TraitListing>,
+
+    /// CHECK: PDA holding the Attributes plugin of assets minted or migrated
+    /// since attribute writes moved to the program
+    #[account(seeds = [b"attributes_authority"], bump)]
+    pub attributes_authority: AccountInfo<'info>,
+
+    /// CHECK: Metaplex Core program
+    #[account(address = mpl_core::ID)]
+    pub mpl_core_program: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// 🏷️ Context for Listing an NFT
+#[derive(Accounts)]
+pub struct ListNFT<'info> {
+    #[account(mut)]
+    pub seller: Signer<'info>,
+
+    /// CHECK: Ownership verified against the mpl-core asset data
+    #[account(mut)]
+    pub asset: AccountInfo<'info>,
+
+    /// CHECK: Handled by mpl-core
+    #[account(mut)]
+    pub collection: AccountInfo<'info>,
+
+    #[account(
+        has_one = asset @ NftError::NftStateAssetMismatch,
+        seeds = [b"nft_state", asset.key().as_ref()],
+        bump = nft_state.bump
+    )]
+    pub nft_state: Account<'info, NftState>,
+
+    #[account(
+        init,
+        payer = seller,
+        space = 8 + Listing::INIT_SPACE,
+        seeds = [b"listing", asset.key().as_ref()],
+        bump
+    )]
+    pub listing: Account<'info, Listing>,
+
+    #[account(seeds = [b"program_config"], bump = program_config.bump)]
+    pub program_config: Account<'info, ProgramConfig>,
+
+    /// CHECK: Metaplex Core program
+    #[account(address = mpl_core::ID)]
+    pub mpl_core_program: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// 🛍️ Context for Buying a Listed NFT
+#[derive(Accounts)]
+pub struct BuyNFT<'info> {
+    #[account(mut)]
+    pub buyer: Signer<'info>,
+
+    /// CHECK: Bound to the listing
+    #[account(mut, address = listing.asset)]
+    pub asset: AccountInfo<'info>,
+
+    /// CHECK: Handled by mpl-core
+    #[account(mut)]
+    pub collection: AccountInfo<'info>,
+
+    #[account(
+        has_one = asset @ NftError::NftStateAssetMismatch,
+        seeds = [b"nft_state", asset.key().as_ref()],
+        bump = nft_state.bump
+    )]
+    pub nft_state: Account<'info, NftState>,
+
+    /// CHECK: Receives sale proceeds, bound to the listing
+    #[account(mut, address = listing.seller)]
+    pub seller: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        close = seller,
+        seeds = [b"listing", listing.asset.as_ref()],
+        bump = listing.bump
+    )]
+    pub listing: Account<'info, Listing>,
+
+    /// CHECK: The buyer's MarketMaker PDA; may be uninitialized (fee paid per trade)
+    #[account(mut, seeds = [b"market_maker", buyer.key().as_ref()], bump)]
+    pub market_maker: AccountInfo<'info>,
+
+    #[account(seeds = [b"program_config"], bump = program_config.bump)]
+    pub program_config: Account<'info, ProgramConfig>,
+
+    #[account(mut, seeds = [b"treasury"], bump = program_config.treasury_bump)]
+    pub treasury: SystemAccount<'info>,
+
+    #[account(mut, seeds = [b"economy_ledger"], bump = economy_ledger.bump)]
+    pub economy_ledger: Account<'info, EconomyLedger>,
+
+    #[account(mut, seeds = [b"floor_oracle"], bump = floor_oracle.bump)]
+    pub floor_oracle: Account<'info, FloorOracle>,
+
+    #[account(
+        init_if_needed,
+        payer = buyer,
+        space = 8 + PlayerProfile::INIT_SPACE,
+        seeds = [b"player_profile", buyer.key().as_ref()],
+        bump
+    )]
+    pub player_profile: Account<'info, PlayerProfile>,
+
+    #[account(
+        init_if_needed,
+        payer = buyer,
+        space = 8 + PlayerHistoryPage::INIT_SPACE,
+        seeds = [
+            b"player_history",
+            buyer.key().as_ref(),
+            player_profile.history_page(program_config.max_history_pages).to_le_bytes().as_ref()
+        ],
+        bump
+    )]
+    pub player_history_page: Account<'info, PlayerHistoryPage>,
+
+    /// CHECK: Metaplex Core program
+    #[account(address = mpl_core::ID)]
+    pub mpl_core_program: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// ↩️ Context for Cancelling an NFT Listing
+#[derive(Accounts)]
+pub struct CancelNFTListing<'info> {
+    #[account(mut)]
+    pub seller: Signer<'info>,
+
+    /// CHECK: Bound to the listing
+    #[account(mut, address = listing.asset)]
+    pub asset: AccountInfo<'info>,
+
+    /// CHECK: Handled by mpl-core
+    #[account(mut)]
+    pub collection: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        close = seller,
+        has_one = seller @ NftError::Unauthorized,
+        seeds = [b"listing", listing.asset.as_ref()],
+        bump = listing.bump
+    )]
+    pub listing: Account<'info, Listing>,
+
+    /// CHECK: Metaplex Core program
+    #[account(address = mpl_core::ID)]
+    pub mpl_core_program: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// 🛍️ Context for Buying a Trait
+#[derive(Accounts)]
+pub struct BuyTrait<'info> {
+    #[account(mut)]
+    pub buyer: Signer<'info>,
+
+    /// CHECK: Ownership verified against the mpl-core asset data
+    #[account(mut)]
+    pub buyer_asset: AccountInfo<'info>,
+
+    /// CHECK: Handled by mpl-core
+    #[account(mut)]
+    pub collection: AccountInfo<'info>,
+
+    /// CHECK: The buyer asset's NftState PDA; may be uninitialized (never stamped)
+    #[account(mut, seeds = [b"nft_state", buyer_asset.key().as_ref()], bump)]
+    pub buyer_nft_state: AccountInfo<'info>,
+
+    /// CHECK: Receives sale proceeds, bound to the listing
+    #[account(mut, address = trait_listing.seller)]
+    pub seller: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        close = seller,
+        seeds = [
+            b"trait_listing",
+            trait_listing.asset.as_ref(),
+            trait_listing.trait_key.as_bytes()
+        ],
+        bump = trait_listing.bump
+    )]
+    pub trait_listing: Account<'info, TraitListing>,
+
+    #[account(seeds = [b"program_config"], bump = program_config.bump)]
+    pub program_config: Account<'info, ProgramConfig>,
+
+    #[account(mut, seeds = [b"treasury"], bump = program_config.treasury_bump)]
+    pub treasury: SystemAccount<'info>,
+
+    /// CHECK: PDA holding the Attributes plugin of assets minted or migrated
+    /// since attribute writes moved to the program
+    #[account(seeds = [b"attributes_authority"], bump)]
+    pub attributes_authority: AccountInfo<'info>,
+
+    /// CHECK: Metaplex Core program
+    #[account(address = mpl_core::ID)]
+    pub mpl_core_program: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    #[account(mut, seeds = [b"economy_ledger"], bump = economy_ledger.bump)]
+    pub economy_ledger: Account<'info, EconomyLedger>,
+
+    #[account(
+        init_if_needed,
+        payer = buyer,
+        space = 8 + PlayerProfile::INIT_SPACE,
+        seeds = [b"player_profile", buyer.key().as_ref()],
+        bump
+    )]
+    pub player_profile: Account<'info, PlayerProfile>,
+
+    #[account(
+        init_if_needed,
+        payer = buyer,
+        space = 8 + PlayerHistoryPage::INIT_SPACE,
+        seeds = [
+            b"player_history",
+            buyer.key().as_ref(),
+            player_profile.history_page(program_config.max_history_pages).to_le_bytes().as_ref()
+        ],
+        bump
+    )]
+    pub player_history_page: Account<'info, PlayerHistoryPage>,
+}
+
+/// ↩️ Context for Cancelling a Trait Listing
+#[derive(Accounts)]
+pub struct CancelTraitListing<'info> {
+    #[account(mut)]
+    pub seller: Signer<'info>,
+
+    /// CHECK: Ownership verified against the mpl-core asset data
+    #[account(mut, address = trait_listing.asset)]
+    pub asset: AccountInfo<'info>,
+
+    /// CHECK: Handled by mpl-core
+    #[account(mut)]
+    pub collection: AccountInfo<'info>,
+
+    /// CHECK: This asset's NftState PDA; may be uninitialized (never stamped)
+    #[account(mut, seeds = [b"nft_state", asset.key().as_ref()], bump)]
+    pub nft_state: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        close = seller,
+        has_one = seller @ NftError::Unauthorized,
+        seeds = [
+            b"trait_listing",
+            trait_listing.asset.as_ref(),
+            trait_listing.trait_key.as_bytes()
+        ],
+        bump = trait_listing.bump
+    )]
+    pub trait_listing: Account<'info, TraitListing>,
+
+    #[account(seeds = [b"program_config"], bump = program_config.bump)]
+    pub program_config: Account<'info, ProgramConfig>,
+
+    /// CHECK: PDA holding the Attributes plugin of assets minted or migrated
+    /// since attribute writes moved to the program
+    #[account(seeds = [b"attributes_authority"], bump)]
+    pub attributes_authority: AccountInfo<'info>,
+
+    /// CHECK: Metaplex Core program
+    #[account(address = mpl_core::ID)]
+    pub mpl_core_program: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// 🏟️ Context for Tournament Creation
+#[derive(Accounts)]
+#[instruction(tournament_id: u64)]
+pub struct CreateTournament<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"program_config"],
+        bump = program_config.bump,
+        has_one = authority @ NftError::Unauthorized
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + Tournament::INIT_SPACE,
+        seeds = [b"tournament", tournament_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub tournament: Account<'info, Tournament>,
+
+    #[account(mut, seeds = [b"treasury"], bump = program_config.treasury_bump)]
+    pub treasury: SystemAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    #[account(mut, seeds = [b"economy_ledger"], bump = economy_ledger.bump)]
+    pub economy_ledger: Account<'info, EconomyLedger>,
+}
+
+/// 🎟️ Context for Tournament Entry
+#[derive(Accounts)]
+pub struct EnterTournament<'info> {
+    #[account(mut)]
+    pub entrant: Signer<'info>,
+
+    /// CHECK: Ownership verified against the mpl-core asset data
+    pub asset: AccountInfo<'info>,
+
+    #[account(
+        has_one = asset @ NftError::NftStateAssetMismatch,
+        seeds = [b"nft_state", asset.key().as_ref()],
+        bump = nft_state.bump
+    )]
+    pub nft_state: Account<'info, NftState>,
+
+    #[account(
+        mut,
+        seeds = [b"tournament", tournament.tournament_id.to_le_bytes().as_ref()],
+        bump = tournament.bump
+    )]
+    pub tournament: Account<'info, Tournament>,
+
+    pub system_program: Program<'info, System>,
+
+    #[account(mut, seeds = [b"economy_ledger"], bump = economy_ledger.bump)]
+    pub economy_ledger: Account<'info, EconomyLedger>,
+
+    #[account(
+        init_if_needed,
+        payer = entrant,
+        space = 8 + PlayerProfile::INIT_SPACE,
+        seeds = [b"player_profile", entrant.key().as_ref()],
+        bump
+    )]
+    pub player_profile: Account<'info, PlayerProfile>,
+
+    #[account(seeds = [b"program_config"], bump = program_config.bump)]
+    pub program_config: Account<'info, ProgramConfig>,
+
+    #[account(
+        init_if_needed,
+        payer = entrant,
+        space = 8 + PlayerHistoryPage::INIT_SPACE,
+        seeds = [
+            b"player_history",
+            entrant.key().as_ref(),
+            player_profile.history_page(program_config.max_history_pages).to_le_bytes().as_ref()
+        ],
+        bump
+    )]
+    pub player_history_page: Account<'info, PlayerHistoryPage>,
+}
+
+/// ⚔️ Context for Oracle Match Reports
+#[derive(Accounts)]
+pub struct ReportMatch<'info> {
+    pub oracle: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"tournament", tournament.tournament_id.to_le_bytes().as_ref()],
+        bump = tournament.bump,
+        has_one = oracle @ NftError::Unauthorized
+    )]
+    pub tournament: Account<'info, Tournament>,
+}
+
+/// 💰 Context for Tournament Prize Claims
+#[derive(Accounts)]
+pub struct ClaimTournamentPrize<'info> {
+    #[account(mut)]
+    pub claimant: Signer<'info>,
+
+    /// CHECK: Ownership verified against the mpl-core asset data
+    #[account(mut)]
+    pub asset: AccountInfo<'info>,
+
+    /// CHECK: Handled by mpl-core
+    #[account(mut)]
+    pub collection: AccountInfo<'info>,
+
+    /// CHECK: This asset's NftState PDA; may be uninitialized (never stamped)
+    #[account(mut, seeds = [b"nft_state", asset.key().as_ref()], bump)]
+    pub nft_state: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"tournament", tournament.tournament_id.to_le_bytes().as_ref()],
+        bump = tournament.bump
+    )]
+    pub tournament: Account<'info, Tournament>,
+
+    #[account(seeds = [b"program_config"], bump = program_config.bump)]
+    pub program_config: Account<'info, ProgramConfig>,
+
+    /// CHECK: PDA holding the Attributes plugin of assets minted or migrated
+    /// since attribute writes moved to the program
+    #[account(seeds = [b"attributes_authority"], bump)]
+    pub attributes_authority: AccountInfo<'info>,
+
+    /// CHECK: Metaplex Core program
+    #[account(address = mpl_core::ID)]
+    pub mpl_core_program: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    #[account(mut, seeds = [b"economy_ledger"], bump = economy_ledger.bump)]
+    pub economy_ledger: Account<'info, EconomyLedger>,
+}
+
+/// 🏠 Context for Rental Offers
+#[derive(Accounts)]
+pub struct OfferRental<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    /// CHECK: Ownership verified against the mpl-core asset data
+    #[account(mut)]
+    pub asset: AccountInfo<'info>,
+
+    /// CHECK: Handled by mpl-core
+    #[account(mut)]
+    pub collection: AccountInfo<'info>,
+
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + RentalAgreement::INIT_SPACE,
+        seeds = [b"rental", asset.key().as_ref()],
+        bump
+    )]
+    pub rental: Account<'info, RentalAgreement>,
+
+    /// CHECK: PDA that will hold the asset's FreezeDelegate
+    #[account(seeds = [b"stake_authority"], bump)]
+    pub stake_authority: AccountInfo<'info>,
+
+    /// CHECK: Metaplex Core program
+    #[account(address = mpl_core::ID)]
+    pub mpl_core_program: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// 🔑 Context for Starting a Rental
+#[derive(Accounts)]
+pub struct StartRental<'info> {
+    #[account(mut)]
+    pub borrower: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"rental", rental.asset.as_ref()],
+        bump = rental.bump
+    )]
+    pub rental: Account<'info, RentalAgreement>,
+
+    /// CHECK: Bound to the rental
+    #[account(mut, address = rental.asset)]
+    pub asset: AccountInfo<'info>,
+
+    /// CHECK: Handled by mpl-core
+    #[account(mut)]
+    pub collection: AccountInfo<'info>,
+
+    /// CHECK: PDA holding the rented asset's FreezeDelegate
+    #[account(seeds = [b"stake_authority"], bump)]
+    pub stake_authority: AccountInfo<'info>,
+
+    /// CHECK: Metaplex Core program
+    #[account(address = mpl_core::ID)]
+    pub mpl_core_program: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    #[account(mut, seeds = [b"economy_ledger"], bump = economy_ledger.bump)]
+    pub economy_ledger: Account<'info, EconomyLedger>,
+}
+
+/// 💵 Context for Claiming Rental Income
+#[derive(Accounts)]
+pub struct ClaimRentalIncome<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"rental", rental.asset.as_ref()],
+        bump = rental.bump,
+        has_one = owner @ NftError::Unauthorized
+    )]
+    pub rental: Account<'info, RentalAgreement>,
+
+    /// CHECK: Bound to the rental
+    #[account(mut, address = rental.asset)]
+    pub asset: AccountInfo<'info>,
+
+    /// CHECK: Handled by mpl-core
+    #[account(mut)]
+    pub collection: AccountInfo<'info>,
+
+    /// CHECK: PDA holding the rented asset's FreezeDelegate
+    #[account(seeds = [b"stake_authority"], bump)]
+    pub stake_authority: AccountInfo<'info>,
+
+    /// CHECK: Metaplex Core program
+    #[account(address = mpl_core::ID)]
+    pub mpl_core_program: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    #[account(mut, seeds = [b"economy_ledger"], bump = economy_ledger.bump)]
+    pub economy_ledger: Account<'info, EconomyLedger>,
+}
+
+/// ⏹️ Context for Early Rental Termination
+#[derive(Accounts)]
+pub struct TerminateRentalEarly<'info> {
+    #[account(mut)]
+    pub borrower: Signer<'info>,
+
+    /// CHECK: Receives accrued income and penalty, bound to the rental
+    #[account(mut, address = rental.owner)]
+    pub owner: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"rental", rental.asset.as_ref()],
+        bump = rental.bump,
+        has_one = borrower @ NftError::Unauthorized
+    )]
+    pub rental: Account<'info, RentalAgreement>,
+
+    /// CHECK: Bound to the rental
+    #[account(mut, address = rental.asset)]
+    pub asset: AccountInfo<'info>,
+
+    /// CHECK: Handled by mpl-core
+    #[account(mut)]
+    pub collection: AccountInfo<'info>,
+
+    /// CHECK: PDA holding the rented asset's FreezeDelegate
+    #[account(seeds = [b"stake_authority"], bump)]
+    pub stake_authority: AccountInfo<'info>,
+
+    /// CHECK: Metaplex Core program
+    #[account(address = mpl_core::ID)]
+    pub mpl_core_program: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    #[account(mut, seeds = [b"economy_ledger"], bump = economy_ledger.bump)]
+    pub economy_ledger: Account<'info, EconomyLedger>,
+}
+
+/// 🚪 Context for Closing a Rental Offer
+#[derive(Accounts)]
+pub struct CloseRentalOffer<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    #[account(
+        mut,
+        close = owner,
+        seeds = [b"rental", rental.asset.as_ref()],
+        bump = rental.bump,
+        has_one = owner @ NftError::Unauthorized
+    )]
+    pub rental: Account<'info, RentalAgreement>,
+}
+
+/// 🧪 Context for Initiating a Two-Phase Fusion
+#[derive(Accounts)]
+pub struct InitiateFusion<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    /// CHECK: Ownership verified against the mpl-core asset data
+    pub asset_1: AccountInfo<'info>,
+
+    /// CHECK: Ownership verified against the mpl-core asset data
+    pub asset_2: AccountInfo<'info>,
+
+    /// CHECK: Ownership verified against the mpl-core asset data
+    pub result_asset: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        constraint = nft_state_1.asset == asset_1.key() @ NftError::NftStateAssetMismatch,
+        seeds = [b"nft_state", asset_1.key().as_ref()],
+        bump = nft_state_1.bump,
+        realloc = 8 + NftState::INIT_SPACE,
+        realloc::payer = owner,
+        realloc::zero = false
+    )]
+    pub nft_state_1: Account<'info, NftState>,
+
+    #[account(
+        mut,
+        constraint = nft_state_2.asset == asset_2.key() @ NftError::NftStateAssetMismatch,
+        seeds = [b"nft_state", asset_2.key().as_ref()],
+        bump = nft_state_2.bump,
+        realloc = 8 + NftState::INIT_SPACE,
+        realloc::payer = owner,
+        realloc::zero = false
+    )]
+    pub nft_state_2: Account<'info, NftState>,
+
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + PendingFusion::INIT_SPACE,
+        seeds = [b"pending_fusion", result_asset.key().as_ref()],
+        bump
+    )]
+    pub pending_fusion: Account<'info, PendingFusion>,
+
+    #[account(seeds = [b"program_config"], bump = program_config.bump)]
+    pub program_config: Account<'info, ProgramConfig>,
+
+    /// CHECK: GenerationMap PDA; may be uninitialized (outputs unrouted)
+    #[account(seeds = [b"generation_map"], bump)]
+    pub generation_map: AccountInfo<'info>,
+
+    #[account(mut, seeds = [b"treasury"], bump = program_config.treasury_bump)]
+    pub treasury: SystemAccount<'info>,
+
+    /// CHECK: Incinerator; lamports sent here are burned
+    #[account(mut, address = incinerator::ID)]
+    pub incinerator: AccountInfo<'info>,
+
+    #[account(mut, seeds = [b"economy_ledger"], bump = economy_ledger.bump)]
+    pub economy_ledger: Account<'info, EconomyLedger>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// ✅ Context for Confirming a Two-Phase Fusion
+#[derive(Accounts)]
+pub struct ConfirmFusion<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    #[account(
+        mut,
+        close = owner,
+        has_one = owner @ NftError::Unauthorized,
+        has_one = result_asset,
+        seeds = [b"pending_fusion", result_asset.key().as_ref()],
+        bump = pending_fusion.bump
+    )]
+    pub pending_fusion: Account<'info, PendingFusion>,
+
+    #[account(
+        mut,
+        constraint = nft_state_1.asset == pending_fusion.asset_1 @ NftError::NftStateAssetMismatch,
+        seeds = [b"nft_state", pending_fusion.asset_1.as_ref()],
+        bump = nft_state_1.bump,
+        realloc = 8 + NftState::INIT_SPACE,
+        realloc::payer = owner,
+        realloc::zero = false
+    )]
+    pub nft_state_1: Account<'info, NftState>,
+
+    #[account(
+        mut,
+        constraint = nft_state_2.asset == pending_fusion.asset_2 @ NftError::NftStateAssetMismatch,
+        seeds = [b"nft_state", pending_fusion.asset_2.as_ref()],
+        bump = nft_state_2.bump,
+        realloc = 8 + NftState::INIT_SPACE,
+        realloc::payer = owner,
+        realloc::zero = false
+    )]
+    pub nft_state_2: Account<'info, NftState>,
+
+    /// CHECK: Bound to the pending fusion; ownership re-checked at confirm
+    #[account(address = pending_fusion.asset_1)]
+    pub asset_1: AccountInfo<'info>,
+
+    /// CHECK: Bound to the pending fusion; ownership re-checked at confirm
+    #[account(address = pending_fusion.asset_2)]
+    pub asset_2: AccountInfo<'info>,
+
+    /// CHECK: Bound to the pending fusion
+    #[account(mut)]
+    pub result_asset: AccountInfo<'info>,
+
+    /// CHECK: Handled by mpl-core
+    #[account(mut)]
+    pub collection: AccountInfo<'info>,
+
+    /// CHECK: The result asset's NftState PDA; created at confirm if it
+    /// doesn't exist yet, otherwise read and rewritten in place
+    #[account(mut, seeds = [b"nft_state", result_asset.key().as_ref()], bump)]
+    pub result_nft_state: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"asset_history", result_asset.key().as_ref()],
+        bump = result_history.bump
+    )]
+    pub result_history: Account<'info, AssetHistory>,
+
+    #[account(seeds = [b"program_config"], bump = program_config.bump)]
+    pub program_config: Account<'info, ProgramConfig>,
+
+    /// CHECK: GenerationMap PDA; may be uninitialized (outputs unrouted)
+    #[account(seeds = [b"generation_map"], bump)]
+    pub generation_map: AccountInfo<'info>,
+
+    /// CHECK: SlotHashes sysvar, address-constrained and parsed manually
+    #[account(address = sysvar::slot_hashes::ID)]
+    pub slot_hashes: AccountInfo<'info>,
+
+    /// Credited with prestige for levels past the cap
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = 8 + PlayerProfile::INIT_SPACE,
+        seeds = [b"player_profile", owner.key().as_ref()],
+        bump
+    )]
+    pub player_profile: Account<'info, PlayerProfile>,
+
+    /// CHECK: PDA holding the Attributes plugin of assets minted or migrated
+    /// since attribute writes moved to the program
+    #[account(seeds = [b"attributes_authority"], bump)]
+    pub attributes_authority: AccountInfo<'info>,
+
+    /// CHECK: Metaplex Core program
+    #[account(address = mpl_core::ID)]
+    pub mpl_core_program: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// ❌ Context for Cancelling a Two-Phase Fusion
+#[derive(Accounts)]
+pub struct CancelFusion<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    #[account(
+        mut,
+        close = owner,
+        has_one = owner @ NftError::Unauthorized,
+        seeds = [b"pending_fusion", pending_fusion.result_asset.as_ref()],
+        bump = pending_fusion.bump
+    )]
+    pub pending_fusion: Account<'info, PendingFusion>,
+
+    #[account(
+        mut,
+        constraint = nft_state_1.asset == pending_fusion.asset_1 @ NftError::NftStateAssetMismatch,
+        seeds = [b"nft_state", pending_fusion.asset_1.as_ref()],
+        bump = nft_state_1.bump
+    )]
+    pub nft_state_1: Account<'info, NftState>,
+
+    #[account(
+        mut,
+        constraint = nft_state_2.asset == pending_fusion.asset_2 @ NftError::NftStateAssetMismatch,
+        seeds = [b"nft_state", pending_fusion.asset_2.as_ref()],
+        bump = nft_state_2.bump
+    )]
+    pub nft_state_2: Account<'info, NftState>,
+}
+
+/// 🧩 Context for Registering a Set
+#[derive(Accounts)]
+#[instruction(set_id: u64)]
+pub struct RegisterSet<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"program_config"],
+        bump = program_config.bump,
+        has_one = authority @ NftError::Unauthorized
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + SetDefinition::INIT_SPACE,
+        seeds = [b"set", set_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub set_definition: Account<'info, SetDefinition>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// 🎁 Context for Claiming a Set Bonus
+#[derive(Accounts)]
+pub struct ClaimSetBonus<'info> {
+    #[account(mut)]
+    pub claimant: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"set", set_definition.set_id.to_le_bytes().as_ref()],
+        bump = set_definition.bump
+    )]
+    pub set_definition: Account<'info, SetDefinition>,
+
+    #[account(
+        init,
+        payer = claimant,
+        space = 8 + SetClaim::INIT_SPACE,
+        seeds = [
+            b"set_claim",
+            set_definition.set_id.to_le_bytes().as_ref(),
+            target_asset.key().as_ref()
+        ],
+        bump
+    )]
+    pub set_claim: Account<'info, SetClaim>,
+
+    /// CHECK: Ownership verified against the mpl-core asset data
+    #[account(mut)]
+    pub target_asset: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        constraint = target_nft_state.asset == target_asset.key() @ NftError::NftStateAssetMismatch,
+        seeds = [b"nft_state", target_asset.key().as_ref()],
+        bump = target_nft_state.bump,
+        realloc = 8 + NftState::INIT_SPACE,
+        realloc::payer = claimant,
+        realloc::zero = false
+    )]
+    pub target_nft_state: Account<'info, NftState>,
+
+    /// CHECK: Handled by mpl-core
+    #[account(mut)]
+    pub collection: AccountInfo<'info>,
+
+    #[account(seeds = [b"program_config"], bump = program_config.bump)]
+    pub program_config: Account<'info, ProgramConfig>,
+
+    /// CHECK: PDA holding the Attributes plugin of assets minted or migrated
+    /// since attribute writes moved to the program
+    #[account(seeds = [b"attributes_authority"], bump)]
+    pub attributes_authority: AccountInfo<'info>,
+
+    /// CHECK: Metaplex Core program
+    #[account(address = mpl_core::ID)]
+    pub mpl_core_program: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    #[account(mut, seeds = [b"economy_ledger"], bump = economy_ledger.bump)]
+    pub economy_ledger: Account<'info, EconomyLedger>,
+}
+
+/// 📚 Context for Creating an Asset History
+#[derive(Accounts)]
+pub struct InitializeAssetHistory<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: Only used as a PDA seed
+    pub asset: AccountInfo<'info>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + AssetHistory::INIT_SPACE,
+        seeds = [b"asset_history", asset.key().as_ref()],
+        bump
+    )]
+    pub asset_history: Account<'info, AssetHistory>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// ⏪ Context for Admin Checkpoint Restores
+#[derive(Accounts)]
+pub struct RestoreFromCheckpoint<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"program_config"],
+        bump = program_config.bump,
+        has_one = authority @ NftError::Unauthorized
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"asset_history", asset_history.asset.as_ref()],
+        bump = asset_history.bump
+    )]
+    pub asset_history: Account<'info, AssetHistory>,
+
+    #[account(
+        mut,
+        constraint = nft_state.asset == asset_history.asset @ NftError::NftStateAssetMismatch,
+        seeds = [b"nft_state", asset_history.asset.as_ref()],
+        bump = nft_state.bump
+    )]
+    pub nft_state: Account<'info, NftState>,
+
+    /// CHECK: Handled by mpl-core; bound to the history
+    #[account(mut, address = asset_history.asset @ NftError::InvalidAsset)]
+    pub asset: AccountInfo<'info>,
+
+    /// CHECK: Membership verified against the asset's update authority
+    #[account(mut)]
+    pub collection: AccountInfo<'info>,
+
+    /// CHECK: PDA holding the Attributes plugin of assets minted or migrated
+    /// since attribute writes moved to the program
+    #[account(seeds = [b"attributes_authority"], bump)]
+    pub attributes_authority: AccountInfo<'info>,
+
+    /// CHECK: Metaplex Core program
+    #[account(address = mpl_core::ID)]
+    pub mpl_core_program: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// 🏪 Context for Opening the Energy Shop
+#[derive(Accounts)]
+pub struct InitializeEnergyShop<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"program_config"],
+        bump = program_config.bump,
+        has_one = authority @ NftError::Unauthorized
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + ShopConfig::INIT_SPACE,
+        seeds = [b"shop"],
+        bump
+    )]
+    pub shop_config: Account<'info, ShopConfig>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// 🏪 Context for Repricing the Energy Shop
+#[derive(Accounts)]
+pub struct ConfigureEnergyShop<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"program_config"],
+        bump = program_config.bump,
+        has_one = authority @ NftError::Unauthorized
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+
+    #[account(mut, seeds = [b"shop"], bump = shop_config.bump)]
+    pub shop_config: Account<'info, ShopConfig>,
+}
+
+/// ⚡ Context for Energy Refill Purchases
+#[derive(Accounts)]
+pub struct BuyEnergyRefill<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    /// CHECK: Ownership verified against the mpl-core asset data
+    pub asset: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        has_one = asset @ NftError::NftStateAssetMismatch,
+        seeds = [b"nft_state", asset.key().as_ref()],
+        bump = nft_state.bump,
+        realloc = 8 + NftState::INIT_SPACE,
+        realloc::payer = owner,
+        realloc::zero = false
+    )]
+    pub nft_state: Account<'info, NftState>,
+
+    #[account(seeds = [b"shop"], bump = shop_config.bump)]
+    pub shop_config: Account<'info, ShopConfig>,
+
+    #[account(seeds = [b"program_config"], bump = program_config.bump)]
+    pub program_config: Account<'info, ProgramConfig>,
+
+    #[account(mut, seeds = [b"treasury"], bump = program_config.treasury_bump)]
+    pub treasury: SystemAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    #[account(mut, seeds = [b"economy_ledger"], bump = economy_ledger.bump)]
+    pub economy_ledger: Account<'info, EconomyLedger>,
+
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = 8 + PlayerProfile::INIT_SPACE,
+        seeds = [b"player_profile", owner.key().as_ref()],
+        bump
+    )]
+    pub player_profile: Account<'info, PlayerProfile>,
+
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = 8 + PlayerHistoryPage::INIT_SPACE,
+        seeds = [
+            b"player_history",
+            owner.key().as_ref(),
+            player_profile.history_page(program_config.max_history_pages).to_le_bytes().as_ref()
+        ],
+        bump
+    )]
+    pub player_history_page: Account<'info, PlayerHistoryPage>,
+}
+
+/// 🌳 Context for Publishing a Points Distribution
+#[derive(Accounts)]
+#[instruction(distribution_id: u64)]
+pub struct PublishPointsDistribution<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"program_config"],
+        bump = program_config.bump,
+        has_one = authority @ NftError::Unauthorized
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + PointsDistribution::INIT_SPACE,
+        seeds = [b"points_distribution", distribution_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub points_distribution: Account<'info, PointsDistribution>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// 🎯 Context for Claiming Distributed Points
+#[derive(Accounts)]
+pub struct ClaimPoints<'info> {
+    #[account(mut)]
+    pub claimant: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"points_distribution",
+            points_distribution.distribution_id.to_le_bytes().as_ref()
+        ],
+        bump = points_distribution.bump
+    )]
+    pub points_distribution: Account<'info, PointsDistribution>,
+
+    #[account(
+        init,
+        payer = claimant,
+        space = 8 + PointsClaim::INIT_SPACE,
+        seeds = [
+            b"points_claim",
+            points_distribution.key().as_ref(),
+            claimant.key().as_ref()
+        ],
+        bump
+    )]
+    pub points_claim: Account<'info, PointsClaim>,
+
+    /// CHECK: Ownership verified against the mpl-core asset data
+    pub asset: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        has_one = asset @ NftError::NftStateAssetMismatch,
+        seeds = [b"nft_state", asset.key().as_ref()],
+        bump = nft_state.bump,
+        realloc = 8 + NftState::INIT_SPACE,
+        realloc::payer = claimant,
+        realloc::zero = false
+    )]
+    pub nft_state: Account<'info, NftState>,
+
+    pub system_program: Program<'info, System>,
+
+    #[account(mut, seeds = [b"economy_ledger"], bump = economy_ledger.bump)]
+    pub economy_ledger: Account<'info, EconomyLedger>,
+}
+
+/// 📡 Context for Creating the Signal Board
+#[derive(Accounts)]
+pub struct InitializeSignalBoard<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"program_config"],
+        bump = program_config.bump,
+        has_one = authority @ NftError::Unauthorized
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + SignalBoard::INIT_SPACE,
+        seeds = [b"signal_board"],
+        bump
+    )]
+    pub signal_board: Account<'info, SignalBoard>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// 🪂 Context for Creating an Airdrop Campaign
+#[derive(Accounts)]
+#[instruction(campaign_id: u64, merkle_root: [u8; 32], total_recipients: u32)]
+pub struct CreateAirdropCampaign<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    #[account(
+        init,
+        payer = creator,
+        space = AirdropCampaign::space(total_recipients),
+        seeds = [b"airdrop", campaign_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub airdrop_campaign: Account<'info, AirdropCampaign>,
+
+    pub system_program: Program<'info, System>,
+
+    #[account(mut, seeds = [b"economy_ledger"], bump = economy_ledger.bump)]
+    pub economy_ledger: Account<'info, EconomyLedger>,
+}
+
+/// 🎁 Context for Claiming an Airdrop Allocation
+#[derive(Accounts)]
+pub struct ClaimAirdrop<'info> {
+    #[account(mut)]
+    pub recipient: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"airdrop", airdrop_campaign.campaign_id.to_le_bytes().as_ref()],
+        bump = airdrop_campaign.bump
+    )]
+    pub airdrop_campaign: Account<'info, AirdropCampaign>,
+
+    #[account(mut, seeds = [b"economy_ledger"], bump = economy_ledger.bump)]
+    pub economy_ledger: Account<'info, EconomyLedger>,
+}
+
+/// ⚙️ Context for Cranking an Airdrop Batch
+#[derive(Accounts)]
+pub struct CrankAirdrop<'info> {
+    pub cranker: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"airdrop", airdrop_campaign.campaign_id.to_le_bytes().as_ref()],
+        bump = airdrop_campaign.bump
+    )]
+    pub airdrop_campaign: Account<'info, AirdropCampaign>,
+
+    #[account(mut, seeds = [b"economy_ledger"], bump = economy_ledger.bump)]
+    pub economy_ledger: Account<'info, EconomyLedger>,
+}
+
+/// 📒 Context for Creating the Economy Ledger
+#[derive(Accounts)]
+pub struct InitializeEconomyLedger<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"program_config"],
+        bump = program_config.bump,
+        has_one = authority @ NftError::Unauthorized
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + EconomyLedger::INIT_SPACE,
+        seeds = [b"economy_ledger"],
+        bump
+    )]
+    pub economy_ledger: Account<'info, EconomyLedger>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// ⏳ Context for Creating the Evolution Queue
+#[derive(Accounts)]
+pub struct InitializeEvolutionQueue<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"program_config"],
+        bump = program_config.bump,
+        has_one = authority @ NftError::Unauthorized
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + EvolutionQueue::INIT_SPACE,
+        seeds = [b"evolution_queue"],
+        bump
+    )]
+    pub evolution_queue: Account<'info, EvolutionQueue>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// 📥 Context for Queueing an Evolution
+#[derive(Accounts)]
+pub struct QueueEvolution<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    /// CHECK: Ownership verified against the mpl-core asset data
+    pub asset: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        has_one = asset @ NftError::NftStateAssetMismatch,
+        seeds = [b"nft_state", asset.key().as_ref()],
+        bump = nft_state.bump,
+        realloc = 8 + NftState::INIT_SPACE,
+        realloc::payer = owner,
+        realloc::zero = false
+    )]
+    pub nft_state: Account<'info, NftState>,
+
+    #[account(mut, seeds = [b"evolution_queue"], bump = evolution_queue.bump)]
+    pub evolution_queue: Account<'info, EvolutionQueue>,
+
+    #[account(mut, seeds = [b"economy_ledger"], bump = economy_ledger.bump)]
+    pub economy_ledger: Account<'info, EconomyLedger>,
+
+    /// CHECK: PDA that must hold the asset's Attributes plugin, so the crank can write the result
+    #[account(seeds = [b"attributes_authority"], bump)]
+    pub attributes_authority: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// ⚙️ Context for Cranking the Evolution Queue
+#[derive(Accounts)]
+pub struct ProcessEvolutionQueue<'info> {
+    #[account(mut)]
+    pub cranker: Signer<'info>,
+
+    /// CHECK: The cranker's Cranker PDA; may be uninitialized (unregistered)
+    #[account(mut, seeds = [b"cranker", cranker.key().as_ref()], bump)]
+    pub cranker_registration: AccountInfo<'info>,
+
+    #[account(mut, seeds = [b"treasury"], bump = program_config.treasury_bump)]
+    pub treasury: SystemAccount<'info>,
+
+    #[account(mut, seeds = [b"evolution_queue"], bump = evolution_queue.bump)]
+    pub evolution_queue: Account<'info, EvolutionQueue>,
+
+    #[account(seeds = [b"program_config"], bump = program_config.bump)]
+    pub program_config: Account<'info, ProgramConfig>,
+
+    /// CHECK: GlobalEvent PDA; may be uninitialized (no live event)
+    #[account(seeds = [b"global_event"], bump)]
+    pub global_event: AccountInfo<'info>,
+
+    #[account(mut, seeds = [b"economy_ledger"], bump = economy_ledger.bump)]
+    pub economy_ledger: Account<'info, EconomyLedger>,
+
+    /// CHECK: SlotHashes sysvar, address-constrained and parsed manually
+    #[account(address = sysvar::slot_hashes::ID)]
+    pub slot_hashes: AccountInfo<'info>,
+
+    /// CHECK: PDA holding the queued assets' Attributes plugins; signs the evolved attributes
+    #[account(seeds = [b"attributes_authority"], bump)]
+    pub attributes_authority: AccountInfo<'info>,
+
+    /// CHECK: Metaplex Core program
+    #[account(address = mpl_core::ID)]
+    pub mpl_core_program: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// 🌐 Context for Registering a Collection Base URI
+#[derive(Accounts)]
+pub struct InitializeCollectionUri<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"program_config"],
+        bump = program_config.bump,
+        has_one = authority @ NftError::Unauthorized
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+
+    /// CHECK: Only used as a seed; membership is checked when assets resync
+    pub collection: AccountInfo<'info>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + CollectionUri::INIT_SPACE,
+        seeds = [b"collection_uri", collection.key().as_ref()],
+        bump
+    )]
+    pub collection_uri: Account<'info, CollectionUri>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// 🔄 Context for Scheduling a Base URI Rotation
+#[derive(Accounts)]
+pub struct RotateBaseUri<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"program_config"],
+        bump = program_config.bump,
+        has_one = authority @ NftError::Unauthorized
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"collection_uri", collection_uri.collection.as_ref()],
+        bump = collection_uri.bump
+    )]
+    pub collection_uri: Account<'info, CollectionUri>,
+}
+
+/// 🔁 Context for Resyncing an Asset URI
+#[derive(Accounts)]
+pub struct ResyncAssetUri<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: Membership verified against the asset's update authority
+    #[account(mut)]
+    pub asset: AccountInfo<'info>,
+
+    /// CHECK: Bound to the URI config
+    #[account(mut, address = collection_uri.collection)]
+    pub collection: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"collection_uri", collection_uri.collection.as_ref()],
+        bump = collection_uri.bump
+    )]
+    pub collection_uri: Account<'info, CollectionUri>,
+
+    /// CHECK: PDA the collection delegates update authority to
+    #[account(seeds = [b"uri_authority"], bump)]
+    pub uri_authority: AccountInfo<'info>,
+
+    /// CHECK: Metaplex Core program
+    #[account(address = mpl_core::ID)]
+    pub mpl_core_program: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// 🔒 Context for Minting Straight into Staking
+#[derive(Accounts)]
+pub struct MintAndStake<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: Handled by mpl-core
+    #[account(mut)]
+    pub asset: AccountInfo<'info>,
+
+    /// CHECK: Handled by mpl-core
+    #[account(mut)]
+    pub collection: AccountInfo<'info>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + StakeRecord::INIT_SPACE,
+        seeds = [b"stake", asset.key().as_ref()],
+        bump
+    )]
+    pub stake_record: Account<'info, StakeRecord>,
+
+    /// CHECK: PDA holding freeze authority over staked assets
+    #[account(seeds = [b"stake_authority"], bump)]
+    pub stake_authority: AccountInfo<'info>,
+
+    #[account(mut, seeds = [b"staked_index"], bump = staked_index.bump)]
+    pub staked_index: Account<'info, StakedIndex>,
+
+    #[account(mut, seeds = [b"signal_board"], bump = signal_board.bump)]
+    pub signal_board: Account<'info, SignalBoard>,
+
+    /// CHECK: This collection's CollectionConfig PDA; may be uninitialized (created externally)
+    #[account(seeds = [b"collection_config", collection.key().as_ref()], bump)]
+    pub collection_config: AccountInfo<'info>,
+    
+    /// CHECK: The payer's MintCounterShard PDA; must be initialized if the config is
+    #[account(
+        mut,
+        seeds = [b"mint_shard", collection.key().as_ref(), &[mint_shard_index(&payer.key())]],
+        bump
+    )]
+    pub mint_shard: AccountInfo<'info>,
+
+    /// This collection's EditionCounter; required once editions are numbered
+    #[account(mut, seeds = [b"edition", collection.key().as_ref()], bump = edition_counter.bump)]
+    pub edition_counter: Option<Account<'info, EditionCounter>>,
+
+    /// CHECK: This collection's AchievementTiers PDA; may be uninitialized (default ladder)
+    #[account(seeds = [b"achievement_tiers", collection.key().as_ref()], bump)]
+    pub achievement_tiers: AccountInfo<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + MintReceipt::INIT_SPACE,
+        seeds = [b"receipt", collection.key().as_ref(), payer.key().as_ref()],
+        bump
+    )]
+    pub mint_receipt: Account<'info, MintReceipt>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + PlayerProfile::INIT_SPACE,
+        seeds = [b"player_profile", payer.key().as_ref()],
+        bump
+    )]
+    pub player_profile: Account<'info, PlayerProfile>,
+
+    #[account(mut, seeds = [b"treasury"], bump)]
+    pub treasury: SystemAccount<'info>,
+
+    #[account(mut, seeds = [b"economy_ledger"], bump = economy_ledger.bump)]
+    pub economy_ledger: Account<'info, EconomyLedger>,
+
+    #[account(seeds = [b"program_config"], bump = program_config.bump)]
+    pub program_config: Account<'info, ProgramConfig>,
+
+    /// CHECK: Instructions sysvar, address-constrained
+    #[account(address = sysvar::instructions::ID)]
+    pub instructions_sysvar: AccountInfo<'info>,
+
+    /// CHECK: Metaplex Core program
+    #[account(address = mpl_core::ID)]
+    pub mpl_core_program: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// 🥩 Context for Staking an Owned NFT
+#[derive(Accounts)]
+pub struct StakeNFT<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    /// CHECK: Ownership verified against the mpl-core asset data
+    #[account(mut)]
+    pub asset: AccountInfo<'info>,
+
+    /// CHECK: Handled by mpl-core
+    #[account(mut)]
+    pub collection: AccountInfo<'info>,
+
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + StakeRecord::INIT_SPACE,
+        seeds = [b"stake", asset.key().as_ref()],
+        bump
+    )]
+    pub stake_record: Account<'info, StakeRecord>,
+
+    /// CHECK: PDA holding freeze authority over staked assets
+    #[account(seeds = [b"stake_authority"], bump)]
+    pub stake_authority: AccountInfo<'info>,
+
+    #[account(mut, seeds = [b"staked_index"], bump = staked_index.bump)]
+    pub staked_index: Account<'info, StakedIndex>,
+
+    /// CHECK: Metaplex Core program
+    #[account(address = mpl_core::ID)]
+    pub mpl_core_program: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// 🔓 Context for Unstaking an NFT
+#[derive(Accounts)]
+pub struct UnstakeNFT<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    /// CHECK: Bound to the stake record
+    #[account(mut, address = stake_record.asset)]
+    pub asset: AccountInfo<'info>,
+
+    /// CHECK: Handled by mpl-core
+    #[account(mut)]
+    pub collection: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        close = owner,
+        has_one = owner @ NftError::Unauthorized,
+        seeds = [b"stake", stake_record.asset.as_ref()],
+        bump = stake_record.bump
+    )]
+    pub stake_record: Account<'info, StakeRecord>,
+
+    /// CHECK: PDA holding freeze authority over staked assets
+    #[account(seeds = [b"stake_authority"], bump)]
+    pub stake_authority: AccountInfo<'info>,
+
+    #[account(mut, seeds = [b"staked_index"], bump = staked_index.bump)]
+    pub staked_index: Account<'info, StakedIndex>,
+
+    /// CHECK: Metaplex Core program
+    #[account(address = mpl_core::ID)]
+    pub mpl_core_program: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// 📤 Context for Exporting Configs to a Successor
+#[derive(Accounts)]
+pub struct ExportState<'info> {
+    /// The successor program's `[b"migration"]` PDA
+    pub migration_authority: Signer<'info>,
+
+    #[account(seeds = [b"program_config"], bump = program_config.bump)]
+    pub program_config: Account<'info, ProgramConfig>,
+
+    #[account(seeds = [b"shop"], bump = shop_config.bump)]
+    pub shop_config: Account<'info, ShopConfig>,
+
+    #[account(
+        seeds = [b"collection_uri", collection_uri.collection.as_ref()],
+        bump = collection_uri.bump
+    )]
+    pub collection_uri: Account<'info, CollectionUri>,
+}
+
+/// 📥 Context for Importing a Predecessor's Configs
+#[derive(Accounts)]
+pub struct ImportState<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"program_config"],
+        bump = program_config.bump,
+        has_one = authority @ NftError::Unauthorized
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + ShopConfig::INIT_SPACE,
+        seeds = [b"shop"],
+        bump
+    )]
+    pub shop_config: Account<'info, ShopConfig>,
+
+    /// CHECK: Only used as a seed; must match the exported collection
+    pub collection: AccountInfo<'info>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + CollectionUri::INIT_SPACE,
+        seeds = [b"collection_uri", collection.key().as_ref()],
+        bump
+    )]
+    pub collection_uri: Account<'info, CollectionUri>,
+
+    /// CHECK: This program's migration PDA, signs the export CPI
+    #[account(seeds = [b"migration"], bump)]
+    pub migration_authority: AccountInfo<'info>,
+
+    /// CHECK: The previous deployment; it validates its own accounts
+    #[account(executable)]
+    pub predecessor_program: AccountInfo<'info>,
+
+    /// CHECK: Predecessor's program config, validated by the predecessor
+    pub predecessor_program_config: AccountInfo<'info>,
+
+    /// CHECK: Predecessor's shop config, validated by the predecessor
+    pub predecessor_shop_config: AccountInfo<'info>,
+
+    /// CHECK: Predecessor's collection URI config, validated by the predecessor
+    pub predecessor_collection_uri: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// 🕰️ Context for Setting the Mock Clock
+#[derive(Accounts)]
+pub struct SetMockClock<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"program_config"],
+        bump = program_config.bump,
+        has_one = authority @ NftError::Unauthorized
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + MockClock::INIT_SPACE,
+        seeds = [b"mock_clock"],
+        bump
+    )]
+    pub mock_clock: Account<'info, MockClock>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// 🤝 Context for Granting a Scoped Delegation
+#[derive(Accounts)]
+pub struct GrantDelegation<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    /// CHECK: Ownership verified against the mpl-core asset data
+    pub asset: AccountInfo<'info>,
+
+    /// CHECK: Any key the owner chooses to delegate to
+    pub delegate: AccountInfo<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = 8 + Delegation::INIT_SPACE,
+        seeds = [b"delegation", asset.key().as_ref(), delegate.key().as_ref()],
+        bump
+    )]
+    pub delegation: Account<'info, Delegation>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// ✂️ Context for Revoking a Delegation
+#[derive(Accounts)]
+pub struct RevokeDelegation<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    #[account(
+        mut,
+        close = owner,
+        has_one = owner @ NftError::Unauthorized,
+        seeds = [b"delegation", delegation.asset.as_ref(), delegation.delegate.as_ref()],
+        bump = delegation.bump
+    )]
+    pub delegation: Account<'info, Delegation>,
+}
+
+/// 🗂️ Context for Staked Index Bootstrap
+#[derive(Accounts)]
+pub struct InitializeStakedIndex<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"program_config"],
+        bump = program_config.bump,
+        has_one = authority @ NftError::Unauthorized
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + StakedIndex::INIT_SPACE,
+        seeds = [b"staked_index"],
+        bump
+    )]
+    pub staked_index: Account<'info, StakedIndex>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// 🎟️ Context for Holder Lottery Bootstrap
+#[derive(Accounts)]
+pub struct InitializeHolderLottery<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"program_config"],
+        bump = program_config.bump,
+        has_one = authority @ NftError::Unauthorized
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + HolderLottery::INIT_SPACE,
+        seeds = [b"holder_lottery"],
+        bump
+    )]
+    pub holder_lottery: Account<'info, HolderLottery>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// 🎟️ Context for Retuning the Holder Lottery
+#[derive(Accounts)]
+pub struct ConfigureHolderLottery<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"program_config"],
+        bump = program_config.bump,
+        has_one = authority @ NftError::Unauthorized
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+
+    #[account(mut, seeds = [b"holder_lottery"], bump = holder_lottery.bump)]
+    pub holder_lottery: Account<'info, HolderLottery>,
+}
+
+/// 🎰 Context for Drawing the Holder Lottery
+#[derive(Accounts)]
+pub struct DrawLottery<'info> {
+    #[account(mut, seeds = [b"holder_lottery"], bump = holder_lottery.bump)]
+    pub holder_lottery: Account<'info, HolderLottery>,
+
+    #[account(mut, seeds = [b"staked_index"], bump = staked_index.bump)]
+    pub staked_index: Account<'info, StakedIndex>,
+
+    #[account(seeds = [b"program_config"], bump = program_config.bump)]
+    pub program_config: Account<'info, ProgramConfig>,
+
+    #[account(mut, seeds = [b"treasury"], bump = program_config.treasury_bump)]
+    pub treasury: SystemAccount<'info>,
+
+    /// CHECK: SlotHashes sysvar, address-constrained and parsed manually
+    #[account(address = sysvar::slot_hashes::ID)]
+    pub slot_hashes: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    #[account(mut, seeds = [b"economy_ledger"], bump = economy_ledger.bump)]
+    pub economy_ledger: Account<'info, EconomyLedger>,
+}
+
+/// 💰 Context for Claiming the Holder Lottery Prize
+#[derive(Accounts)]
+pub struct ClaimLotteryPrize<'info> {
+    #[account(mut)]
+    pub winner: Signer<'info>,
+
+    #[account(
+        mut,
+        has_one = winner @ NftError::Unauthorized,
+        seeds = [b"holder_lottery"],
+        bump = holder_lottery.bump
+    )]
+    pub holder_lottery: Account<'info, HolderLottery>,
+
+    #[account(mut, seeds = [b"economy_ledger"], bump = economy_ledger.bump)]
+    pub economy_ledger: Account<'info, EconomyLedger>,
+}
+
+/// 🌍 Context for Configuring a Collection's Region Lock
+#[derive(Accounts)]
+#[instruction(collection: Pubkey)]
+pub struct SetRegionLock<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"program_config"],
+        bump = program_config.bump,
+        has_one = authority @ NftError::Unauthorized
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + RegionLock::INIT_SPACE,
+        seeds = [b"region_lock", collection.as_ref()],
+        bump
+    )]
+    pub region_lock: Account<'info, RegionLock>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// 🔮 Context for Simulating a Mint - reads nothing but the clock
+#[derive(Accounts)]
+pub struct SimulateMint {}
+
+/// 🪪 Context for Describing an Asset; every PDA but the NftState may be uninitialized
+#[derive(Accounts)]
+pub struct DescribeAsset<'info> {
+    /// CHECK: Only its key is used, as the PDA seed
+    pub asset: AccountInfo<'info>,
+
+    #[account(
+        constraint = nft_state.asset == asset.key() @ NftError::NftStateAssetMismatch,
+        seeds = [b"nft_state", asset.key().as_ref()],
+        bump = nft_state.bump
+    )]
+    pub nft_state: Account<'info, NftState>,
+
+    /// CHECK: The GlobalEvent PDA; may be uninitialized (no event)
+    #[account(seeds = [b"global_event"], bump)]
+    pub global_event: AccountInfo<'info>,
+
+    /// CHECK: This asset's Listing PDA; may be uninitialized (not listed)
+    #[account(seeds = [b"listing", asset.key().as_ref()], bump)]
+    pub listing: AccountInfo<'info>,
+
+    /// CHECK: This asset's RentalAgreement PDA; may be uninitialized (no rental)
+    #[account(seeds = [b"rental", asset.key().as_ref()], bump)]
+    pub rental: AccountInfo<'info>,
+
+    /// CHECK: This asset's StakeRecord PDA; may be uninitialized (not staked)
+    #[account(seeds = [b"stake", asset.key().as_ref()], bump)]
+    pub stake_record: AccountInfo<'info>,
+
+    /// CHECK: This asset's ForcedRecall PDA; may be uninitialized (no recall)
+    #[account(seeds = [b"forced_recall", asset.key().as_ref()], bump)]
+    pub forced_recall: AccountInfo<'info>,
+}
+
+/// 🔗 Context for Binding an NftState to its Asset
+#[derive(Accounts)]
+pub struct BindNftState<'info> {
+    /// CHECK: Only its key is used, as the NftState PDA seed
+    pub asset: AccountInfo<'info>,
+
+    #[account(mut, seeds = [b"nft_state", asset.key().as_ref()], bump)]
+    pub nft_state: Account<'info, NftState>,
+}
+
+/// 📊 Context for Snapshotting a Page of NftStates
+#[derive(Accounts)]
+#[instruction(epoch: u64)]
+pub struct SnapshotStates<'info> {
+    #[account(mut)]
+    pub cranker: Signer<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = cranker,
+        space = 8 + SnapshotPage::INIT_SPACE,
+        seeds = [b"snapshot", epoch.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub snapshot_page: Account<'info, SnapshotPage>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// 🎨 Context for Creating a Community Bounty
+#[derive(Accounts)]
+#[instruction(bounty_id: u64)]
+pub struct CreateBounty<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    #[account(
+        init,
+        payer = creator,
+        space = 8 + Bounty::INIT_SPACE,
+        seeds = [b"bounty", bounty_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub bounty: Account<'info, Bounty>,
+
+    pub system_program: Program<'info, System>,
+
+    #[account(mut, seeds = [b"economy_ledger"], bump = economy_ledger.bump)]
+    pub economy_ledger: Account<'info, EconomyLedger>,
+}
+
+/// 🏅 Context for Awarding a Community Bounty
+#[derive(Accounts)]
+pub struct AwardBounty<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    #[account(
+        mut,
+        close = creator,
+        has_one = creator @ NftError::Unauthorized,
+        seeds = [b"bounty", bounty.bounty_id.to_le_bytes().as_ref()],
+        bump = bounty.bump
+    )]
+    pub bounty: Account<'info, Bounty>,
+
+    /// CHECK: Any wallet the creator picks; only receives lamports and the NFT
+    #[account(mut)]
+    pub winner: AccountInfo<'info>,
+
+    /// Required only when the bounty promised a commemorative NFT
+    #[account(mut)]
+    pub commemorative_asset: Option<Signer<'info>>,
+
+    /// CHECK: Handled by mpl-core
+    #[account(mut)]
+    pub collection: Option<AccountInfo<'info>>,
+
+    #[account(seeds = [b"program_config"], bump = program_config.bump)]
+    pub program_config: Account<'info, ProgramConfig>,
+
+    /// CHECK: Metaplex Core program
+    #[account(address = mpl_core::ID)]
+    pub mpl_core_program: Option<AccountInfo<'info>>,
+
+    pub system_program: Program<'info, System>,
+
+    #[account(mut, seeds = [b"economy_ledger"], bump = economy_ledger.bump)]
+    pub economy_ledger: Account<'info, EconomyLedger>,
+}
+
+/// 🌱 Context for Initializing an Asset's NftState
+#[derive(Accounts)]
+pub struct InitializeNftState<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: Must be an mpl-core asset; attributes read from its data
+    pub asset: AccountInfo<'info>,
+
+    /// CHECK: Membership verified against the asset's update authority
+    pub collection: AccountInfo<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + NftState::INIT_SPACE,
+        seeds = [b"nft_state", asset.key().as_ref()],
+        bump
+    )]
+    pub nft_state: Account<'info, NftState>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// 🎉 Context for Starting a Global Event
+#[derive(Accounts)]
+pub struct StartGlobalEvent<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"program_config"],
+        bump = program_config.bump,
+        has_one = authority @ NftError::Unauthorized
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + GlobalEvent::INIT_SPACE,
+        seeds = [b"global_event"],
+        bump
+    )]
+    pub global_event: Account<'info, GlobalEvent>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// 📅 Context for Subscribing
+#[derive(Accounts)]
+pub struct Subscribe<'info> {
+    #[account(mut)]
+    pub wallet: Signer<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = wallet,
+        space = 8 + Subscription::INIT_SPACE,
+        seeds = [b"subscription", wallet.key().as_ref()],
+        bump
+    )]
+    pub subscription: Account<'info, Subscription>,
+
+    #[account(
+        init_if_needed,
+        payer = wallet,
+        space = 8 + PlayerProfile::INIT_SPACE,
+        seeds = [b"player_profile", wallet.key().as_ref()],
+        bump
+    )]
+    pub player_profile: Account<'info, PlayerProfile>,
+
+    #[account(seeds = [b"program_config"], bump = program_config.bump)]
+    pub program_config: Account<'info, ProgramConfig>,
+
+    #[account(mut, seeds = [b"treasury"], bump = program_config.treasury_bump)]
+    pub treasury: SystemAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    #[account(mut, seeds = [b"economy_ledger"], bump = economy_ledger.bump)]
+    pub economy_ledger: Account<'info, EconomyLedger>,
+}
+
+/// 💧 Context for Settling a Subscription Stream
+#[derive(Accounts)]
+pub struct SettleSubscription<'info> {
+    #[account(
+        mut,
+        seeds = [b"subscription", subscription.wallet.as_ref()],
+        bump = subscription.bump
+    )]
+    pub subscription: Account<'info, Subscription>,
+
+    #[account(seeds = [b"program_config"], bump = program_config.bump)]
+    pub program_config: Account<'info, ProgramConfig>,
+
+    #[account(mut, seeds = [b"treasury"], bump = program_config.treasury_bump)]
+    pub treasury: SystemAccount<'info>,
+
+    #[account(mut, seeds = [b"economy_ledger"], bump = economy_ledger.bump)]
+    pub economy_ledger: Account<'info, EconomyLedger>,
+}
+
+/// 🛑 Context for Cancelling a Subscription
+#[derive(Accounts)]
+pub struct CancelSubscription<'info> {
+    #[account(mut)]
+    pub wallet: Signer<'info>,
+
+    #[account(
+        mut,
+        close = wallet,
+        has_one = wallet @ NftError::Unauthorized,
+        seeds = [b"subscription", wallet.key().as_ref()],
+        bump = subscription.bump
+    )]
+    pub subscription: Account<'info, Subscription>,
+
+    #[account(
+        mut,
+        has_one = wallet @ NftError::Unauthorized,
+        seeds = [b"player_profile", wallet.key().as_ref()],
+        bump = player_profile.bump
+    )]
+    pub player_profile: Account<'info, PlayerProfile>,
+
+    #[account(seeds = [b"program_config"], bump = program_config.bump)]
+    pub program_config: Account<'info, ProgramConfig>,
+
+    #[account(mut, seeds = [b"treasury"], bump = program_config.treasury_bump)]
+    pub treasury: SystemAccount<'info>,
+
+    #[account(mut, seeds = [b"economy_ledger"], bump = economy_ledger.bump)]
+    pub economy_ledger: Account<'info, EconomyLedger>,
+}
+
+/// 🎲 Context for Committing a Starter Pack
+#[derive(Accounts)]
+pub struct CommitStarterPack<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: Handled by mpl-core at mint time
+    pub collection: AccountInfo<'info>,
+
+    #[account(seeds = [b"program_config"], bump = program_config.bump)]
+    pub program_config: Account<'info, ProgramConfig>,
+
+    /// CHECK: This collection's CollectionConfig PDA; may be uninitialized (created externally)
+    #[account(seeds = [b"collection_config", collection.key().as_ref()], bump)]
+    pub collection_config: AccountInfo<'info>,
+
+    /// CHECK: The payer's MintCounterShard PDA; must be initialized if the config is
+    #[account(
+        mut,
+        seeds = [b"mint_shard", collection.key().as_ref(), &[mint_shard_index(&payer.key())]],
+        bump
+    )]
+    pub mint_shard: AccountInfo<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + MintReceipt::INIT_SPACE,
+        seeds = [b"receipt", collection.key().as_ref(), payer.key().as_ref()],
+        bump
+    )]
+    pub mint_receipt: Account<'info, MintReceipt>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + RollCommitment::INIT_SPACE,
+        seeds = [b"roll_commitment", payer.key().as_ref()],
+        bump
+    )]
+    pub roll_commitment: Account<'info, RollCommitment>,
+
+    #[account(mut, seeds = [b"treasury"], bump = program_config.treasury_bump)]
+    pub treasury: SystemAccount<'info>,
+
+    #[account(mut, seeds = [b"economy_ledger"], bump = economy_ledger.bump)]
+    pub economy_ledger: Account<'info, EconomyLedger>,
+
+    /// CHECK: Instructions sysvar, address-constrained
+    #[account(address = sysvar::instructions::ID)]
+    pub instructions_sysvar: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// 🎁 Context for Starter Pack Mint
+#[derive(Accounts)]
+pub struct MintStarterPack<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(mut)]
+    pub asset_1: Signer<'info>,
+
+    #[account(mut)]
+    pub asset_2: Signer<'info>,
+
+    #[account(mut)]
+    pub asset_3: Signer<'info>,
+
+    /// CHECK: Handled by mpl-core
+    #[account(mut)]
+    pub collection: AccountInfo<'info>,
+
+    #[account(mut, seeds = [b"signal_board"], bump = signal_board.bump)]
+    pub signal_board: Account<'info, SignalBoard>,
+
+    #[account(seeds = [b"program_config"], bump = program_config.bump)]
+    pub program_config: Account<'info, ProgramConfig>,
+
+    /// CHECK: This collection's CollectionConfig PDA; may be uninitialized (created externally)
+    #[account(seeds = [b"collection_config", collection.key().as_ref()], bump)]
+    pub collection_config: AccountInfo<'info>,
+    
+    /// This collection's EditionCounter; required once editions are numbered
+    #[account(mut, seeds = [b"edition", collection.key().as_ref()], bump = edition_counter.bump)]
+    pub edition_counter: Option<Account<'info, EditionCounter>>,
+
+    /// CHECK: This collection's AchievementTiers PDA; may be uninitialized (default ladder)
+    #[account(seeds = [b"achievement_tiers", collection.key().as_ref()], bump)]
+    pub achievement_tiers: AccountInfo<'info>,
+
+    /// The pack opened by `commit_starter_pack`; closed back to its opener
+    #[account(
+        mut,
+        close = payer,
+        constraint = roll_commitment.owner == payer.key() @ NftError::Unauthorized,
+        seeds = [b"roll_commitment", payer.key().as_ref()],
+        bump = roll_commitment.bump
+    )]
+    pub roll_commitment: Account<'info, RollCommitment>,
+
+    /// CHECK: SlotHashes sysvar, address-constrained and parsed manually
+    #[account(address = sysvar::slot_hashes::ID)]
+    pub slot_hashes: AccountInfo<'info>,
+
+    /// CHECK: Instructions sysvar, address-constrained
+    #[account(address = sysvar::instructions::ID)]
+    pub instructions_sysvar: AccountInfo<'info>,
+
+    /// CHECK: Metaplex Core program
+    #[account(address = mpl_core::ID)]
+    pub mpl_core_program: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// 🚨 Context for Scheduling a Forced Recall
+#[derive(Accounts)]
+pub struct ScheduleForcedRecall<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"program_config"],
+        bump = program_config.bump,
+        has_one = authority @ NftError::Unauthorized
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+
+    /// CHECK: The asset under notice; only its key is recorded
+    pub asset: AccountInfo<'info>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + ForcedRecall::INIT_SPACE,
+        seeds = [b"forced_recall", asset.key().as_ref()],
+        bump
+    )]
+    pub forced_recall: Account<'info, ForcedRecall>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// 🚨 Context for Cancelling a Forced Recall
+#[derive(Accounts)]
+pub struct CancelForcedRecall<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"program_config"],
+        bump = program_config.bump,
+        has_one = authority @ NftError::Unauthorized
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+
+    #[account(
+        mut,
+        close = authority,
+        seeds = [b"forced_recall", forced_recall.asset.as_ref()],
+        bump = forced_recall.bump
+    )]
+    pub forced_recall: Account<'info, ForcedRecall>,
+}
+
+/// 🚨 Context for Executing a Forced Recall
+#[derive(Accounts)]
+pub struct ExecuteForcedRecall<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"program_config"],
+        bump = program_config.bump,
+        has_one = authority @ NftError::Unauthorized
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+
+    #[account(
+        mut,
+        has_one = asset @ NftError::InvalidAsset,
+        seeds = [b"forced_recall", asset.key().as_ref()],
+        bump = forced_recall.bump
+    )]
+    pub forced_recall: Account<'info, ForcedRecall>,
+
+    /// CHECK: Handled by mpl-core
+    #[account(mut)]
+    pub asset: AccountInfo<'info>,
+
+    /// CHECK: Handled by mpl-core
+    #[account(mut)]
+    pub collection: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        has_one = asset @ NftError::NftStateAssetMismatch,
+        seeds = [b"nft_state", asset.key().as_ref()],
+        bump = nft_state.bump,
+        realloc = 8 + NftState::INIT_SPACE,
+        realloc::payer = authority,
+        realloc::zero = false
+    )]
+    pub nft_state: Account<'info, NftState>,
+
+    #[account(mut, seeds = [b"asset_history", asset.key().as_ref()], bump = asset_history.bump)]
+    pub asset_history: Account<'info, AssetHistory>,
+
+    /// CHECK: Verified against the asset's current owner
+    #[account(mut)]
+    pub holder: AccountInfo<'info>,
+
+    #[account(mut)]
+    pub voucher_asset: Signer<'info>,
+
+    #[account(mut, seeds = [b"treasury"], bump = program_config.treasury_bump)]
+    pub treasury: SystemAccount<'info>,
+
+    #[account(mut, seeds = [b"economy_ledger"], bump = economy_ledger.bump)]
+    pub economy_ledger: Account<'info, EconomyLedger>,
+
+    /// CHECK: Metaplex Core program
+    #[account(address = mpl_core::ID)]
+    pub mpl_core_program: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// ✂️ Context for Splitting an NFT
+#[derive(Accounts)]
+pub struct SplitNFT<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    /// CHECK: Ownership verified against the mpl-core asset data; burned
+    #[account(mut)]
+    pub asset: AccountInfo<'info>,
+
+    /// CHECK: Membership verified against the asset's update authority
+    #[account(mut)]
+    pub collection: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        close = owner,
+        has_one = asset @ NftError::NftStateAssetMismatch,
+        seeds = [b"nft_state", asset.key().as_ref()],
+        bump = nft_state.bump,
+        realloc = 8 + NftState::INIT_SPACE,
+        realloc::payer = owner,
+        realloc::zero = false
+    )]
+    pub nft_state: Account<'info, NftState>,
+
+    #[account(mut, seeds = [b"asset_history", asset.key().as_ref()], bump = asset_history.bump)]
+    pub asset_history: Account<'info, AssetHistory>,
+
+    #[account(mut)]
+    pub child_asset_1: Signer<'info>,
+
+    #[account(mut)]
+    pub child_asset_2: Signer<'info>,
+
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + NftState::INIT_SPACE,
+        seeds = [b"nft_state", child_asset_1.key().as_ref()],
+        bump
+    )]
+    pub child_state_1: Account<'info, NftState>,
+
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + NftState::INIT_SPACE,
+        seeds = [b"nft_state", child_asset_2.key().as_ref()],
+        bump
+    )]
+    pub child_state_2: Account<'info, NftState>,
+
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + SplitRecord::INIT_SPACE,
+        seeds = [b"split", asset.key().as_ref()],
+        bump
+    )]
+    pub split_record: Account<'info, SplitRecord>,
+
+    #[account(seeds = [b"program_config"], bump = program_config.bump)]
+    pub program_config: Account<'info, ProgramConfig>,
+
+    /// CHECK: Metaplex Core program
+    #[account(address = mpl_core::ID)]
+    pub mpl_core_program: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// 🏛️ Context for Creating a Collection
+#[derive(Accounts)]
+pub struct CreateCollection<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(mut)]
+    pub collection: Signer<'info>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + CollectionConfig::INIT_SPACE,
+        seeds = [b"collection_config", collection.key().as_ref()],
+        bump
+    )]
+    pub collection_config: Account<'info, CollectionConfig>,
+
+    /// CHECK: Metaplex Core program
+    #[account(address = mpl_core::ID)]
+    pub mpl_core_program: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// ✍️ Context for Handing a Collection to its Program Authority PDA
+#[derive(Accounts)]
+pub struct AssignProgramAuthority<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        has_one = authority @ NftError::Unauthorized,
+        has_one = collection @ NftError::CollectionMismatch,
+        seeds = [b"collection_config", collection.key().as_ref()],
+        bump = collection_config.bump
+    )]
+    pub collection_config: Account<'info, CollectionConfig>,
+
+    /// CHECK: Handled by mpl-core
+    #[account(mut)]
+    pub collection: AccountInfo<'info>,
+
+    /// CHECK: PDA only; becomes the collection's update authority
+    #[account(seeds = [b"collection_authority", collection.key().as_ref()], bump)]
+    pub collection_authority: AccountInfo<'info>,
+
+    /// CHECK: Metaplex Core program
+    #[account(address = mpl_core::ID)]
+    pub mpl_core_program: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// 🏛️ Context for Updating a CollectionConfig
+#[derive(Accounts)]
+pub struct UpdateCollectionConfig<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        has_one = authority @ NftError::Unauthorized,
+        seeds = [b"collection_config", collection_config.collection.as_ref()],
+        bump = collection_config.bump
+    )]
+    pub collection_config: Account<'info, CollectionConfig>,
+}
+
+/// 🎓 Context for Transferring Experience Between Assets
+#[derive(Accounts)]
+pub struct TransferXp<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    /// CHECK: Ownership verified against the mpl-core asset data
+    #[account(mut)]
+    pub from_asset: AccountInfo<'info>,
+
+    /// CHECK: Ownership verified against the mpl-core asset data
+    #[account(mut)]
+    pub to_asset: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        constraint = from_nft_state.asset == from_asset.key() @ NftError::NftStateAssetMismatch,
+        seeds = [b"nft_state", from_asset.key().as_ref()],
+        bump = from_nft_state.bump,
+        realloc = 8 + NftState::INIT_SPACE,
+        realloc::payer = owner,
+        realloc::zero = false
+    )]
+    pub from_nft_state: Account<'info, NftState>,
+
+    #[account(
+        mut,
+        constraint = to_nft_state.asset == to_asset.key() @ NftError::NftStateAssetMismatch,
+        seeds = [b"nft_state", to_asset.key().as_ref()],
+        bump = to_nft_state.bump,
+        realloc = 8 + NftState::INIT_SPACE,
+        realloc::payer = owner,
+        realloc::zero = false
+    )]
+    pub to_nft_state: Account<'info, NftState>,
+
+    /// CHECK: Membership verified against both assets' update authority
+    #[account(mut)]
+    pub collection: AccountInfo<'info>,
+
+    #[account(seeds = [b"program_config"], bump = program_config.bump)]
+    pub program_config: Account<'info, ProgramConfig>,
+
+    /// Credited with prestige for levels past the cap
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = 8 + PlayerProfile::INIT_SPACE,
+        seeds = [b"player_profile", owner.key().as_ref()],
+        bump
+    )]
+    pub player_profile: Account<'info, PlayerProfile>,
+
+    /// CHECK: PDA holding the Attributes plugin of assets minted or migrated
+    /// since attribute writes moved to the program
+    #[account(seeds = [b"attributes_authority"], bump)]
+    pub attributes_authority: AccountInfo<'info>,
+
+    /// CHECK: Metaplex Core program
+    #[account(address = mpl_core::ID)]
+    pub mpl_core_program: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// 🏛️ Context for Capping a Collection's Supply
+#[derive(Accounts)]
+pub struct SetCollectionMaxSupply<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// CHECK: mpl-core collection; update authority checked on first use
+    pub collection: AccountInfo<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + CollectionConfig::INIT_SPACE,
+        seeds = [b"collection_config", collection.key().as_ref()],
+        bump
+    )]
+    pub collection_config: Account<'info, CollectionConfig>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// 🧮 Context for Reconciling a Mint Counter Shard
+#[derive(Accounts)]
+#[instruction(shard_index: u8)]
+pub struct ReconcileCounters<'info> {
+    #[account(mut)]
+    pub cranker: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"collection_config", collection_config.collection.as_ref()],
+        bump = collection_config.bump
+    )]
+    pub collection_config: Account<'info, CollectionConfig>,
+
+    #[account(
+        init_if_needed,
+        payer = cranker,
+        space = 8 + MintCounterShard::INIT_SPACE,
+        seeds = [b"mint_shard", collection_config.collection.as_ref(), &[shard_index]],
+        bump
+    )]
+    pub mint_shard: Account<'info, MintCounterShard>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// 🔍 Context for Auditing an Asset's Attributes
+#[derive(Accounts)]
+pub struct AuditAttributes<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// CHECK: Verified as an mpl-core asset in `collection`
+    #[account(mut)]
+    pub asset: AccountInfo<'info>,
+
+    /// CHECK: Handled by mpl-core
+    #[account(mut)]
+    pub collection: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        constraint = nft_state.asset == asset.key() @ NftError::NftStateAssetMismatch,
+        seeds = [b"nft_state", asset.key().as_ref()],
+        bump = nft_state.bump,
+        realloc = 8 + NftState::INIT_SPACE,
+        realloc::payer = authority,
+        realloc::zero = false
+    )]
+    pub nft_state: Account<'info, NftState>,
+
+    /// CHECK: PDA holding the Attributes plugin of assets minted or migrated
+    /// since attribute writes moved to the program
+    #[account(seeds = [b"attributes_authority"], bump)]
+    pub attributes_authority: AccountInfo<'info>,
+
+    #[account(seeds = [b"program_config"], bump = program_config.bump)]
+    pub program_config: Account<'info, ProgramConfig>,
+
+    /// CHECK: Metaplex Core program
+    #[account(address = mpl_core::ID)]
+    pub mpl_core_program: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// ⚔️ Context for Opening a Guild Objective
+#[derive(Accounts)]
+#[instruction(objective_id: u64)]
+pub struct CreateObjective<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    #[account(
+        init,
+        payer = creator,
+        space = 8 + GuildObjective::INIT_SPACE,
+        seeds = [b"objective", creator.key().as_ref(), objective_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub objective: Account<'info, GuildObjective>,
+
+    #[account(mut, seeds = [b"economy_ledger"], bump = economy_ledger.bump)]
+    pub economy_ledger: Account<'info, EconomyLedger>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// ⚔️ Context for Contributing to a Guild Objective
+#[derive(Accounts)]
+pub struct ContributeToObjective<'info> {
+    #[account(mut)]
+    pub contributor: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"objective", objective.creator.as_ref(), objective.objective_id.to_le_bytes().as_ref()],
+        bump = objective.bump
+    )]
+    pub objective: Account<'info, GuildObjective>,
+
+    /// CHECK: Ownership verified against the mpl-core asset data
+    pub asset: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        constraint = nft_state.asset == asset.key() @ NftError::NftStateAssetMismatch,
+        seeds = [b"nft_state", asset.key().as_ref()],
+        bump = nft_state.bump,
+        realloc = 8 + NftState::INIT_SPACE,
+        realloc::payer = contributor,
+        realloc::zero = false
+    )]
+    pub nft_state: Account<'info, NftState>,
+
+    #[account(
+        init,
+        payer = contributor,
+        space = 8 + ObjectiveContribution::INIT_SPACE,
+        seeds = [b"objective_contribution", objective.key().as_ref(), asset.key().as_ref()],
+        bump
+    )]
+    pub contribution: Account<'info, ObjectiveContribution>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// ⚔️ Context for Settling an Objective Contribution
+#[derive(Accounts)]
+pub struct SettleContribution<'info> {
+    #[account(mut)]
+    pub contributor: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"objective", objective.creator.as_ref(), objective.objective_id.to_le_bytes().as_ref()],
+        bump = objective.bump
+    )]
+    pub objective: Account<'info, GuildObjective>,
+
+    #[account(
+        mut,
+        close = contributor,
+        has_one = contributor @ NftError::Unauthorized,
+        has_one = objective @ NftError::Unauthorized,
+        has_one = asset @ NftError::NftStateAssetMismatch,
+        seeds = [b"objective_contribution", objective.key().as_ref(), asset.key().as_ref()],
+        bump = contribution.bump
+    )]
+    pub contribution: Account<'info, ObjectiveContribution>,
+
+    /// CHECK: Bound to the contribution
+    #[account(mut)]
+    pub asset: AccountInfo<'info>,
+
+    /// CHECK: Handled by mpl-core
+    #[account(mut)]
+    pub collection: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        constraint = nft_state.asset == asset.key() @ NftError::NftStateAssetMismatch,
+        seeds = [b"nft_state", asset.key().as_ref()],
+        bump = nft_state.bump,
+        realloc = 8 + NftState::INIT_SPACE,
+        realloc::payer = contributor,
+        realloc::zero = false
+    )]
+    pub nft_state: Account<'info, NftState>,
+
+    #[account(seeds = [b"program_config"], bump = program_config.bump)]
+    pub program_config: Account<'info, ProgramConfig>,
+
+    #[account(mut, seeds = [b"economy_ledger"], bump = economy_ledger.bump)]
+    pub economy_ledger: Account<'info, EconomyLedger>,
+
+    /// CHECK: PDA holding the Attributes plugin of assets minted or migrated
+    /// since attribute writes moved to the program
+    #[account(seeds = [b"attributes_authority"], bump)]
+    pub attributes_authority: AccountInfo<'info>,
+
+    /// CHECK: Metaplex Core program
+    #[account(address = mpl_core::ID)]
+    pub mpl_core_program: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// ⚔️ Context for Closing a Guild Objective
+#[derive(Accounts)]
+pub struct CloseObjective<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    #[account(
+        mut,
+        close = creator,
+        has_one = creator @ NftError::Unauthorized,
+        seeds = [b"objective", creator.key().as_ref(), objective.objective_id.to_le_bytes().as_ref()],
+        bump = objective.bump
+    )]
+    pub objective: Account<'info, GuildObjective>,
+
+    #[account(mut, seeds = [b"economy_ledger"], bump = economy_ledger.bump)]
+    pub economy_ledger: Account<'info, EconomyLedger>,
+}
+
+/// 💰 Context for Withdrawing a Collection's Mint Proceeds
+#[derive(Accounts)]
+pub struct WithdrawTreasury<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        has_one = authority @ NftError::Unauthorized,
+        seeds = [b"collection_config", collection_config.collection.as_ref()],
+        bump = collection_config.bump
+    )]
+    pub collection_config: Account<'info, CollectionConfig>,
+
+    #[account(mut, seeds = [b"treasury"], bump)]
+    pub treasury: SystemAccount<'info>,
+
+    #[account(mut, seeds = [b"economy_ledger"], bump = economy_ledger.bump)]
+    pub economy_ledger: Account<'info, EconomyLedger>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// 🌳 Context for Appending NftStates to an Epoch's State Root
+#[derive(Accounts)]
+#[instruction(epoch: u64)]
+pub struct BuildStateRoot<'info> {
+    #[account(mut)]
+    pub cranker: Signer<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = cranker,
+        space = 8 + StateRoot::INIT_SPACE,
+        seeds = [b"state_root", epoch.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub state_root: Account<'info, StateRoot>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// 🌳 Context for Finalizing an Epoch's State Root
+#[derive(Accounts)]
+#[instruction(epoch: u64)]
+pub struct FinalizeStateRoot<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"program_config"],
+        bump = program_config.bump,
+        has_one = authority @ NftError::Unauthorized
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"state_root", epoch.to_le_bytes().as_ref()],
+        bump = state_root.bump
+    )]
+    pub state_root: Account<'info, StateRoot>,
+}
+
+/// 🤝 Context for Registering a Market Maker
+#[derive(Accounts)]
+pub struct RegisterMarketMaker<'info> {
+    #[account(mut)]
+    pub wallet: Signer<'info>,
+
+    #[account(
+        init,
+        payer = wallet,
+        space = 8 + MarketMaker::INIT_SPACE,
+        seeds = [b"market_maker", wallet.key().as_ref()],
+        bump
+    )]
+    pub market_maker: Account<'info, MarketMaker>,
+
+    #[account(mut, seeds = [b"economy_ledger"], bump = economy_ledger.bump)]
+    pub economy_ledger: Account<'info, EconomyLedger>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// 🤝 Context for Settling a Market Maker's Accrued Fees
+#[derive(Accounts)]
+pub struct SettleMarketMaker<'info> {
+    #[account(mut)]
+    pub wallet: Signer<'info>,
+
+    #[account(
+        mut,
+        has_one = wallet @ NftError::Unauthorized,
+        seeds = [b"market_maker", wallet.key().as_ref()],
+        bump = market_maker.bump
+    )]
+    pub market_maker: Account<'info, MarketMaker>,
+
+    #[account(mut, seeds = [b"treasury"], bump)]
+    pub treasury: SystemAccount<'info>,
+
+    #[account(mut, seeds = [b"economy_ledger"], bump = economy_ledger.bump)]
+    pub economy_ledger: Account<'info, EconomyLedger>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// 🚨 Context for Claiming a Market Maker Default
+#[derive(Accounts)]
+pub struct ClaimMarketMakerDefault<'info> {
+    pub cranker: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"market_maker", market_maker.wallet.as_ref()],
+        bump = market_maker.bump
+    )]
+    pub market_maker: Account<'info, MarketMaker>,
+
+    #[account(mut, seeds = [b"treasury"], bump)]
+    pub treasury: SystemAccount<'info>,
+
+    #[account(mut, seeds = [b"economy_ledger"], bump = economy_ledger.bump)]
+    pub economy_ledger: Account<'info, EconomyLedger>,
+}
+
+/// 🤝 Context for Deregistering a Market Maker
+#[derive(Accounts)]
+pub struct CloseMarketMaker<'info> {
+    #[account(mut)]
+    pub wallet: Signer<'info>,
+
+    #[account(
+        mut,
+        close = wallet,
+        has_one = wallet @ NftError::Unauthorized,
+        seeds = [b"market_maker", wallet.key().as_ref()],
+        bump = market_maker.bump
+    )]
+    pub market_maker: Account<'info, MarketMaker>,
+
+    #[account(mut, seeds = [b"economy_ledger"], bump = economy_ledger.bump)]
+    pub economy_ledger: Account<'info, EconomyLedger>,
+}
+
+/// 🪪 Context for Writing a Verified Owner Handle; the SNS name record
+/// follows as the first remaining account
+#[derive(Accounts)]
+pub struct SetOwnerHandle<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    /// CHECK: Ownership verified against the mpl-core asset data
+    #[account(mut)]
+    pub asset: AccountInfo<'info>,
+
+    /// CHECK: Handled by mpl-core
+    #[account(mut)]
+    pub collection: AccountInfo<'info>,
+
+    /// CHECK: This asset's NftState PDA; may be uninitialized (never stamped)
+    #[account(mut, seeds = [b"nft_state", asset.key().as_ref()], bump)]
+    pub nft_state: AccountInfo<'info>,
+
+    #[account(seeds = [b"program_config"], bump = program_config.bump)]
+    pub program_config: Account<'info, ProgramConfig>,
+
+    /// CHECK: PDA holding the Attributes plugin of assets minted or migrated
+    /// since attribute writes moved to the program
+    #[account(seeds = [b"attributes_authority"], bump)]
+    pub attributes_authority: AccountInfo<'info>,
+
+    /// CHECK: Metaplex Core program
+    #[account(address = mpl_core::ID)]
+    pub mpl_core_program: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// 🔢 Context for Numbering a Collection's Editions
+#[derive(Accounts)]
+pub struct EnableEditions<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        has_one = authority @ NftError::Unauthorized,
+        seeds = [b"collection_config", collection_config.collection.as_ref()],
+        bump = collection_config.bump
+    )]
+    pub collection_config: Account<'info, CollectionConfig>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + EditionCounter::INIT_SPACE,
+        seeds = [b"edition", collection_config.collection.as_ref()],
+        bump
+    )]
+    pub edition_counter: Account<'info, EditionCounter>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// 🩺 Context for Validating a Collection's Configuration
+#[derive(Accounts)]
+pub struct ValidateConfig<'info> {
+    #[account(seeds = [b"program_config"], bump = program_config.bump)]
+    pub program_config: Account<'info, ProgramConfig>,
+
+    #[account(
+        seeds = [b"collection_config", collection_config.collection.as_ref()],
+        bump = collection_config.bump
+    )]
+    pub collection_config: Account<'info, CollectionConfig>,
+
+    /// CHECK: The ShopConfig PDA; may be uninitialized (no shop)
+    #[account(seeds = [b"shop"], bump)]
+    pub shop_config: AccountInfo<'info>,
+
+    /// CHECK: This collection's EditionCounter PDA; may be uninitialized
+    #[account(seeds = [b"edition", collection_config.collection.as_ref()], bump)]
+    pub edition_counter: AccountInfo<'info>,
+}
+
+/// 🛡️ Context for Configuring a Collection's Evolution Insurance
+#[derive(Accounts)]
+pub struct ConfigureInsurance<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        has_one = authority @ NftError::Unauthorized,
+        seeds = [b"collection_config", collection_config.collection.as_ref()],
+        bump = collection_config.bump
+    )]
+    pub collection_config: Account<'info, CollectionConfig>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + InsurancePool::INIT_SPACE,
+        seeds = [b"insurance_pool", collection_config.collection.as_ref()],
+        bump
+    )]
+    pub insurance_pool: Account<'info, InsurancePool>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// 🛡️ Context for Insuring a Queued Evolution
+#[derive(Accounts)]
+pub struct InsureEvolution<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    /// CHECK: Ownership verified against the mpl-core asset data
+    pub asset: AccountInfo<'info>,
+
+    #[account(
+        has_one = asset @ NftError::NftStateAssetMismatch,
+        seeds = [b"nft_state", asset.key().as_ref()],
+        bump = nft_state.bump
+    )]
+    pub nft_state: Account<'info, NftState>,
+
+    #[account(
+        mut,
+        seeds = [b"insurance_pool", nft_state.collection.as_ref()],
+        bump = insurance_pool.bump
+    )]
+    pub insurance_pool: Account<'info, InsurancePool>,
+
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + Insurance::INIT_SPACE,
+        seeds = [b"insurance", asset.key().as_ref()],
+        bump
+    )]
+    pub insurance: Account<'info, Insurance>,
+
+    #[account(mut, seeds = [b"economy_ledger"], bump = economy_ledger.bump)]
+    pub economy_ledger: Account<'info, EconomyLedger>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// 🛡️ Context for Settling Evolution Insurance
+#[derive(Accounts)]
+pub struct SettleInsurance<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"nft_state", insurance.asset.as_ref()],
+        bump = nft_state.bump
+    )]
+    pub nft_state: Account<'info, NftState>,
+
+    #[account(
+        mut,
+        close = owner,
+        has_one = owner @ NftError::Unauthorized,
+        seeds = [b"insurance", insurance.asset.as_ref()],
+        bump = insurance.bump
+    )]
+    pub insurance: Account<'info, Insurance>,
+
+    #[account(
+        mut,
+        seeds = [b"insurance_pool", nft_state.collection.as_ref()],
+        bump = insurance_pool.bump
+    )]
+    pub insurance_pool: Account<'info, InsurancePool>,
+
+    #[account(mut, seeds = [b"economy_ledger"], bump = economy_ledger.bump)]
+    pub economy_ledger: Account<'info, EconomyLedger>,
+}
+
+/// 👥 Context for Minting from a Collection's Team Allocation
+#[derive(Accounts)]
+pub struct MintReserved<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        has_one = authority @ NftError::Unauthorized,
+        has_one = collection @ NftError::CollectionMismatch,
+        seeds = [b"collection_config", collection.key().as_ref()],
+        bump = collection_config.bump
+    )]
+    pub collection_config: Account<'info, CollectionConfig>,
+
+    /// CHECK: Handled by mpl-core
+    #[account(mut)]
+    pub asset: AccountInfo<'info>,
+
+    /// CHECK: Handled by mpl-core
+    #[account(mut)]
+    pub collection: AccountInfo<'info>,
+
+    /// CHECK: This collection's program authority PDA; signs the mint if it is
+    /// the collection's update authority
+    #[account(seeds = [b"collection_authority", collection.key().as_ref()], bump)]
+    pub collection_authority: AccountInfo<'info>,
+
+    /// This collection's EditionCounter; required once editions are numbered
+    #[account(mut, seeds = [b"edition", collection.key().as_ref()], bump = edition_counter.bump)]
+    pub edition_counter: Option<Account<'info, EditionCounter>>,
+
+    /// CHECK: This collection's AchievementTiers PDA; may be uninitialized (default ladder)
+    #[account(seeds = [b"achievement_tiers", collection.key().as_ref()], bump)]
+    pub achievement_tiers: AccountInfo<'info>,
+
+    #[account(seeds = [b"program_config"], bump = program_config.bump)]
+    pub program_config: Account<'info, ProgramConfig>,
+
+    /// CHECK: Instructions sysvar, address-constrained
+    #[account(address = sysvar::instructions::ID)]
+    pub instructions_sysvar: AccountInfo<'info>,
+
+    /// CHECK: Metaplex Core program
+    #[account(address = mpl_core::ID)]
+    pub mpl_core_program: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// 🪓 Context for Bonding a Cranker
+#[derive(Accounts)]
+pub struct RegisterCranker<'info> {
+    #[account(mut)]
+    pub wallet: Signer<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = wallet,
+        space = 8 + Cranker::INIT_SPACE,
+        seeds = [b"cranker", wallet.key().as_ref()],
+        bump
+    )]
+    pub cranker: Account<'info, Cranker>,
+
+    #[account(mut, seeds = [b"economy_ledger"], bump = economy_ledger.bump)]
+    pub economy_ledger: Account<'info, EconomyLedger>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// 🪓 Context for Deregistering a Cranker
+#[derive(Accounts)]
+pub struct CloseCranker<'info> {
+    #[account(mut)]
+    pub wallet: Signer<'info>,
+
+    #[account(
+        mut,
+        close = wallet,
+        has_one = wallet @ NftError::Unauthorized,
+        seeds = [b"cranker", wallet.key().as_ref()],
+        bump = cranker.bump
+    )]
+    pub cranker: Account<'info, Cranker>,
+
+    #[account(mut, seeds = [b"economy_ledger"], bump = economy_ledger.bump)]
+    pub economy_ledger: Account<'info, EconomyLedger>,
+}
+
+/// 🏆 Context for Naming a Collection's Achievement Ladder
+#[derive(Accounts)]
+pub struct SetAchievementTiers<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        has_one = authority @ NftError::Unauthorized,
+        seeds = [b"collection_config", collection_config.collection.as_ref()],
+        bump = collection_config.bump
+    )]
+    pub collection_config: Account<'info, CollectionConfig>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + AchievementTiers::INIT_SPACE,
+        seeds = [b"achievement_tiers", collection_config.collection.as_ref()],
+        bump
+    )]
+    pub achievement_tiers: Account<'info, AchievementTiers>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// 🧪 Context for Sunsetting a Collection's Beta Assets
+#[derive(Accounts)]
+pub struct SunsetBetaAssets<'info> {
+    /// Any wallet; pays for attribute reallocs and any missing player profiles
+    #[account(mut)]
     pub payer: Signer<'info>,
+
+    #[account(
+        mut,
+        has_one = collection @ NftError::CollectionMismatch,
+        seeds = [b"collection_config", collection.key().as_ref()],
+        bump = collection_config.bump
+    )]
+    pub collection_config: Account<'info, CollectionConfig>,
+
+    /// CHECK: Handled by mpl-core
+    #[account(mut)]
+    pub collection: AccountInfo<'info>,
+
+    /// CHECK: PDA only; must be the collection's update authority
+    #[account(seeds = [b"collection_authority", collection.key().as_ref()], bump)]
+    pub collection_authority: AccountInfo<'info>,
+
+    #[account(seeds = [b"program_config"], bump = program_config.bump)]
+    pub program_config: Account<'info, ProgramConfig>,
+
+    /// CHECK: SlotHashes sysvar, address-constrained and parsed manually
+    #[account(address = sysvar::slot_hashes::ID)]
+    pub slot_hashes: AccountInfo<'info>,
+
+    /// CHECK: PDA holding the Attributes plugin of assets minted or migrated
+    /// since attribute writes moved to the program
+    #[account(seeds = [b"attributes_authority"], bump)]
+    pub attributes_authority: AccountInfo<'info>,
+
+    /// CHECK: Metaplex Core program
+    #[account(address = mpl_core::ID)]
+    pub mpl_core_program: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// ✅ Context for a Daily Check-In
+#[derive(Accounts)]
+pub struct CheckIn<'info> {
+    #[account(mut)]
+    pub wallet: Signer<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = wallet,
+        space = 8 + PlayerProfile::INIT_SPACE,
+        seeds = [b"player_profile", wallet.key().as_ref()],
+        bump
+    )]
+    pub player_profile: Account<'info, PlayerProfile>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// 💤 Context for Claiming Idle XP on a Staked Asset
+#[derive(Accounts)]
+pub struct ClaimIdleXp<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    /// CHECK: Bound to the stake record
+    #[account(mut, address = stake_record.asset)]
+    pub asset: AccountInfo<'info>,
+
+    /// CHECK: Handled by mpl-core
+    #[account(mut)]
+    pub collection: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        has_one = owner @ NftError::Unauthorized,
+        seeds = [b"stake", stake_record.asset.as_ref()],
+        bump = stake_record.bump
+    )]
+    pub stake_record: Account<'info, StakeRecord>,
+
+    #[account(
+        mut,
+        constraint = nft_state.asset == asset.key() @ NftError::NftStateAssetMismatch,
+        seeds = [b"nft_state", asset.key().as_ref()],
+        bump = nft_state.bump,
+        realloc = 8 + NftState::INIT_SPACE,
+        realloc::payer = owner,
+        realloc::zero = false
+    )]
+    pub nft_state: Account<'info, NftState>,
+
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = 8 + PlayerProfile::INIT_SPACE,
+        seeds = [b"player_profile", owner.key().as_ref()],
+        bump
+    )]
+    pub player_profile: Account<'info, PlayerProfile>,
+
+    /// CHECK: PDA holding the Attributes plugin of assets minted or migrated
+    /// since attribute writes moved to the program
+    #[account(seeds = [b"attributes_authority"], bump)]
+    pub attributes_authority: AccountInfo<'info>,
+
+    #[account(seeds = [b"program_config"], bump = program_config.bump)]
+    pub program_config: Account<'info, ProgramConfig>,
+
+    /// CHECK: Metaplex Core program
+    #[account(address = mpl_core::ID)]
+    pub mpl_core_program: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// 📨 Context for Minting into Custody
+#[derive(Accounts)]
+#[instruction(name: String, uri: String, level: u64, rarity: String, fusion_potential: u64, claim_key: Pubkey)]
+pub struct MintCustodial<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        has_one = authority @ NftError::Unauthorized,
+        has_one = collection @ NftError::CollectionMismatch,
+        seeds = [b"collection_config", collection.key().as_ref()],
+        bump = collection_config.bump
+    )]
+    pub collection_config: Account<'info, CollectionConfig>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + PendingClaim::INIT_SPACE,
+        seeds = [b"pending_claim", claim_key.as_ref()],
+        bump
+    )]
+    pub pending_claim: Account<'info, PendingClaim>,
+
+    /// CHECK: Handled by mpl-core
+    #[account(mut)]
+    pub asset: AccountInfo<'info>,
+
+    /// CHECK: Handled by mpl-core
+    #[account(mut)]
+    pub collection: AccountInfo<'info>,
+
+    /// CHECK: This collection's program authority PDA; signs the mint if it is
+    /// the collection's update authority
+    #[account(seeds = [b"collection_authority", collection.key().as_ref()], bump)]
+    pub collection_authority: AccountInfo<'info>,
+
+    /// This collection's EditionCounter; required once editions are numbered
+    #[account(mut, seeds = [b"edition", collection.key().as_ref()], bump = edition_counter.bump)]
+    pub edition_counter: Option<Account<'info, EditionCounter>>,
+
+    /// CHECK: This collection's AchievementTiers PDA; may be uninitialized (default ladder)
+    #[account(seeds = [b"achievement_tiers", collection.key().as_ref()], bump)]
+    pub achievement_tiers: AccountInfo<'info>,
+
+    #[account(seeds = [b"program_config"], bump = program_config.bump)]
+    pub program_config: Account<'info, ProgramConfig>,
+
+    /// CHECK: Instructions sysvar, address-constrained
+    #[account(address = sysvar::instructions::ID)]
+    pub instructions_sysvar: AccountInfo<'info>,
+
+    /// CHECK: Metaplex Core program
+    #[account(address = mpl_core::ID)]
+    pub mpl_core_program: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// 📨 Context for Claiming a Custodial Asset
+#[derive(Accounts)]
+pub struct ClaimCustodialAsset<'info> {
+    /// Pays the transfer; may be a relayer rather than the buyer
+    #[account(mut)]
+    pub claimer: Signer<'info>,
+
+    /// CHECK: Receives the asset; bound by the claim key's signature
+    pub destination: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        close = partner,
+        seeds = [b"pending_claim", pending_claim.claim_key.as_ref()],
+        bump = pending_claim.bump
+    )]
+    pub pending_claim: Account<'info, PendingClaim>,
+
+    /// CHECK: Receives the PendingClaim's rent
+    #[account(mut, address = pending_claim.partner)]
+    pub partner: AccountInfo<'info>,
+
+    /// CHECK: Bound to the pending claim
+    #[account(mut, address = pending_claim.asset)]
+    pub asset: AccountInfo<'info>,
+
+    /// CHECK: Bound to the pending claim
+    #[account(mut, address = pending_claim.collection)]
+    pub collection: AccountInfo<'info>,
+
+    /// CHECK: Instructions sysvar, address-constrained
+    #[account(address = sysvar::instructions::ID)]
+    pub instructions_sysvar: AccountInfo<'info>,
+
+    /// CHECK: Metaplex Core program
+    #[account(address = mpl_core::ID)]
+    pub mpl_core_program: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// 📉 Context for Creating the Floor Oracle
+#[derive(Accounts)]
+pub struct InitializeFloorOracle<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"program_config"],
+        bump = program_config.bump,
+        has_one = authority @ NftError::Unauthorized
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + FloorOracle::INIT_SPACE,
+        seeds = [b"floor_oracle"],
+        bump
+    )]
+    pub floor_oracle: Account<'info, FloorOracle>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// 📉 Context for Reading the Floor Oracle
+#[derive(Accounts)]
+pub struct ReadFloorOracle<'info> {
+    #[account(seeds = [b"floor_oracle"], bump = floor_oracle.bump)]
+    pub floor_oracle: Account<'info, FloorOracle>,
+}
+
+/// 🔐 Context for Refunding an Escrowed Mint
+#[derive(Accounts)]
+pub struct RefundMint<'info> {
+    #[account(mut)]
+    pub minter: Signer<'info>,
+
+    #[account(
+        mut,
+        has_one = collection @ NftError::CollectionMismatch,
+        seeds = [b"collection_config", collection.key().as_ref()],
+        bump = collection_config.bump
+    )]
+    pub collection_config: Account<'info, CollectionConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"receipt", collection.key().as_ref(), minter.key().as_ref()],
+        bump = mint_receipt.bump
+    )]
+    pub mint_receipt: Account<'info, MintReceipt>,
+
+    /// CHECK: Ownership and membership verified against the mpl-core asset data
+    #[account(mut)]
+    pub asset: AccountInfo<'info>,
+
+    /// CHECK: Handled by mpl-core
+    #[account(mut)]
+    pub collection: AccountInfo<'info>,
+
+    #[account(mut, seeds = [b"treasury"], bump)]
+    pub treasury: SystemAccount<'info>,
+
+    #[account(mut, seeds = [b"economy_ledger"], bump = economy_ledger.bump)]
+    pub economy_ledger: Account<'info, EconomyLedger>,
+
+    /// CHECK: Metaplex Core program
+    #[account(address = mpl_core::ID)]
+    pub mpl_core_program: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// 🧊 Context for Freezing an Asset
+#[derive(Accounts)]
+pub struct FreezeAsset<'info> {
+    /// The asset's owner, or the program admin
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// CHECK: Handled by mpl-core
+    #[account(mut)]
+    pub asset: AccountInfo<'info>,
+
+    /// CHECK: Handled by mpl-core
+    #[account(mut)]
+    pub collection: AccountInfo<'info>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + FreezeRecord::INIT_SPACE,
+        seeds = [b"freeze_record", asset.key().as_ref()],
+        bump
+    )]
+    pub freeze_record: Account<'info, FreezeRecord>,
+
+    #[account(seeds = [b"program_config"], bump = program_config.bump)]
+    pub program_config: Account<'info, ProgramConfig>,
+
+    /// CHECK: PDA holding freeze authority over program-frozen assets
+    #[account(seeds = [b"stake_authority"], bump)]
+    pub stake_authority: AccountInfo<'info>,
+
+    /// CHECK: Metaplex Core program
+    #[account(address = mpl_core::ID)]
+    pub mpl_core_program: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// 🌡️ Context for Thawing an Asset
+#[derive(Accounts)]
+pub struct ThawAsset<'info> {
+    /// Whoever froze the asset, or the program admin
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// CHECK: Bound to the freeze record
+    #[account(mut, address = freeze_record.asset)]
+    pub asset: AccountInfo<'info>,
+
+    /// CHECK: Handled by mpl-core
+    #[account(mut)]
+    pub collection: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        close = frozen_by,
+        seeds = [b"freeze_record", asset.key().as_ref()],
+        bump = freeze_record.bump
+    )]
+    pub freeze_record: Account<'info, FreezeRecord>,
+
+    /// CHECK: Receives the FreezeRecord's rent
+    #[account(mut, address = freeze_record.frozen_by)]
+    pub frozen_by: AccountInfo<'info>,
+
+    #[account(seeds = [b"program_config"], bump = program_config.bump)]
+    pub program_config: Account<'info, ProgramConfig>,
+
+    /// CHECK: PDA holding freeze authority over program-frozen assets
+    #[account(seeds = [b"stake_authority"], bump)]
+    pub stake_authority: AccountInfo<'info>,
+
+    /// CHECK: Metaplex Core program
+    #[account(address = mpl_core::ID)]
+    pub mpl_core_program: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// 🌱 Context for Creating the Staking Reward Pool
+#[derive(Accounts)]
+pub struct InitializeStakingRewardPool<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"program_config"],
+        bump = program_config.bump,
+        has_one = authority @ NftError::Unauthorized
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + StakingRewardPool::INIT_SPACE,
+        seeds = [b"staking_reward_pool"],
+        bump
+    )]
+    pub staking_reward_pool: Account<'info, StakingRewardPool>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// 🌱 Context for Rebalancing a Collection's Treasury Share
+#[derive(Accounts)]
+pub struct RebalanceTreasury<'info> {
+    pub cranker: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"collection_config", collection_config.collection.as_ref()],
+        bump = collection_config.bump
+    )]
+    pub collection_config: Account<'info, CollectionConfig>,
+
+    #[account(mut, seeds = [b"staking_reward_pool"], bump = staking_reward_pool.bump)]
+    pub staking_reward_pool: Account<'info, StakingRewardPool>,
+
+    #[account(seeds = [b"program_config"], bump = program_config.bump)]
+    pub program_config: Account<'info, ProgramConfig>,
+
+    #[account(mut, seeds = [b"treasury"], bump = program_config.treasury_bump)]
+    pub treasury: SystemAccount<'info>,
+
+    #[account(mut, seeds = [b"economy_ledger"], bump = economy_ledger.bump)]
+    pub economy_ledger: Account<'info, EconomyLedger>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// 🤝 Context for Setting or Revoking the Program's Transfer Delegation
+#[derive(Accounts)]
+pub struct SetTransferDelegate<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    /// CHECK: Ownership checked in the handler
+    #[account(mut)]
+    pub asset: AccountInfo<'info>,
+
+    /// CHECK: Handled by mpl-core
+    #[account(mut)]
+    pub collection: AccountInfo<'info>,
+
+    /// CHECK: PDA that program_transfer signs with
+    #[account(seeds = [b"transfer_authority"], bump)]
+    pub transfer_authority: AccountInfo<'info>,
+
+    /// CHECK: Metaplex Core program
+    #[account(address = mpl_core::ID)]
+    pub mpl_core_program: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// ✍️ Context for Approving or Revoking an Asset's Update Delegate
+#[derive(Accounts)]
+pub struct SetUpdateDelegate<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    /// CHECK: Ownership checked in the handler
+    #[account(mut)]
+    pub asset: AccountInfo<'info>,
+
+    /// CHECK: Handled by mpl-core
+    #[account(mut)]
+    pub collection: AccountInfo<'info>,
+
+    /// CHECK: This collection's program authority PDA; must be the
+    /// collection's update authority, as it manages the plugin
+    #[account(seeds = [b"collection_authority", collection.key().as_ref()], bump)]
+    pub collection_authority: AccountInfo<'info>,
+
+    /// CHECK: Metaplex Core program
+    #[account(address = mpl_core::ID)]
+    pub mpl_core_program: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// 🚚 Context for a Program-Controlled Transfer
+#[derive(Accounts)]
+pub struct ProgramTransfer<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"program_config"],
+        bump = program_config.bump,
+        has_one = authority @ NftError::Unauthorized
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+
+    /// CHECK: Handled by mpl-core; must carry the program's TransferDelegate
+    #[account(mut)]
+    pub asset: AccountInfo<'info>,
+
+    /// CHECK: Handled by mpl-core
+    #[account(mut)]
+    pub collection: AccountInfo<'info>,
+
+    /// CHECK: Any wallet or PDA may receive the asset
+    pub new_owner: AccountInfo<'info>,
+
+    /// CHECK: PDA holding the asset's TransferDelegate
+    #[account(seeds = [b"transfer_authority"], bump)]
+    pub transfer_authority: AccountInfo<'info>,
+
+    /// CHECK: Metaplex Core program
+    #[account(address = mpl_core::ID)]
+    pub mpl_core_program: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// 🔥 Context for Burning an NFT
+#[derive(Accounts)]
+pub struct BurnNFT<'info> {
+    /// The asset's owner, or the holder of its BurnDelegate
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// CHECK: Must be the asset's owner; receives the NftState's rent
+    #[account(mut)]
+    pub owner: AccountInfo<'info>,
+
+    /// CHECK: Handled by mpl-core
+    #[account(mut)]
+    pub asset: AccountInfo<'info>,
+
+    /// CHECK: Handled by mpl-core
+    #[account(mut)]
+    pub collection: AccountInfo<'info>,
+
+    /// CHECK: The asset's NftState PDA; may be uninitialized (never evolved)
+    #[account(mut, seeds = [b"nft_state", asset.key().as_ref()], bump)]
+    pub nft_state: AccountInfo<'info>,
+
+    #[account(mut, seeds = [b"asset_history", asset.key().as_ref()], bump = asset_history.bump)]
+    pub asset_history: Account<'info, AssetHistory>,
+
+    /// CHECK: This collection's CollectionConfig PDA; may be uninitialized (created externally)
+    #[account(mut, seeds = [b"collection_config", collection.key().as_ref()], bump)]
+    pub collection_config: AccountInfo<'info>,
+
+    /// CHECK: Metaplex Core program
+    #[account(address = mpl_core::ID)]
+    pub mpl_core_program: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// 🤝 Context for Creating an OTC Deal
+#[derive(Accounts)]
+#[instruction(deal_id: u64)]
+pub struct CreateDeal<'info> {
+    #[account(mut)]
+    pub seller: Signer<'info>,
+
+    #[account(
+        init,
+        payer = seller,
+        space = 8 + Deal::INIT_SPACE,
+        seeds = [b"deal", seller.key().as_ref(), &deal_id.to_le_bytes()],
+        bump
+    )]
+    pub deal: Account<'info, Deal>,
+
+    #[account(seeds = [b"program_config"], bump = program_config.bump)]
+    pub program_config: Account<'info, ProgramConfig>,
+
+    /// CHECK: Metaplex Core program
+    #[account(address = mpl_core::ID)]
+    pub mpl_core_program: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// ✅ Context for Accepting an OTC Deal
+#[derive(Accounts)]
+pub struct AcceptDeal<'info> {
+    #[account(mut)]
+    pub buyer: Signer<'info>,
+
+    /// CHECK: Bound to the deal; receives the ask and the deal's rent
+    #[account(mut, address = deal.seller)]
+    pub seller: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        close = seller,
+        seeds = [b"deal", deal.seller.as_ref(), &deal.deal_id.to_le_bytes()],
+        bump = deal.bump
+    )]
+    pub deal: Account<'info, Deal>,
+
+    /// The buyer's account for the asked token; required when the ask has tokens
+    #[account(mut)]
+    pub buyer_token_account: Option<Account<'info, TokenAccount>>,
+
+    /// The seller's account for the asked token; required when the ask has tokens
+    #[account(mut)]
+    pub seller_token_account: Option<Account<'info, TokenAccount>>,
+
+    #[account(seeds = [b"program_config"], bump = program_config.bump)]
+    pub program_config: Account<'info, ProgramConfig>,
+
+    /// CHECK: Metaplex Core program
+    #[account(address = mpl_core::ID)]
+    pub mpl_core_program: AccountInfo<'info>,
+
+    pub token_program: Program<'info, Token>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// ↩️ Context for Cancelling an OTC Deal
+#[derive(Accounts)]
+pub struct CancelDeal<'info> {
+    /// The seller, or the deal's named counterparty
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// CHECK: Bound to the deal; gets the bundle and the deal's rent back
+    #[account(mut, address = deal.seller)]
+    pub seller: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        close = seller,
+        seeds = [b"deal", deal.seller.as_ref(), &deal.deal_id.to_le_bytes()],
+        bump = deal.bump
+    )]
+    pub deal: Account<'info, Deal>,
+
+    /// CHECK: Metaplex Core program
+    #[account(address = mpl_core::ID)]
+    pub mpl_core_program: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// ✍️ Context for Migrating Assets' Attributes Authority
+#[derive(Accounts)]
+pub struct MigrateAttributesAuthority<'info> {
+    /// The collection's update authority, unless it was handed to the program
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// CHECK: Handled by mpl-core
+    #[account(mut)]
+    pub collection: AccountInfo<'info>,
+
+    /// CHECK: This collection's program authority PDA; signs the migration if
+    /// it is the collection's update authority
+    #[account(seeds = [b"collection_authority", collection.key().as_ref()], bump)]
+    pub collection_authority: AccountInfo<'info>,
+
+    /// CHECK: PDA the Attributes plugins are handed to
+    #[account(seeds = [b"attributes_authority"], bump)]
+    pub attributes_authority: AccountInfo<'info>,
+
+    /// CHECK: Metaplex Core program
+    #[account(address = mpl_core::ID)]
+    pub mpl_core_program: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// 🧬 Context for Setting the Generation Map
+#[derive(Accounts)]
+pub struct SetGenerationMap<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"program_config"],
+        bump = program_config.bump,
+        has_one = authority @ NftError::Unauthorized
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + GenerationMap::INIT_SPACE,
+        seeds = [b"generation_map"],
+        bump
+    )]
+    pub generation_map: Account<'info, GenerationMap>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// 🏛️ Context for Adding or Updating a Collection-Level Plugin
+#[derive(Accounts)]
+pub struct ManageCollectionPlugin<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        has_one = authority @ NftError::Unauthorized,
+        has_one = collection @ NftError::CollectionMismatch,
+        seeds = [b"collection_config", collection.key().as_ref()],
+        bump = collection_config.bump
+    )]
+    pub collection_config: Account<'info, CollectionConfig>,
+
+    /// CHECK: Handled by mpl-core
+    #[account(mut)]
+    pub collection: AccountInfo<'info>,
+
+    /// CHECK: This collection's program authority PDA; signs if it is the
+    /// collection's update authority
+    #[account(seeds = [b"collection_authority", collection.key().as_ref()], bump)]
+    pub collection_authority: AccountInfo<'info>,
+
+    /// CHECK: Metaplex Core program
+    #[account(address = mpl_core::ID)]
+    pub mpl_core_program: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// 🗳️ Context for Opening a Holder Poll
+#[derive(Accounts)]
+#[instruction(poll_id: u64)]
+pub struct CreatePoll<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        has_one = authority @ NftError::Unauthorized,
+        seeds = [b"collection_config", collection_config.collection.as_ref()],
+        bump = collection_config.bump
+    )]
+    pub collection_config: Account<'info, CollectionConfig>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + Poll::INIT_SPACE,
+        seeds = [b"poll", collection_config.collection.as_ref(), &poll_id.to_le_bytes()],
+        bump
+    )]
+    pub poll: Account<'info, Poll>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// 🗳️ Context for Voting in a Holder Poll
+#[derive(Accounts)]
+pub struct Vote<'info> {
+    /// Owner of the voting assets; pays for their PollVote PDAs
+    #[account(mut)]
+    pub voter: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"poll", poll.collection.as_ref(), &poll.poll_id.to_le_bytes()],
+        bump = poll.bump
+    )]
+    pub poll: Account<'info, Poll>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// 🏁 Context for Applying a Holder Poll's Result
+#[derive(Accounts)]
+pub struct ApplyPollResult<'info> {
+    #[account(
+        mut,
+        seeds = [b"poll", poll.collection.as_ref(), &poll.poll_id.to_le_bytes()],
+        bump = poll.bump
+    )]
+    pub poll: Account<'info, Poll>,
+
+    #[account(
+        mut,
+        seeds = [b"collection_config", poll.collection.as_ref()],
+        bump = collection_config.bump
+    )]
+    pub collection_config: Account<'info, CollectionConfig>,
+}
+
+/// 🛑 Context for Cancelling a Stalled Tournament
+#[derive(Accounts)]
+pub struct CancelTournament<'info> {
+    pub caller: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"tournament", tournament.tournament_id.to_le_bytes().as_ref()],
+        bump = tournament.bump
+    )]
+    pub tournament: Account<'info, Tournament>,
+
+    #[account(seeds = [b"program_config"], bump = program_config.bump)]
+    pub program_config: Account<'info, ProgramConfig>,
+
+    #[account(mut, seeds = [b"treasury"], bump = program_config.treasury_bump)]
+    pub treasury: SystemAccount<'info>,
+
+    #[account(mut, seeds = [b"economy_ledger"], bump = economy_ledger.bump)]
+    pub economy_ledger: Account<'info, EconomyLedger>,
+}
+
+/// 💸 Context for Refunding Tournament Entries
+#[derive(Accounts)]
+pub struct RefundTournamentEntry<'info> {
+    #[account(mut)]
+    pub entrant: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"tournament", tournament.tournament_id.to_le_bytes().as_ref()],
+        bump = tournament.bump
+    )]
+    pub tournament: Account<'info, Tournament>,
+
+    #[account(mut, seeds = [b"economy_ledger"], bump = economy_ledger.bump)]
+    pub economy_ledger: Account<'info, EconomyLedger>,
+}
+/// 🏆 Enhanced State Account with Advanced Features
+///
+/// Packed for rent: a layout version byte, then fixed-width fields widest
+/// first, rarity as a one-byte enum and u32 counters. Accounts still in the
+/// original String-rarity layout (see `LegacyNftState`) load transparently and
+/// are reallocated and rewritten packed on their next write, so the account
+/// traits are implemented by hand rather than by `#[account]`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace)]
+pub struct NftState {
+    /// NFT_STATE_VERSION once written by this layout; 0 in a freshly created account
+    pub version: u8,
+    /// Asset this state belongs to, checked by every context that loads it
+    pub asset: Pubkey,
+    /// Collection the asset was first seen in; every later call must match it
+    pub collection: Pubkey,
+    /// `hash_attributes` of the Attributes plugin as this program last wrote it;
+    /// zero until the first write or audit
+    pub attributes_hash: [u8; 32],
+    pub level: u64,
+    pub achievement_points: u64,
+    pub energy: u64,
+    pub mint_date: i64,
+    pub last_updated: i64,
+    pub last_refill_day: i64,
+    pub evolution_count: u32,
+    pub fusion_potential: u32,
+    /// Rule tables in effect the last time the asset updated, evolved or fused
+    pub rule_versions: RuleVersions,
+    pub rarity: Rarity,
+    pub lock_state: LockState,
+    pub refills_today: u8,
+    pub bump: u8,
+}
+
+// 📐 3 * 32 + 6 * 8 + 2 * 4 + 3 * 2 + 5 * 1 bytes of fields
+const _: () = assert!(NftState::INIT_SPACE == 163);
+
+impl Discriminator for NftState {
+    /// `sha256("account:NftState")[..8]`, as `#[account]` would derive it
+    const DISCRIMINATOR: &'static [u8] = &[249, 203, 49, 84, 4, 13, 85, 203];
+}
+
+impl Owner for NftState {
+    fn owner() -> Pubkey {
+        crate::ID
+    }
+}
+
+impl AccountSerialize for NftState {
+    fn try_serialize<W: std::io::Write>(&self, writer: &mut W) -> Result<()> {
+        if writer.write_all(Self::DISCRIMINATOR).is_err() || AnchorSerialize::serialize(self, writer).is_err() {
+            return Err(ErrorCode::AccountDidNotSerialize.into());
+        }
+        Ok(())
+    }
+}
+
+impl AccountDeserialize for NftState {
+    fn try_deserialize(buf: &mut &[u8]) -> Result<Self> {
+        if buf.len() < Self::DISCRIMINATOR.len() {
+            return Err(ErrorCode::AccountDiscriminatorNotFound.into());
+        }
+        if &buf[..Self::DISCRIMINATOR.len()] != Self::DISCRIMINATOR {
+            return Err(error!(ErrorCode::AccountDiscriminatorMismatch).with_account_name("NftState"));
+        }
+        Self::try_deserialize_unchecked(buf)
+    }
+
+    /// Legacy accounts are always shorter than a packed state, so only a
+    /// full-size account is read by its version byte
+    fn try_deserialize_unchecked(buf: &mut &[u8]) -> Result<Self> {
+        let mut data: &[u8] = &buf[Self::DISCRIMINATOR.len()..];
+        if data.len() < Self::INIT_SPACE {
+            let legacy = LegacyNftState::deserialize(&mut data).map_err(|_| ErrorCode::AccountDidNotDeserialize)?;
+            return Ok(legacy.upgrade());
+        }
+        require!(data[0] == 0 || data[0] == NFT_STATE_VERSION, NftError::UnsupportedStateVersion);
+        let mut state = NftState::deserialize(&mut data).map_err(|_| ErrorCode::AccountDidNotDeserialize)?;
+        state.version = NFT_STATE_VERSION;
+        Ok(state)
+    }
+}
+
+/// 🏚️ NftState as first deployed, before packing; only ever read, and
+/// upgraded in memory - the account itself is rewritten on its next write
+#[derive(AnchorDeserialize)]
+pub struct LegacyNftState {
+    pub level: u64,
+    pub rarity: String,
+    pub mint_date: i64,
+    pub last_updated: i64,
+    pub evolution_count: u64,
+    pub fusion_potential: u64,
+    pub asset: Pubkey,
+    pub achievement_points: u64,
+}
+
+impl LegacyNftState {
+    /// The packed equivalent; fields the old layout lacked start at their
+    /// defaults, and the bump it never stored is re-derived
+    pub fn upgrade(self) -> NftState {
+        let (_, bump) = Pubkey::find_program_address(&[b"nft_state", self.asset.as_ref()], &crate::ID);
+        NftState {
+            version: NFT_STATE_VERSION,
+            asset: self.asset,
+            level: self.level,
+            achievement_points: self.achievement_points,
+            mint_date: self.mint_date,
+            last_updated: self.last_updated,
+            evolution_count: u32::try_from(self.evolution_count).unwrap_or(u32::MAX),
+            fusion_potential: u32::try_from(self.fusion_potential).unwrap_or(u32::MAX),
+            rarity: Rarity::from_name(&self.rarity).unwrap_or_default(),
+            bump,
+            ..NftState::default()
+        }
+    }
+}
+
+/// 💎 Rarity ladder, stored as a single byte
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug, Default, InitSpace)]
+pub enum Rarity {
+    #[default]
+    Common,
+    Uncommon,
+    Rare,
+    Epic,
+    Legendary,
+    Mythic,
+    Divine,
+}
+
+impl Rarity {
+    pub fn as_str(&self) -> &'static str {
+        rarity_name(*self as u8)
+    }
+
+    /// Parse a rarity name, rejecting anything off the ladder
+    pub fn from_name(name: &str) -> Option<Self> {
+        [
+            Self::Common,
+            Self::Uncommon,
+            Self::Rare,
+            Self::Epic,
+            Self::Legendary,
+            Self::Mythic,
+            Self::Divine,
+        ]
+        .into_iter()
+        .find(|rarity| rarity.as_str() == name)
+    }
+
+    /// Bonus XP per level gained on a metadata update; Mythic and above share the top rate
+    pub fn reward_multiplier(&self) -> u64 {
+        (*self as u64 + 1).min(6)
+    }
+
+    /// Rarity's share of `battle_rating`, one step per rung of the ladder
+    pub fn battle_weight(&self) -> u64 {
+        *self as u64 + 1
+    }
+}
+
+impl std::fmt::Display for Rarity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// 🔒 Lock states that block stat-changing instructions while an operation is pending
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, InitSpace)]
+pub enum LockState {
+    Unlocked,
+    PendingFusion,
+    PendingEvolution,
+    /// Frozen or burned by a forced recall; never unlocks
+    Recalled,
+    /// Committed to a guild objective until its contribution is settled
+    Committed,
+    /// Frozen at its collection's beta sunset; never unlocks
+    Sunset,
+}
+
+impl NftState {
+    /// Whether `info` still holds the pre-packing layout and needs
+    /// reallocating before it can be written
+    pub fn is_legacy(info: &AccountInfo) -> bool {
+        info.data_len() < 8 + Self::INIT_SPACE
+    }
+
+    /// Pin the state to `collection` on first use, then insist on it
+    pub fn bind_collection(&mut self, collection: Pubkey) -> Result<()> {
+        if self.collection == Pubkey::default() {
+            self.collection = collection;
+        }
+        require_keys_eq!(self.collection, collection, NftError::CollectionMismatch);
+        Ok(())
+    }
+
+    /// Record the asset and PDA bump on first use, then insist on both
+    pub fn bind_asset(&mut self, asset: Pubkey, bump: u8) -> Result<()> {
+        if self.asset == Pubkey::default() {
+            self.asset = asset;
+            self.bump = bump;
+        }
+        require_keys_eq!(self.asset, asset, NftError::NftStateAssetMismatch);
+        require!(self.bump == bump, NftError::NftStateAssetMismatch);
+        Ok(())
+    }
+
+    /// Stamp the rule versions an action ran under and announce them
+    pub fn apply_rules(&mut self, action: RuleAction, rule_versions: RuleVersions) {
+        self.rule_versions = rule_versions;
+        emit!(RulesApplied { asset: self.asset, action, rule_versions });
+    }
+}
+
+impl Default for NftState {
+    fn default() -> Self {
+        Self {
+            version: NFT_STATE_VERSION,
+            asset: Pubkey::default(),
+            collection: Pubkey::default(),
+            attributes_hash: [0; 32],
+            level: 0,
+            achievement_points: 0,
+            energy: 0,
+            mint_date: 0,
+            last_updated: 0,
+            last_refill_day: 0,
+            evolution_count: 0,
+            fusion_potential: 0,
+            rule_versions: RuleVersions::default(),
+            rarity: Rarity::Common,
+            lock_state: LockState::Unlocked,
+            refills_today: 0,
+            bump: 0,
+        }
+    }
+}
+
+/// ⚙️ Global Program Configuration
+#[account]
+#[derive(InitSpace)]
+pub struct ProgramConfig {
+    pub authority: Pubkey,
+    pub trait_fee_bps: u16,
+    pub max_attributes: u16,
+    pub max_attribute_bytes: u32,
+    pub attribute_overflow_policy: AttributeOverflowPolicy,
+    /// Backend key that signs mint price quotes (default key = quotes disabled)
+    pub quote_signer: Pubkey,
+    /// Backend key that signs gasless mint vouchers (default key = vouchers disabled)
+    pub voucher_signer: Pubkey,
+    pub fusion_confirmation_window_seconds: i64,
+    /// How long after a checkpoint the admin may still restore it
+    pub dispute_window_seconds: i64,
+    /// Freeze name and URI once an asset has evolved at least once
+    pub lock_identity_after_evolution: bool,
+    /// Lifetime-spend thresholds and their perks, ascending
+    #[max_len(MAX_VIP_TIERS)]
+    pub vip_tiers: Vec<VipTier>,
+    pub fusion_outcome_table: FusionOutcomeTable,
+    /// Program allowed to pull this deployment's configs via `export_state`
+    pub successor_program: Pubkey,
+    pub royalty_mode: RoyaltyMode,
+    /// Marketplace fee per rarity tier (Common … Divine) in RarityScaled mode
+    pub rarity_fee_bps: [u16; 7],
+    /// Chance that a failed Legendary → Mythic attempt drops the asset to Epic (0 = off)
+    pub evolution_downgrade_bps: u16,
+    /// Current version of each rule table, stamped onto assets as they act
+    pub rule_versions: RuleVersions,
+    pub subscription_plan: SubscriptionPlan,
+    /// Source behind every random roll (evolution, fusion, lottery, packs)
+    pub randomness_provider: RandomnessProvider,
+    pub fusion_fee: FusionFee,
+    /// Pages kept per wallet before the oldest is recycled
+    pub max_history_pages: u32,
+    /// Paid from the treasury to the holder of a force-recalled asset
+    pub recall_compensation_lamports: u64,
+    /// Share of a split asset's level and fusion potential its two children keep between them
+    pub split_retain_bps: u16,
+    /// Attribute values that bar an asset from program listings and transfers
+    #[max_len(MAX_TRANSFER_RESTRICTIONS)]
+    pub transfer_restrictions: Vec<TransferRestriction>,
+    /// Share of transferred levels lost in transit between two assets
+    pub xp_transfer_loss_bps: u16,
+    /// Lowest level a donor asset may be drained to
+    pub xp_donor_min_level: u64,
+    /// Which attribute-writing paths emit AttributesChanged diffs
+    pub attribute_diff_events: AttributeDiffEvents,
+    /// Highest level update and evolution reach; levels past it become prestige
+    pub max_level: u64,
+    /// Where clients deep-link error codes for localized explanations; empty = unset
+    #[max_len(MAX_BASE_URI_LEN)]
+    pub docs_base_uri: String,
+    pub bump: u8,
+    pub treasury_bump: u8,
+}
+
+impl ProgramConfig {
+    /// `level` clamped to `max_level`, and how many levels were cut off; an
+    /// asset already past a since-lowered cap keeps its `current` level
+    pub fn cap_level(&self, current: u64, level: u64) -> (u64, u64) {
+        let capped = level.min(self.max_level.max(current));
+        (capped, level - capped)
+    }
+
+    /// Marketplace fee for a whole-NFT sale of the given rarity
+    pub fn marketplace_fee_bps(&self, rarity: Rarity) -> u16 {
+        match self.royalty_mode {
+            RoyaltyMode::Flat => self.trait_fee_bps,
+            RoyaltyMode::RarityScaled => self.rarity_fee_bps[rarity as usize],
+        }
+    }
+
+    /// Highest VIP tier reached at `lifetime_spend` (no perks below the first threshold)
+    pub fn vip_tier(&self, lifetime_spend: u64) -> VipTier {
+        self.vip_tiers
+            .iter()
+            .rev()
+            .find(|tier| lifetime_spend >= tier.min_lifetime_spend)
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// VIP perks stacked with subscription perks while the profile's subscription is paid up
+    pub fn perks(&self, profile: &PlayerProfile, now: i64) -> VipTier {
+        let mut perks = self.vip_tier(profile.lifetime_spend_lamports);
+        if profile.subscribed_until > now {
+            let plan = &self.subscription_plan;
+            perks.fee_discount_bps = perks.fee_discount_bps.max(plan.fee_discount_bps);
+            perks.bonus_energy = perks.bonus_energy.saturating_add(plan.bonus_energy);
+        }
+        perks
+    }
+}
+
+/// 👑 How the marketplace fee on NFT sales is chosen
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, InitSpace)]
+pub enum RoyaltyMode {
+    /// Same fee as trait sales
+    Flat,
+    /// Looked up by the listed asset's rarity; higher tiers pay less
+    RarityScaled,
+}
+
+/// 🎲 Where random rolls come from
+///
+/// Only the slot-hash source is implemented. Switchboard and ORAO VRF were
+/// dropped: reading either needs the oracle's client crate in the build and
+/// its request account on every roll, and neither is linked here, so
+/// selecting one could only fail every roll. The enum stays on
+/// `ProgramConfig` so an oracle can come back as a new variant behind
+/// `Randomness::revealed`, since every roll already commits before it
+/// reveals, without changing the account layout.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, Default, InitSpace)]
+pub enum RandomnessProvider {
+    /// Commit to a future slot and reveal its hash from the SlotHashes sysvar.
+    /// Free, but a block producer can bias the outcome.
+    #[default]
+    SlotHashCommitReveal,
+}
+
+/// 💎 One VIP tier: reached at a lifetime spend, grants fee and energy perks
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, Default, InitSpace)]
+pub struct VipTier {
+    pub min_lifetime_spend: u64,
+    pub fee_discount_bps: u16,
+    pub bonus_energy: u64,
+}
+
+/// 📏 What happens when a write would push an asset past its attribute cap
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, InitSpace)]
+pub enum AttributeOverflowPolicy {
+    /// Fail the instruction
+    Reject,
+    /// Drop the oldest cosmetic (non-core) attributes until the list fits
+    PruneOldest,
+}
+
+/// 🛒 Escrowed Trait Listing - holds a detached attribute until bought
+#[account]
+#[derive(InitSpace)]
+pub struct TraitListing {
+    pub seller: Pubkey,
+    pub asset: Pubkey,
+    #[max_len(32)]
+    pub trait_key: String,
+    #[max_len(64)]
+    pub trait_value: String,
+    pub price_lamports: u64,
+    pub listed_at: i64,
+    pub bump: u8,
+}
+
+/// 🏷️ NFT Listing - escrows the asset and snapshots what the buyer is shown
+#[account]
+#[derive(InitSpace)]
+pub struct Listing {
+    pub seller: Pubkey,
+    pub asset: Pubkey,
+    pub price_lamports: u64,
+    pub level: u64,
+    pub rarity: Rarity,
+    pub power_score: u64,
+    pub attributes_hash: [u8; 32],
+    pub listed_at: i64,
+    pub bump: u8,
+}
+
+/// 🤝 What a deal's seller wants for the bundle; every part is paid on accept
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default, InitSpace)]
+pub struct DealAsk {
+    pub lamports: u64,
+    /// `Pubkey::default()` when no tokens are asked
+    pub token_mint: Pubkey,
+    pub token_amount: u64,
+    /// Specific assets the buyer must hand over
+    #[max_len(MAX_DEAL_ASSETS)]
+    pub assets: Vec<Pubkey>,
+}
+
+/// 🤝 OTC Deal - escrows a bundle of assets until it is accepted or cancelled
+#[account]
+#[derive(InitSpace)]
+pub struct Deal {
+    pub seller: Pubkey,
+    pub deal_id: u64,
+    /// The only wallet that may accept; `Pubkey::default()` = open to anyone
+    pub counterparty: Pubkey,
+    #[max_len(MAX_DEAL_ASSETS)]
+    pub offered_assets: Vec<Pubkey>,
+    pub ask: DealAsk,
+    pub created_at: i64,
+    pub bump: u8,
+}
+
+/// 🏟️ Single-Elimination Tournament with Escrowed Prize Pool
+#[account]
+#[derive(InitSpace)]
+pub struct Tournament {
+    pub tournament_id: u64,
+    pub authority: Pubkey,
+    pub oracle: Pubkey,
+    pub entry_fee_lamports: u64,
+    pub prize_pool: u64,
+    /// Share of `prize_pool` the treasury put in, returned if the tournament is cancelled
+    pub treasury_contribution: u64,
+    pub registration_closes_at: i64,
+    /// No results are accepted after this; without a champion, anyone may cancel
+    pub completion_deadline: i64,
+    pub max_entrants: u8,
+    pub round: u8,
+    pub champion: Option<u8>,
+    pub prize_claimed: bool,
+    pub cancelled: bool,
+    pub bump: u8,
+    #[max_len(16)]
+    pub entrants: Vec<TournamentEntrant>,
+    /// Current round slots (entrant index or `BRACKET_BYE`), paired two by two
+    #[max_len(16)]
+    pub bracket: Vec<u8>,
+    /// Winner of each current-round match (`BRACKET_UNDECIDED` until reported)
+    #[max_len(8)]
+    pub match_winners: Vec<u8>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug, InitSpace)]
+pub struct TournamentEntrant {
+    pub asset: Pubkey,
+    /// Wallet that paid the entry fee, and gets it back if the tournament is cancelled
+    pub owner: Pubkey,
+    pub battle_rating: u64,
+    pub refunded: bool,
+}
+
+/// 🧾 Replay Guard - one receipt per (wallet, nonce) quote or mint voucher
+#[account]
+#[derive(InitSpace)]
+pub struct QuoteReceipt {
+    pub wallet: Pubkey,
+    pub nonce: u64,
+    pub price_lamports: u64,
+    pub used_at: i64,
+    pub bump: u8,
+}
+
+/// 🏠 Rental Agreement - escrowed prepayment streamed to the owner per second
+#[account]
+#[derive(InitSpace)]
+pub struct RentalAgreement {
+    pub owner: Pubkey,
+    pub asset: Pubkey,
+    /// Default key while the offer is open
+    pub borrower: Pubkey,
+    pub rate_lamports_per_second: u64,
+    pub max_duration_seconds: i64,
+    pub early_termination_penalty_bps: u16,
+    pub started_at: i64,
+    pub ends_at: i64,
+    pub escrowed: u64,
+    pub claimed: u64,
+    pub bump: u8,
+}
+
+impl RentalAgreement {
+    pub fn is_active(&self) -> bool {
+        self.borrower != Pubkey::default()
+    }
+
+    /// Total income earned by the owner up to `now`, capped at the escrowed amount
+    pub fn accrued_at(&self, now: i64) -> Result<u64> {
+        let elapsed = (now.min(self.ends_at) - self.started_at).max(0) as u64;
+        let accrued = self
+            .rate_lamports_per_second
+            .checked_mul(elapsed)
+            .ok_or(NftError::MathOverflow)?;
+        Ok(accrued.min(self.escrowed))
+    }
+
+    pub fn reset(&mut self) {
+        self.borrower = Pubkey::default();
+        self.started_at = 0;
+        self.ends_at = 0;
+        self.escrowed = 0;
+        self.claimed = 0;
+    }
+}
+
+/// 🎲 A roll committed to a future slot by the first call of a two-call
+/// action (`commit_evolution`, `commit_fusion`, `commit_starter_pack`); the
+/// second call rolls from that slot's hash and closes it
+#[account]
+#[derive(InitSpace)]
+pub struct RollCommitment {
+    pub owner: Pubkey,
+    pub action: RollAction,
+    /// Hash of the inputs the roll was committed for; the reveal must present the same ones
+    pub subject: [u8; 32],
+    pub reveal_slot: u64,
+    pub bump: u8,
+}
+
+impl RollCommitment {
+    /// Commit `owner`'s roll for `action` on `subject` to ROLL_REVEAL_DELAY_SLOTS past `slot`
+    pub fn open(&mut self, owner: Pubkey, action: RollAction, subject: [u8; 32], slot: u64, bump: u8) {
+        self.owner = owner;
+        self.action = action;
+        self.subject = subject;
+        self.reveal_slot = slot + ROLL_REVEAL_DELAY_SLOTS;
+        self.bump = bump;
+    }
+
+    /// Seed for the committed roll once its slot has passed; `None` if the
+    /// slot hash has aged out, which callers settle as the worst outcome
+    pub fn reveal(
+        &self,
+        randomness: &Randomness,
+        action: RollAction,
+        subject: [u8; 32],
+        slot: u64,
+    ) -> Result<Option<[u8; 32]>> {
+        require!(self.action == action && self.subject == subject, NftError::RollCommitmentMismatch);
+        require!(slot > self.reveal_slot, NftError::RollRevealPending);
+        randomness.revealed(self.reveal_slot, &[self.owner.as_ref(), &self.subject])
+    }
+}
+
+/// 🎲 Which action a RollCommitment is for
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, InitSpace)]
+pub enum RollAction {
+    Evolve,
+    Fuse,
+    /// `beta` records whether the pack was paid for in a beta phase
+    StarterPack { beta: bool },
+}
+
+/// 🧪 Pending Two-Phase Fusion - locked inputs and the previewed result
+#[account]
+#[derive(InitSpace)]
+pub struct PendingFusion {
+    pub owner: Pubkey,
+    pub asset_1: Pubkey,
+    pub asset_2: Pubkey,
+    pub result_asset: Pubkey,
+    #[max_len(32)]
+    pub fusion_type: String,
+    pub level: u64,
+    pub fusion_potential: u32,
+    pub fusion_multiplier: u64,
+    /// Slot whose hash rolls the rarity at confirm
+    pub reveal_slot: u64,
+    pub evolution_count: u32,
+    /// Rules the preview was computed under, stamped on the result at confirm
+    pub rule_versions: RuleVersions,
+    /// Generation the result is stamped with at confirm
+    pub generation: u8,
+    pub initiated_at: i64,
+    pub confirm_deadline: i64,
+    pub bump: u8,
+}
+
+/// 🧩 Set Definition - a combo of pieces that unlocks a bonus
+#[account]
+#[derive(InitSpace)]
+pub struct SetDefinition {
+    pub set_id: u64,
+    #[max_len(32)]
+    pub name: String,
+    #[max_len(8)]
+    pub requirements: Vec<SetRequirement>,
+    pub bonus_points: u64,
+    /// Empty when the set only grants achievement points
+    #[max_len(32)]
+    pub bonus_attribute_key: String,
+    #[max_len(64)]
+    pub bonus_attribute_value: String,
+    pub claims: u64,
+    pub bump: u8,
+}
+
+/// 🧩 One piece of a set - empty fields act as wildcards
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug, InitSpace)]
+pub struct SetRequirement {
+    /// Default key matches any collection
+    pub collection: Pubkey,
+    #[max_len(32)]
+    pub trait_key: String,
+    #[max_len(64)]
+    pub trait_value: String,
+}
+
+impl SetRequirement {
+    /// Whether an asset in `collection` with `attributes` counts as this piece
+    pub fn is_met_by(&self, collection: Option<Pubkey>, attributes: &[Attribute]) -> bool {
+        let in_collection = self.collection == Pubkey::default() || collection == Some(self.collection);
+        let has_trait = self.trait_key.is_empty()
+            || attributes.iter().any(|attribute| {
+                attribute.key == self.trait_key
+                    && (self.trait_value.is_empty() || attribute.value == self.trait_value)
+            });
+        in_collection && has_trait
+    }
+}
+
+/// 🎁 Set Claim Record - one per (set, target asset), so selling a boosted
+/// asset can't earn its new owner the same bonus again
+#[account]
+#[derive(InitSpace)]
+pub struct SetClaim {
+    pub set_id: u64,
+    /// Claimant at the time of the claim
+    pub wallet: Pubkey,
+    pub target_asset: Pubkey,
+    pub claimed_at: i64,
+    pub bump: u8,
+}
+
+/// 📚 Asset History - ring buffer of compact pre-operation checkpoints
+#[account]
+#[derive(InitSpace)]
+pub struct AssetHistory {
+    pub asset: Pubkey,
+    pub next_index: u8,
+    pub bump: u8,
+    #[max_len(4)]
+    pub checkpoints: Vec<Checkpoint>,
+}
+
+impl AssetHistory {
+    /// Record a checkpoint, overwriting the oldest one once the buffer is full
+    pub fn record(
+        &mut self,
+        operation: CheckpointOperation,
+        nft_state: &NftState,
+        attributes_hash: [u8; 32],
+        now: i64,
+    ) {
+        let checkpoint = Checkpoint {
+            operation,
+            level: nft_state.level,
+            rarity: nft_state.rarity,
+            evolution_count: nft_state.evolution_count,
+            fusion_potential: nft_state.fusion_potential,
+            achievement_points: nft_state.achievement_points,
+            attributes_hash,
+            taken_at: now,
+            restored: false,
+        };
+
+        let slot = self.next_index as usize;
+        if slot < self.checkpoints.len() {
+            self.checkpoints[slot] = checkpoint;
+        } else {
+            self.checkpoints.push(checkpoint);
+        }
+        self.next_index = ((slot + 1) % MAX_CHECKPOINTS) as u8;
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug, InitSpace)]
+pub struct Checkpoint {
+    pub operation: CheckpointOperation,
+    pub level: u64,
+    pub rarity: Rarity,
+    pub evolution_count: u32,
+    pub fusion_potential: u32,
+    pub achievement_points: u64,
+    pub attributes_hash: [u8; 32],
+    pub taken_at: i64,
+    pub restored: bool,
+}
+
+/// 💥 Destructive operations that are checkpointed first
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, InitSpace)]
+pub enum CheckpointOperation {
+    Fusion,
+    Burn,
+    Prestige,
+    Recall,
+}
+
+/// 🏪 Shop Configuration - priced consumables sold by the program
+#[account]
+#[derive(InitSpace)]
+pub struct ShopConfig {
+    pub refill_base_price_lamports: u64,
+    /// Each refill in the same UTC day costs this much more than the previous one
+    pub refill_price_step_bps: u16,
+    pub refill_energy_amount: u64,
+    pub max_refills_per_day: u8,
+    pub max_energy: u64,
+    pub bump: u8,
+}
+
+impl ShopConfig {
+    pub fn configure(
+        &mut self,
+        refill_base_price_lamports: u64,
+        refill_price_step_bps: u16,
+        refill_energy_amount: u64,
+        max_refills_per_day: u8,
+        max_energy: u64,
+    ) -> Result<()> {
+        require!(
+            refill_energy_amount > 0 && max_refills_per_day > 0 && max_energy > 0,
+            NftError::InvalidShopConfig
+        );
+
+        self.refill_base_price_lamports = refill_base_price_lamports;
+        self.refill_price_step_bps = refill_price_step_bps;
+        self.refill_energy_amount = refill_energy_amount;
+        self.max_refills_per_day = max_refills_per_day;
+        self.max_energy = max_energy;
+
+        msg!("🏪 Energy shop: {} energy for {} lamports (+{} bps per refill, {} per day)",
+             refill_energy_amount, refill_base_price_lamports, refill_price_step_bps,
+             max_refills_per_day);
+
+        Ok(())
+    }
+
+    /// Price of the next refill after `refills_today` purchases (compounding escalation)
+    pub fn refill_price(&self, refills_today: u8) -> Result<u64> {
+        compound_bps(self.refill_base_price_lamports, self.refill_price_step_bps, refills_today as u32)
+    }
+}
+
+/// 🎖️ Holder-Tier Mint Phase - access gated on prior-collection holdings
+#[account]
+#[derive(InitSpace)]
+pub struct HolderPhase {
+    pub collection: Pubkey,
+    pub prior_collection: Pubkey,
+    pub min_assets: u8,
+    pub min_level: u64,
+    pub starts_at: i64,
+    pub ends_at: i64,
+    pub minted: u64,
+    pub bump: u8,
+}
+
+/// 🌳 Merkle Points Distribution - root of (wallet, amount) leaves
+#[account]
+#[derive(InitSpace)]
+pub struct PointsDistribution {
+    pub distribution_id: u64,
+    pub merkle_root: [u8; 32],
+    pub published_at: i64,
+    pub total_claimed: u64,
+    pub claims: u64,
+    pub bump: u8,
+}
+
+/// 🎯 Points Claim Receipt - replay guard per (distribution, wallet)
+#[account]
+#[derive(InitSpace)]
+pub struct PointsClaim {
+    pub distribution_id: u64,
+    pub wallet: Pubkey,
+    pub amount: u64,
+    pub bump: u8,
+}
+
+/// 📡 Signal Board - the last K significant events in a fixed layout
+///
+/// Other programs can read this account synchronously: after the 8-byte
+/// discriminator come `head: u16`, `total_signals: u64`, `bump: u8`, then
+/// `SIGNAL_BOARD_SLOTS` fixed-size `Signal` records. `head` is the slot the
+/// next signal will be written to.
+#[account]
+#[derive(InitSpace)]
+pub struct SignalBoard {
+    pub head: u16,
+    pub total_signals: u64,
+    pub bump: u8,
+    pub signals: [Signal; SIGNAL_BOARD_SLOTS],
+}
+
+impl SignalBoard {
+    pub fn record(
+        &mut self,
+        kind: SignalKind,
+        asset: Pubkey,
+        level: u64,
+        rarity: &str,
+        clock: &Clock,
+    ) {
+        self.signals[self.head as usize] = Signal {
+            kind,
+            asset,
+            level,
+            rarity_tier: rarity_tier(rarity),
+            slot: clock.slot,
+            timestamp: clock.unix_timestamp,
+        };
+        self.head = ((self.head as usize + 1) % SIGNAL_BOARD_SLOTS) as u16;
+        self.total_signals += 1;
+    }
+}
+
+/// 📡 One fixed-size event record (58 bytes)
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, Default, InitSpace)]
+pub struct Signal {
+    pub kind: SignalKind,
+    pub asset: Pubkey,
+    pub level: u64,
+    pub rarity_tier: u8,
+    pub slot: u64,
+    pub timestamp: i64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, Default, InitSpace)]
+pub enum SignalKind {
+    #[default]
+    Empty,
+    Mint,
+    LegendaryEvolution,
+}
+
+/// 🪂 Airdrop Campaign - escrowed SOL plus a per-index delivery bitmap
+#[account]
+pub struct AirdropCampaign {
+    pub creator: Pubkey,
+    pub campaign_id: u64,
+    pub merkle_root: [u8; 32],
+    pub total_recipients: u32,
+    pub amount_per_recipient: u64,
+    pub delivered: u32,
+    /// Lowest index not yet delivered; every index below it has landed
+    pub cursor: u32,
+    pub bump: u8,
+    pub delivered_bitmap: Vec<u8>,
+}
+
+impl AirdropCampaign {
+    pub fn bitmap_len(total_recipients: u32) -> usize {
+        (total_recipients as usize).div_ceil(8)
+    }
+
+    pub fn space(total_recipients: u32) -> usize {
+        8 + 32 + 8 + 32 + 4 + 8 + 4 + 4 + 1 + 4 + Self::bitmap_len(total_recipients)
+    }
+
+    pub fn verify_recipient(&self, index: u32, recipient: &Pubkey, proof: &[[u8; 32]]) -> Result<()> {
+        require!(index < self.total_recipients, NftError::InvalidAirdropIndex);
+        let leaf = hashv(&[&index.to_le_bytes(), recipient.as_ref()]).to_bytes();
+        require!(
+            verify_merkle_proof(proof, self.merkle_root, leaf),
+            NftError::InvalidMerkleProof
+        );
+        Ok(())
+    }
+
+    pub fn is_delivered(&self, index: u32) -> bool {
+        self.delivered_bitmap[index as usize / 8] & (1 << (index % 8)) != 0
+    }
+
+    pub fn mark_delivered(&mut self, index: u32) {
+        self.delivered_bitmap[index as usize / 8] |= 1 << (index % 8);
+        self.delivered += 1;
+    }
+
+    pub fn advance_cursor(&mut self) {
+        while self.cursor < self.total_recipients && self.is_delivered(self.cursor) {
+            self.cursor += 1;
+        }
+    }
+}
+
+/// ⚙️ One push delivery: the recipient's leaf index and its proof
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct AirdropDelivery {
+    pub index: u32,
+    pub proof: Vec<[u8; 32]>,
+}
+
+/// 📒 Economy Ledger - cumulative SOL and points flows for public audit
+///
+/// Lamport totals only ever grow; what is currently held in escrow is
+/// `sol_escrow_deposited - sol_escrow_released`.
+#[account]
+#[derive(InitSpace)]
+pub struct EconomyLedger {
+    /// Fees and sales paid into the treasury
+    pub sol_collected: u64,
+    /// Treasury lamports moved out to fund prize pools
+    pub sol_treasury_spent: u64,
+    /// Prizes and airdrops paid to players
+    pub sol_rewards_paid: u64,
+    pub sol_escrow_deposited: u64,
+    pub sol_escrow_released: u64,
+    pub points_issued: u64,
+    /// Fees sent to the incinerator instead of the treasury
+    pub sol_burned: u64,
+    /// SPL token mint payments into the treasury's token accounts, in base
+    /// units of each collection's payment mint
+    pub tokens_collected: u64,
+    pub bump: u8,
+}
+
+impl EconomyLedger {
+    pub fn record_collected(&mut self, amount: u64) -> Result<()> {
+        Self::accumulate(&mut self.sol_collected, amount)
+    }
+
+    pub fn record_treasury_spent(&mut self, amount: u64) -> Result<()> {
+        Self::accumulate(&mut self.sol_treasury_spent, amount)
+    }
+
+    pub fn record_escrow_deposit(&mut self, amount: u64) -> Result<()> {
+        Self::accumulate(&mut self.sol_escrow_deposited, amount)
+    }
+
+    pub fn record_escrow_release(&mut self, amount: u64) -> Result<()> {
+        Self::accumulate(&mut self.sol_escrow_released, amount)
+    }
+
+    /// Rewards are always paid out of an escrow, so both totals move
+    pub fn record_reward(&mut self, amount: u64) -> Result<()> {
+        self.record_escrow_release(amount)?;
+        Self::accumulate(&mut self.sol_rewards_paid, amount)
+    }
+
+    pub fn record_burned(&mut self, amount: u64) -> Result<()> {
+        Self::accumulate(&mut self.sol_burned, amount)
+    }
+
+    pub fn record_tokens_collected(&mut self, amount: u64) -> Result<()> {
+        Self::accumulate(&mut self.tokens_collected, amount)
+    }
+
+    pub fn record_points_issued(&mut self, amount: u64) -> Result<()> {
+        Self::accumulate(&mut self.points_issued, amount)
+    }
+
+    pub fn escrow_held(&self) -> u64 {
+        self.sol_escrow_deposited.saturating_sub(self.sol_escrow_released)
+    }
+
+    fn accumulate(total: &mut u64, amount: u64) -> Result<()> {
+        *total = total.checked_add(amount).ok_or(NftError::MathOverflow)?;
+        Ok(())
+    }
+}
+
+/// ⏳ Evolution Queue - evolutions awaiting their committed slot hash
+#[account]
+#[derive(InitSpace)]
+pub struct EvolutionQueue {
+    pub crank_incentive_lamports: u64,
+    pub processed: u64,
+    pub bump: u8,
+    #[max_len(MAX_QUEUED_EVOLUTIONS)]
+    pub entries: Vec<QueuedEvolution>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, InitSpace)]
+pub struct QueuedEvolution {
+    pub asset: Pubkey,
+    pub owner: Pubkey,
+    /// Slot whose hash decides the evolution roll
+    pub reveal_slot: u64,
+    pub queued_at: i64,
+    pub incentive_lamports: u64,
+}
+
+/// 👤 Player Profile - per-wallet lifetime spend driving VIP perks
+#[account]
+#[derive(InitSpace, Default)]
+pub struct PlayerProfile {
+    pub wallet: Pubkey,
+    pub lifetime_spend_lamports: u64,
+    /// Subscription perks apply until this time
+    pub subscribed_until: i64,
+    /// Actions ever appended to this wallet's history pages
+    pub history_len: u64,
+    /// UTC day index of the wallet's last recorded activity
+    pub last_active_day: i64,
+    /// Consecutive active days ending on `last_active_day`
+    pub streak_days: u32,
+    /// One point per consecutive active day; LEGENDARY_LUCK_COST is spent on a Legendary mint
+    pub luck: u32,
+    /// When each WalletReward was last claimed, indexed by reward; per wallet,
+    /// so rotating across assets can't reset them
+    pub reward_claimed_at: [i64; WALLET_REWARD_KINDS],
+    /// Earned by levelling assets past the program's level cap
+    pub prestige: u64,
+    pub bump: u8,
+}
+
+/// 🎁 Wallet-scoped rewards, each on its own per-wallet cooldown
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum WalletReward {
+    CheckIn,
+    IdleXp,
+}
+
+impl WalletReward {
+    pub fn cooldown(self) -> i64 {
+        match self {
+            WalletReward::CheckIn => CHECK_IN_COOLDOWN,
+            WalletReward::IdleXp => IDLE_XP_WALLET_COOLDOWN,
+        }
+    }
+}
+
+impl PlayerProfile {
+    /// Bind a freshly created profile to its wallet
+    pub fn bind(&mut self, wallet: Pubkey, bump: u8) {
+        self.wallet = wallet;
+        self.bump = bump;
+    }
+
+    /// Claim a wallet-scoped reward, at most once per its cooldown whichever
+    /// asset it is claimed through
+    pub fn claim_reward(&mut self, reward: WalletReward, now: i64) -> Result<()> {
+        let claimed_at = &mut self.reward_claimed_at[reward as usize];
+        require!(
+            *claimed_at == 0 || now >= *claimed_at + reward.cooldown(),
+            NftError::WalletCooldownActive
+        );
+        *claimed_at = now;
+        Ok(())
+    }
+
+    /// Credit the prestige for `excess_levels` gained past the level cap
+    pub fn add_prestige(&mut self, excess_levels: u64) -> u64 {
+        let earned = excess_levels.saturating_mul(PRESTIGE_PER_LEVEL);
+        self.prestige = self.prestige.saturating_add(earned);
+        earned
+    }
+
+    /// Count `now`'s day toward the streak; a missed day starts it, and the luck, over
+    pub fn record_activity(&mut self, now: i64) {
+        let today = now.div_euclid(86400);
+        if self.streak_days > 0 && today == self.last_active_day {
+            return;
+        }
+        if self.streak_days > 0 && today == self.last_active_day + 1 {
+            self.streak_days = self.streak_days.saturating_add(1);
+            self.luck = self.luck.saturating_add(1);
+        } else {
+            self.streak_days = 1;
+            self.luck = 1;
+        }
+        self.last_active_day = today;
+    }
+
+    /// Bind the profile, count today's activity, and spend LEGENDARY_LUCK_COST
+    /// luck if there's enough; true when this mint should roll Legendary
+    pub fn take_mint_luck(&mut self, wallet: Pubkey, bump: u8, now: i64) -> bool {
+        self.bind(wallet, bump);
+        self.record_activity(now);
+        if self.luck < LEGENDARY_LUCK_COST {
+            return false;
+        }
+        self.luck -= LEGENDARY_LUCK_COST;
+        true
+    }
+
+    /// Bind a freshly created profile and add `amount` to its lifetime spend
+    pub fn record_spend(&mut self, wallet: Pubkey, bump: u8, amount: u64) -> Result<()> {
+        self.bind(wallet, bump);
+        self.lifetime_spend_lamports = self
+            .lifetime_spend_lamports
+            .checked_add(amount)
+            .ok_or(NftError::MathOverflow)?;
+        Ok(())
+    }
+
+    /// History page the next action lands on
+    pub fn history_page(&self, max_pages: u32) -> u32 {
+        ((self.history_len / MAX_HISTORY_PAGE_ENTRIES as u64) % max_pages.max(1) as u64) as u32
+    }
+
+    /// Append an action to `page`, which must be the one at `history_page(max_pages)`
+    pub fn record_action(
+        &mut self,
+        page: &mut PlayerHistoryPage,
+        page_bump: u8,
+        max_pages: u32,
+        action: PlayerAction,
+        asset: Pubkey,
+        timestamp: i64,
+    ) -> Result<()> {
+        self.record_activity(timestamp);
+        let sequence = self.history_len;
+        let first_sequence = sequence - sequence % MAX_HISTORY_PAGE_ENTRIES as u64;
+        // ♻️ A new page, or one last written a full lap ago - start it over
+        if page.wallet != self.wallet || page.first_sequence != first_sequence {
+            page.wallet = self.wallet;
+            page.index = self.history_page(max_pages);
+            page.first_sequence = first_sequence;
+            page.entries.clear();
+            page.bump = page_bump;
+        }
+        page.entries.push(PlayerHistoryEntry { action, asset, timestamp });
+        self.history_len = sequence.checked_add(1).ok_or(NftError::MathOverflow)?;
+        Ok(())
+    }
+}
+
+/// 📜 Player History Page - one fixed-size page of a wallet's action log
+///
+/// Pages live at `[b"player_history", wallet, index]`. Action `n` lands on
+/// page `(n / MAX_HISTORY_PAGE_ENTRIES) % max_history_pages`, so once the cap
+/// is reached the oldest page is overwritten; `first_sequence` orders pages.
+#[account]
+#[derive(InitSpace)]
+pub struct PlayerHistoryPage {
+    pub wallet: Pubkey,
+    pub index: u32,
+    /// Log position of `entries[0]`
+    pub first_sequence: u64,
+    #[max_len(MAX_HISTORY_PAGE_ENTRIES)]
+    pub entries: Vec<PlayerHistoryEntry>,
+    pub bump: u8,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, InitSpace)]
+pub struct PlayerHistoryEntry {
+    pub action: PlayerAction,
+    pub asset: Pubkey,
+    pub timestamp: i64,
+}
+
+/// 📜 Player-initiated actions recorded in history pages
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, InitSpace)]
+pub enum PlayerAction {
+    QuotedMint,
+    Evolve,
+    Fuse,
+    BuyTrait,
+    BuyNft,
+    EnterTournament,
+    EnergyRefill,
+}
+
+/// 🌐 Collection Base URI - current base, pending rotation, and retired bases
+///
+/// Resyncing asset URIs signs as the `[b"uri_authority"]` PDA, so the
+/// collection must list that PDA as an update delegate.
+#[account]
+#[derive(InitSpace)]
+pub struct CollectionUri {
+    pub collection: Pubkey,
+    #[max_len(MAX_BASE_URI_LEN)]
+    pub base_uri: String,
+    /// Commitment to the content served under `base_uri`
+    pub proof_hash: [u8; 32],
+    pub version: u32,
+    #[max_len(MAX_BASE_URI_LEN)]
+    pub pending_base_uri: String,
+    pub pending_proof_hash: [u8; 32],
+    /// When the pending base takes over (0 = nothing pending)
+    pub pending_effective_at: i64,
+    #[max_len(MAX_URI_HISTORY)]
+    pub history: Vec<RetiredBaseUri>,
+    pub bump: u8,
+}
+
+impl CollectionUri {
+    /// Swap in the pending base once its timelock has elapsed
+    pub fn promote_pending(&mut self, now: i64) {
+        if self.pending_effective_at == 0 || now < self.pending_effective_at {
+            return;
+        }
+        if self.history.len() == MAX_URI_HISTORY {
+            self.history.remove(0);
+        }
+        self.history.push(RetiredBaseUri {
+            base_uri: std::mem::take(&mut self.base_uri),
+            proof_hash: self.proof_hash,
+            version: self.version,
+            retired_at: self.pending_effective_at,
+        });
+        self.base_uri = std::mem::take(&mut self.pending_base_uri);
+        self.proof_hash = self.pending_proof_hash;
+        self.pending_proof_hash = [0; 32];
+        self.pending_effective_at = 0;
+        self.version += 1;
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, InitSpace)]
+pub struct RetiredBaseUri {
+    #[max_len(MAX_BASE_URI_LEN)]
+    pub base_uri: String,
+    pub proof_hash: [u8; 32],
+    pub version: u32,
+    pub retired_at: i64,
+}
+
+/// 🥩 Stake Record - one per staked asset
+#[account]
+#[derive(InitSpace)]
+pub struct StakeRecord {
+    pub owner: Pubkey,
+    pub asset: Pubkey,
+    pub staked_at: i64,
+    /// Idle XP accrues from here; reset by each `claim_idle_xp`
+    pub idle_claimed_at: i64,
+    pub bump: u8,
+}
+
+/// 🧊 Freeze Record - one per asset frozen with `freeze_asset`
+#[account]
+#[derive(InitSpace)]
+pub struct FreezeRecord {
+    pub asset: Pubkey,
+    /// Owner or admin that froze it; may thaw it and gets the rent back
+    pub frozen_by: Pubkey,
+    pub frozen_at: i64,
+    pub bump: u8,
+}
+
+/// 🎰 Fusion Outcome Table - odds in basis points, summing to MAX_BPS
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, InitSpace)]
+pub struct FusionOutcomeTable {
+    pub critical_bps: u16,
+    pub upgraded_bps: u16,
+    pub sideways_bps: u16,
+    pub downgraded_bps: u16,
+}
+
+impl Default for FusionOutcomeTable {
+    fn default() -> Self {
+        Self {
+            critical_bps: 200,
+            upgraded_bps: 6_000,
+            sideways_bps: 3_000,
+            downgraded_bps: 800,
+        }
+    }
+}
+
+impl FusionOutcomeTable {
+    pub fn is_valid(&self) -> bool {
+        self.critical_bps as u32
+            + self.upgraded_bps as u32
+            + self.sideways_bps as u32
+            + self.downgraded_bps as u32
+            == MAX_BPS as u32
+    }
+
+    /// Map a roll in `0..MAX_BPS` onto an outcome
+    pub fn outcome(&self, roll: u16) -> FusionOutcome {
+        let critical = self.critical_bps;
+        let upgraded = critical + self.upgraded_bps;
+        let sideways = upgraded + self.sideways_bps;
+        match roll {
+            r if r < critical => FusionOutcome::Critical,
+            r if r < upgraded => FusionOutcome::Upgraded,
+            r if r < sideways => FusionOutcome::Sideways,
+            _ => FusionOutcome::Downgraded,
+        }
+    }
+}
+
+/// 🧬 How a fusion result's rarity compares to the better of its inputs
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, InitSpace)]
+pub enum FusionOutcome {
+    Upgraded,
+    Sideways,
+    Downgraded,
+    /// Two rarity tiers up
+    Critical,
+}
+
+/// 🔥 Emitted whenever a fusion result is written to an asset
+#[event]
+pub struct FusionCompleted {
+    pub owner: Pubkey,
+    pub result_asset: Pubkey,
+    pub outcome: FusionOutcome,
+    pub level: u64,
+    pub rarity: Rarity,
+    pub fusion_type: String,
+}
+
+/// 📜 Emitted whenever an asset's state changes under the program's rule tables
+#[event]
+pub struct RulesApplied {
+    pub asset: Pubkey,
+    pub action: RuleAction,
+    pub rule_versions: RuleVersions,
+}
+
+/// 🎉 Emitted whenever a live GlobalEvent modifies an asset's action
+#[event]
+pub struct GlobalEventApplied {
+    pub asset: Pubkey,
+    pub name: String,
+    pub evolution_chance_bonus_bps: u16,
+    pub cooldown_reduction_bps: u16,
+}
+
+/// 🧬 How a queued evolution resolved
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum EvolutionOutcome {
+    Evolved,
+    Failed,
+    /// Failed Legendary → Mythic attempt that cost the asset a tier
+    Downgraded,
+}
+
+/// 🌟 Emitted for every queued evolution the crank finalizes
+#[event]
+pub struct EvolutionFinalized {
+    pub asset: Pubkey,
+    pub outcome: EvolutionOutcome,
+    pub level: u64,
+    pub rarity: Rarity,
+}
+
+/// 🚨 Emitted when a forced recall is put on notice
+#[event]
+pub struct ForcedRecallScheduled {
+    pub asset: Pubkey,
+    pub mode: RecallMode,
+    pub reason_hash: [u8; 32],
+    pub compensation_lamports: u64,
+    pub executable_at: i64,
+}
+
+/// 🚨 Emitted when a pending forced recall is withdrawn
+#[event]
+pub struct ForcedRecallCancelled {
+    pub asset: Pubkey,
+}
+
+/// 🚨 Emitted when a forced recall burns or freezes its asset
+#[event]
+pub struct ForcedRecallExecuted {
+    pub asset: Pubkey,
+    pub holder: Pubkey,
+    pub mode: RecallMode,
+    pub compensation_lamports: u64,
+    pub voucher: Pubkey,
+}
+
+/// ✂️ Emitted when an asset is split into two
+#[event]
+pub struct NftSplit {
+    pub owner: Pubkey,
+    pub parent: Pubkey,
+    pub children: [Pubkey; 2],
+    pub levels: [u64; 2],
+    pub rarity: Rarity,
+}
+
+/// 🔍 Emitted by every attribute audit that compared against a baseline
+#[event]
+pub struct AttributesAudited {
+    pub asset: Pubkey,
+    pub expected: [u8; 32],
+    pub found: [u8; 32],
+    pub tampered: bool,
+    pub repaired: bool,
+}
+
+/// 🌳 Emitted when an epoch's state root is sealed
+#[event]
+pub struct StateRootPublished {
+    pub epoch: u64,
+    pub root: [u8; 32],
+    pub leaf_count: u64,
+}
+
+/// 🚨 Emitted when a market maker's overdue fees are seized from collateral
+#[event]
+pub struct MarketMakerDefaulted {
+    pub wallet: Pubkey,
+    pub seized_lamports: u64,
+    pub remaining_collateral: u64,
+}
+
+/// 🔢 Emitted for every mint into a collection with numbered editions
+#[event]
+pub struct EditionMinted {
+    pub collection: Pubkey,
+    pub asset: Pubkey,
+    pub edition: u32,
+    pub max_supply: u32,
+}
+
+/// 🪓 Emitted when a registered cranker is slashed for invalid crank work
+#[event]
+pub struct CrankerSlashed {
+    pub wallet: Pubkey,
+    pub faults: u32,
+    pub slashed_lamports: u64,
+    pub remaining_bond: u64,
+}
+
+/// 🧪 Emitted for every beta asset `sunset_beta_assets` retires
+#[event]
+pub struct BetaAssetSunset {
+    pub collection: Pubkey,
+    pub asset: Pubkey,
+    pub mode: BetaSunsetMode,
+    pub level: u64,
+    pub rarity: Rarity,
+}
+
+/// 📨 Emitted when a custodial asset reaches its buyer's wallet
+#[event]
+pub struct CustodialAssetClaimed {
+    pub asset: Pubkey,
+    pub claim_key: Pubkey,
+    pub wallet: Pubkey,
+}
+
+/// 🔐 Emitted when an escrowed drop is finalized and its proceeds released
+#[event]
+pub struct DropFinalized {
+    pub collection: Pubkey,
+    pub proceeds_lamports: u64,
+}
+
+/// 🚚 Emitted when `program_transfer` moves an asset between owners
+#[event]
+pub struct AssetProgramTransferred {
+    pub asset: Pubkey,
+    pub from: Pubkey,
+    pub to: Pubkey,
+}
+
+/// 🔥 Emitted when `burn_nft` burns an asset
+#[event]
+pub struct AssetBurned {
+    pub asset: Pubkey,
+    pub collection: Pubkey,
+    pub owner: Pubkey,
+    pub burned_by: Pubkey,
+    /// The collection's burn count after this one; 0 without a CollectionConfig
+    pub total_burned: u32,
+}
+
+/// 🤝 Emitted when a seller escrows a bundle in a new deal
+#[event]
+pub struct DealCreated {
+    pub deal: Pubkey,
+    pub seller: Pubkey,
+    pub counterparty: Pubkey,
+    pub offered_assets: u8,
+}
+
+/// ✅ Emitted when a deal's swap executes
+#[event]
+pub struct DealAccepted {
+    pub deal: Pubkey,
+    pub seller: Pubkey,
+    pub buyer: Pubkey,
+}
+
+/// ↩️ Emitted when a deal is called off and its bundle returned
+#[event]
+pub struct DealCancelled {
+    pub deal: Pubkey,
+    pub seller: Pubkey,
+    pub cancelled_by: Pubkey,
+}
+
+/// ✍️ Emitted when an owner approves (`Some`) or revokes (`None`) an update delegate
+#[event]
+pub struct UpdateDelegateChanged {
+    pub asset: Pubkey,
+    pub owner: Pubkey,
+    pub delegate: Option<Pubkey>,
+}
+
+/// 🌐 Emitted right before an instruction fails with `code`, carrying the
+/// values behind the failure so clients can explain it in any language
+#[event]
+pub struct ErrorContext {
+    /// The NftError's error number
+    pub code: u32,
+    pub expected: i128,
+    pub actual: i128,
+}
+
+/// 🗳️ Emitted when a collection authority opens a holder poll
+#[event]
+pub struct PollCreated {
+    pub poll: Pubkey,
+    pub collection: Pubkey,
+    pub parameter: PollParameter,
+    pub options: u8,
+    pub ends_at: i64,
+}
+
+/// 🗳️ Emitted for each vote, weighted by the voter's assets
+#[event]
+pub struct PollVoted {
+    pub poll: Pubkey,
+    pub voter: Pubkey,
+    pub option: u8,
+    pub weight: u64,
+}
+
+/// 🏁 Emitted when a poll's winning value is written to its collection's config
+#[event]
+pub struct PollApplied {
+    pub poll: Pubkey,
+    pub collection: Pubkey,
+    pub parameter: PollParameter,
+    pub value: u64,
+    pub total_votes: u64,
+}
+
+/// 🌱 Emitted when a collection's proceeds are split into the staking reward pool
+#[event]
+pub struct TreasuryRebalanced {
+    pub collection: Pubkey,
+    pub epoch: i64,
+    pub inflow_lamports: u64,
+    pub top_up_lamports: u64,
+}
+
+/// 🎖️ Emitted when levels past the cap are converted into a wallet's prestige
+#[event]
+pub struct PrestigeEarned {
+    pub wallet: Pubkey,
+    pub asset: Pubkey,
+    pub excess_levels: u64,
+    pub prestige: u64,
+}
+
+/// 🧊 Emitted when `freeze_asset` / `thaw_asset` locks or unlocks an asset
+#[event]
+pub struct AssetFreezeChanged {
+    pub asset: Pubkey,
+    pub authority: Pubkey,
+    pub frozen: bool,
+}
+
+/// 🔀 Emitted when the program rewrites an asset's attributes, listing only
+/// the keys whose values changed so indexers needn't refetch the asset
+#[event]
+pub struct AttributesChanged {
+    pub asset: Pubkey,
+    pub action: RuleAction,
+    pub changes: Vec<AttributeChange>,
+}
+
+/// 🔀 One changed attribute; None means the key was absent on that side
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug)]
+pub struct AttributeChange {
+    pub key: String,
+    pub old_value: Option<String>,
+    pub new_value: Option<String>,
+}
+
+/// 🚚 State Export - everything a successor deployment needs to recreate configs
+///
+/// Kept under the 1 KiB return-data limit, so retired base URIs stay readable
+/// on the old deployment rather than travelling with the export, and the
+/// voucher signer is set again on the successor with `set_voucher_signer`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct StateExport {
+    pub trait_fee_bps: u16,
+    pub max_attributes: u16,
+    pub max_attribute_bytes: u32,
+    pub attribute_overflow_policy: AttributeOverflowPolicy,
+    pub quote_signer: Pubkey,
+    pub fusion_confirmation_window_seconds: i64,
+    pub dispute_window_seconds: i64,
+    pub lock_identity_after_evolution: bool,
+    pub vip_tiers: Vec<VipTier>,
+    pub fusion_outcome_table: FusionOutcomeTable,
+    pub royalty_mode: RoyaltyMode,
+    pub rarity_fee_bps: [u16; 7],
+    pub evolution_downgrade_bps: u16,
+    pub rule_versions: RuleVersions,
+    pub subscription_plan: SubscriptionPlan,
+    pub randomness_provider: RandomnessProvider,
+    pub fusion_fee: FusionFee,
+    pub max_history_pages: u32,
+    pub recall_compensation_lamports: u64,
+    pub split_retain_bps: u16,
+    pub transfer_restrictions: Vec<TransferRestriction>,
+    pub xp_transfer_loss_bps: u16,
+    pub xp_donor_min_level: u64,
+    pub attribute_diff_events: AttributeDiffEvents,
+    pub max_level: u64,
+    pub docs_base_uri: String,
+    pub refill_base_price_lamports: u64,
+    pub refill_price_step_bps: u16,
+    pub refill_energy_amount: u64,
+    pub max_refills_per_day: u8,
+    pub max_energy: u64,
+    pub collection: Pubkey,
+    pub base_uri: String,
+    pub base_uri_proof_hash: [u8; 32],
+    pub base_uri_version: u32,
+}
+
+/// 🕰️ Mock Clock - test-only timestamp override (see `program_clock`)
+#[account]
+#[derive(InitSpace)]
+pub struct MockClock {
+    pub unix_timestamp: i64,
+    pub bump: u8,
+}
+
+/// 🤝 Delegation Scope - which asset actions a delegate may perform
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, InitSpace)]
+pub enum DelegationScope {
+    UpdateOnly,
+    EvolveOnly,
+    FuseOnly,
+    Full,
+}
+
+/// 🎮 Asset actions that may be performed by a delegate instead of the owner
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum DelegatedAction {
+    Update,
+    Evolve,
+    Fuse,
+}
+
+impl DelegationScope {
+    pub fn permits(&self, action: DelegatedAction) -> bool {
+        matches!(
+            (self, action),
+            (DelegationScope::Full, _)
+                | (DelegationScope::UpdateOnly, DelegatedAction::Update)
+                | (DelegationScope::EvolveOnly, DelegatedAction::Evolve)
+                | (DelegationScope::FuseOnly, DelegatedAction::Fuse)
+        )
+    }
+}
+
+/// 🤝 Delegation - one per (asset, delegate) pair
+#[account]
+#[derive(InitSpace)]
+pub struct Delegation {
+    pub asset: Pubkey,
+    pub owner: Pubkey,
+    pub delegate: Pubkey,
+    pub scope: DelegationScope,
+    pub bump: u8,
+}
+
+/// 🗂️ Staked Index - every currently staked asset, for enumeration
+#[account]
+#[derive(InitSpace)]
+pub struct StakedIndex {
+    #[max_len(MAX_STAKED_ASSETS)]
+    pub entries: Vec<StakedEntry>,
+    /// Set while a lottery draw is committed but unrevealed; entries can't change
+    pub draw_pending: bool,
+    pub bump: u8,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, InitSpace)]
+pub struct StakedEntry {
+    pub asset: Pubkey,
+    pub owner: Pubkey,
+}
+
+impl StakedIndex {
+    pub fn insert(&mut self, asset: Pubkey, owner: Pubkey) -> Result<()> {
+        require!(!self.draw_pending, NftError::LotteryRevealPending);
+        require!(self.entries.len() < MAX_STAKED_ASSETS, NftError::StakedIndexFull);
+        self.entries.push(StakedEntry { asset, owner });
+        Ok(())
+    }
+
+    pub fn remove(&mut self, asset: &Pubkey) -> Result<()> {
+        require!(!self.draw_pending, NftError::LotteryRevealPending);
+        if let Some(position) = self.entries.iter().position(|entry| entry.asset == *asset) {
+            self.entries.swap_remove(position);
+        }
+        Ok(())
+    }
+}
+
+/// 🎟️ Holder Lottery - one draw per epoch over the staked population
+#[account]
+#[derive(InitSpace)]
+pub struct HolderLottery {
+    pub epoch_seconds: i64,
+    pub prize_lamports: u64,
+    pub epoch: u64,
+    pub next_draw_at: i64,
+    pub winner: Pubkey,
+    pub winner_asset: Pubkey,
+    pub unclaimed_prize: u64,
+    /// Slot the pending draw's seed is committed to; 0 = no draw pending
+    pub reveal_slot: u64,
+    pub bump: u8,
+}
+
+impl HolderLottery {
+    pub fn configure(&mut self, epoch_seconds: i64, prize_lamports: u64) -> Result<()> {
+        require!(epoch_seconds > 0 && prize_lamports > 0, NftError::InvalidLotteryTerms);
+        self.epoch_seconds = epoch_seconds;
+        self.prize_lamports = prize_lamports;
+        Ok(())
+    }
+
+    /// First epoch boundary strictly after `now`
+    pub fn next_boundary(&self, now: i64) -> i64 {
+        (now.div_euclid(self.epoch_seconds) + 1) * self.epoch_seconds
+    }
+}
+
+/// 🌱 Staking Reward Pool - escrow for staking reward emissions, topped up
+/// from collection proceeds by `rebalance_treasury`
+#[account]
+#[derive(InitSpace)]
+pub struct StakingRewardPool {
+    pub epoch_seconds: i64,
+    /// Lamports ever moved in from the treasury
+    pub total_topped_up: u64,
+    pub bump: u8,
+}
+
+impl StakingRewardPool {
+    /// Index of the epoch `now` falls in
+    pub fn epoch_at(&self, now: i64) -> i64 {
+        now.div_euclid(self.epoch_seconds)
+    }
+}
+
+/// 🌍 Region Lock - per-collection geo-restriction on `mint_nft`
+#[account]
+#[derive(InitSpace)]
+pub struct RegionLock {
+    pub collection: Pubkey,
+    /// Signs (wallet, collection, expires_at) for buyers who passed KYC
+    pub kyc_oracle: Pubkey,
+    pub enabled: bool,
+    pub bump: u8,
+}
+
+/// 🌍 KYC oracle attestation accompanying a mint into a region-locked collection
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct RegionAttestation {
+    pub expires_at: i64,
+}
+
+/// 📨 Bytes a custodial claim code's key signs to release `pending_claim` to
+/// `destination`; the signature reveals nothing about the code
+pub fn custodial_claim_message(pending_claim: &Pubkey, destination: &Pubkey) -> Vec<u8> {
+    [b"custodial_claim".as_ref(), crate::ID.as_ref(), pending_claim.as_ref(), destination.as_ref()].concat()
+}
+
+/// 🧾 Bytes the backend signs for a price quote; domain-separated from
+/// vouchers and bound to this program and the collection being minted into
+pub fn quote_message(wallet: &Pubkey, collection: &Pubkey, price_lamports: u64, expires_at: i64, nonce: u64) -> Vec<u8> {
+    [
+        b"mint_quote".as_ref(),
+        crate::ID.as_ref(),
+        collection.as_ref(),
+        wallet.as_ref(),
+        &price_lamports.to_le_bytes(),
+        &expires_at.to_le_bytes(),
+        &nonce.to_le_bytes(),
+    ]
+    .concat()
+}
+
+/// 🎟️ Backend-signed permission to mint one asset into `collection` for `recipient`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug)]
+pub struct MintVoucher {
+    pub name: String,
+    pub uri: String,
+    pub recipient: Pubkey,
+    pub collection: Pubkey,
+    pub expires_at: i64,
+    pub nonce: u64,
+}
+
+impl MintVoucher {
+    /// Bytes the backend signs; domain-separated from price quotes, bound to
+    /// this program and collection, and length-prefixed so name and uri can't
+    /// be shifted into each other
+    pub fn message(&self) -> Vec<u8> {
+        [
+            b"mint_voucher".as_ref(),
+            crate::ID.as_ref(),
+            self.collection.as_ref(),
+            self.recipient.as_ref(),
+            &self.expires_at.to_le_bytes(),
+            &self.nonce.to_le_bytes(),
+            &(self.name.len() as u32).to_le_bytes(),
+            self.name.as_bytes(),
+            &(self.uri.len() as u32).to_le_bytes(),
+            self.uri.as_bytes(),
+        ]
+        .concat()
+    }
+}
+
+/// 📋 A wallet's allowlist membership proof for `mint_allowlisted`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug)]
+pub struct AllowlistProof {
+    pub proof: Vec<[u8; 32]>,
+    /// Lifetime mints the leaf grants this wallet; 0 = no allocation beyond the collection's limits
+    pub allocation: u32,
+}
+
+/// 📦 One asset's worth of `mint_nft_batch` arguments
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug)]
+pub struct BatchMintParams {
+    pub name: String,
+    pub uri: String,
+    pub level: u64,
+    pub rarity: String,
+    pub fusion_potential: u64,
+}
+
+/// 🪪 Asset Status - everything a wallet needs for a status card, from
+/// `describe_asset`; fields are only ever appended, with `version` bumped
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug)]
+pub struct AssetStatus {
+    pub version: u8,
+    pub asset: Pubkey,
+    pub level: u64,
+    pub rarity: Rarity,
+    pub lock_state: LockState,
+    pub energy: u64,
+    /// Update cooldowns run from here, scaled by `cooldown_multiplier`
+    pub last_updated: i64,
+    pub cooldown_multiplier: u8,
+    pub refills_today: u8,
+    pub active_event: Option<ActiveBuff>,
+    pub listed_price_lamports: Option<u64>,
+    pub staked: bool,
+    pub rented_until: Option<i64>,
+    /// Set while a forced recall is scheduled but not yet executed
+    pub recall_executable_at: Option<i64>,
+}
+
+/// 🎉 The live GlobalEvent's boosts, as reported by `describe_asset`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug)]
+pub struct ActiveBuff {
+    pub name: String,
+    pub evolution_chance_bonus_bps: u16,
+    pub cooldown_reduction_bps: u16,
+    pub ends_at: i64,
+}
+
+/// 🔮 Mint Preview - the attributes `simulate_mint` predicts
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug)]
+pub struct MintPreview {
+    pub rarity: String,
+    pub attributes: Vec<PreviewAttribute>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug)]
+pub struct PreviewAttribute {
+    pub key: String,
+    pub value: String,
+}
+
+/// 📜 Rule Versions - one counter per rule table, bumped whenever it changes
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, Default, InitSpace)]
+pub struct RuleVersions {
+    /// Evolution odds, rarity ladder and downgrade penalty
+    pub evolution: u16,
+    /// Rarity-scaled update cooldowns
+    pub cooldown: u16,
+    /// Fusion outcome odds and rarity rewards
+    pub rarity: u16,
+}
+
+/// 📜 Which kind of action a RulesApplied event records
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum RuleAction {
+    Update,
+    Evolve,
+    Fuse,
+}
+
+/// 📊 Snapshot Page - running NftState statistics for one epoch
+#[account]
+#[derive(InitSpace)]
+pub struct SnapshotPage {
+    pub epoch: u64,
+    pub states_counted: u64,
+    pub total_level: u64,
+    pub total_evolutions: u64,
+    /// States per rarity tier, Common … Divine
+    pub rarity_counts: [u32; 7],
+    /// Highest NftState address counted so far; pages must continue above it
+    pub last_state: Pubkey,
+    pub bump: u8,
+}
+
+impl SnapshotPage {
+    pub fn record(&mut self, nft_state: &NftState) -> Result<()> {
+        self.states_counted += 1;
+        self.total_level = self
+            .total_level
+            .checked_add(nft_state.level)
+            .ok_or(NftError::MathOverflow)?;
+        self.total_evolutions += nft_state.evolution_count as u64;
+        self.rarity_counts[nft_state.rarity as usize] += 1;
+        Ok(())
+    }
+
+    pub fn average_level(&self) -> u64 {
+        self.total_level.checked_div(self.states_counted).unwrap_or(0)
+    }
+}
+
+/// 🎨 Bounty - escrowed reward for a community contest
+#[account]
+#[derive(InitSpace)]
+pub struct Bounty {
+    pub creator: Pubkey,
+    pub bounty_id: u64,
+    /// Hash of the off-chain contest rules the award is judged against
+    pub requirements_hash: [u8; 32],
+    pub reward_lamports: u64,
+    /// Metadata URI for the winner's commemorative NFT, if any
+    #[max_len(MAX_BASE_URI_LEN)]
+    pub commemorative_uri: Option<String>,
+    pub bump: u8,
+}
+
+/// 🎉 Global Event - collection-wide stat modifiers for a limited time
+#[account]
+#[derive(InitSpace)]
+pub struct GlobalEvent {
+    #[max_len(MAX_GLOBAL_EVENT_NAME_LEN)]
+    pub name: String,
+    /// Relative boost to evolution odds (2_500 = +25%), capped at 100%
+    pub evolution_chance_bonus_bps: u16,
+    /// Relative cut to update cooldowns (5_000 = -50%)
+    pub cooldown_reduction_bps: u16,
+    pub starts_at: i64,
+    pub ends_at: i64,
+    pub bump: u8,
+}
+
+impl GlobalEvent {
+    pub fn is_live(&self, now: i64) -> bool {
+        self.starts_at <= now && now < self.ends_at
+    }
+
+    pub fn boost_evolution_chance(&self, chance: i64) -> i64 {
+        let boosted = chance * (MAX_BPS as i64 + self.evolution_chance_bonus_bps as i64) / MAX_BPS as i64;
+        boosted.min(100)
+    }
+
+    pub fn shorten_cooldown(&self, cooldown: i64) -> i64 {
+        cooldown * (MAX_BPS - self.cooldown_reduction_bps) as i64 / MAX_BPS as i64
+    }
+
+    /// Tell frontends which boosts shaped this asset's action
+    pub fn announce(&self, asset: Pubkey) {
+        emit!(GlobalEventApplied {
+            asset,
+            name: self.name.clone(),
+            evolution_chance_bonus_bps: self.evolution_chance_bonus_bps,
+            cooldown_reduction_bps: self.cooldown_reduction_bps,
+        });
+    }
+}
+
+/// 📅 Subscription Plan - price per period and the perks it buys (price 0 = off)
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, Default, InitSpace)]
+pub struct SubscriptionPlan {
+    pub price_per_period_lamports: u64,
+    pub period_seconds: i64,
+    pub fee_discount_bps: u16,
+    pub bonus_energy: u64,
+}
+
+/// 🔥 Fusion Fee - flat SOL charge per fusion, split between burn and treasury (0 = free)
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, Default, InitSpace)]
+pub struct FusionFee {
+    pub lamports: u64,
+    /// Share sent to the incinerator; the treasury keeps the rest and any rounding dust
+    pub burn_bps: u16,
+}
+
+/// 🔀 Attribute Diff Events - per-path switch for AttributesChanged
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, InitSpace)]
+pub struct AttributeDiffEvents {
+    pub update: bool,
+    pub evolve: bool,
+    pub fuse: bool,
+}
+
+impl Default for AttributeDiffEvents {
+    fn default() -> Self {
+        Self { update: true, evolve: true, fuse: true }
+    }
+}
+
+impl AttributeDiffEvents {
+    pub fn enabled(&self, action: RuleAction) -> bool {
+        match action {
+            RuleAction::Update => self.update,
+            RuleAction::Evolve => self.evolve,
+            RuleAction::Fuse => self.fuse,
+        }
+    }
+}
+
+/// 🚫 Transfer Restriction - assets carrying `key = value` can't be listed or
+/// moved by program flows (e.g. `quest_active = true` mid-quest)
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug, InitSpace)]
+pub struct TransferRestriction {
+    #[max_len(MAX_TRAIT_KEY_LEN)]
+    pub key: String,
+    #[max_len(MAX_TRAIT_VALUE_LEN)]
+    pub value: String,
+}
+
+/// 📅 Subscription - a wallet's prepaid escrow, streamed to the treasury one
+/// period at a time at the price in force when it subscribed
+#[account]
+#[derive(InitSpace)]
+pub struct Subscription {
+    pub wallet: Pubkey,
+    pub price_per_period_lamports: u64,
+    pub period_seconds: i64,
+    pub started_at: i64,
+    pub escrowed: u64,
+    /// Portion of `escrowed` already streamed to the treasury
+    pub settled: u64,
+    pub bump: u8,
+}
+
+impl Subscription {
+    /// End of the last period the escrow fully covers
+    pub fn paid_through(&self) -> i64 {
+        let periods = self.escrowed / self.price_per_period_lamports;
+        self.started_at + periods as i64 * self.period_seconds
+    }
+
+    /// Owed to the treasury by `now`; each period is charged as it begins
+    pub fn accrued_at(&self, now: i64) -> u64 {
+        let elapsed = (now - self.started_at).max(0);
+        let periods_started = (elapsed / self.period_seconds + 1) as u64;
+        periods_started
+            .saturating_mul(self.price_per_period_lamports)
+            .min(self.escrowed)
+    }
+
+    /// End of the period running at `now`, capped at what has been paid for
+    pub fn current_period_end(&self, now: i64) -> i64 {
+        let elapsed = (now - self.started_at).max(0);
+        (self.started_at + (elapsed / self.period_seconds + 1) * self.period_seconds)
+            .min(self.paid_through())
+    }
+}
+
+/// 🚨 Forced Recall - timelocked, public record of an admin burn / freeze
+///
+/// Kept after execution as the audit trail of who was compensated and which
+/// voucher replaced the asset.
+#[account]
+#[derive(InitSpace)]
+pub struct ForcedRecall {
+    pub asset: Pubkey,
+    pub mode: RecallMode,
+    /// Hash of the off-chain takedown notice
+    pub reason_hash: [u8; 32],
+    pub compensation_lamports: u64,
+    #[max_len(MAX_BASE_URI_LEN)]
+    pub voucher_uri: String,
+    pub scheduled_at: i64,
+    pub executable_at: i64,
+    /// 0 while pending
+    pub executed_at: i64,
+    pub holder: Pubkey,
+    pub voucher: Pubkey,
+    pub bump: u8,
+}
+
+/// 🚨 What a forced recall does to the asset
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, InitSpace)]
+pub enum RecallMode {
+    Burn,
+    Freeze,
+}
+
+/// ✂️ Split Record - lineage of a burned asset and the two it became
+#[account]
+#[derive(InitSpace)]
+pub struct SplitRecord {
+    pub parent: Pubkey,
+    pub children: [Pubkey; 2],
+    pub parent_level: u64,
+    pub parent_rarity: Rarity,
+    pub split_at: i64,
+    pub bump: u8,
+}
+
+/// 🏛️ Collection Config - supply and pricing rules for a collection, created
+/// with it by `create_collection` or attached later by `set_collection_max_supply`
+#[account]
+#[derive(InitSpace)]
+pub struct CollectionConfig {
+    pub collection: Pubkey,
+    pub authority: Pubkey,
+    /// 0 = uncapped
+    pub max_supply: u32,
+    /// Program mints folded in from the mint shards by `reconcile_counters`,
+    /// plus custodial mints
+    pub total_minted: u32,
+    /// Assets burned through `burn_nft` and `refund_mint`, for deflation stats
+    pub total_burned: u32,
+    /// Supply handed out to mint shards as quota and not yet reconciled
+    pub reserved_supply: u32,
+    /// Team supply only `mint_reserved` can mint, carved out of `max_supply`
+    pub team_allocation: u32,
+    /// `mint_reserved` mints so far; not counted in `total_minted`
+    pub team_minted: u32,
+    /// Lifetime program mints allowed per wallet; 0 = unlimited
+    pub max_per_wallet: u32,
+    /// Staged launch windows in time order; when any are set, mints outside them are refused
+    #[max_len(MAX_MINT_PHASES)]
+    pub phases: Vec<MintPhase>,
+    /// Merkle root over `hashv([wallet, allocation u32 le])` leaves for `mint_allowlisted`
+    pub allowlist_root: [u8; 32],
+    pub mint_price_lamports: u64,
+    /// How the lamport price climbs with supply; applies on top of a live phase's price
+    pub price_curve: PriceCurve,
+    /// SPL mint `mint_nft_with_token` is paid in; default = token payment disabled
+    pub payment_mint: Pubkey,
+    /// Per-mint price in `payment_mint` base units
+    pub token_price: u64,
+    /// Whitelist-token mint `mint_with_burn` can burn from; default = off
+    pub burn_token_mint: Pubkey,
+    pub burn_token_amount: u64,
+    /// Collection whose assets `mint_with_burn` can burn instead; default = off
+    pub burn_asset_collection: Pubkey,
+    /// Stamp each mint with its number from the collection's EditionCounter
+    pub numbered_editions: bool,
+    /// Public mints must be the only program mint in their transaction and
+    /// called directly, not through CPI
+    pub anti_bot: bool,
+    /// Reconciled mint-price proceeds held in the treasury for the authority to withdraw
+    pub proceeds_lamports: u64,
+    /// Escrowed drops: proceeds stay locked until `finalize_drop`, and past
+    /// this deadline unfinalized mints can be refunded; 0 = no escrow
+    pub refund_deadline: i64,
+    pub drop_finalized: bool,
+    /// Share of newly reconciled proceeds `rebalance_treasury` moves into
+    /// the staking reward pool; 0 = off
+    pub reward_split_bps: u16,
+    /// Proceeds reconciled since the last rebalance
+    pub unrebalanced_proceeds: u64,
+    /// Reward pool epoch this collection was last rebalanced in
+    pub rebalanced_epoch: i64,
+    pub royalty_bps: u16,
+    /// How royalties split between creators; empty pays the authority everything
+    #[max_len(MAX_ROYALTY_CREATORS)]
+    pub royalty_creators: Vec<RoyaltyCreator>,
+    pub plugin_bundle: MintPluginBundle,
+    /// When `sunset_beta_assets` may start retiring beta-phase mints; 0 = not scheduled
+    pub beta_sunset_at: i64,
+    pub beta_sunset_mode: BetaSunsetMode,
+    /// Slot the sunset's graduation roll is committed to; 0 = not committed yet
+    pub beta_sunset_reveal_slot: u64,
+    /// Revealed graduation seed every asset's roll derives from; zero until revealed
+    pub beta_sunset_seed: [u8; 32],
+    /// Shortest base cooldown `update_nft_metadata` accepts, in seconds; 0 = no floor
+    pub min_update_cooldown: i64,
+    pub bump: u8,
+}
+
+impl CollectionConfig {
+    /// Collection Royalties set through the program also drive the asset-level
+    /// Royalties minted from this config
+    pub fn sync_royalties(&mut self, plugin: &CollectionPlugin) {
+        if let CollectionPlugin::Royalties { basis_points, creators } = plugin {
+            self.royalty_bps = *basis_points;
+            self.royalty_creators = creators.clone();
+        }
+    }
+
+    /// Mint proceeds are held back for refunds until the drop is finalized
+    pub fn escrow_active(&self) -> bool {
+        self.refund_deadline != 0 && !self.drop_finalized
+    }
+
+    /// Supply already spoken for: public mints, shard quota and the whole team allocation
+    pub fn committed_supply(&self) -> u32 {
+        self.total_minted
+            .saturating_add(self.reserved_supply)
+            .saturating_add(self.team_allocation)
+    }
+
+    /// The phase `now` falls in; None when the collection isn't staged
+    pub fn active_phase(&self, now: i64) -> Result<Option<&MintPhase>> {
+        if self.phases.is_empty() {
+            return Ok(None);
+        }
+        let phase = self
+            .phases
+            .iter()
+            .find(|phase| phase.starts_at <= now && now < phase.ends_at)
+            .ok_or(NftError::PhaseNotActive)?;
+        Ok(Some(phase))
+    }
+
+    /// The bundle's plugins, ready for `CreateV1`
+    pub fn mint_plugins(&self) -> Vec<PluginAuthorityPair> {
+        let mut plugins = Vec::new();
+        if self.plugin_bundle.royalties {
+            plugins.push(PluginAuthorityPair {
+                plugin: Plugin::Royalties(royalties(self.royalty_bps, &self.royalty_creators, self.authority)),
+                authority: None,
+            });
+        }
+        if self.plugin_bundle.freeze_delegate {
+            let (stake_authority, _) = Pubkey::find_program_address(&[b"stake_authority"], &crate::ID);
+            plugins.push(PluginAuthorityPair {
+                plugin: Plugin::FreezeDelegate(FreezeDelegate { frozen: false }),
+                authority: Some(PluginAuthority::Address { address: stake_authority }),
+            });
+        }
+        if self.plugin_bundle.burn_delegate {
+            plugins.push(PluginAuthorityPair {
+                plugin: Plugin::BurnDelegate(BurnDelegate {}),
+                authority: Some(PluginAuthority::UpdateAuthority),
+            });
+        }
+        plugins
+    }
+}
+
+/// 🏛️ A plugin the collection authority can manage on the collection account
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug)]
+pub enum CollectionPlugin {
+    /// Collection-wide royalties; an empty split pays the authority everything
+    Royalties { basis_points: u16, creators: Vec<RoyaltyCreator> },
+    MasterEdition { max_supply: Option<u32>, name: Option<String>, uri: Option<String> },
+    Attributes { attributes: Vec<PreviewAttribute> },
+}
+
+impl CollectionPlugin {
+    pub fn name(&self) -> &'static str {
+        match self {
+            CollectionPlugin::Royalties { .. } => "Royalties",
+            CollectionPlugin::MasterEdition { .. } => "MasterEdition",
+            CollectionPlugin::Attributes { .. } => "Attributes",
+        }
+    }
+
+    /// The mpl-core plugin, after checking it against the program's limits
+    pub fn to_plugin(&self, authority: Pubkey) -> Result<Plugin> {
+        match self {
+            CollectionPlugin::Royalties { basis_points, creators } => {
+                require!(*basis_points <= MAX_BPS, NftError::InvalidFeeBps);
+                require!(valid_royalty_split(creators), NftError::InvalidRoyaltySplit);
+                Ok(Plugin::Royalties(royalties(*basis_points, creators, authority)))
+            }
+            CollectionPlugin::MasterEdition { max_supply, name, uri } => {
+                require!(
+                    name.as_ref().is_none_or(|name| name.len() <= MAX_BASE_URI_LEN)
+                        && uri.as_ref().is_none_or(|uri| uri.len() <= MAX_BASE_URI_LEN),
+                    NftError::InvalidCollectionPlugin
+                );
+                Ok(Plugin::MasterEdition(MasterEdition {
+                    max_supply: *max_supply,
+                    name: name.clone(),
+                    uri: uri.clone(),
+                }))
+            }
+            CollectionPlugin::Attributes { attributes } => {
+                require!(
+                    attributes.iter().all(|attribute| {
+                        !attribute.key.is_empty()
+                            && attribute.key.len() <= MAX_TRAIT_KEY_LEN
+                            && attribute.value.len() <= MAX_TRAIT_VALUE_LEN
+                    }),
+                    NftError::InvalidCollectionPlugin
+                );
+                let attribute_list = attributes
+                    .iter()
+                    .map(|attribute| Attribute { key: attribute.key.clone(), value: attribute.value.clone() })
+                    .collect();
+                Ok(Plugin::Attributes(Attributes { attribute_list }))
+            }
+        }
+    }
+}
+
+/// 🧩 Optional plugins every asset minted into a program-created collection
+/// starts with; Attributes is always included
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, Default, InitSpace)]
+pub struct MintPluginBundle {
+    /// Asset-level Royalties at the collection's royalty_bps and creator split
+    pub royalties: bool,
+    /// Thawed FreezeDelegate held by the program's stake authority PDA
+    pub freeze_delegate: bool,
+    /// BurnDelegate held by the collection's update authority
+    pub burn_delegate: bool,
+}
+
+/// 👑 One creator's share of a collection's royalties
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, InitSpace)]
+pub struct RoyaltyCreator {
+    pub address: Pubkey,
+    /// Percent of the royalty; a split's shares sum to 100
+    pub percentage: u8,
+}
+
+/// 🗓️ One stage of a collection's launch, e.g. presale, allowlist or public
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, InitSpace)]
+pub struct MintPhase {
+    pub starts_at: i64,
+    pub ends_at: i64,
+    /// Replaces the collection's mint price while the phase is live
+    pub price_lamports: u64,
+    /// Only `mint_allowlisted` may mint while the phase is live
+    pub allowlist_required: bool,
+    /// Assets minted while the phase is live are beta assets, retired by `sunset_beta_assets`
+    pub beta: bool,
+}
+
+/// 📈 Bonding curve a collection's mint price follows as supply grows
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, InitSpace)]
+pub enum PriceCurve {
+    /// Every mint pays the unit price
+    Flat,
+    /// Each mint already made adds `step_lamports`
+    Linear { step_lamports: u64 },
+    /// Every `every` mints already made raise the price by `step_bps`, compounding
+    Exponential { step_bps: u16, every: u32 },
+}
+
+impl PriceCurve {
+    /// Total for `count` mints starting at supply `minted`, each priced off
+    /// `unit_price` and the supply before it
+    pub fn price(&self, unit_price: u64, minted: u32, count: u32) -> Result<u64> {
+        let end = minted.checked_add(count).ok_or(NftError::MathOverflow)?;
+        let mut total = 0u64;
+        for supply in minted..end {
+            let price = match *self {
+                PriceCurve::Flat => unit_price,
+                PriceCurve::Linear { step_lamports } => step_lamports
+                    .checked_mul(supply as u64)
+                    .and_then(|step| step.checked_add(unit_price))
+                    .ok_or(NftError::MathOverflow)?,
+                PriceCurve::Exponential { step_bps, every } => {
+                    pow_bps(unit_price, step_bps, supply / every.max(1))?
+                }
+            };
+            total = total.checked_add(price).ok_or(NftError::MathOverflow)?;
+        }
+        Ok(total)
+    }
+}
+
+/// 🧪 What `sunset_beta_assets` does with a collection's beta assets
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, InitSpace)]
+pub enum BetaSunsetMode {
+    /// Re-roll into a mainline asset with bonus levels and a shot at the next rarity
+    Graduate,
+    /// Freeze for good through the asset's beta PermanentFreezeDelegate
+    Freeze,
+}
+
+/// 🧮 Mint Counter Shard - one of a collection's MINT_COUNTER_SHARDS write
+/// targets for mint counting, so a busy drop doesn't serialize on its config
+#[account]
+#[derive(InitSpace)]
+pub struct MintCounterShard {
+    pub collection: Pubkey,
+    pub index: u8,
+    /// Mints counted here since the last reconcile
+    pub pending: u32,
+    /// Mints this shard may still count before the next reconcile (capped collections)
+    pub quota: u32,
+    /// Mint-price lamports paid through this shard since the last reconcile
+    pub pending_proceeds: u64,
+    pub bump: u8,
+}
+
+impl MintCounterShard {
+    /// Count `count` mints, drawing them from the shard's quota when supply is capped
+    pub fn record_mint(&mut self, count: u32, capped: bool) -> Result<()> {
+        if capped {
+            self.quota = self.quota.checked_sub(count).ok_or(NftError::SupplyExhausted)?;
+        }
+        self.pending = self.pending.checked_add(count).ok_or(NftError::MathOverflow)?;
+        Ok(())
+    }
+}
+
+/// 🧾 Mint Receipt - how many assets a wallet has minted into a collection
+#[account]
+#[derive(InitSpace)]
+pub struct MintReceipt {
+    pub collection: Pubkey,
+    pub minter: Pubkey,
+    pub minted: u32,
+    /// Mints and lamports paid while the collection's drop was escrowed,
+    /// refundable by `refund_mint` if it is never finalized
+    pub escrowed_mints: u32,
+    pub escrowed_lamports: u64,
+    pub bump: u8,
+}
+
+impl MintReceipt {
+    /// Bind a freshly created receipt to its collection and wallet
+    pub fn bind(&mut self, collection: Pubkey, minter: Pubkey, bump: u8) {
+        self.collection = collection;
+        self.minter = minter;
+        self.bump = bump;
+    }
+}
+
+/// ⚔️ Guild Objective - a shared power target with an escrowed reward pool
+#[account]
+#[derive(InitSpace)]
+pub struct GuildObjective {
+    pub creator: Pubkey,
+    pub objective_id: u64,
+    pub target_power: u64,
+    pub deadline: i64,
+    pub reward_lamports: u64,
+    /// Points spent plus battle ratings committed so far
+    pub total_power: u64,
+    /// Contributions not yet settled; the objective can't close until 0
+    pub open_contributions: u32,
+    /// When total_power first reached target_power; 0 if it hasn't
+    pub met_at: i64,
+    pub bump: u8,
+}
+
+/// ⚔️ Objective Contribution - one asset's stake in a guild objective
+#[account]
+#[derive(InitSpace)]
+pub struct ObjectiveContribution {
+    pub objective: Pubkey,
+    pub contributor: Pubkey,
+    pub asset: Pubkey,
+    pub points_spent: u64,
+    pub power: u64,
+    /// Whether the asset itself is locked in until settlement
+    pub committed: bool,
+    pub bump: u8,
+}
+
+/// 🌳 State Root - append-only merkle tree over every (asset, level, rarity)
+/// for one epoch, built incrementally so only the right-hand frontier is stored
+#[account]
+#[derive(InitSpace)]
+pub struct StateRoot {
+    pub epoch: u64,
+    pub leaf_count: u64,
+    /// Left sibling awaiting a partner at each height of the tree
+    pub frontier: [[u8; 32]; STATE_ROOT_DEPTH],
+    /// Highest NftState address appended so far; pages must continue above it
+    pub last_state: Pubkey,
+    /// Published root; zero until finalized
+    pub root: [u8; 32],
+    pub finalized: bool,
+    pub bump: u8,
+}
+
+impl StateRoot {
+    pub fn append(&mut self, leaf: [u8; 32]) -> Result<()> {
+        require!(self.leaf_count < 1 << STATE_ROOT_DEPTH, NftError::StateRootFull);
+        let mut node = leaf;
+        let mut index = self.leaf_count;
+        for height in 0..STATE_ROOT_DEPTH {
+            if index & 1 == 0 {
+                self.frontier[height] = node;
+                break;
+            }
+            node = merkle_parent(&self.frontier[height], &node);
+            index >>= 1;
+        }
+        self.leaf_count += 1;
+        Ok(())
+    }
+
+    /// Root over the appended leaves, with empty subtrees padded by zero hashes
+    pub fn compute_root(&self) -> [u8; 32] {
+        let mut node = [0u8; 32];
+        let mut zero = [0u8; 32];
+        let mut size = self.leaf_count;
+        for height in 0..STATE_ROOT_DEPTH {
+            node = if size & 1 == 1 {
+                merkle_parent(&self.frontier[height], &node)
+            } else {
+                merkle_parent(&node, &zero)
+            };
+            zero = merkle_parent(&zero, &zero);
+            size >>= 1;
+        }
+        node
+    }
+
+    /// Check an asset's game state against the finalized root
+    pub fn verify(&self, asset: &Pubkey, level: u64, rarity: Rarity, proof: &[[u8; 32]]) -> bool {
+        self.finalized && verify_merkle_proof(proof, self.root, state_leaf(asset, level, rarity))
+    }
+}
+
+/// 🤝 Market Maker - collateralised account whose marketplace fees are
+/// accrued per trade and settled once a day in a single netted payment
+#[account]
+#[derive(InitSpace)]
+pub struct MarketMaker {
+    pub wallet: Pubkey,
+    /// Lamports escrowed in this PDA; accrued fees may never exceed it
+    pub collateral_lamports: u64,
+    /// Fees owed to the treasury since the last settlement
+    pub accrued_fees: u64,
+    pub deferred_trades: u32,
+    /// When the first unsettled fee was accrued
+    pub period_start: i64,
+    /// Missed a settlement; fees are charged per trade from then on
+    pub defaulted: bool,
+    pub bump: u8,
+}
+
+impl MarketMaker {
+    pub fn settlement_due(&self) -> i64 {
+        self.period_start + MARKET_MAKER_SETTLEMENT_PERIOD
+    }
+
+    /// Accrue `fee` if the maker is in good standing, its settlement isn't
+    /// overdue and collateral still covers everything owed
+    pub fn try_accrue(&mut self, fee: u64, now: i64) -> Result<bool> {
+        if self.defaulted || (self.accrued_fees > 0 && now >= self.settlement_due()) {
+            return Ok(false);
+        }
+        let accrued = self.accrued_fees.checked_add(fee).ok_or(NftError::MathOverflow)?;
+        if accrued > self.collateral_lamports {
+            return Ok(false);
+        }
+        if self.accrued_fees == 0 {
+            self.period_start = now;
+        }
+        self.accrued_fees = accrued;
+        self.deferred_trades += 1;
+        Ok(true)
+    }
+}
+
+/// 🔢 Edition Counter - next sequential edition number for a collection
+#[account]
+#[derive(InitSpace)]
+pub struct EditionCounter {
+    pub collection: Pubkey,
+    pub next_edition: u32,
+    pub bump: u8,
+}
+
+/// 🔢 A mint's place in its collection
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Edition {
+    pub number: u32,
+    /// 0 = uncapped
+    pub max_supply: u32,
+}
+
+impl Edition {
+    /// The edition `offset` places after this one
+    pub fn nth(self, offset: u32) -> Self {
+        Edition { number: self.number + offset, ..self }
+    }
+
+    /// "#0042 / 1000", or "#0042" for uncapped collections
+    pub fn label(&self) -> String {
+        match self.max_supply {
+            0 => format!("#{:04}", self.number),
+            max_supply => format!("#{:04} / {}", self.number, max_supply),
+        }
+    }
+}
+
+/// 🩺 Config Report - every consistency problem `validate_config` found;
+/// fields are only ever appended, with `version` bumped
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug)]
+pub struct ConfigReport {
+    pub version: u8,
+    pub collection: Pubkey,
+    pub valid: bool,
+    pub issues: Vec<ConfigIssue>,
+}
+
+/// 🩺 One problem in a collection's configuration
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ConfigIssue {
+    /// A basis-point setting is above 10 000
+    BpsOverMax { field: ConfigField },
+    /// A rarity tier's marketplace fee is above 10 000 bps
+    RarityFeeOverMax { rarity: Rarity },
+    /// Fusion outcome odds don't sum to 10 000 bps
+    FusionOutcomesIncomplete { total_bps: u32 },
+    /// VIP tiers aren't in ascending lifetime-spend order
+    VipTiersUnordered,
+    /// A mint phase's window is empty or starts before the previous one ends
+    PhaseWindowInvalid { index: u8 },
+    /// An allowlist-only phase is set without an allowlist root
+    AllowlistRootMissing { index: u8 },
+    /// A beta phase has no sunset scheduled, or runs past it
+    BetaSunsetMissing { index: u8 },
+    /// Max supply is below what is already minted or handed out as shard quota
+    SupplyBelowCommitted { committed: u32 },
+    /// Burn-to-mint names a whitelist token but burns none of it
+    BurnAmountZero,
+    /// Editions are numbered but the EditionCounter doesn't exist
+    EditionCounterMissing,
+    /// The energy refill price overflows before the daily refill cap
+    RefillCurveOverflows,
+    /// The energy shop can't sell any energy
+    ShopEmpty,
+}
+
+/// 🩺 Basis-point settings checked by `validate_config`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ConfigField {
+    TraitFee,
+    EvolutionDowngrade,
+    FusionFeeBurn,
+    SplitRetain,
+    XpTransferLoss,
+    SubscriptionDiscount,
+    VipDiscount,
+    Royalty,
+    RefillPriceStep,
+}
+
+impl ProgramConfig {
+    pub fn config_issues(&self) -> Vec<ConfigIssue> {
+        let mut issues = Vec::new();
+        let bps_fields = [
+            (ConfigField::TraitFee, self.trait_fee_bps),
+            (ConfigField::EvolutionDowngrade, self.evolution_downgrade_bps),
+            (ConfigField::FusionFeeBurn, self.fusion_fee.burn_bps),
+            (ConfigField::SplitRetain, self.split_retain_bps),
+            (ConfigField::XpTransferLoss, self.xp_transfer_loss_bps),
+            (ConfigField::SubscriptionDiscount, self.subscription_plan.fee_discount_bps),
+        ];
+        let vip_fields = self.vip_tiers.iter().map(|tier| (ConfigField::VipDiscount, tier.fee_discount_bps));
+        for (field, bps) in bps_fields.into_iter().chain(vip_fields) {
+            if bps > MAX_BPS {
+                issues.push(ConfigIssue::BpsOverMax { field });
+            }
+        }
+        for (tier, bps) in self.rarity_fee_bps.iter().enumerate() {
+            if *bps > MAX_BPS {
+                issues.push(ConfigIssue::RarityFeeOverMax {
+                    rarity: Rarity::from_name(rarity_name(tier as u8)).unwrap_or_default(),
+                });
+            }
+        }
+        if !self.fusion_outcome_table.is_valid() {
+            let table = &self.fusion_outcome_table;
+            issues.push(ConfigIssue::FusionOutcomesIncomplete {
+                total_bps: table.critical_bps as u32
+                    + table.upgraded_bps as u32
+                    + table.sideways_bps as u32
+                    + table.downgraded_bps as u32,
+            });
+        }
+        if !self
+            .vip_tiers
+            .windows(2)
+            .all(|pair| pair[0].min_lifetime_spend < pair[1].min_lifetime_spend)
+        {
+            issues.push(ConfigIssue::VipTiersUnordered);
+        }
+        issues
+    }
+}
+
+impl CollectionConfig {
+    pub fn config_issues(&self) -> Vec<ConfigIssue> {
+        let mut issues = Vec::new();
+        if self.royalty_bps > MAX_BPS {
+            issues.push(ConfigIssue::BpsOverMax { field: ConfigField::Royalty });
+        }
+        for (index, phase) in self.phases.iter().enumerate() {
+            let overlaps = index > 0 && self.phases[index - 1].ends_at > phase.starts_at;
+            if phase.starts_at >= phase.ends_at || overlaps {
+                issues.push(ConfigIssue::PhaseWindowInvalid { index: index as u8 });
+            }
+            if phase.allowlist_required && self.allowlist_root == [0; 32] {
+                issues.push(ConfigIssue::AllowlistRootMissing { index: index as u8 });
+            }
+            if phase.beta && (self.beta_sunset_at == 0 || phase.ends_at > self.beta_sunset_at) {
+                issues.push(ConfigIssue::BetaSunsetMissing { index: index as u8 });
+            }
+        }
+        let committed = self.committed_supply();
+        if self.max_supply != 0 && self.max_supply < committed {
+            issues.push(ConfigIssue::SupplyBelowCommitted { committed });
+        }
+        if self.burn_token_mint != Pubkey::default() && self.burn_token_amount == 0 {
+            issues.push(ConfigIssue::BurnAmountZero);
+        }
+        issues
+    }
+}
+
+impl ShopConfig {
+    pub fn config_issues(&self) -> Vec<ConfigIssue> {
+        let mut issues = Vec::new();
+        if self.refill_price_step_bps > MAX_BPS {
+            issues.push(ConfigIssue::BpsOverMax { field: ConfigField::RefillPriceStep });
+        }
+        if self.refill_energy_amount == 0 || self.max_refills_per_day == 0 || self.max_energy == 0 {
+            issues.push(ConfigIssue::ShopEmpty);
+        }
+        if self.refill_price(self.max_refills_per_day.saturating_sub(1)).is_err() {
+            issues.push(ConfigIssue::RefillCurveOverflows);
+        }
+        issues
+    }
+}
+
+/// 🛡️ Insurance Pool - a collection's self-funded evolution insurance:
+/// successful evolutions' stakes fund payouts on failed ones
+#[account]
+#[derive(InitSpace)]
+pub struct InsurancePool {
+    pub collection: Pubkey,
+    /// Share of a stake paid back on failure; above MAX_BPS pays a bonus from the pool
+    pub refund_bps: u16,
+    /// Achievement points granted to an asset whose insured evolution failed
+    pub pity_points: u64,
+    pub max_stake_lamports: u64,
+    /// Lamports free to pay out, from stakes of successful evolutions
+    pub balance_lamports: u64,
+    /// Stakes of insured evolutions not yet settled
+    pub outstanding_stakes: u64,
+    pub bump: u8,
+}
+
+/// 🛡️ Insurance - one asset's stake against its pending queued evolution
+#[account]
+#[derive(InitSpace)]
+pub struct Insurance {
+    pub asset: Pubkey,
+    pub owner: Pubkey,
+    pub stake_lamports: u64,
+    /// The asset's evolution count when insured; higher at settlement = success
+    pub evolution_count: u32,
+    pub bump: u8,
+}
+
+/// ✍️ A collection's program authority PDA, signing mpl-core CPIs in place of the payer
+#[derive(Clone, Copy)]
+pub struct ProgramSigner<'a, 'info> {
+    pub authority: &'a AccountInfo<'info>,
+    pub seeds: &'a [&'a [u8]],
+}
+
+/// ✍️ The `["attributes_authority"]` PDA holding assets' Attributes plugins, and its bump
+#[derive(Clone, Copy)]
+pub struct AttributesAuthority<'a, 'info> {
+    pub account: &'a AccountInfo<'info>,
+    pub bump: u8,
+}
+
+impl<'a, 'info> AttributesAuthority<'a, 'info> {
+    pub fn new(account: &'a AccountInfo<'info>, bump: u8) -> Self {
+        Self { account, bump }
+    }
+}
+
+/// 🪓 Cranker - bonded maintenance worker with priority on paid crank work
+#[account]
+#[derive(InitSpace)]
+pub struct Cranker {
+    pub wallet: Pubkey,
+    /// Lamports escrowed in this PDA above rent; slashed for invalid work
+    pub bond_lamports: u64,
+    pub jobs_completed: u64,
+    pub faults: u32,
+    pub bump: u8,
+}
+
+impl Cranker {
+    /// Priority needs the full bond; a slashed cranker tops up to regain it
+    pub fn is_bonded(&self) -> bool {
+        self.bond_lamports >= CRANKER_BOND
+    }
+
+    /// Take CRANKER_SLASH_BPS of the bond per fault; returns the lamports taken
+    pub fn slash(&mut self, faults: u32) -> Result<u64> {
+        let slashed = bps_of(self.bond_lamports, CRANKER_SLASH_BPS)?
+            .saturating_mul(faults as u64)
+            .min(self.bond_lamports);
+        self.bond_lamports -= slashed;
+        self.faults = self.faults.saturating_add(faults);
+        Ok(slashed)
+    }
+}
+
+/// 🏆 Achievement Tiers - a collection's own names for the mint achievement ladder
+#[account]
+#[derive(InitSpace)]
+pub struct AchievementTiers {
+    pub collection: Pubkey,
+    /// Ascending by `min_level`; the first starts at level 0
+    #[max_len(MAX_ACHIEVEMENT_TIERS)]
+    pub tiers: Vec<AchievementTier>,
+    pub bump: u8,
+}
+
+impl AchievementTiers {
+    /// Name of the highest tier `level` has reached
+    pub fn name_for(&self, level: u64) -> &str {
+        self.tiers
+            .iter()
+            .rev()
+            .find(|tier| level >= tier.min_level)
+            .map_or("", |tier| tier.name.as_str())
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug, InitSpace)]
+pub struct AchievementTier {
+    pub min_level: u64,
+    #[max_len(MAX_ACHIEVEMENT_NAME_LEN)]
+    pub name: String,
+}
+
+/// 🧬 Generation Map - which collection each fusion output generation goes into
+#[account]
+#[derive(InitSpace)]
+pub struct GenerationMap {
+    /// At most one route per generation
+    #[max_len(MAX_GENERATION_ROUTES)]
+    pub routes: Vec<GenerationRoute>,
+    pub bump: u8,
+}
+
+impl GenerationMap {
+    /// The collection outputs of `generation` must be in, if routed
+    pub fn collection_for(&self, generation: u8) -> Option<Pubkey> {
+        self.routes.iter().find(|route| route.generation == generation).map(|route| route.collection)
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, InitSpace)]
+pub struct GenerationRoute {
+    pub generation: u8,
+    pub collection: Pubkey,
+}
+
+/// 📨 Pending Claim - an asset minted into custody, owned by this PDA until
+/// the holder of its claim code shows up with a wallet
+#[account]
+#[derive(InitSpace)]
+pub struct PendingClaim {
+    /// Public half of the ed25519 keypair seeded by `hashv(["custodial_claim", code])`
+    pub claim_key: Pubkey,
+    pub asset: Pubkey,
+    pub collection: Pubkey,
+    /// Onboarding partner that minted it; gets the rent back on claim
+    pub partner: Pubkey,
+    pub created_at: i64,
+    pub bump: u8,
+}
+
+/// 📉 Floor Oracle - rolling per-rarity floor estimates from marketplace sales
+///
+/// Fixed layout for synchronous reads: after the 8-byte discriminator come
+/// `bump: u8` then one FloorEstimate per rarity, Common first.
+#[account]
+#[derive(InitSpace)]
+pub struct FloorOracle {
+    pub bump: u8,
+    pub floors: [FloorEstimate; 7],
+}
+
+/// 📉 One rarity's floor: an EMA that follows cheaper sales faster than
+/// dearer ones, so it tracks the floor rather than the average
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, Default, InitSpace)]
+pub struct FloorEstimate {
+    pub ema_lamports: u64,
+    pub last_sale_lamports: u64,
+    pub sales: u32,
+    pub updated_at: i64,
+}
+
+impl FloorOracle {
+    /// Fold a sale into its rarity's estimate; the first sale seeds it
+    pub fn record_sale(&mut self, rarity: Rarity, price_lamports: u64, now: i64) -> Result<()> {
+        let floor = &mut self.floors[rarity as usize];
+        floor.ema_lamports = if floor.sales == 0 {
+            price_lamports
+        } else if price_lamports < floor.ema_lamports {
+            floor.ema_lamports - bps_of(floor.ema_lamports - price_lamports, FLOOR_EMA_DOWN_BPS)?
+        } else {
+            floor.ema_lamports + bps_of(price_lamports - floor.ema_lamports, FLOOR_EMA_UP_BPS)?
+        };
+        floor.last_sale_lamports = price_lamports;
+        floor.sales = floor.sales.saturating_add(1);
+        floor.updated_at = now;
+        Ok(())
+    }
+}
+
+/// 🗳️ Poll - holders choosing one value for a CollectionConfig parameter,
+/// one vote per asset; `apply_poll_result` writes the winner once voting
+/// closes with quorum
+#[account]
+#[derive(InitSpace)]
+pub struct Poll {
+    pub collection: Pubkey,
+    pub poll_id: u64,
+    pub parameter: PollParameter,
+    /// Distinct values the parameter can take
+    #[max_len(MAX_POLL_OPTIONS)]
+    pub options: Vec<u64>,
+    /// Votes per option, in `options` order
+    #[max_len(MAX_POLL_OPTIONS)]
+    pub tallies: Vec<u64>,
+    /// Fewest votes in total for the result to be applied
+    pub quorum: u64,
+    pub ends_at: i64,
+    pub applied: bool,
+    pub bump: u8,
+}
+
+impl Poll {
+    pub fn total_votes(&self) -> u64 {
+        self.tallies.iter().sum()
+    }
+
+    /// The most-voted option's value; ties go to the earlier option
+    pub fn winning_value(&self) -> u64 {
+        let mut winner = 0;
+        for (i, tally) in self.tallies.iter().enumerate() {
+            if *tally > self.tallies[winner] {
+                winner = i;
+            }
+        }
+        self.options[winner]
+    }
+}
+
+/// 🗳️ The CollectionConfig parameters holders can vote on
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, InitSpace)]
+pub enum PollParameter {
+    /// `min_update_cooldown`, in seconds
+    UpdateCooldown,
+    MaxPerWallet,
+    MintPriceLamports,
+    RewardSplitBps,
+}
+
+impl PollParameter {
+    /// Whether `value` fits the parameter's field and range
+    pub fn accepts(self, value: u64) -> bool {
+        match self {
+            PollParameter::UpdateCooldown => i64::try_from(value).is_ok(),
+            PollParameter::MaxPerWallet => u32::try_from(value).is_ok(),
+            PollParameter::MintPriceLamports => true,
+            PollParameter::RewardSplitBps => value <= MAX_BPS as u64,
+        }
+    }
+
+    /// Write an accepted `value` into the parameter's field
+    pub fn apply(self, config: &mut CollectionConfig, value: u64) {
+        match self {
+            PollParameter::UpdateCooldown => config.min_update_cooldown = value as i64,
+            PollParameter::MaxPerWallet => config.max_per_wallet = value as u32,
+            PollParameter::MintPriceLamports => config.mint_price_lamports = value,
+            PollParameter::RewardSplitBps => config.reward_split_bps = value as u16,
+        }
+    }
+}
+
+/// 🧾 Poll Vote - `["poll_vote", poll, asset]`, marks an asset as having voted
+#[account]
+#[derive(InitSpace)]
+pub struct PollVote {
+    pub option: u8,
+}
+
+/// 🎯 Comprehensive Error Codes for Better UX
+///
+/// Each message is a stable short code; clients look it up under
+/// `ProgramConfig.docs_base_uri` for a localized explanation
+#[error_code]
+pub enum NftError {
+    /// Cannot update metadata too soon - cooldown period active
+    #[msg("update-too-soon")]
+    UpdateTooSoon,
     
-    /// CHECK: Handled by mpl-core
-    #[account(mut)]
-    pub asset: AccountInfo<'info>,
+    /// Level progression must be forward-only - cannot decrease level
+    #[msg("invalid-level-progression")]
+    InvalidLevelProgression,
     
-    #[account(
-        init_if_needed,
-        payer = payer,
-        space = 8 + 8 + 32 + 8 + 8 + 8 + 8 + 8, // Enhanced space for new fields
-        seeds = [b"nft_state", asset.key().as_ref()],
-        bump
-    )]
-    pub nft_state: Account<'info, NftState>,
+    /// NFT is not ready for evolution - time requirement not met
+    #[msg("evolution-not-ready")]
+    EvolutionNotReady,
     
-    /// CHECK: Metaplex Core program
-    pub mpl_core_program: AccountInfo<'info>,
+    /// Evolution failed - probability check unsuccessful (no longer raised)
+    #[msg("evolution-failed")]
+    EvolutionFailed,
     
-    pub system_program: Program<'info, System>,
+    /// Cannot fuse the same NFT with itself
+    #[msg("cannot-fuse-same-nft")]
+    CannotFuseSameNFT,
+    
+    /// Fusion requirements not met - check NFT compatibility
+    #[msg("fusion-requirements-not-met")]
+    FusionRequirementsNotMet,
+    
+    /// Invalid rarity level specified
+    #[msg("invalid-rarity")]
+    InvalidRarity,
+    
+    /// Achievement points insufficient for this action
+    #[msg("insufficient-achievement-points")]
+    InsufficientAchievementPoints,
+    
+    /// Time-locked feature - wait for unlock period
+    #[msg("time-locked-feature")]
+    TimeLockedFeature,
+    
+    /// Fusion potential exhausted
+    #[msg("fusion-potential-exhausted")]
+    FusionPotentialExhausted,
+    
+    /// Signer is not authorized for this action
+    #[msg("unauthorized")]
+    Unauthorized,
+    
+    /// Fee basis points cannot exceed 10000
+    #[msg("invalid-fee-bps")]
+    InvalidFeeBps,
+    
+    /// Arithmetic overflow
+    #[msg("math-overflow")]
+    MathOverflow,
+    
+    /// Account is not a valid Metaplex Core asset
+    #[msg("invalid-asset")]
+    InvalidAsset,
+    
+    /// Signer does not own this asset
+    #[msg("not-asset-owner")]
+    NotAssetOwner,
+    
+    /// Core attributes cannot be traded
+    #[msg("trait-not-tradable")]
+    TraitNotTradable,
+    
+    /// Trait not found on asset
+    #[msg("trait-not-found")]
+    TraitNotFound,
+    
+    /// Asset already has a trait with this key
+    #[msg("trait-already-present")]
+    TraitAlreadyPresent,
+    
+    /// Trait value exceeds maximum length
+    #[msg("trait-value-too-long")]
+    TraitValueTooLong,
+    
+    /// Trait price must be greater than zero
+    #[msg("invalid-trait-price")]
+    InvalidTraitPrice,
+    
+    /// Attribute limits must leave room for all core attributes
+    #[msg("invalid-attribute-limits")]
+    InvalidAttributeLimits,
+    
+    /// Asset attribute count or size limit exceeded
+    #[msg("attribute-limit-exceeded")]
+    AttributeLimitExceeded,
+    
+    /// Treasury balance too low for this payout
+    #[msg("insufficient-treasury-balance")]
+    InsufficientTreasuryBalance,
+    
+    /// Tournament size must be a power of two between 2 and 16
+    #[msg("invalid-tournament-size")]
+    InvalidTournamentSize,
+    
+    /// Tournament registration must close in the future, and before its completion deadline
+    #[msg("invalid-tournament-schedule")]
+    InvalidTournamentSchedule,
+    
+    /// Tournament registration is closed
+    #[msg("tournament-registration-closed")]
+    TournamentRegistrationClosed,
+    
+    /// Tournament is full
+    #[msg("tournament-full")]
+    TournamentFull,
+    
+    /// Asset is already entered in this tournament
+    #[msg("already-entered-tournament")]
+    AlreadyEnteredTournament,
+    
+    /// Tournament has not started yet
+    #[msg("tournament-not-started")]
+    TournamentNotStarted,
+    
+    /// Tournament already has a champion
+    #[msg("tournament-finished")]
+    TournamentFinished,
+    
+    /// Tournament needs at least two entrants
+    #[msg("not-enough-entrants")]
+    NotEnoughEntrants,
+    
+    /// Invalid match or winner for the current round
+    #[msg("invalid-match")]
+    InvalidMatch,
+    
+    /// Match result already reported
+    #[msg("match-already-reported")]
+    MatchAlreadyReported,
+    
+    /// Tournament has no champion yet
+    #[msg("tournament-not-finished")]
+    TournamentNotFinished,
+    
+    /// Tournament prize already claimed
+    #[msg("prize-already-claimed")]
+    PrizeAlreadyClaimed,
+    
+    /// Asset is not the tournament champion
+    #[msg("not-tournament-champion")]
+    NotTournamentChampion,
+    
+    /// Escrow balance too low for this payout
+    #[msg("insufficient-escrow-balance")]
+    InsufficientEscrowBalance,
+    
+    /// Quote-based minting is not enabled
+    #[msg("quotes-disabled")]
+    QuotesDisabled,
+    
+    /// Price quote has expired
+    #[msg("quote-expired")]
+    QuoteExpired,
+    
+    /// Missing or invalid ed25519 signature instruction
+    #[msg("invalid-signature")]
+    InvalidSignature,
+    
+    /// Rental rate and duration must be positive and within the offer
+    #[msg("invalid-rental-terms")]
+    InvalidRentalTerms,
+    
+    /// Asset is currently rented
+    #[msg("rental-active")]
+    RentalActive,
+    
+    /// Asset has no active rental
+    #[msg("no-active-rental")]
+    NoActiveRental,
+    
+    /// Rental term has already ended
+    #[msg("rental-expired")]
+    RentalExpired,
+    
+    /// Nothing to claim yet
+    #[msg("nothing-to-claim")]
+    NothingToClaim,
+    
+    /// Asset is locked by a pending operation
+    #[msg("asset-locked")]
+    AssetLocked,
+    
+    /// Fusion confirmation window must be positive
+    #[msg("invalid-fusion-window")]
+    InvalidFusionWindow,
+    
+    /// Fusion confirmation window has expired - cancel to unlock inputs
+    #[msg("fusion-window-expired")]
+    FusionWindowExpired,
+    
+    /// Invalid set definition
+    #[msg("invalid-set-definition")]
+    InvalidSetDefinition,
+    
+    /// Wallet does not own a complete set that includes the target asset
+    #[msg("set-incomplete")]
+    SetIncomplete,
+    
+    /// Dispute window must be positive
+    #[msg("invalid-dispute-window")]
+    InvalidDisputeWindow,
+    
+    /// Checkpoint not found
+    #[msg("checkpoint-not-found")]
+    CheckpointNotFound,
+    
+    /// Checkpoint has already been restored
+    #[msg("checkpoint-already-restored")]
+    CheckpointAlreadyRestored,
+    
+    /// Dispute window for this checkpoint has closed
+    #[msg("dispute-window-closed")]
+    DisputeWindowClosed,
+    
+    /// Invalid shop configuration
+    #[msg("invalid-shop-config")]
+    InvalidShopConfig,
+    
+    /// Daily energy refill limit reached
+    #[msg("daily-refill-limit-reached")]
+    DailyRefillLimitReached,
+    
+    /// Energy is already full
+    #[msg("energy-full")]
+    EnergyFull,
+    
+    /// Invalid mint phase configuration
+    #[msg("invalid-phase-config")]
+    InvalidPhaseConfig,
+    
+    /// Mint phase is not active
+    #[msg("phase-not-active")]
+    PhaseNotActive,
+    
+    /// Remaining accounts are malformed
+    #[msg("invalid-remaining-accounts")]
+    InvalidRemainingAccounts,
+    
+    /// Not enough qualifying assets held from the prior collection
+    #[msg("holder-requirement-not-met")]
+    HolderRequirementNotMet,
+    
+    /// Account is not the state PDA for this asset
+    #[msg("invalid-nft-state")]
+    InvalidNftState,
+    
+    /// Invalid merkle proof
+    #[msg("invalid-merkle-proof")]
+    InvalidMerkleProof,
+    
+    /// Nothing to update
+    #[msg("nothing-to-update")]
+    NothingToUpdate,
+    
+    /// Name and URI are locked after the first evolution
+    #[msg("identity-locked")]
+    IdentityLocked,
+    
+    /// Airdrop needs 1 to MAX_AIRDROP_RECIPIENTS recipients and a non-zero amount
+    #[msg("invalid-airdrop-terms")]
+    InvalidAirdropTerms,
+    
+    /// Airdrop index is out of range
+    #[msg("invalid-airdrop-index")]
+    InvalidAirdropIndex,
+    
+    /// Airdrop allocation already delivered
+    #[msg("airdrop-already-delivered")]
+    AirdropAlreadyDelivered,
+    
+    /// Asset does not belong to the expected collection
+    #[msg("collection-mismatch")]
+    CollectionMismatch,
+    
+    /// Evolution queue is full
+    #[msg("evolution-queue-full")]
+    EvolutionQueueFull,
+    
+    /// No queued evolutions were ready to process
+    #[msg("nothing-to-process")]
+    NothingToProcess,
+    
+    /// SlotHashes sysvar data is malformed
+    #[msg("invalid-slot-hashes")]
+    InvalidSlotHashes,
+    
+    /// VIP tiers must be ascending by spend with discounts within 100%
+    #[msg("invalid-vip-tiers")]
+    InvalidVipTiers,
+    
+    /// Base URI is empty, too long, or unchanged
+    #[msg("invalid-base-uri")]
+    InvalidBaseUri,
+    
+    /// A base URI rotation is already pending
+    #[msg("uri-rotation-pending")]
+    UriRotationPending,
+    
+    /// Asset already uses the current base URI
+    #[msg("asset-uri-current")]
+    AssetUriCurrent,
+    
+    /// Asset URI does not match any recorded base URI
+    #[msg("unknown-base-uri")]
+    UnknownBaseUri,
+    
+    /// Fusion outcome odds must sum to 10000 bps
+    #[msg("invalid-fusion-outcome-table")]
+    InvalidFusionOutcomeTable,
+    
+    /// No successor program has been configured
+    #[msg("no-successor-program")]
+    NoSuccessorProgram,
+    
+    /// Predecessor returned no valid state export
+    #[msg("invalid-state-export")]
+    InvalidStateExport,
+    
+    /// Listing price must be greater than zero
+    #[msg("invalid-listing-price")]
+    InvalidListingPrice,
+    
+    /// NFT changed since it was listed
+    #[msg("listing-stale")]
+    ListingStale,
+    
+    /// Mock clock is only available in test-clock builds
+    #[msg("test-clock-disabled")]
+    TestClockDisabled,
+
+    /// Delegation does not cover this action
+    #[msg("delegation-scope-denied")]
+    DelegationScopeDenied,
+
+    /// Lottery needs a positive epoch length and prize
+    #[msg("invalid-lottery-terms")]
+    InvalidLotteryTerms,
+
+    /// Lottery epoch has not ended yet
+    #[msg("lottery-not-due")]
+    LotteryNotDue,
+
+    /// No assets are currently staked
+    #[msg("no-staked-assets")]
+    NoStakedAssets,
+
+    /// Staked index is full
+    #[msg("staked-index-full")]
+    StakedIndexFull,
+
+    /// Collection is region-locked and needs a KYC oracle attestation
+    #[msg("region-attestation-required")]
+    RegionAttestationRequired,
+
+    /// Region attestation has expired
+    #[msg("region-attestation-expired")]
+    RegionAttestationExpired,
+
+    /// NftState is not bound to this asset
+    #[msg("nft-state-asset-mismatch")]
+    NftStateAssetMismatch,
+
+    /// Rule table versions can only move forward
+    #[msg("rule-version-regressed")]
+    RuleVersionRegressed,
+
+    /// Snapshots can only be taken for the current epoch
+    #[msg("snapshot-epoch-closed")]
+    SnapshotEpochClosed,
+
+    /// Snapshot pages must list NftStates in ascending address order
+    #[msg("snapshot-out-of-order")]
+    SnapshotOutOfOrder,
+
+    /// Bounty needs a non-zero reward and a URI within limits
+    #[msg("invalid-bounty-terms")]
+    InvalidBountyTerms,
+
+    /// Commemorative NFT accounts are required for this bounty
+    #[msg("commemorative-accounts-missing")]
+    CommemorativeAccountsMissing,
+
+    /// Global event needs a short name, cooldown cut ≤ 100% and start before end
+    #[msg("invalid-global-event")]
+    InvalidGlobalEvent,
+
+    /// Subscription plan needs a positive period and a discount of at most 100%
+    #[msg("invalid-subscription-plan")]
+    InvalidSubscriptionPlan,
+
+    /// Subscriptions are not enabled
+    #[msg("subscriptions-disabled")]
+    SubscriptionsDisabled,
+    /// 🎲 Randomness provider is not supported by this deployment (no longer raised)
+    #[msg("randomness-provider-unavailable")]
+    RandomnessProviderUnavailable,
+    /// 📜 History page cap must be at least one page
+    #[msg("invalid-history-cap")]
+    InvalidHistoryCap,
+    /// 🚨 Invalid forced recall terms
+    #[msg("invalid-recall-terms")]
+    InvalidRecallTerms,
+    /// ⏳ Forced recall timelock has not elapsed
+    #[msg("recall-timelock-active")]
+    RecallTimelockActive,
+    /// 🚨 Forced recall already executed
+    #[msg("recall-already-executed")]
+    RecallAlreadyExecuted,
+    /// ✂️ Asset is too low-level to split
+    #[msg("split-requirements-not-met")]
+    SplitRequirementsNotMet,
+    /// 🚫 Asset attributes currently bar it from being transferred
+    #[msg("transfer-restricted")]
+    TransferRestricted,
+    /// 🏛️ Collection max supply reached
+    #[msg("supply-exhausted")]
+    SupplyExhausted,
+    /// 🎓 Experience transfer would deliver nothing
+    #[msg("invalid-xp-transfer")]
+    InvalidXpTransfer,
+    /// 🎓 Donor asset would drop below the minimum level
+    #[msg("donor-below-level-floor")]
+    DonorBelowLevelFloor,
+    /// 🏛️ Max supply can't be set below the number already minted
+    #[msg("invalid-max-supply")]
+    InvalidMaxSupply,
+    /// This mint shard hasn't been initialized - run reconcile_counters for it first
+    #[msg("mint-shard-not-ready")]
+    MintShardNotReady,
+    /// Mint shard index is out of range
+    #[msg("invalid-mint-shard")]
+    InvalidMintShard,
+    /// Batch mint needs 1 to MAX_BATCH_MINT entries, each with a signing asset and its NftState PDA
+    #[msg("invalid-batch-mint")]
+    InvalidBatchMint,
+    /// This wallet has reached the collection's mint limit
+    #[msg("mint-limit-exceeded")]
+    MintLimitExceeded,
+    /// Objective needs a positive target, a future deadline and a non-zero contribution
+    #[msg("invalid-objective-terms")]
+    InvalidObjectiveTerms,
+    /// This objective's deadline has passed
+    #[msg("objective-closed")]
+    ObjectiveClosed,
+    /// This objective is still open, or has unsettled contributions
+    #[msg("objective-still-open")]
+    ObjectiveStillOpen,
+    /// Mint phases must be time-ordered, non-overlapping, at most MAX_MINT_PHASES, and have an allowlist root if any require one
+    #[msg("invalid-mint-phases")]
+    InvalidMintPhases,
+    /// Wallet isn't on the collection's allowlist, or the phase is allowlist-only
+    #[msg("not-on-allowlist")]
+    NotOnAllowlist,
+
+    /// This epoch's state root is already finalized
+    #[msg("state-root-finalized")]
+    StateRootFinalized,
+
+    /// State root tree is full
+    #[msg("state-root-full")]
+    StateRootFull,
+
+    /// This collection does not accept token payment
+    #[msg("token-payment-disabled")]
+    TokenPaymentDisabled,
+
+    /// Token account is not for the collection's payment mint
+    #[msg("invalid-payment-mint")]
+    InvalidPaymentMint,
+
+    /// Market makers must escrow at least the minimum collateral
+    #[msg("insufficient-collateral")]
+    InsufficientCollateral,
+
+    /// Market maker settlement is not past its grace period
+    #[msg("market-maker-not-in-default")]
+    MarketMakerNotInDefault,
+
+    /// Market maker has unsettled fees
+    #[msg("market-maker-unsettled")]
+    MarketMakerUnsettled,
+
+    /// Burn-to-mint needs the collection's configured whitelist token or burn-collection asset
+    #[msg("invalid-burn-payment")]
+    InvalidBurnPayment,
+
+    /// Name record is not the .sol domain's Name Service account, or is owned by another wallet
+    #[msg("invalid-name-record")]
+    InvalidNameRecord,
+
+    /// This collection numbers its editions; pass its EditionCounter
+    #[msg("edition-counter-missing")]
+    EditionCounterMissing,
+
+    /// Insurance needs a positive stake within the collection's maximum
+    #[msg("invalid-insurance-terms")]
+    InvalidInsuranceTerms,
+
+    /// Only an asset with a queued evolution can be insured
+    #[msg("evolution-not-queued")]
+    EvolutionNotQueued,
+
+    /// The insured evolution has not been finalized yet
+    #[msg("evolution-still-pending")]
+    EvolutionStillPending,
+
+    /// Team allocation can't drop below team mints or push committed supply past max supply
+    #[msg("invalid-team-allocation")]
+    InvalidTeamAllocation,
+
+    /// This collection's team allocation has been fully minted
+    #[msg("team-allocation-exhausted")]
+    TeamAllocationExhausted,
+
+    /// Voucher minting is not enabled
+    #[msg("vouchers-disabled")]
+    VouchersDisabled,
+
+    /// Mint voucher has expired
+    #[msg("voucher-expired")]
+    VoucherExpired,
+
+    /// The recipient account isn't the voucher's recipient
+    #[msg("voucher-recipient-mismatch")]
+    VoucherRecipientMismatch,
+
+    /// Achievement tiers must start at level 0, ascend, and have short non-empty names
+    #[msg("invalid-achievement-tiers")]
+    InvalidAchievementTiers,
+
+    /// Beta sunset must come after every beta phase ends
+    #[msg("invalid-beta-sunset")]
+    InvalidBetaSunset,
+
+    /// The collection's beta sunset hasn't been reached
+    #[msg("beta-sunset-not-reached")]
+    BetaSunsetNotReached,
+
+    /// The collection must be handed to its program authority first
+    #[msg("program-authority-required")]
+    ProgramAuthorityRequired,
+
+    /// Exponential price curves need a 1-10 000 bps step and a non-zero interval
+    #[msg("invalid-price-curve")]
+    InvalidPriceCurve,
+
+    /// This wallet already claimed that reward - wallet cooldown active
+    #[msg("wallet-cooldown-active")]
+    WalletCooldownActive,
+
+    /// Anti-bot collections can't be minted through another program
+    #[msg("mint-via-cpi")]
+    MintViaCpi,
+
+    /// Anti-bot collections allow one mint per transaction
+    #[msg("multiple-mints-in-transaction")]
+    MultipleMintsInTransaction,
+
+    /// Claim code's key didn't sign this claim to this destination
+    #[msg("invalid-claim-code")]
+    InvalidClaimCode,
+
+    /// Reveal deadline must be in the future and the drop not already escrowed
+    #[msg("invalid-refund-deadline")]
+    InvalidRefundDeadline,
+
+    /// This collection's drop isn't escrowed
+    #[msg("drop-not-escrowed")]
+    DropNotEscrowed,
+
+    /// Escrowed drop proceeds are locked until the drop is finalized
+    #[msg("drop-not-finalized")]
+    DropNotFinalized,
+
+    /// The reveal deadline has passed; the drop can no longer be finalized
+    #[msg("refund-deadline-passed")]
+    RefundDeadlinePassed,
+
+    /// Refunds open only after the reveal deadline
+    #[msg("refund-deadline-not-reached")]
+    RefundDeadlineNotReached,
+
+    /// No escrowed mints left to refund
+    #[msg("nothing-to-refund")]
+    NothingToRefund,
+
+    /// Royalty split needs at most 5 distinct creators whose shares sum to 100
+    #[msg("invalid-royalty-split")]
+    InvalidRoyaltySplit,
+
+    /// Asset is already frozen
+    #[msg("asset-already-frozen")]
+    AssetAlreadyFrozen,
+
+    /// Asset's FreezeDelegate plugin is not held by the program
+    #[msg("freeze-authority-mismatch")]
+    FreezeAuthorityMismatch,
+
+    /// Reward pool epoch must be positive
+    #[msg("invalid-reward-epoch")]
+    InvalidRewardEpoch,
+
+    /// Collection was already rebalanced this epoch
+    #[msg("rebalance-not-due")]
+    RebalanceNotDue,
+
+    /// NftState was written by a newer layout version
+    #[msg("unsupported-state-version")]
+    UnsupportedStateVersion,
+
+    /// A deal needs 1 to MAX_DEAL_ASSETS offered assets and at most MAX_DEAL_ASSETS asked
+    #[msg("invalid-deal-bundle")]
+    InvalidDealBundle,
+
+    /// This deal is reserved for another counterparty
+    #[msg("not-deal-counterparty")]
+    NotDealCounterparty,
+
+    /// Only the deal's seller or counterparty can cancel it
+    #[msg("not-deal-party")]
+    NotDealParty,
+
+    /// Asset does not match the deal
+    #[msg("deal-asset-mismatch")]
+    DealAssetMismatch,
+
+    /// Generation routes must be unique, at most MAX_GENERATION_ROUTES, and for Gen2 or later
+    #[msg("invalid-generation-map")]
+    InvalidGenerationMap,
+
+    /// Fusion output must be in the collection its generation is routed to
+    #[msg("generation-route-mismatch")]
+    GenerationRouteMismatch,
+
+    /// Collection plugin data exceeds the program's name, URI or attribute limits
+    #[msg("invalid-collection-plugin")]
+    InvalidCollectionPlugin,
+
+    /// Poll needs 2-MAX_POLL_OPTIONS distinct valid options, a quorum and an end in the future
+    #[msg("invalid-poll")]
+    InvalidPoll,
+
+    /// Voting in this poll has closed
+    #[msg("poll-closed")]
+    PollClosed,
+
+    /// Poll has no such option
+    #[msg("invalid-poll-option")]
+    InvalidPollOption,
+
+    /// Votes need an (asset, poll_vote) pair per asset, with each asset's PollVote PDA
+    #[msg("invalid-poll-vote")]
+    InvalidPollVote,
+
+    /// This asset already voted in the poll
+    #[msg("asset-already-voted")]
+    AssetAlreadyVoted,
+
+    /// Poll results can only be applied once voting has closed
+    #[msg("poll-still-open")]
+    PollStillOpen,
+
+    /// This poll's result was already applied
+    #[msg("poll-already-applied")]
+    PollAlreadyApplied,
+
+    /// Not enough votes were cast to reach the poll's quorum
+    #[msg("poll-quorum-not-reached")]
+    PollQuorumNotReached,
+
+    /// Asset's Attributes plugin is still held by its update authority; run migrate_attributes_authority first
+    #[msg("attributes-not-migrated")]
+    AttributesNotMigrated,
+    /// The collection is not the one the voucher was signed for
+    #[msg("voucher-collection-mismatch")]
+    VoucherCollectionMismatch,
+    /// A lottery draw is committed and waiting for its reveal slot
+    #[msg("lottery-reveal-pending")]
+    LotteryRevealPending,
+    /// Tournament passed its completion deadline or was cancelled
+    #[msg("tournament-expired")]
+    TournamentExpired,
+    /// Tournament can only be cancelled once it stalls past its deadline
+    #[msg("tournament-not-stalled")]
+    TournamentNotStalled,
+    /// Entry fees are only refunded from a cancelled tournament
+    #[msg("tournament-not-cancelled")]
+    TournamentNotCancelled,
+    /// This operation must checkpoint the asset first; create its AssetHistory
+    #[msg("asset-history-required")]
+    AssetHistoryRequired,
+    /// Attribute list doesn't hash to the checkpoint's attributes hash
+    #[msg("checkpoint-attributes-mismatch")]
+    CheckpointAttributesMismatch,
+    /// Checkpointed asset no longer exists, so it can't be restored in place
+    #[msg("checkpoint-not-restorable")]
+    CheckpointNotRestorable,
+    /// 📈 Mint shard must be reconciled before more curved-price mints
+    #[msg("curve-shard-backlog")]
+    CurveShardBacklog,
+    /// 🎲 Fusion roll isn't revealed yet - confirm after its reveal slot
+    #[msg("fusion-reveal-pending")]
+    FusionRevealPending,
+    /// 🎲 Fusion roll is revealed - confirm it, or cancel once the window closes
+    #[msg("fusion-revealed")]
+    FusionRevealed,
+    /// 🎲 Committed roll isn't revealed yet - call again after its reveal slot
+    #[msg("roll-reveal-pending")]
+    RollRevealPending,
+    /// 🎲 Committed roll was opened for a different action or different inputs
+    #[msg("roll-commitment-mismatch")]
+    RollCommitmentMismatch,
+    /// 🧪 Fusion result asset already has an NftState
+    #[msg("fusion-result-has-state")]
+    FusionResultHasState,
+    /// 👤 Account is not the expected wallet's PlayerProfile PDA
+    #[msg("invalid-player-profile")]
+    InvalidPlayerProfile,
+}
+
+/// 📐 Attribute keys managed by the program - never tradable as cosmetics
+pub const CORE_ATTRIBUTE_KEYS: [&str; 25] = [
+    "level",
+    "rarity",
+    "mint_date",
+    "mint_hour",
+    "fusion_potential",
+    "fusion_bonus",
+    "fusion_bonus_used",
+    "fusion_multiplier",
+    "fusion_type",
+    "fused_at",
+    "achievement_level",
+    "last_updated",
+    "bonus_experience",
+    "cooldown_multiplier",
+    "evolved_at",
+    "evolution_count",
+    "evolution_chance",
+    "tournament_wins",
+    "last_tournament_won",
+    "owner_handle",
+    "owner_handle_wallet",
+    "edition",
+    "beta",
+    "soulbound",
+    "generation",
+];
+
+pub const MAX_BPS: u16 = 10_000;
+pub const MAX_TRAIT_KEY_LEN: usize = 32;
+pub const MAX_TRAIT_VALUE_LEN: usize = 64;
+pub const DEFAULT_MAX_ATTRIBUTES: u16 = 32;
+pub const DEFAULT_MAX_ATTRIBUTE_BYTES: u32 = 2048;
+pub const MAX_TOURNAMENT_ENTRANTS: usize = 16;
+pub const MAX_FUSION_TYPE_LEN: usize = 32;
+pub const MAX_SET_REQUIREMENTS: usize = 8;
+pub const DEFAULT_FUSION_CONFIRMATION_WINDOW: i64 = 600;
+pub const DEFAULT_DISPUTE_WINDOW: i64 = 3 * 86400;
+pub const MAX_CHECKPOINTS: usize = 4;
+pub const SIGNAL_BOARD_SLOTS: usize = 16;
+pub const MAX_AIRDROP_RECIPIENTS: u32 = 16_384;
+pub const MAX_QUEUED_EVOLUTIONS: usize = 32;
+pub const MAX_STAKED_ASSETS: usize = 128;
+pub const MAX_GLOBAL_EVENT_NAME_LEN: usize = 32;
+pub const MAX_HISTORY_PAGE_ENTRIES: usize = 32;
+pub const DEFAULT_MAX_HISTORY_PAGES: u32 = 8;
+pub const LEGENDARY_LUCK_COST: u32 = 7;
+/// Every instruction that mints, counted by `enforce_single_mint`
+pub const MINT_DISCRIMINATORS: [&[u8]; 13] = [
+    instruction::MintNft::DISCRIMINATOR,
+    instruction::MintAllowlisted::DISCRIMINATOR,
+    instruction::MintNftWithToken::DISCRIMINATOR,
+    instruction::MintWithBurn::DISCRIMINATOR,
+    instruction::MintNftBatch::DISCRIMINATOR,
+    instruction::MintNftWithQuote::DISCRIMINATOR,
+    instruction::MintWithVoucher::DISCRIMINATOR,
+    instruction::MintNftHolderPhase::DISCRIMINATOR,
+    instruction::MintAndStake::DISCRIMINATOR,
+    instruction::CommitStarterPack::DISCRIMINATOR,
+    instruction::MintStarterPack::DISCRIMINATOR,
+    instruction::MintReserved::DISCRIMINATOR,
+    instruction::MintCustodial::DISCRIMINATOR,
+];
+pub const WALLET_REWARD_KINDS: usize = 2;
+pub const CHECK_IN_COOLDOWN: i64 = 86400;
+/// Luck a check-in adds on top of the streak's own
+pub const CHECK_IN_LUCK: u32 = 1;
+/// Staked time per idle level, and the most levels one claim can bank
+pub const IDLE_XP_SECONDS: i64 = 6 * 3600;
+pub const IDLE_XP_MAX_LEVELS: u64 = 4;
+pub const IDLE_XP_WALLET_COOLDOWN: i64 = 86400;
+/// Cumulative starter pack odds in bps: Common, Uncommon, Rare, Epic, Legendary
+pub const STARTER_PACK_WEIGHTS_BPS: [u16; 5] = [6_000, 8_500, 9_500, 9_900, 10_000];
+pub const EVOLUTION_REVEAL_DELAY_SLOTS: u64 = 4;
+pub const LOTTERY_REVEAL_DELAY_SLOTS: u64 = 4;
+pub const FUSION_REVEAL_DELAY_SLOTS: u64 = 4;
+pub const ROLL_REVEAL_DELAY_SLOTS: u64 = 4;
+pub const MAX_VIP_TIERS: usize = 4;
+pub const MAX_TRANSFER_RESTRICTIONS: usize = 4;
+/// Weight a sale below / above a rarity's floor estimate gets in its EMA
+pub const FLOOR_EMA_DOWN_BPS: u16 = 5_000;
+pub const FLOOR_EMA_UP_BPS: u16 = 1_000;
+/// Common pays the most, Divine the least
+pub const DEFAULT_RARITY_FEE_BPS: [u16; 7] = [800, 650, 500, 400, 300, 250, 200];
+pub const MAX_BASE_URI_LEN: usize = 128;
+pub const MAX_URI_HISTORY: usize = 4;
+pub const BASE_URI_ROTATION_TIMELOCK: i64 = 2 * 86400;
+pub const FORCED_RECALL_TIMELOCK: i64 = 2 * 86400;
+pub const DEFAULT_SPLIT_RETAIN_BPS: u16 = 8_000;
+pub const DEFAULT_XP_TRANSFER_LOSS_BPS: u16 = 2_500;
+pub const DEFAULT_MAX_LEVEL: u64 = 100;
+/// Layout version byte leading every packed NftState
+pub const NFT_STATE_VERSION: u8 = 1;
+/// Prestige a wallet earns per level gained past the program's level cap
+pub const PRESTIGE_PER_LEVEL: u64 = 1;
+pub const MINT_COUNTER_SHARDS: u8 = 16;
+/// Most unreconciled mints a shard holds while its collection has a price curve
+pub const CURVE_SHARD_PENDING_LIMIT: u32 = 8;
+pub const MAX_BATCH_MINT: usize = 5;
+pub const ASSET_STATUS_VERSION: u8 = 1;
+pub const CONFIG_REPORT_VERSION: u8 = 1;
+pub const MAX_MINT_PHASES: usize = 4;
+pub const MAX_ROYALTY_CREATORS: usize = 5;
+pub const STATE_ROOT_DEPTH: usize = 20;
+pub const MIN_MARKET_MAKER_COLLATERAL: u64 = 5_000_000_000;
+pub const MARKET_MAKER_SETTLEMENT_PERIOD: i64 = 86400;
+pub const MARKET_MAKER_GRACE_PERIOD: i64 = 6 * 3600;
+pub const SNS_PROGRAM_ID: Pubkey = pubkey!("namesLPneVptA9Z5rqUDD9tMTWEJwofgaYwp8cawRkX");
+pub const SNS_SOL_TLD: Pubkey = pubkey!("58PwtjSDuFHuUkYjH9BYnnQKHfwo9reZhC2zMJv9JPkx");
+pub const MAX_FUSION_INPUTS: usize = 5;
+/// Most assets either side of an OTC deal can put in
+pub const MAX_DEAL_ASSETS: usize = 5;
+pub const MAX_GENERATION_ROUTES: usize = 8;
+/// Most values a holder poll can offer
+pub const MAX_POLL_OPTIONS: usize = 4;
+/// Fewest fusion inputs that can roll each output rarity tier above the best input's
+pub const FUSION_MIN_INPUTS: [usize; 7] = [2, 2, 2, 2, 3, 4, 5];
+pub const CRANKER_BOND: u64 = 100_000_000;
+pub const CRANKER_SLASH_BPS: u16 = 2_500;
+/// Slots after a queued evolution's reveal slot that only bonded crankers may finalize it
+pub const CRANKER_PRIORITY_SLOTS: u64 = 150;
+pub const MAX_ACHIEVEMENT_TIERS: usize = 8;
+pub const MAX_ACHIEVEMENT_NAME_LEN: usize = 24;
+/// `beta` attribute values: minted in a beta phase, then graduated or frozen at its sunset
+pub const BETA_LIVE: &str = "live";
+pub const BETA_GRADUATED: &str = "graduated";
+pub const BETA_FROZEN: &str = "frozen";
+/// Levels a beta asset gains when it graduates
+pub const BETA_GRADUATION_LEVELS: u64 = 2;
+/// Chance a graduating beta asset also moves up a rarity tier
+pub const BETA_UPGRADE_BPS: u16 = 2_500;
+pub const BRACKET_BYE: u8 = u8::MAX;
+pub const BRACKET_UNDECIDED: u8 = u8::MAX - 1;
+
+/// 🧩 Cosmetic traits are anything outside the program-managed schema
+pub fn is_tradable_trait(key: &str) -> bool {
+    !key.is_empty() && key.len() <= MAX_TRAIT_KEY_LEN && !CORE_ATTRIBUTE_KEYS.contains(&key)
+}
+
+/// 📦 Serialized size of an attribute list (4-byte length prefix per string)
+pub fn attribute_bytes(attribute_list: &[Attribute]) -> usize {
+    attribute_list
+        .iter()
+        .map(|attribute| 8 + attribute.key.len() + attribute.value.len())
+        .sum()
+}
+
+/// 📏 Apply the configured attribute cap, pruning or rejecting per policy
+pub fn enforce_attribute_limits(
+    config: &ProgramConfig,
+    attribute_list: &mut Vec<Attribute>,
+) -> Result<()> {
+    let over_limit = |list: &Vec<Attribute>| {
+        list.len() > config.max_attributes as usize
+            || attribute_bytes(list) > config.max_attribute_bytes as usize
+    };
+
+    while over_limit(attribute_list) {
+        require!(
+            config.attribute_overflow_policy == AttributeOverflowPolicy::PruneOldest,
+            NftError::AttributeLimitExceeded
+        );
+        // 🧹 Oldest cosmetic attribute sits closest to the front of the list
+        let oldest_cosmetic = attribute_list
+            .iter()
+            .position(|attribute| !CORE_ATTRIBUTE_KEYS.contains(&attribute.key.as_str()))
+            .ok_or(NftError::AttributeLimitExceeded)?;
+        let pruned = attribute_list.remove(oldest_cosmetic);
+        msg!("🧹 Pruned attribute '{}' to stay within limits", pruned.key);
+    }
+
+    Ok(())
+}
+
+/// 🔎 Load an mpl-core asset and verify its current owner
+pub fn load_owned_asset(asset: &AccountInfo, owner: &Pubkey) -> Result<BaseAssetV1> {
+    let base_asset = load_core_asset(asset)?;
+    require_keys_eq!(base_asset.owner, *owner, NftError::NotAssetOwner);
+    Ok(base_asset)
+}
+
+/// 🧩 Deserialize an mpl-core asset, whoever owns it
+pub fn load_core_asset(asset: &AccountInfo) -> Result<BaseAssetV1> {
+    require_keys_eq!(*asset.owner, mpl_core::ID, NftError::InvalidAsset);
+    BaseAssetV1::from_bytes(&asset.try_borrow_data()?).map_err(|_| error!(NftError::InvalidAsset))
+}
+
+/// 📜 Read the attribute list from an asset's Attributes plugin (empty if absent)
+pub fn fetch_attributes(asset: &AccountInfo) -> Result<Vec<Attribute>> {
+    match fetch_plugin::<BaseAssetV1, Attributes>(asset, PluginType::Attributes) {
+        Ok((_, attributes, _)) => Ok(attributes.attribute_list),
+        Err(_) => Ok(vec![]),
+    }
+}
+
+/// 🧩 An asset's current attributes with `changes` upserted over them, within
+/// the program's attribute limits. A plugin write replaces the whole list, so
+/// writes that only change some keys must start from this, or they erase the rest
+pub fn merge_attributes(
+    config: &ProgramConfig,
+    asset: &AccountInfo,
+    changes: Vec<Attribute>,
+) -> Result<Vec<Attribute>> {
+    let mut attribute_list = fetch_attributes(asset)?;
+    for Attribute { key, value } in changes {
+        upsert_attribute(&mut attribute_list, &key, value);
+    }
+    enforce_attribute_limits(config, &mut attribute_list)?;
+    Ok(attribute_list)
+}
+
+/// ✍️ Replace an asset's Attributes plugin data with the given list, returning
+/// its `hash_attributes` for the asset's NftState; build the list with
+/// `merge_attributes` unless keys are meant to be dropped
+pub fn write_attributes<'info>(
+    backend: &dyn AssetBackend<'info>,
+    asset: &AccountInfo<'info>,
+    collection: &AccountInfo<'info>,
+    authority: &AccountInfo<'info>,
+    attributes_authority: AttributesAuthority<'_, 'info>,
+    attribute_list: Vec<Attribute>,
+) -> Result<[u8; 32]> {
+    write_attributes_signed(
+        backend,
+        asset,
+        collection,
+        authority,
+        authority,
+        attributes_authority,
+        attribute_list,
+        &[],
+    )
+}
+
+/// ✍️ `write_attributes` for a PDA authority signing with `signer_seeds`, with
+/// a separate `payer` covering any realloc. `authority` only signs for assets
+/// whose Attributes plugin hasn't moved to the program's attributes authority
+#[allow(clippy::too_many_arguments)]
+pub fn write_attributes_signed<'info>(
+    backend: &dyn AssetBackend<'info>,
+    asset: &AccountInfo<'info>,
+    collection: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    authority: &AccountInfo<'info>,
+    attributes_authority: AttributesAuthority<'_, 'info>,
+    attribute_list: Vec<Attribute>,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<[u8; 32]> {
+    let attributes_hash = hash_attributes(&attribute_list);
+    let bump = [attributes_authority.bump];
+    let program_seeds: &[&[u8]] = &[b"attributes_authority", &bump];
+    let program_signer = [program_seeds];
+    let call = if attributes_held_by(asset, attributes_authority.account.key) {
+        AssetCall::new(asset, collection, payer, attributes_authority.account).signed(&program_signer)
+    } else {
+        // 🏚️ Not migrated yet: the plugin still answers to the update authority
+        AssetCall::new(asset, collection, payer, authority).signed(signer_seeds)
+    };
+    backend
+        .update_plugin(call, Plugin::Attributes(Attributes { attribute_list }))?;
+    Ok(attributes_hash)
+}
+
+/// ✍️ Whether `asset`'s Attributes plugin answers to `attributes_authority`
+pub fn attributes_held_by(asset: &AccountInfo, attributes_authority: &Pubkey) -> bool {
+    matches!(
+        fetch_plugin::<BaseAssetV1, Attributes>(asset, PluginType::Attributes),
+        Ok((PluginAuthority::Address { address }, _, _)) if address == *attributes_authority
+    )
+}
+
+/// ✍️ Address of the `["attributes_authority"]` PDA new assets' Attributes plugin is held by
+pub fn attributes_authority_key() -> Pubkey {
+    Pubkey::find_program_address(&[b"attributes_authority"], &crate::ID).0
+}
+
+/// ✏️ Set an attribute value, appending it if the key is new
+pub fn upsert_attribute(attribute_list: &mut Vec<Attribute>, key: &str, value: String) {
+    match attribute_list.iter_mut().find(|attribute| attribute.key == key) {
+        Some(attribute) => attribute.value = value,
+        None => attribute_list.push(Attribute { key: key.to_string(), value }),
+    }
+}
+
+/// ⚔️ Battle rating used for tournament seeding
+pub fn battle_rating(nft_state: &NftState) -> u64 {
+    nft_state.level.saturating_mul(100)
+        .saturating_add((nft_state.evolution_count as u64).saturating_mul(50))
+        .saturating_add(nft_state.rarity.battle_weight() * 250)
+        .saturating_add(nft_state.achievement_points)
+}
+
+/// 🌱 Standard bracket placement: seed 1 meets the lowest seed, top seeds meet last
+pub fn seed_bracket(entrants: &[TournamentEntrant]) -> Vec<u8> {
+    let mut by_rating: Vec<u8> = (0..entrants.len() as u8).collect();
+    by_rating.sort_by(|&a, &b| {
+        entrants[b as usize].battle_rating.cmp(&entrants[a as usize].battle_rating)
+    });
+
+    let size = entrants.len().next_power_of_two();
+    let mut seeds = vec![1usize];
+    while seeds.len() < size {
+        let round_size = seeds.len() * 2;
+        seeds = seeds
+            .iter()
+            .flat_map(|&seed| [seed, round_size + 1 - seed])
+            .collect();
+    }
+
+    seeds
+        .into_iter()
+        .map(|seed| by_rating.get(seed - 1).copied().unwrap_or(BRACKET_BYE))
+        .collect()
+}
+
+/// 🎫 Pair up bracket slots, auto-advancing anyone drawn against a bye
+pub fn resolve_byes(bracket: &[u8]) -> Vec<u8> {
+    bracket
+        .chunks(2)
+        .map(|pair| match (pair[0], pair[1]) {
+            (BRACKET_BYE, other) | (other, BRACKET_BYE) => other,
+            _ => BRACKET_UNDECIDED,
+        })
+        .collect()
+}
+
+/// 🏦 Pay lamports out of the system-owned treasury PDA
+pub fn pay_from_treasury<'info>(
+    treasury: &SystemAccount<'info>,
+    to: &AccountInfo<'info>,
+    system_program: &Program<'info, System>,
+    treasury_bump: u8,
+    amount: u64,
+) -> Result<()> {
+    system_program::transfer(
+        CpiContext::new_with_signer(
+            system_program.to_account_info(),
+            system_program::Transfer {
+                from: treasury.to_account_info(),
+                to: to.clone(),
+            },
+            &[&[b"treasury", &[treasury_bump]]],
+        ),
+        amount,
+    )
+}
+
+/// 💸 Move lamports out of a program-owned escrow account
+pub fn transfer_lamports_from_program_account(
+    from: &AccountInfo,
+    to: &AccountInfo,
+    amount: u64,
+) -> Result<()> {
+    let rent_exempt = Rent::get()?.minimum_balance(from.data_len());
+    require!(
+        from.lamports().saturating_sub(amount) >= rent_exempt,
+        NftError::InsufficientEscrowBalance
+    );
+    **from.try_borrow_mut_lamports()? -= amount;
+    **to.try_borrow_mut_lamports()? += amount;
+    Ok(())
+}
+
+/// 🎯 Shared mint pipeline: dynamic rarity, achievements, and the mpl-core create CPI
+/// `clock` is the caller's resolved `program_clock`, so the mock clock reaches the rarity roll
+#[allow(clippy::too_many_arguments)]
+pub fn mint_core_asset<'info>(
+    backend: &dyn AssetBackend<'info>,
+    asset: &AccountInfo<'info>,
+    collection: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    name: String,
+    uri: String,
+    level: u64,
+    rarity: &str,
+    fusion_potential: u64,
+    lucky: bool,
+    extra_plugins: Vec<PluginAuthorityPair>,
+    edition: Option<Edition>,
+    program_signer: Option<ProgramSigner<'_, 'info>>,
+    asset_seeds: Option<&[&[u8]]>,
+    owner: Option<&AccountInfo<'info>>,
+    achievement_tiers: Option<&AchievementTiers>,
+    config: &ProgramConfig,
+    clock: &Clock,
+) -> Result<String> {
+    let MintRoll { rarity: dynamic_rarity, achievement_level, hour, mut attributes } =
+        roll_mint(level, rarity, fusion_potential, clock.unix_timestamp, lucky, achievement_tiers);
+    if let Some(edition) = edition {
+        attributes.push(Attribute { key: "edition".to_string(), value: edition.label() });
+    }
+    if extra_plugins.contains(&beta_plugin()) {
+        attributes.push(Attribute { key: "beta".to_string(), value: BETA_LIVE.to_string() });
+    }
+    if extra_plugins.contains(&soulbound_plugin()) {
+        attributes.push(Attribute { key: "soulbound".to_string(), value: true.to_string() });
+    }
+
+    create_core_asset(
+        backend,
+        asset,
+        collection,
+        payer,
+        name,
+        uri,
+        attributes,
+        extra_plugins,
+        program_signer,
+        asset_seeds,
+        owner,
+        config,
+    )?;
+
+    if let Some(edition) = edition {
+        emit!(EditionMinted {
+            collection: collection.key(),
+            asset: asset.key(),
+            edition: edition.number,
+            max_supply: edition.max_supply,
+        });
+        msg!("🔢 Edition {}", edition.label());
+    }
+
+    msg!("🎉 NFT minted with {} rarity at hour {}!", dynamic_rarity, hour);
+    msg!("🏆 Achievement: {} | Fusion Potential: {}", achievement_level, fusion_potential);
+
+    Ok(dynamic_rarity)
+}
+
+/// 🏗️ Create an asset owned by `owner` (default `payer`) in `collection` with
+/// exactly the given attributes, signed for by the collection's program
+/// authority if given; a PDA `asset` signs with `asset_seeds` instead of a keypair
+#[allow(clippy::too_many_arguments)]
+pub fn create_core_asset<'info>(
+    backend: &dyn AssetBackend<'info>,
+    asset: &AccountInfo<'info>,
+    collection: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    name: String,
+    uri: String,
+    mut attribute_list: Vec<Attribute>,
+    extra_plugins: Vec<PluginAuthorityPair>,
+    program_signer: Option<ProgramSigner<'_, 'info>>,
+    asset_seeds: Option<&[&[u8]]>,
+    owner: Option<&AccountInfo<'info>>,
+    config: &ProgramConfig,
+) -> Result<()> {
+    enforce_attribute_limits(config, &mut attribute_list)?;
+    let authority = program_signer.map_or(payer, |signer| signer.authority);
+    let signer_seeds: Vec<&[&[u8]]> =
+        program_signer.iter().map(|signer| signer.seeds).chain(asset_seeds).collect();
+    backend.create(
+        AssetCall::new(asset, collection, payer, authority).signed(&signer_seeds),
+        owner.unwrap_or(payer),
+        name,
+        uri,
+        [PluginAuthorityPair {
+            plugin: Plugin::Attributes(Attributes { attribute_list }),
+            authority: Some(PluginAuthority::Address { address: attributes_authority_key() }),
+        }]
+        .into_iter()
+        .chain(extra_plugins)
+        .collect(),
+    )?;
+
+    // 🏷️ The created asset must actually sit in the requested collection
+    verify_collection_membership(asset, collection.key)?;
+    Ok(())
+}
+
+/// 🎲 Everything the mint pipeline derives from its inputs
+pub struct MintRoll {
+    pub rarity: String,
+    pub achievement_level: String,
+    pub hour: i64,
+    pub attributes: Vec<Attribute>,
+}
+
+/// 🎲 Run the mint attribute pipeline for a mint at `unix_timestamp`, with no
+/// side effects; `lucky` mints spent a charge of streak luck and roll Legendary,
+/// and a collection's AchievementTiers rename the achievement ladder
+pub fn roll_mint(
+    level: u64,
+    rarity: &str,
+    fusion_potential: u64,
+    unix_timestamp: i64,
+    lucky: bool,
+    achievement_tiers: Option<&AchievementTiers>,
+) -> MintRoll {
+    // 🍀 Streak luck, not the clock, decides Legendary mints
+    let hour = (unix_timestamp / 3600) % 24;
+    let dynamic_rarity = if lucky { "Legendary" } else { rarity };
+
+    // 🏆 Achievement system - track minting milestones
+    let achievement_level = match achievement_tiers {
+        Some(tiers) => tiers.name_for(level).to_string(),
+        None => default_achievement_level(level).to_string(),
+    };
+
+    // 🧬 Fusion potential affects future evolution
+    let fusion_bonus = fusion_potential * 10;
+
+    let attributes = vec![
+        Attribute { key: "level".to_string(), value: level.to_string() },
+        Attribute { key: "rarity".to_string(), value: dynamic_rarity.to_string() },
+        Attribute { key: "mint_date".to_string(), value: unix_timestamp.to_string() },
+        Attribute { key: "fusion_potential".to_string(), value: fusion_potential.to_string() },
+        Attribute { key: "achievement_level".to_string(), value: achievement_level.clone() },
+        Attribute { key: "fusion_bonus".to_string(), value: fusion_bonus.to_string() },
+        Attribute { key: "mint_hour".to_string(), value: hour.to_string() },
+    ];
+
+    MintRoll { rarity: dynamic_rarity.to_string(), achievement_level, hour, attributes }
+}
+
+/// 🏆 The built-in achievement ladder, for collections without AchievementTiers
+pub fn default_achievement_level(level: u64) -> &'static str {
+    match level {
+        1..=10 => "Novice",
+        11..=25 => "Apprentice",
+        26..=50 => "Expert",
+        51..=75 => "Master",
+        _ => "Grandmaster",
+    }
+}
+
+/// ✍️ Verify the ed25519 precompile instruction right before this one signed `message`
+pub fn verify_ed25519_instruction(
+    instructions_sysvar: &AccountInfo,
+    expected_signer: &Pubkey,
+    expected_message: &[u8],
+) -> Result<()> {
+    let current_index = load_current_index_checked(instructions_sysvar)? as usize;
+    require!(current_index > 0, NftError::InvalidSignature);
+    let ed25519_ix = load_instruction_at_checked(current_index - 1, instructions_sysvar)?;
+    require_keys_eq!(ed25519_ix.program_id, ed25519_program::ID, NftError::InvalidSignature);
+    require!(ed25519_ix.accounts.is_empty(), NftError::InvalidSignature);
+
+    // 📐 Header: [num_signatures, padding] then one 14-byte offsets record
+    let data = &ed25519_ix.data;
+    require!(data.len() >= 16 && data[0] == 1, NftError::InvalidSignature);
+    let read_u16 = |at: usize| u16::from_le_bytes([data[at], data[at + 1]]);
+    let public_key_offset = read_u16(6) as usize;
+    let message_offset = read_u16(10) as usize;
+    let message_size = read_u16(12) as usize;
+
+    // 🔒 All offsets must point into the ed25519 instruction's own data
+    require!(
+        read_u16(4) == u16::MAX && read_u16(8) == u16::MAX && read_u16(14) == u16::MAX,
+        NftError::InvalidSignature
+    );
+
+    let public_key = data
+        .get(public_key_offset..public_key_offset + 32)
+        .ok_or(NftError::InvalidSignature)?;
+    let message = data
+        .get(message_offset..message_offset + message_size)
+        .ok_or(NftError::InvalidSignature)?;
+
+    require!(public_key == expected_signer.as_ref(), NftError::InvalidSignature);
+    require!(message == expected_message, NftError::InvalidSignature);
+
+    Ok(())
+}
+
+/// 📊 The roll-independent part of a fusion: (level, fusion potential, multiplier).
+/// Levels are weighted by rarity relative to the rarest input, and every
+/// input past the best two counts half as much as the one before it
+pub fn fusion_stats(inputs: &[&NftState], fusion_type: &str) -> (u64, u32, u64) {
+    let fusion_multiplier = match fusion_type {
+        "Power" => 2,
+        "Speed" => 3,
+        "Magic" => 4,
+        "Legendary" => 5,
+        _ => 1,
+    };
+
+    // 📊 Attribute fusion calculation
+    let base_tier = inputs.iter().map(|state| state.rarity as u8).max().unwrap_or_default();
+    let weight = |state: &NftState| state.rarity as u64 + 1;
+    let mut weighted_levels: Vec<u64> = inputs
+        .iter()
+        .map(|state| state.level.saturating_mul(weight(state)) / (base_tier as u64 + 1))
+        .collect();
+    weighted_levels.sort_unstable_by(|a, b| b.cmp(a));
+    let combined: u64 = weighted_levels
+        .iter()
+        .enumerate()
+        .map(|(i, level)| level >> i.saturating_sub(1))
+        .sum();
+    let level = combined * fusion_multiplier / 2;
+    let fusion_potential = inputs.iter().map(|state| state.fusion_potential).sum::<u32>()
+        + inputs.len().saturating_sub(1) as u32;
+
+    (level, fusion_potential, fusion_multiplier)
+}
+
+/// 🎲 Fused rarity for an `outcome`: it moves relative to the best input,
+/// but only climbs as high as the input count allows
+pub fn fusion_rarity(inputs: &[&NftState], outcome: FusionOutcome) -> Rarity {
+    let base_tier = inputs.iter().map(|state| state.rarity as u8).max().unwrap_or_default();
+    let rolled_tier = match outcome {
+        FusionOutcome::Critical => base_tier + 2,
+        FusionOutcome::Upgraded => base_tier + 1,
+        FusionOutcome::Sideways => base_tier,
+        FusionOutcome::Downgraded => base_tier.saturating_sub(1),
+    };
+    let max_tier = FUSION_MIN_INPUTS
+        .iter()
+        .rposition(|&min_inputs| min_inputs <= inputs.len())
+        .unwrap_or_default() as u8;
+    let tier = rolled_tier.min(max_tier.max(base_tier));
+    Rarity::from_name(rarity_name(tier)).unwrap_or(Rarity::Divine)
+}
+
+/// 🗂️ Collection an asset belongs to, if any
+pub fn asset_collection(base_asset: &BaseAssetV1) -> Option<Pubkey> {
+    match base_asset.update_authority {
+        UpdateAuthority::Collection(collection) => Some(collection),
+        _ => None,
+    }
+}
+
+/// 🧬 An asset's `generation` attribute; assets without one are Gen1
+pub fn asset_generation(asset: &AccountInfo) -> Result<u8> {
+    Ok(fetch_attributes(asset)?
+        .iter()
+        .find(|attribute| attribute.key == "generation")
+        .and_then(|attribute| attribute.value.parse().ok())
+        .unwrap_or(1))
+}
+
+/// 🧬 Require a fusion output of `generation` to be in the collection the
+/// GenerationMap routes it to, if any
+pub fn require_generation_route(generation_map: &AccountInfo, generation: u8, result_asset: &AccountInfo) -> Result<()> {
+    let Some(collection) = load_if_initialized::<GenerationMap>(generation_map)?
+        .and_then(|map| map.collection_for(generation))
+    else {
+        return Ok(());
+    };
+    require!(
+        asset_collection(&load_core_asset(result_asset)?) == Some(collection),
+        NftError::GenerationRouteMismatch
+    );
+    Ok(())
+}
+
+/// 🏷️ Require an mpl-core asset to belong to `collection`
+pub fn verify_collection_membership(asset: &AccountInfo, collection: &Pubkey) -> Result<()> {
+    require_keys_eq!(*asset.owner, mpl_core::ID, NftError::InvalidAsset);
+    let base_asset = BaseAssetV1::from_bytes(&asset.try_borrow_data()?)
+        .map_err(|_| error!(NftError::InvalidAsset))?;
+    require!(
+        asset_collection(&base_asset) == Some(*collection),
+        NftError::CollectionMismatch
+    );
+    Ok(())
+}
+
+/// #️⃣ Order-sensitive hash of an attribute list
+pub fn hash_attributes(attribute_list: &[Attribute]) -> [u8; 32] {
+    let mut hasher = Hasher::default();
+    for attribute in attribute_list {
+        hasher.hash(&(attribute.key.len() as u32).to_le_bytes());
+        hasher.hash(attribute.key.as_bytes());
+        hasher.hash(&(attribute.value.len() as u32).to_le_bytes());
+        hasher.hash(attribute.value.as_bytes());
+    }
+    hasher.result().to_bytes()
+}
+
+/// 📥 Load an NftState passed outside the typed context, checking its PDA binding
+pub fn load_nft_state(state_info: &AccountInfo, asset: &Pubkey) -> Result<NftState> {
+    let (expected, _) = Pubkey::find_program_address(&[b"nft_state", asset.as_ref()], &crate::ID);
+    require_keys_eq!(*state_info.key, expected, NftError::InvalidNftState);
+    require_keys_eq!(*state_info.owner, crate::ID, NftError::InvalidNftState);
+    let nft_state = NftState::try_deserialize(&mut &state_info.try_borrow_data()?[..])?;
+    require_keys_eq!(nft_state.asset, *asset, NftError::NftStateAssetMismatch);
+    Ok(nft_state)
+}
+
+/// 🌳 Verify a merkle proof using sorted-pair hashing
+pub fn verify_merkle_proof(proof: &[[u8; 32]], root: [u8; 32], leaf: [u8; 32]) -> bool {
+    let computed = proof.iter().fold(leaf, |node, sibling| merkle_parent(&node, sibling));
+    computed == root
+}
+
+/// 🌳 Sorted-pair parent hash, so proofs need no left/right flags
+pub fn merkle_parent(a: &[u8; 32], b: &[u8; 32]) -> [u8; 32] {
+    if a <= b {
+        hashv(&[a, b]).to_bytes()
+    } else {
+        hashv(&[b, a]).to_bytes()
+    }
+}
+
+/// 🌳 State-root leaf for one asset's game state
+pub fn state_leaf(asset: &Pubkey, level: u64, rarity: Rarity) -> [u8; 32] {
+    hashv(&[asset.as_ref(), &level.to_le_bytes(), &[rarity as u8]]).to_bytes()
+}
+
+/// 🎚️ Numeric rarity tier (Common = 0 … Divine = 6)
+pub fn rarity_tier(rarity: &str) -> u8 {
+    match rarity {
+        "Common" => 0,
+        "Uncommon" => 1,
+        "Rare" => 2,
+        "Epic" => 3,
+        "Legendary" => 4,
+        "Mythic" => 5,
+        _ => 6,
+    }
+}
+
+/// 🎚️ Rarity name for a tier, capped at Divine
+pub fn rarity_name(tier: u8) -> &'static str {
+    match tier {
+        0 => "Common",
+        1 => "Uncommon",
+        2 => "Rare",
+        3 => "Epic",
+        4 => "Legendary",
+        5 => "Mythic",
+        _ => "Divine",
+    }
+}
+
+/// 🎲 Fusion outcome from a revealed seed; a roll whose slot hash aged out
+/// before the reveal counts as a downgrade, so waiting can't buy a reroll
+pub fn fusion_outcome(outcome_table: &FusionOutcomeTable, seed: Option<[u8; 32]>) -> FusionOutcome {
+    match seed {
+        Some(seed) => outcome_table.outcome(roll_below(&seed, 0, MAX_BPS as u64) as u16),
+        None => FusionOutcome::Downgraded,
+    }
+}
+
+/// 🔥 Charge `fee` from `payer`: the burn share to the incinerator, the rest
+/// to the treasury, both recorded in the ledger; returns (burned, to treasury)
+pub fn charge_fusion_fee<'info>(
+    fee: FusionFee,
+    payer: &AccountInfo<'info>,
+    incinerator: &AccountInfo<'info>,
+    treasury: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    economy_ledger: &mut EconomyLedger,
+) -> Result<(u64, u64)> {
+    let Split { share: burned, rest: to_treasury } =
+        split_bps(fee.lamports, fee.burn_bps, Rounding::Floor)?;
+    for (amount, to) in [(burned, incinerator), (to_treasury, treasury)] {
+        if amount > 0 {
+            system_program::transfer(
+                CpiContext::new(
+                    system_program.clone(),
+                    system_program::Transfer { from: payer.clone(), to: to.clone() },
+                ),
+                amount,
+            )?;
+        }
+    }
+    economy_ledger.record_burned(burned)?;
+    economy_ledger.record_collected(to_treasury)?;
+    Ok((burned, to_treasury))
+}
+
+/// 🔬 Inputs of a multi-input fusion: the payer may fuse the first two,
+/// owns every extra (asset, nft_state) pair and lists no input twice.
+/// Returns the input keys in order, the extras' states and the output's
+/// generation, one past its newest input
+pub fn gather_fusion_inputs(
+    payer: &Pubkey,
+    assets: [&AccountInfo; 2],
+    delegations: [Option<&Account<Delegation>>; 2],
+    extra_pairs: &[AccountInfo],
+) -> Result<(Vec<Pubkey>, Vec<NftState>, u8)> {
+    // 🤝 Each input must be the payer's own or delegated with fuse rights
+    for (asset, delegation) in assets.into_iter().zip(delegations) {
+        authorize_asset_action(asset, payer, delegation, DelegatedAction::Fuse)?;
+    }
+
+    // ➕ Extra inputs ride along as (asset, nft_state) pairs
+    require!(
+        extra_pairs.len().is_multiple_of(2) && 2 + extra_pairs.len() / 2 <= MAX_FUSION_INPUTS,
+        NftError::InvalidRemainingAccounts
+    );
+    let mut input_keys = vec![assets[0].key(), assets[1].key()];
+    let mut extra_states = Vec::with_capacity(extra_pairs.len() / 2);
+    let mut input_generation = asset_generation(assets[0])?.max(asset_generation(assets[1])?);
+    for pair in extra_pairs.chunks_exact(2) {
+        let (asset, state_info) = (&pair[0], &pair[1]);
+        load_owned_asset(asset, payer)?;
+        extra_states.push(load_nft_state(state_info, asset.key)?);
+        input_keys.push(asset.key());
+        input_generation = input_generation.max(asset_generation(asset)?);
+    }
+
+    for (i, key) in input_keys.iter().enumerate() {
+        require!(!input_keys[..i].contains(key), NftError::CannotFuseSameNFT);
+    }
+
+    Ok((input_keys, extra_states, input_generation.saturating_add(1)))
+}
+
+/// 🎲 What an evolution roll is committed to: the asset and how often it has evolved
+pub fn evolution_subject(asset: &Pubkey, evolution_count: u32) -> [u8; 32] {
+    hashv(&[asset.as_ref(), &evolution_count.to_le_bytes()]).to_bytes()
+}
+
+/// 🎲 What a fusion roll is committed to: the fusion type, every input in
+/// order and the result asset
+pub fn fusion_subject(fusion_type: &str, inputs: &[Pubkey], result_asset: &Pubkey) -> [u8; 32] {
+    let mut parts: Vec<&[u8]> = Vec::with_capacity(inputs.len() + 2);
+    parts.push(fusion_type.as_bytes());
+    parts.extend(inputs.iter().map(Pubkey::as_ref));
+    parts.push(result_asset.as_ref());
+    hashv(&parts).to_bytes()
+}
+
+/// 🎲 Percent chance that an NFT of `rarity` evolves
+pub fn evolution_chance(rarity: Rarity) -> i64 {
+    match rarity {
+        Rarity::Common => 100,      // 100% chance to evolve
+        Rarity::Uncommon => 85,     // 85% chance
+        Rarity::Rare => 70,         // 70% chance
+        Rarity::Epic => 50,         // 50% chance
+        Rarity::Legendary => 25,    // 25% chance
+        _ => 10,                    // 10% chance for Mythic
+    }
+}
+
+/// ⬆️ The rarity an NFT evolves into
+pub fn next_rarity(rarity: Rarity) -> Rarity {
+    match rarity {
+        Rarity::Common => Rarity::Uncommon,
+        Rarity::Uncommon => Rarity::Rare,
+        Rarity::Rare => Rarity::Epic,
+        Rarity::Epic => Rarity::Legendary,
+        Rarity::Legendary => Rarity::Mythic,
+        _ => Rarity::Divine,
+    }
+}
+
+/// ⬇️ The rarity a split child drops to (Common is the floor)
+pub fn previous_rarity(rarity: Rarity) -> Rarity {
+    match rarity {
+        Rarity::Divine => Rarity::Mythic,
+        Rarity::Mythic => Rarity::Legendary,
+        Rarity::Legendary => Rarity::Epic,
+        Rarity::Epic => Rarity::Rare,
+        Rarity::Rare => Rarity::Uncommon,
+        _ => Rarity::Common,
+    }
+}
+
+/// 🎲 The one place random seeds come from
+///
+/// Every roll is committed to a future slot first and revealed from it
+/// later, so no caller can simulate a roll before it is fixed. The reveal
+/// is a 32-byte seed, domain-separated by the caller's `domain` parts, and
+/// sliced with `roll_below`.
+pub struct Randomness<'a, 'info> {
+    provider: RandomnessProvider,
+    slot_hashes: &'a AccountInfo<'info>,
+}
+
+impl<'a, 'info> Randomness<'a, 'info> {
+    pub fn new(provider: RandomnessProvider, slot_hashes: &'a AccountInfo<'info>) -> Self {
+        Self { provider, slot_hashes }
+    }
+
+    /// Seed for a roll committed to `slot`; `None` once the slot can no longer be revealed
+    pub fn revealed(&self, slot: u64, domain: &[&[u8]]) -> Result<Option<[u8; 32]>> {
+        match self.provider {
+            RandomnessProvider::SlotHashCommitReveal => Ok(
+                slot_hash_at(self.slot_hashes, slot)?.map(|slot_hash| domain_seed(&slot_hash, domain)),
+            ),
+        }
+    }
+}
+
+fn domain_seed(entropy: &[u8; 32], domain: &[&[u8]]) -> [u8; 32] {
+    let mut parts: Vec<&[u8]> = Vec::with_capacity(domain.len() + 1);
+    parts.push(entropy);
+    parts.extend_from_slice(domain);
+    hashv(&parts).to_bytes()
+}
+
+/// 🎯 The `index`-th 8-byte lane of a seed, reduced into `0..bound` (four lanes per seed)
+pub fn roll_below(seed: &[u8; 32], index: usize, bound: u64) -> u64 {
+    u64::from_le_bytes(seed[index * 8..index * 8 + 8].try_into().unwrap()) % bound
+}
+
+/// 🔎 Look up a slot's hash in the SlotHashes sysvar without deserializing all of it
+///
+/// Layout: `len: u64` followed by `(slot: u64, hash: [u8; 32])` records, newest first.
+pub fn slot_hash_at(slot_hashes: &AccountInfo, slot: u64) -> Result<Option<[u8; 32]>> {
+    let data = slot_hashes.try_borrow_data()?;
+    require!(data.len() >= 8, NftError::InvalidSlotHashes);
+    let len = u64::from_le_bytes(data[..8].try_into().unwrap()) as usize;
+    for record in data[8..].chunks_exact(40).take(len) {
+        let record_slot = u64::from_le_bytes(record[..8].try_into().unwrap());
+        if record_slot == slot {
+            return Ok(Some(record[8..].try_into().unwrap()));
+        }
+        if record_slot < slot {
+            break;
+        }
+    }
+    Ok(None)
+}
+
+/// 🧊 Freeze or thaw an asset through the stake authority PDA's FreezeDelegate
+/// plugin; that PDA is the program's one freeze authority, for staking and
+/// `freeze_asset` alike
+#[allow(clippy::too_many_arguments)]
+pub fn set_stake_freeze<'info>(
+    backend: &dyn AssetBackend<'info>,
+    asset: &AccountInfo<'info>,
+    collection: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    stake_authority: &AccountInfo<'info>,
+    stake_authority_bump: u8,
+    frozen: bool,
+) -> Result<()> {
+    backend.update_plugin(
+        AssetCall::new(asset, collection, payer, stake_authority)
+            .signed(&[&[b"stake_authority", &[stake_authority_bump]]]),
+        Plugin::FreezeDelegate(FreezeDelegate { frozen }),
+    )
+}
+
+/// 🏛️ What a collection's CollectionConfig asks of a mint
+pub struct CollectionMintTerms {
+    pub price_lamports: u64,
+    /// Plugins each new asset is created with, alongside Attributes
+    pub plugins: Vec<PluginAuthorityPair>,
+    /// The price is held for refunds until the drop is finalized
+    pub escrowed: bool,
+}
+
+/// 🧪 Marks a beta mint: a thawed PermanentFreezeDelegate held by the
+/// collection's update authority, so its sunset can freeze it without the holder
+pub fn beta_plugin() -> PluginAuthorityPair {
+    PluginAuthorityPair {
+        plugin: Plugin::PermanentFreezeDelegate(PermanentFreezeDelegate { frozen: false }),
+        authority: Some(PluginAuthority::UpdateAuthority),
+    }
+}
+
+/// 🪢 Makes a mint soulbound: a frozen PermanentFreezeDelegate with no
+/// authority, so no one - holder, collection or program - can ever thaw it
+pub fn soulbound_plugin() -> PluginAuthorityPair {
+    PluginAuthorityPair {
+        plugin: Plugin::PermanentFreezeDelegate(PermanentFreezeDelegate { frozen: true }),
+        authority: Some(PluginAuthority::None),
+    }
+}
+
+/// 🧮 Which of a collection's mint shards a payer counts against
+pub fn mint_shard_index(payer: &Pubkey) -> u8 {
+    hashv(&[payer.as_ref()]).to_bytes()[0] % MINT_COUNTER_SHARDS
+}
+
+/// 🏛️ Count `count` mints against the payer's receipt and shard of a collection's
+/// CollectionConfig and return the price owed (the live phase's, if staged,
+/// along the price curve) and plugin bundle, plus the beta plugin during a beta phase; the config
+/// itself is only read, so concurrent mints don't contend for it. Collections
+/// created outside this program have no config and mint free with Attributes only
+pub fn apply_collection_config(
+    collection_config: &AccountInfo,
+    mint_shard: &AccountInfo,
+    mint_receipt: &mut MintReceipt,
+    count: u32,
+    now: i64,
+    allowlist: Option<&AllowlistProof>,
+) -> Result<CollectionMintTerms> {
+    mint_receipt.minted = mint_receipt.minted.checked_add(count).ok_or(NftError::MathOverflow)?;
+    if collection_config.owner != &crate::ID {
+        require!(allowlist.is_none(), NftError::NotOnAllowlist);
+        return Ok(CollectionMintTerms { price_lamports: 0, plugins: vec![], escrowed: false });
+    }
+    let config = CollectionConfig::try_deserialize(&mut &collection_config.try_borrow_data()?[..])?;
+    require!(
+        config.max_per_wallet == 0 || mint_receipt.minted <= config.max_per_wallet,
+        NftError::MintLimitExceeded
+    );
+    if let Some(allowlist) = allowlist {
+        let leaf = hashv(&[mint_receipt.minter.as_ref(), &allowlist.allocation.to_le_bytes()]).to_bytes();
+        require!(
+            config.allowlist_root != [0; 32]
+                && verify_merkle_proof(&allowlist.proof, config.allowlist_root, leaf),
+            NftError::NotOnAllowlist
+        );
+        require!(
+            allowlist.allocation == 0 || mint_receipt.minted <= allowlist.allocation,
+            NftError::MintLimitExceeded
+        );
+    }
+    let mut unit_price = config.mint_price_lamports;
+    let mut plugins = config.mint_plugins();
+    if let Some(phase) = config.active_phase(now)? {
+        require!(!phase.allowlist_required || allowlist.is_some(), NftError::NotOnAllowlist);
+        unit_price = phase.price_lamports;
+        if phase.beta {
+            plugins.push(beta_plugin());
+        }
+    }
+    require_keys_eq!(*mint_shard.owner, crate::ID, NftError::MintShardNotReady);
+    let mut shard = MintCounterShard::try_deserialize(&mut &mint_shard.try_borrow_data()?[..])?;
+    // 📈 Curves see reconciled supply plus this shard's own unreconciled mints.
+    // The other shards' are invisible here, so each shard's backlog is capped
+    // to keep a curved price at most (MINT_COUNTER_SHARDS - 1) * the cap mints behind
+    let minted = config.total_minted.saturating_add(shard.pending);
+    require!(
+        config.price_curve == PriceCurve::Flat
+            || shard.pending.saturating_add(count) <= CURVE_SHARD_PENDING_LIMIT,
+        NftError::CurveShardBacklog
+    );
+    shard.record_mint(count, config.max_supply != 0)?;
+    shard.try_serialize(&mut &mut mint_shard.try_borrow_mut_data()?[..])?;
+    Ok(CollectionMintTerms {
+        price_lamports: config.price_curve.price(unit_price, minted, count)?,
+        plugins,
+        escrowed: config.escrow_active(),
+    })
+}
+
+/// 🏛️ Apply a collection's CollectionConfig to `count` mints, charging its
+/// mint price from `payer` into the treasury; returns the plugin bundle to mint with
+#[allow(clippy::too_many_arguments)]
+pub fn charge_collection_mint<'info>(
+    collection_config: &AccountInfo<'info>,
+    mint_shard: &AccountInfo<'info>,
+    mint_receipt: &mut MintReceipt,
+    payer: &AccountInfo<'info>,
+    treasury: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    economy_ledger: &mut EconomyLedger,
+    count: u32,
+    now: i64,
+    allowlist: Option<&AllowlistProof>,
+) -> Result<Vec<PluginAuthorityPair>> {
+    let terms = apply_collection_config(collection_config, mint_shard, mint_receipt, count, now, allowlist)?;
+    if terms.price_lamports > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                system_program.clone(),
+                system_program::Transfer { from: payer.clone(), to: treasury.clone() },
+            ),
+            terms.price_lamports,
+        )?;
+    }
+    economy_ledger.record_collected(terms.price_lamports)?;
+    if terms.escrowed {
+        // 🔐 Tracked per wallet so an unfinalized drop can refund it
+        mint_receipt.escrowed_mints = mint_receipt.escrowed_mints.checked_add(count).ok_or(NftError::MathOverflow)?;
+        mint_receipt.escrowed_lamports = mint_receipt
+            .escrowed_lamports
+            .checked_add(terms.price_lamports)
+            .ok_or(NftError::MathOverflow)?;
+    }
+    if terms.price_lamports > 0 {
+        // 💰 Credited to the collection's withdrawable proceeds at the next reconcile
+        let mut shard = MintCounterShard::try_deserialize(&mut &mint_shard.try_borrow_data()?[..])?;
+        shard.pending_proceeds = shard
+            .pending_proceeds
+            .checked_add(terms.price_lamports)
+            .ok_or(NftError::MathOverflow)?;
+        shard.try_serialize(&mut &mut mint_shard.try_borrow_mut_data()?[..])?;
+    }
+    Ok(terms.plugins)
+}
+
+/// 🚫 Transfer policy every program flow that lists or moves an asset goes through:
+/// the asset must be unlocked and carry none of the configured restricted attributes
+pub fn ensure_transferable(config: &ProgramConfig, asset: &AccountInfo, nft_state: &NftState) -> Result<()> {
+    require!(nft_state.lock_state == LockState::Unlocked, NftError::AssetLocked);
+    if config.transfer_restrictions.is_empty() {
+        return Ok(());
+    }
+    let attribute_list = fetch_attributes(asset)?;
+    let restricted = config.transfer_restrictions.iter().any(|restriction| {
+        attribute_list
+            .iter()
+            .any(|attribute| attribute.key == restriction.key && attribute.value == restriction.value)
+    });
+    require!(!restricted, NftError::TransferRestricted);
+    Ok(())
+}
+
+/// 📦 Transfer an asset out of its listing PDA escrow
+#[allow(clippy::too_many_arguments)]
+pub fn release_listed_asset<'info>(
+    backend: &dyn AssetBackend<'info>,
+    asset: &AccountInfo<'info>,
+    collection: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    listing: &AccountInfo<'info>,
+    new_owner: &AccountInfo<'info>,
+    listing_bump: u8,
+) -> Result<()> {
+    backend.transfer(
+        AssetCall::new(asset, collection, payer, listing)
+            .signed(&[&[b"listing", asset.key.as_ref(), &[listing_bump]]]),
+        new_owner,
+    )
+}
+
+/// 🤝 An asset entering a deal must belong to `owner` and be free to move:
+/// unlocked, and clear of the program's transfer restrictions
+pub fn ensure_deal_asset_transferable(
+    config: &ProgramConfig,
+    asset: &AccountInfo,
+    state_info: &AccountInfo,
+    owner: &Pubkey,
+) -> Result<()> {
+    load_owned_asset(asset, owner)?;
+    let (expected_state, _) = Pubkey::find_program_address(&[b"nft_state", asset.key.as_ref()], &crate::ID);
+    require_keys_eq!(*state_info.key, expected_state, NftError::InvalidNftState);
+    match load_if_initialized::<NftState>(state_info)? {
+        Some(state) => ensure_transferable(config, asset, &state),
+        None => Ok(()),
+    }
+}
+
+/// 📦 Transfer a deal's escrowed bundle to `new_owner`, signed by the deal PDA.
+/// `pairs` holds an (asset, collection) pair per offered asset, in deal order
+pub fn release_deal_assets<'info>(
+    backend: &dyn AssetBackend<'info>,
+    deal: &Deal,
+    deal_info: &AccountInfo<'info>,
+    pairs: &[AccountInfo<'info>],
+    payer: &AccountInfo<'info>,
+    new_owner: &AccountInfo<'info>,
+) -> Result<()> {
+    let deal_id = deal.deal_id.to_le_bytes();
+    let seeds: &[&[u8]] = &[b"deal", deal.seller.as_ref(), &deal_id, &[deal.bump]];
+    for (pair, offered) in pairs.chunks_exact(2).zip(&deal.offered_assets) {
+        let (asset, collection) = (&pair[0], &pair[1]);
+        require_keys_eq!(asset.key(), *offered, NftError::DealAssetMismatch);
+        backend
+            .transfer(AssetCall::new(asset, collection, payer, deal_info).signed(&[seeds]), new_owner)?;
+    }
+    Ok(())
+}
+
+/// 🚚 Transfer an asset through the program's TransferDelegate, signed by the
+/// `["transfer_authority"]` PDA
+#[allow(clippy::too_many_arguments)]
+pub fn transfer_as_program<'info>(
+    backend: &dyn AssetBackend<'info>,
+    asset: &AccountInfo<'info>,
+    collection: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    transfer_authority: &AccountInfo<'info>,
+    new_owner: &AccountInfo<'info>,
+    transfer_authority_bump: u8,
+) -> Result<()> {
+    backend.transfer(
+        AssetCall::new(asset, collection, payer, transfer_authority)
+            .signed(&[&[b"transfer_authority", &[transfer_authority_bump]]]),
+        new_owner,
+    )
 }
 
-/// 🌟 Enhanced Context for Advanced NFT Evolution
-#[derive(Accounts)]
-pub struct EvolveNFT<'info> {
-    #[account(mut)]
-    pub payer: Signer<'info>,
-    
-    /// CHECK: Handled by mpl-core
-    #[account(mut)]
-    pub asset: AccountInfo<'info>,
-    
-    #[account(
-        mut,
-        seeds = [b"nft_state", asset.key().as_ref()],
-        bump
-    )]
-    pub nft_state: Account<'info, NftState>,
-    
-    /// CHECK: Metaplex Core program
-    pub mpl_core_program: AccountInfo<'info>,
-    
-    pub system_program: Program<'info, System>,
+/// 🧹 Close a program-owned account passed as a plain account, sending all
+/// of its lamports to `destination`
+pub fn close_program_account<'info>(account: &AccountInfo<'info>, destination: &AccountInfo<'info>) -> Result<()> {
+    let refunded = destination.lamports().checked_add(account.lamports()).ok_or(NftError::MathOverflow)?;
+    **account.try_borrow_mut_lamports()? = 0;
+    **destination.try_borrow_mut_lamports()? = refunded;
+    account.assign(&system_program::ID);
+    account.resize(0)?;
+    Ok(())
 }
 
-/// 🔥 Context for NFT Fusion
-#[derive(Accounts)]
-pub struct FuseNFTs<'info> {
-    #[account(mut)]
-    pub payer: Signer<'info>,
-    
-    /// CHECK: Handled by mpl-core
-    #[account(mut)]
-    pub asset_1: AccountInfo<'info>,
-    
-    /// CHECK: Handled by mpl-core
-    #[account(mut)]
-    pub asset_2: AccountInfo<'info>,
-    
-    /// CHECK: Handled by mpl-core
-    #[account(mut)]
-    pub result_asset: AccountInfo<'info>,
-    
-    #[account(
-        seeds = [b"nft_state", asset_1.key().as_ref()],
-        bump
-    )]
-    pub nft_state_1: Account<'info, NftState>,
-    
-    #[account(
-        seeds = [b"nft_state", asset_2.key().as_ref()],
-        bump
-    )]
-    pub nft_state_2: Account<'info, NftState>,
-    
-    #[account(
-        init_if_needed,
-        payer = payer,
-        space = 8 + 8 + 32 + 8 + 8 + 8 + 8 + 8,
-        seeds = [b"nft_state", result_asset.key().as_ref()],
-        bump
-    )]
-    pub result_nft_state: Account<'info, NftState>,
-    
-    /// CHECK: Metaplex Core program
-    pub mpl_core_program: AccountInfo<'info>,
-    
-    pub system_program: Program<'info, System>,
+/// 🏚️ Grow a legacy NftState passed as a plain account to the packed size,
+/// `payer` covering the extra rent, so it can be written back
+pub fn upgrade_nft_state<'info>(
+    nft_state: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+) -> Result<()> {
+    if !NftState::is_legacy(nft_state) {
+        return Ok(());
+    }
+    let space = 8 + NftState::INIT_SPACE;
+    let top_up = Rent::get()?.minimum_balance(space).saturating_sub(nft_state.lamports());
+    if top_up > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                system_program.clone(),
+                system_program::Transfer { from: payer.clone(), to: nft_state.clone() },
+            ),
+            top_up,
+        )?;
+    }
+    nft_state.resize(space)?;
+    Ok(())
 }
 
-/// 🏆 Enhanced State Account with Advanced Features
-#[account]
-pub struct NftState {
-    pub level: u64,
-    pub rarity: String,
-    pub mint_date: i64,
-    pub last_updated: i64,
-    pub evolution_count: u64,
-    pub fusion_potential: u64,
-    pub asset: Pubkey,
-    pub achievement_points: u64,
+/// 🕰️ The clock handlers should use
+///
+/// With the `test-clock` feature, a MockClock PDA passed as the last remaining
+/// account overrides `unix_timestamp`; otherwise this is `Clock::get()`.
+pub fn program_clock(remaining_accounts: &[AccountInfo]) -> Result<Clock> {
+    #[allow(unused_mut)]
+    let mut clock = Clock::get()?;
+    #[cfg(feature = "test-clock")]
+    if let Some(mock_clock) = remaining_accounts.last().filter(|info| is_mock_clock(info)) {
+        let mock_clock = MockClock::try_deserialize(&mut &mock_clock.try_borrow_data()?[..])?;
+        if mock_clock.unix_timestamp != 0 {
+            clock.unix_timestamp = mock_clock.unix_timestamp;
+        }
+    }
+    #[cfg(not(feature = "test-clock"))]
+    let _ = remaining_accounts;
+    Ok(clock)
 }
 
-impl Default for NftState {
-    fn default() -> Self {
-        Self {
-            level: 0,
-            rarity: "Common".to_string(),
-            mint_date: 0,
-            last_updated: 0,
-            evolution_count: 0,
-            fusion_potential: 0,
-            asset: Pubkey::default(),
-            achievement_points: 0,
+/// 🕰️ Remaining accounts with a trailing MockClock PDA stripped off
+pub fn without_mock_clock<'a, 'info>(
+    remaining_accounts: &'a [AccountInfo<'info>],
+) -> &'a [AccountInfo<'info>] {
+    match remaining_accounts.split_last() {
+        Some((last, rest)) if cfg!(feature = "test-clock") && is_mock_clock(last) => rest,
+        _ => remaining_accounts,
+    }
+}
+
+fn is_mock_clock(info: &AccountInfo) -> bool {
+    *info.owner == crate::ID
+        && *info.key == Pubkey::find_program_address(&[b"mock_clock"], &crate::ID).0
+}
+
+/// 🤝 Require `actor` to own `asset` or hold a delegation covering `action`
+///
+/// A delegation only counts while the granting owner still owns the asset,
+/// so grants lapse automatically when the asset changes hands. The asset's
+/// UpdateDelegate also covers updates and evolutions; it is an mpl-core
+/// plugin and stays with the asset until an owner revokes it.
+pub fn authorize_asset_action(
+    asset: &AccountInfo,
+    actor: &Pubkey,
+    delegation: Option<&Account<Delegation>>,
+    action: DelegatedAction,
+) -> Result<()> {
+    require_keys_eq!(*asset.owner, mpl_core::ID, NftError::InvalidAsset);
+    let base_asset = BaseAssetV1::from_bytes(&asset.try_borrow_data()?)
+        .map_err(|_| error!(NftError::InvalidAsset))?;
+    if base_asset.owner == *actor {
+        return Ok(());
+    }
+    if matches!(action, DelegatedAction::Update | DelegatedAction::Evolve) && is_update_delegate(asset, actor) {
+        return Ok(());
+    }
+
+    let delegation = delegation.ok_or(NftError::NotAssetOwner)?;
+    require_keys_eq!(delegation.asset, asset.key(), NftError::DelegationScopeDenied);
+    require_keys_eq!(delegation.delegate, *actor, NftError::DelegationScopeDenied);
+    require_keys_eq!(delegation.owner, base_asset.owner, NftError::DelegationScopeDenied);
+    require!(delegation.scope.permits(action), NftError::DelegationScopeDenied);
+    Ok(())
+}
+
+/// ✍️ Whether `actor` holds the asset's UpdateDelegate plugin, directly or
+/// as one of its additional delegates
+pub fn is_update_delegate(asset: &AccountInfo, actor: &Pubkey) -> bool {
+    match fetch_plugin::<BaseAssetV1, UpdateDelegate>(asset, PluginType::UpdateDelegate) {
+        Ok((authority, delegate, _)) => {
+            authority == PluginAuthority::Address { address: *actor } || delegate.additional_delegates.contains(actor)
         }
+        Err(_) => false,
     }
 }
 
-/// 🎯 Comprehensive Error Codes for Better UX
-#[error_code]
-pub enum NftError {
-    #[msg("Cannot update metadata too soon - cooldown period active")]
-    UpdateTooSoon,
-    
-    #[msg("Level progression must be forward-only - cannot decrease level")]
-    InvalidLevelProgression,
-    
-    #[msg("NFT is not ready for evolution - time requirement not met")]
-    EvolutionNotReady,
-    
-    #[msg("Evolution failed - probability check unsuccessful")]
-    EvolutionFailed,
-    
-    #[msg("Cannot fuse the same NFT with itself")]
-    CannotFuseSameNFT,
-    
-    #[msg("Fusion requirements not met - check NFT compatibility")]
-    FusionRequirementsNotMet,
-    
-    #[msg("Invalid rarity level specified")]
-    InvalidRarity,
-    
-    #[msg("Achievement points insufficient for this action")]
-    InsufficientAchievementPoints,
-    
-    #[msg("Time-locked feature - wait for unlock period")]
-    TimeLockedFeature,
-    
-    #[msg("Fusion potential exhausted")]
-    FusionPotentialExhausted,
-} 
\ No newline at end of file
+/// 🎉 The live GlobalEvent, if its PDA exists and `now` is inside its window
+pub fn active_global_event(global_event: &AccountInfo, now: i64) -> Result<Option<GlobalEvent>> {
+    if *global_event.owner != crate::ID {
+        return Ok(None);
+    }
+    let event = GlobalEvent::try_deserialize(&mut &global_event.try_borrow_data()?[..])?;
+    Ok(event.is_live(now).then_some(event))
+}
+
+/// 🎁 Map a 0..MAX_BPS roll onto the starter pack rarity weights
+pub fn starter_pack_rarity(roll: u16) -> Rarity {
+    let tier = STARTER_PACK_WEIGHTS_BPS
+        .iter()
+        .position(|&threshold| roll < threshold)
+        .unwrap_or(STARTER_PACK_WEIGHTS_BPS.len() - 1);
+    Rarity::from_name(rarity_name(tier as u8)).unwrap_or_default()
+}
+
+/// 🌍 Require a fresh KYC oracle attestation if the collection's RegionLock is enabled
+pub fn enforce_region_lock(
+    region_lock: &AccountInfo,
+    instructions_sysvar: &AccountInfo,
+    payer: &Pubkey,
+    collection: &Pubkey,
+    attestation: Option<RegionAttestation>,
+    now: i64,
+) -> Result<()> {
+    if region_lock.owner != &crate::ID {
+        return Ok(());
+    }
+    let region_lock = RegionLock::try_deserialize(&mut &region_lock.try_borrow_data()?[..])?;
+    if !region_lock.enabled {
+        return Ok(());
+    }
+    let attestation = attestation.ok_or(NftError::RegionAttestationRequired)?;
+    require!(now <= attestation.expires_at, NftError::RegionAttestationExpired);
+    let attestation_message =
+        [payer.as_ref(), collection.as_ref(), &attestation.expires_at.to_le_bytes()].concat();
+    verify_ed25519_instruction(instructions_sysvar, &region_lock.kyc_oracle, &attestation_message)
+}
+
+/// 🌱 Create the NftState PDA for `asset` outside of Anchor's `init`, for
+/// instructions that take their assets as remaining accounts; returns its bump
+pub fn create_nft_state_account<'info>(
+    nft_state: &AccountInfo<'info>,
+    asset: &Pubkey,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+) -> Result<u8> {
+    let (expected, bump) = Pubkey::find_program_address(&[b"nft_state", asset.as_ref()], &crate::ID);
+    require_keys_eq!(*nft_state.key, expected, NftError::InvalidNftState);
+    let space = 8 + NftState::INIT_SPACE;
+    system_program::create_account(
+        CpiContext::new_with_signer(
+            system_program.clone(),
+            system_program::CreateAccount { from: payer.clone(), to: nft_state.clone() },
+            &[&[b"nft_state", asset.as_ref(), &[bump]]],
+        ),
+        Rent::get()?.minimum_balance(space),
+        space as u64,
+        &crate::ID,
+    )?;
+    Ok(bump)
+}
+
+/// ⏰ How many times its base cooldown an asset of `rarity` waits between updates
+pub fn cooldown_multiplier(rarity: Rarity) -> i64 {
+    match rarity {
+        Rarity::Common => 1,
+        Rarity::Uncommon => 2,
+        Rarity::Rare => 3,
+        Rarity::Epic => 4,
+        Rarity::Legendary => 5,
+        _ => 6,
+    }
+}
+
+/// 📭 Deserialize an optional program PDA, or None if it was never created
+pub fn load_if_initialized<T: AccountDeserialize>(info: &AccountInfo) -> Result<Option<T>> {
+    if info.owner != &crate::ID {
+        return Ok(None);
+    }
+    Ok(Some(T::try_deserialize(&mut &info.try_borrow_data()?[..])?))
+}
+
+/// #️⃣ Record a freshly written attribute hash on an NftState passed as a
+/// plain account; states that don't exist yet pick it up when initialized
+pub fn stamp_attributes_hash(nft_state: &AccountInfo, attributes_hash: [u8; 32]) -> Result<()> {
+    // 🏚️ Legacy states have no room for it until upgraded; zero means "never written"
+    if nft_state.owner != &crate::ID || NftState::is_legacy(nft_state) {
+        return Ok(());
+    }
+    let mut state = NftState::try_deserialize(&mut &nft_state.try_borrow_data()?[..])?;
+    state.attributes_hash = attributes_hash;
+    state.try_serialize(&mut &mut nft_state.try_borrow_mut_data()?[..])
+}
+
+/// 🎯 Shared body of `mint_nft` and `mint_allowlisted`
+#[allow(clippy::too_many_arguments)]
+pub fn process_mint_nft(
+    ctx: Context<MintNFT>,
+    name: String,
+    uri: String,
+    level: u64,
+    rarity: String,
+    fusion_potential: u64,
+    mint_index: u64,
+    region_attestation: Option<RegionAttestation>,
+    allowlist: Option<&AllowlistProof>,
+    soulbound: bool,
+) -> Result<()> {
+    let clock = program_clock(ctx.remaining_accounts)?;
+    let now = clock.unix_timestamp;
+    let payer = ctx.accounts.payer.to_account_info();
+
+    // 🌍 Region-locked collections need a fresh KYC oracle attestation
+    enforce_region_lock(
+        &ctx.accounts.region_lock,
+        &ctx.accounts.instructions_sysvar,
+        payer.key,
+        ctx.accounts.collection.key,
+        region_attestation,
+        now,
+    )?;
+    // 🤖 Anti-bot collections allow one direct mint per transaction
+    enforce_single_mint(&ctx.accounts.collection_config, &ctx.accounts.instructions_sysvar)?;
+
+    // 🍀 A full charge of streak luck turns the mint Legendary
+    let lucky = ctx.accounts.player_profile.take_mint_luck(payer.key(), ctx.bumps.player_profile, now);
+    // 🏛️ Program-created collections cap supply, price and plugin bundle
+    ctx.accounts.mint_receipt.bind(ctx.accounts.collection.key(), payer.key(), ctx.bumps.mint_receipt);
+    // ✍️ Collections handed to the program are signed for by their authority PDA
+    let collection_key = ctx.accounts.collection.key();
+    let authority_bump = [ctx.bumps.collection_authority];
+    let authority_seeds: &[&[u8]] = &[b"collection_authority", collection_key.as_ref(), &authority_bump];
+    let program_signer = program_signer(&ctx.accounts.collection, &ctx.accounts.collection_authority, authority_seeds)?;
+    // 📍 The asset address is derived from the client's nonce, not a client keypair
+    let mint_index_bytes = mint_index.to_le_bytes();
+    let asset_bump = [ctx.bumps.asset];
+    let asset_seeds: &[&[u8]] = &[b"asset", collection_key.as_ref(), &mint_index_bytes, &asset_bump];
+    let mut plugins = charge_collection_mint(
+        &ctx.accounts.collection_config,
+        &ctx.accounts.mint_shard,
+        &mut ctx.accounts.mint_receipt,
+        &payer,
+        &ctx.accounts.treasury.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+        &mut ctx.accounts.economy_ledger,
+        1,
+        now,
+        allowlist,
+    )?;
+    // 🪢 A badge is frozen for good, so it never takes part in a beta sunset
+    if soulbound {
+        plugins.retain(|plugin| *plugin != beta_plugin());
+        plugins.push(soulbound_plugin());
+    }
+    let achievement_tiers = load_if_initialized::<AchievementTiers>(&ctx.accounts.achievement_tiers)?;
+    let edition = claim_editions(
+        &ctx.accounts.collection_config,
+        ctx.accounts.edition_counter.as_deref_mut(),
+        1,
+    )?;
+
+    let minted_rarity = mint_core_asset(
+        &MplCore::new(&ctx.accounts.mpl_core_program, &ctx.accounts.system_program.to_account_info()),
+        &ctx.accounts.asset,
+        &ctx.accounts.collection,
+        &payer,
+        name,
+        uri,
+        level,
+        &rarity,
+        fusion_potential,
+        lucky,
+        plugins,
+        edition,
+        program_signer,
+        Some(asset_seeds),
+        ctx.accounts.recipient.as_ref(),
+        achievement_tiers.as_ref(),
+        &ctx.accounts.program_config,
+        &clock,
+    )?;
+    ctx.accounts.signal_board.record(
+        SignalKind::Mint,
+        ctx.accounts.asset.key(),
+        level,
+        &minted_rarity,
+        &program_clock(ctx.remaining_accounts)?,
+    );
+
+    Ok(())
+}
+
+/// 🤝 Accrue a buyer's marketplace fee on their MarketMaker account, if they
+/// have one that can take it; false means the fee is charged now
+pub fn defer_market_maker_fee(market_maker_info: &AccountInfo, fee: u64, now: i64) -> Result<bool> {
+    if fee == 0 {
+        return Ok(false);
+    }
+    let Some(mut market_maker) = load_if_initialized::<MarketMaker>(market_maker_info)? else {
+        return Ok(false);
+    };
+    if !market_maker.try_accrue(fee, now)? {
+        return Ok(false);
+    }
+    market_maker.try_serialize(&mut &mut market_maker_info.try_borrow_mut_data()?[..])?;
+    Ok(true)
+}
+
+/// 🪪 Check that `name_record` is the Solana Name Service account for
+/// `name`.sol and that `wallet` owns it
+pub fn verify_sns_owner(name_record: &AccountInfo, name: &str, wallet: &Pubkey) -> Result<()> {
+    require!(!name.is_empty(), NftError::InvalidNameRecord);
+    let hashed_name = hashv(&[b"SPL Name Service", name.as_bytes()]).to_bytes();
+    let (expected, _) = Pubkey::find_program_address(
+        &[&hashed_name, Pubkey::default().as_ref(), SNS_SOL_TLD.as_ref()],
+        &SNS_PROGRAM_ID,
+    );
+    require_keys_eq!(*name_record.key, expected, NftError::InvalidNameRecord);
+    require_keys_eq!(*name_record.owner, SNS_PROGRAM_ID, NftError::InvalidNameRecord);
+    // 📇 Record header: parent (32) | owner (32) | class (32)
+    let data = name_record.try_borrow_data()?;
+    require!(data.len() >= 96, NftError::InvalidNameRecord);
+    require!(&data[32..64] == wallet.as_ref(), NftError::InvalidNameRecord);
+    Ok(())
+}
+
+/// 🔢 Claim `count` sequential edition numbers from the collection's
+/// EditionCounter; None when the collection doesn't number its editions
+pub fn claim_editions(
+    collection_config: &AccountInfo,
+    edition_counter: Option<&mut EditionCounter>,
+    count: u32,
+) -> Result<Option<Edition>> {
+    let Some(config) = load_if_initialized::<CollectionConfig>(collection_config)? else {
+        return Ok(None);
+    };
+    if !config.numbered_editions {
+        return Ok(None);
+    }
+    let counter = edition_counter.ok_or(NftError::EditionCounterMissing)?;
+    let number = counter.next_edition;
+    counter.next_edition = number.checked_add(count).ok_or(NftError::MathOverflow)?;
+    Ok(Some(Edition { number, max_supply: config.max_supply }))
+}
+
+/// ✍️ The collection's program authority as a signer, if the collection has
+/// been handed to it; None means the payer must be its update authority
+pub fn program_signer<'a, 'info>(
+    collection: &AccountInfo<'info>,
+    collection_authority: &'a AccountInfo<'info>,
+    seeds: &'a [&'a [u8]],
+) -> Result<Option<ProgramSigner<'a, 'info>>> {
+    require_keys_eq!(*collection.owner, mpl_core::ID, NftError::InvalidAsset);
+    let base_collection = BaseCollectionV1::from_bytes(&collection.try_borrow_data()?)
+        .map_err(|_| error!(NftError::InvalidAsset))?;
+    Ok((base_collection.update_authority == collection_authority.key())
+        .then_some(ProgramSigner { authority: collection_authority, seeds }))
+}
+
+/// 🔀 Keys whose values differ between two attribute lists, in `new`'s order
+/// followed by keys `new` drops
+pub fn attribute_diff(previous: &[Attribute], new: &[Attribute]) -> Vec<AttributeChange> {
+    let value_in = |list: &[Attribute], key: &str| {
+        list.iter().find(|attribute| attribute.key == key).map(|attribute| attribute.value.clone())
+    };
+    let changed = new.iter().filter_map(|attribute| {
+        let old_value = value_in(previous, &attribute.key);
+        (old_value.as_deref() != Some(attribute.value.as_str())).then(|| AttributeChange {
+            key: attribute.key.clone(),
+            old_value,
+            new_value: Some(attribute.value.clone()),
+        })
+    });
+    let dropped = previous
+        .iter()
+        .filter(|attribute| value_in(new, &attribute.key).is_none())
+        .map(|attribute| AttributeChange {
+            key: attribute.key.clone(),
+            old_value: Some(attribute.value.clone()),
+            new_value: None,
+        });
+    changed.chain(dropped).collect()
+}
+
+/// 🔀 Emit AttributesChanged for a rewrite of `asset`'s attributes, if anything changed
+pub fn emit_attribute_diff(asset: Pubkey, action: RuleAction, previous: &[Attribute], new: &[Attribute]) {
+    let changes = attribute_diff(previous, new);
+    if !changes.is_empty() {
+        emit!(AttributesChanged { asset, action, changes });
+    }
+}
+
+/// 🤖 Reject a public mint into an anti-bot collection unless it is the only
+/// program mint in the transaction and was called directly, not through CPI
+pub fn enforce_single_mint(collection_config: &AccountInfo, instructions_sysvar: &AccountInfo) -> Result<()> {
+    if collection_config.owner != &crate::ID {
+        return Ok(());
+    }
+    let config = CollectionConfig::try_deserialize(&mut &collection_config.try_borrow_data()?[..])?;
+    if !config.anti_bot {
+        return Ok(());
+    }
+    require!(get_stack_height() == TRANSACTION_LEVEL_STACK_HEIGHT, NftError::MintViaCpi);
+    let mut mints = 0usize;
+    let mut index = 0usize;
+    while let Ok(instruction) = load_instruction_at_checked(index, instructions_sysvar) {
+        if instruction.program_id == crate::ID
+            && MINT_DISCRIMINATORS.iter().any(|discriminator| instruction.data.starts_with(discriminator))
+        {
+            mints += 1;
+        }
+        index += 1;
+    }
+    require!(mints == 1, NftError::MultipleMintsInTransaction);
+    Ok(())
+}
+
+/// 👑 A creator split is empty (authority takes all) or up to
+/// MAX_ROYALTY_CREATORS distinct creators whose shares sum to 100
+pub fn valid_royalty_split(creators: &[RoyaltyCreator]) -> bool {
+    let total: u32 = creators.iter().map(|creator| creator.percentage as u32).sum();
+    let distinct = creators
+        .iter()
+        .enumerate()
+        .all(|(i, creator)| creators[..i].iter().all(|earlier| earlier.address != creator.address));
+    creators.is_empty() || (creators.len() <= MAX_ROYALTY_CREATORS && total == 100 && distinct)
+}
+
+/// 👑 Royalties plugin data for a split, falling back to `authority` at 100%
+pub fn royalties(basis_points: u16, creators: &[RoyaltyCreator], authority: Pubkey) -> Royalties {
+    let creators = if creators.is_empty() {
+        vec![Creator { address: authority, percentage: 100 }]
+    } else {
+        creators
+            .iter()
+            .map(|creator| Creator { address: creator.address, percentage: creator.percentage })
+            .collect()
+    };
+    Royalties { basis_points, creators, rule_set: RuleSet::None }
+}
+
+/// 🎖️ Convert levels cut off by the cap into the wallet's prestige
+pub fn overflow_to_prestige(profile: &mut PlayerProfile, asset: Pubkey, excess_levels: u64) {
+    if excess_levels == 0 {
+        return;
+    }
+    let prestige = profile.add_prestige(excess_levels);
+    emit!(PrestigeEarned { wallet: profile.wallet, asset, excess_levels, prestige });
+}
+
+/// 🎖️ `overflow_to_prestige` for cranks, whose profiles arrive as remaining
+/// accounts: checks `profile_info` is `wallet`'s PlayerProfile PDA and, if
+/// there is anything to credit, creates it at `payer`'s expense when missing
+pub fn credit_prestige<'info>(
+    profile_info: &AccountInfo<'info>,
+    wallet: &Pubkey,
+    asset: Pubkey,
+    excess_levels: u64,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+) -> Result<()> {
+    let (expected, bump) = Pubkey::find_program_address(&[b"player_profile", wallet.as_ref()], &crate::ID);
+    require_keys_eq!(*profile_info.key, expected, NftError::InvalidPlayerProfile);
+    if excess_levels == 0 {
+        return Ok(());
+    }
+    let mut profile = match load_if_initialized::<PlayerProfile>(profile_info)? {
+        Some(profile) => profile,
+        None => {
+            let space = 8 + PlayerProfile::INIT_SPACE;
+            system_program::create_account(
+                CpiContext::new_with_signer(
+                    system_program.clone(),
+                    system_program::CreateAccount { from: payer.clone(), to: profile_info.clone() },
+                    &[&[b"player_profile", wallet.as_ref(), &[bump]]],
+                ),
+                Rent::get()?.minimum_balance(space),
+                space as u64,
+                &crate::ID,
+            )?;
+            PlayerProfile { wallet: *wallet, bump, ..Default::default() }
+        }
+    };
+    overflow_to_prestige(&mut profile, asset, excess_levels);
+    profile.try_serialize(&mut &mut profile_info.try_borrow_mut_data()?[..])
+}
+
+/// 🌐 `error`, after emitting an ErrorContext with what was `expected` and
+/// what was found instead
+pub fn fail_with_context(error: NftError, expected: impl Into<i128>, actual: impl Into<i128>) -> Error {
+    emit!(ErrorContext { code: error.into(), expected: expected.into(), actual: actual.into() });
+    error!(error)
+}
+
+/// 🗳️ Create the PollVote PDA marking `asset` as having voted in `poll`;
+/// an asset that already has one can't vote again
+pub fn record_poll_vote<'info>(
+    poll_vote: &AccountInfo<'info>,
+    poll: &Pubkey,
+    asset: &Pubkey,
+    option: u8,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+) -> Result<()> {
+    let (expected, bump) = Pubkey::find_program_address(&[b"poll_vote", poll.as_ref(), asset.as_ref()], &crate::ID);
+    require_keys_eq!(*poll_vote.key, expected, NftError::InvalidPollVote);
+    require!(poll_vote.owner != &crate::ID, NftError::AssetAlreadyVoted);
+    let space = 8 + PollVote::INIT_SPACE;
+    system_program::create_account(
+        CpiContext::new_with_signer(
+            system_program.clone(),
+            system_program::CreateAccount { from: payer.clone(), to: poll_vote.clone() },
+            &[&[b"poll_vote", poll.as_ref(), asset.as_ref(), &[bump]]],
+        ),
+        Rent::get()?.minimum_balance(space),
+        space as u64,
+        &crate::ID,
+    )?;
+    PollVote { option }.try_serialize(&mut &mut poll_vote.try_borrow_mut_data()?[..])
+}
+
+/// 🌟 Settle one queued evolution from its revealed `roll_hash`: roll against
+/// `chance`, apply the outcome to `nft_state`, write it to the asset's
+/// attributes through `backend` and unlock the state. Also returns the levels
+/// cut off by the level cap, for the caller to credit as prestige
+#[allow(clippy::too_many_arguments)]
+pub fn resolve_queued_evolution<'info>(
+    backend: &dyn AssetBackend<'info>,
+    config: &ProgramConfig,
+    nft_state: &mut NftState,
+    asset: &AccountInfo<'info>,
+    collection: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    attributes_authority: AttributesAuthority<'_, 'info>,
+    roll_hash: &[u8; 32],
+    chance: i64,
+    now: i64,
+) -> Result<(EvolutionOutcome, u64)> {
+    let roll = roll_below(roll_hash, 0, 100) as i64;
+    let downgrade_roll = roll_below(roll_hash, 1, MAX_BPS as u64) as u16;
+
+    let mut excess_levels = 0;
+    let (outcome, changes) = if roll <= chance {
+        (nft_state.level, excess_levels) = config.cap_level(nft_state.level, nft_state.level + 1);
+        nft_state.rarity = next_rarity(nft_state.rarity);
+        nft_state.evolution_count += 1;
+        nft_state.last_updated = now;
+        msg!("🌟 Queued evolution of {} → {} level {}", asset.key, nft_state.rarity, nft_state.level);
+        (EvolutionOutcome::Evolved, vec![
+            Attribute { key: "level".to_string(), value: nft_state.level.to_string() },
+            Attribute { key: "rarity".to_string(), value: nft_state.rarity.to_string() },
+            Attribute { key: "evolved_at".to_string(), value: now.to_string() },
+            Attribute { key: "evolution_count".to_string(), value: nft_state.evolution_count.to_string() },
+            Attribute { key: "evolution_chance".to_string(), value: chance.to_string() },
+        ])
+    } else if nft_state.rarity == Rarity::Legendary && downgrade_roll < config.evolution_downgrade_bps {
+        // 💀 Endgame risk: a failed Mythic attempt can cost a tier
+        nft_state.rarity = Rarity::Epic;
+        nft_state.last_updated = now;
+        msg!("💀 Queued evolution of {} failed and downgraded it to {}", asset.key, nft_state.rarity);
+        (EvolutionOutcome::Downgraded, vec![
+            Attribute { key: "rarity".to_string(), value: nft_state.rarity.to_string() },
+        ])
+    } else {
+        msg!("💨 Queued evolution of {} failed (roll {})", asset.key, roll);
+        (EvolutionOutcome::Failed, vec![])
+    };
+    // ✍️ Mirror the result onto the asset, as evolve_nft does
+    if !changes.is_empty() {
+        nft_state.attributes_hash = write_attributes(
+            backend,
+            asset,
+            collection,
+            payer,
+            attributes_authority,
+            merge_attributes(config, asset, changes)?,
+        )?;
+    }
+    nft_state.apply_rules(RuleAction::Evolve, config.rule_versions);
+    emit!(EvolutionFinalized {
+        asset: asset.key(),
+        outcome,
+        level: nft_state.level,
+        rarity: nft_state.rarity,
+    });
+    nft_state.lock_state = LockState::Unlocked;
+    Ok((outcome, excess_levels))
+}
+
+/// 📸 Checkpoint an asset before levels past the cap turn into prestige; the
+/// history is only required when that happens
+pub fn checkpoint_prestige(
+    asset_history: &mut Option<Account<AssetHistory>>,
+    nft_state: &NftState,
+    asset: &AccountInfo,
+    now: i64,
+) -> Result<()> {
+    let history = asset_history.as_mut().ok_or(NftError::AssetHistoryRequired)?;
+    history.record(CheckpointOperation::Prestige, nft_state, hash_attributes(&fetch_attributes(asset)?), now);
+    Ok(())
+}
+
+/// 🧩 Assign every set requirement its own piece (`eligible[requirement][piece]`)
+///
+/// Maximum bipartite matching by augmenting paths, so a piece that fits
+/// several requirements is moved when a later requirement needs it. Returns
+/// the piece chosen for each requirement, or `None` if the set is incomplete.
+pub fn match_set_pieces(eligible: &[Vec<bool>]) -> Option<Vec<usize>> {
+    fn augment(
+        requirement: usize,
+        eligible: &[Vec<bool>],
+        visited: &mut [bool],
+        owner: &mut [Option<usize>],
+    ) -> bool {
+        for (piece, fits) in eligible[requirement].iter().enumerate() {
+            if !fits || visited[piece] {
+                continue;
+            }
+            visited[piece] = true;
+            if owner[piece].is_none_or(|other| augment(other, eligible, visited, owner)) {
+                owner[piece] = Some(requirement);
+                return true;
+            }
+        }
+        false
+    }
+
+    let piece_count = eligible.first().map_or(0, Vec::len);
+    let mut owner: Vec<Option<usize>> = vec![None; piece_count];
+    for requirement in 0..eligible.len() {
+        let mut visited = vec![false; piece_count];
+        if !augment(requirement, eligible, &mut visited, &mut owner) {
+            return None;
+        }
+    }
+
+    let mut matching = vec![0; eligible.len()];
+    for (piece, requirement) in owner.iter().enumerate() {
+        if let Some(requirement) = requirement {
+            matching[*requirement] = piece;
+        }
+    }
+    Some(matching)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::{BackendCall, RecordingBackend};
+
+    fn test_config() -> ProgramConfig {
+        let zeroed = vec![0u8; 8 + ProgramConfig::INIT_SPACE];
+        let mut config = ProgramConfig::try_deserialize_unchecked(&mut zeroed.as_slice()).unwrap();
+        config.max_attributes = 32;
+        config.max_attribute_bytes = 4096;
+        config.max_level = DEFAULT_MAX_LEVEL;
+        config
+    }
+
+    /// Empty, writable accounts with fresh keys, leaked to live for the test
+    fn accounts<const N: usize>() -> [AccountInfo<'static>; N] {
+        std::array::from_fn(|_| {
+            let key = Box::leak(Box::new(Pubkey::new_unique()));
+            AccountInfo::new(key, false, true, Box::leak(Box::new(0)), &mut [], &mpl_core::ID, false, 0)
+        })
+    }
+
+    fn recorded_attributes(calls: &[BackendCall]) -> Vec<Attribute> {
+        match calls {
+            [BackendCall::UpdatePlugin { plugin: Plugin::Attributes(attributes), .. }] => attributes.attribute_list.clone(),
+            other => panic!("expected one Attributes write, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn nft_state_serializes_to_its_declared_space() {
+        let state = NftState {
+            version: NFT_STATE_VERSION,
+            asset: Pubkey::new_unique(),
+            collection: Pubkey::new_unique(),
+            attributes_hash: [0xab; 32],
+            level: u64::MAX,
+            achievement_points: u64::MAX - 1,
+            energy: 100,
+            mint_date: i64::MIN,
+            last_updated: i64::MAX,
+            last_refill_day: 20_000,
+            evolution_count: u32::MAX,
+            fusion_potential: 7,
+            rule_versions: RuleVersions { evolution: 3, cooldown: u16::MAX, rarity: 1 },
+            rarity: Rarity::Divine,
+            lock_state: LockState::Sunset,
+            refills_today: 2,
+            bump: 254,
+        };
+        let mut bytes = Vec::new();
+        state.try_serialize(&mut bytes).unwrap();
+        assert_eq!(bytes.len(), 8 + NftState::INIT_SPACE);
+
+        let loaded = NftState::try_deserialize(&mut bytes.as_slice()).unwrap();
+        let mut reserialized = Vec::new();
+        loaded.try_serialize(&mut reserialized).unwrap();
+        assert_eq!(reserialized, bytes);
+        assert_eq!((loaded.asset, loaded.rarity, loaded.lock_state), (state.asset, Rarity::Divine, LockState::Sunset));
+    }
+
+    #[test]
+    fn quote_message_binds_program_and_collection() {
+        let (wallet, collection) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let message = quote_message(&wallet, &collection, 5_000, 1_000, 1);
+        assert_eq!(&message[..10], b"mint_quote");
+        assert_eq!(&message[10..42], crate::ID.as_ref());
+        assert_eq!(&message[42..74], collection.as_ref());
+        assert_ne!(quote_message(&wallet, &Pubkey::new_unique(), 5_000, 1_000, 1), message);
+    }
+
+    #[test]
+    fn voucher_message_binds_program_and_collection() {
+        let voucher = MintVoucher {
+            name: "Hero".to_string(),
+            uri: "https://example.com/hero.json".to_string(),
+            recipient: Pubkey::new_unique(),
+            collection: Pubkey::new_unique(),
+            expires_at: 1_000,
+            nonce: 1,
+        };
+        let message = voucher.message();
+        assert_eq!(&message[..12], b"mint_voucher");
+        assert_eq!(&message[12..44], crate::ID.as_ref());
+        assert_eq!(&message[44..76], voucher.collection.as_ref());
+
+        let elsewhere = MintVoucher { collection: Pubkey::new_unique(), ..voucher.clone() };
+        assert_ne!(elsewhere.message(), message);
+    }
+
+    #[test]
+    fn write_attributes_records_the_full_list() {
+        let [asset, collection, payer, authority] = accounts::<4>();
+        let backend = RecordingBackend::default();
+        let list = vec![Attribute { key: "level".to_string(), value: "3".to_string() }];
+
+        let hash = write_attributes(&backend, &asset, &collection, &payer, AttributesAuthority::new(&authority, 255), list.clone())
+            .unwrap();
+
+        assert_eq!(hash, hash_attributes(&list));
+        assert_eq!(recorded_attributes(&backend.calls.borrow()), list);
+    }
+
+    #[test]
+    fn queued_evolution_writes_the_evolved_attributes() {
+        let [asset, collection, payer, authority] = accounts::<4>();
+        let backend = RecordingBackend::default();
+        let mut nft_state = NftState { level: 1, lock_state: LockState::PendingEvolution, ..Default::default() };
+
+        let (outcome, _) = resolve_queued_evolution(
+            &backend,
+            &test_config(),
+            &mut nft_state,
+            &asset,
+            &collection,
+            &payer,
+            AttributesAuthority::new(&authority, 255),
+            &[7u8; 32],
+            evolution_chance(Rarity::Common),
+            1_000,
+        )
+        .unwrap();
+
+        assert_eq!(outcome, EvolutionOutcome::Evolved);
+        assert_eq!((nft_state.level, nft_state.rarity, nft_state.lock_state), (2, Rarity::Uncommon, LockState::Unlocked));
+        let written = recorded_attributes(&backend.calls.borrow());
+        assert_eq!(nft_state.attributes_hash, hash_attributes(&written));
+        assert!(written.contains(&Attribute { key: "level".to_string(), value: "2".to_string() }));
+        assert!(written.contains(&Attribute { key: "rarity".to_string(), value: Rarity::Uncommon.to_string() }));
+    }
+
+    #[test]
+    fn queued_evolution_downgrade_writes_only_rarity() {
+        let [asset, collection, payer, authority] = accounts::<4>();
+        let backend = RecordingBackend::default();
+        let mut config = test_config();
+        config.evolution_downgrade_bps = MAX_BPS;
+        let mut nft_state =
+            NftState { rarity: Rarity::Legendary, lock_state: LockState::PendingEvolution, ..Default::default() };
+
+        let (outcome, _) = resolve_queued_evolution(
+            &backend,
+            &config,
+            &mut nft_state,
+            &asset,
+            &collection,
+            &payer,
+            AttributesAuthority::new(&authority, 255),
+            &[7u8; 32],
+            -1,
+            1_000,
+        )
+        .unwrap();
+
+        assert_eq!(outcome, EvolutionOutcome::Downgraded);
+        assert_eq!(nft_state.rarity, Rarity::Epic);
+        assert_eq!(
+            recorded_attributes(&backend.calls.borrow()),
+            vec![Attribute { key: "rarity".to_string(), value: Rarity::Epic.to_string() }]
+        );
+    }
+
+    #[test]
+    fn failed_queued_evolution_touches_nothing_on_chain() {
+        let [asset, collection, payer, authority] = accounts::<4>();
+        let backend = RecordingBackend::default();
+        let mut nft_state = NftState { level: 4, lock_state: LockState::PendingEvolution, ..Default::default() };
+
+        let (outcome, _) = resolve_queued_evolution(
+            &backend,
+            &test_config(),
+            &mut nft_state,
+            &asset,
+            &collection,
+            &payer,
+            AttributesAuthority::new(&authority, 255),
+            &[7u8; 32],
+            -1,
+            1_000,
+        )
+        .unwrap();
+
+        assert_eq!(outcome, EvolutionOutcome::Failed);
+        assert_eq!((nft_state.level, nft_state.lock_state), (4, LockState::Unlocked));
+        assert!(backend.calls.borrow().is_empty());
+    }
+
+    #[test]
+    fn release_deal_assets_transfers_in_deal_order() {
+        let [asset_a, collection_a, asset_b, collection_b, deal_info, buyer] = accounts::<6>();
+        let deal = Deal {
+            seller: Pubkey::new_unique(),
+            deal_id: 9,
+            counterparty: Pubkey::default(),
+            offered_assets: vec![asset_a.key(), asset_b.key()],
+            ask: DealAsk { lamports: 0, token_mint: Pubkey::default(), token_amount: 0, assets: vec![] },
+            created_at: 0,
+            bump: 254,
+        };
+        let backend = RecordingBackend::default();
+
+        let pairs = [asset_a.clone(), collection_a, asset_b.clone(), collection_b];
+        release_deal_assets(&backend, &deal, &deal_info, &pairs, &buyer, &buyer).unwrap();
+
+        assert_eq!(
+            *backend.calls.borrow(),
+            vec![
+                BackendCall::Transfer { asset: asset_a.key(), new_owner: buyer.key() },
+                BackendCall::Transfer { asset: asset_b.key(), new_owner: buyer.key() },
+            ]
+        );
+
+        let swapped = Deal { offered_assets: vec![asset_b.key(), asset_a.key()], ..deal };
+        let backend = RecordingBackend::default();
+        let err = release_deal_assets(&backend, &swapped, &deal_info, &pairs, &buyer, &buyer).unwrap_err();
+        assert_eq!(err, NftError::DealAssetMismatch.into());
+        assert!(backend.calls.borrow().is_empty());
+    }
+
+    #[test]
+    fn release_listed_asset_transfers_to_the_buyer() {
+        let [asset, collection, payer, listing, buyer] = accounts::<5>();
+        let backend = RecordingBackend::default();
+
+        release_listed_asset(&backend, &asset, &collection, &payer, &listing, &buyer, 253).unwrap();
+
+        assert_eq!(*backend.calls.borrow(), vec![BackendCall::Transfer { asset: asset.key(), new_owner: buyer.key() }]);
+    }
+
+    #[test]
+    fn set_pieces_are_rematched_when_greedy_choice_blocks_a_later_requirement() {
+        // Piece 0 fits both requirements, piece 1 only the first: first-fit
+        // would give piece 0 to the first requirement and strand the second
+        let eligible = vec![vec![true, true], vec![true, false]];
+        assert_eq!(match_set_pieces(&eligible), Some(vec![1, 0]));
+
+        // Two requirements that only one piece can fill
+        let eligible = vec![vec![true, false], vec![true, false]];
+        assert_eq!(match_set_pieces(&eligible), None);
+    }
+
+    #[test]
+    fn set_requirement_wildcards_match_any_piece() {
+        let collection = Pubkey::new_unique();
+        let fire = [Attribute { key: "element".into(), value: "fire".into() }];
+        let requirement = |collection, key: &str, value: &str| SetRequirement {
+            collection,
+            trait_key: key.into(),
+            trait_value: value.into(),
+        };
+
+        assert!(requirement(Pubkey::default(), "", "").is_met_by(None, &[]));
+        assert!(requirement(collection, "element", "").is_met_by(Some(collection), &fire));
+        assert!(requirement(Pubkey::default(), "element", "fire").is_met_by(None, &fire));
+        assert!(!requirement(Pubkey::default(), "element", "water").is_met_by(None, &fire));
+        assert!(!requirement(collection, "", "").is_met_by(Some(Pubkey::new_unique()), &fire));
+    }
+}
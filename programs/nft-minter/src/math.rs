@@ -0,0 +1,223 @@
+//! 🧮 Economic math shared by every fee, price and payout
+//!
+//! Rounding policy: anything the program *charges* rounds in the treasury's
+//! favour (`Ceil`), anything it *pays out or discounts* rounds down (`Floor`).
+//! Splits always return both halves so no lamport is ever left unaccounted for;
+//! the treasury-bound half absorbs the rounding remainder.
+
+use anchor_lang::prelude::*;
+
+use crate::{NftError, MAX_BPS};
+
+/// ↕️ Which way a non-exact division goes
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Rounding {
+    Floor,
+    Ceil,
+}
+
+/// ➗ `amount * numerator / denominator` in u128, rounded as requested
+pub fn mul_div(amount: u64, numerator: u64, denominator: u64, rounding: Rounding) -> Result<u64> {
+    require!(denominator > 0, NftError::MathOverflow);
+    let product = (amount as u128)
+        .checked_mul(numerator as u128)
+        .ok_or(NftError::MathOverflow)?;
+    let quotient = match rounding {
+        Rounding::Floor => product / denominator as u128,
+        Rounding::Ceil => product.div_ceil(denominator as u128),
+    };
+    u64::try_from(quotient).map_err(|_| error!(NftError::MathOverflow))
+}
+
+/// 💱 Basis-point share of an amount, rounded down
+pub fn bps_of(amount: u64, bps: u16) -> Result<u64> {
+    mul_div(amount, bps as u64, MAX_BPS as u64, Rounding::Floor)
+}
+
+/// ✂️ Both halves of a basis-point split; `share + rest == amount` always
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Split {
+    pub share: u64,
+    pub rest: u64,
+}
+
+/// ✂️ Split `amount` into a `bps` share and the rest, rounding the share as requested
+pub fn split_bps(amount: u64, bps: u16, rounding: Rounding) -> Result<Split> {
+    require!(bps <= MAX_BPS, NftError::InvalidFeeBps);
+    let share = mul_div(amount, bps as u64, MAX_BPS as u64, rounding)?;
+    Ok(Split { share, rest: amount - share })
+}
+
+/// 🏦 Marketplace fee split: the treasury's fee rounds up, so the seller's
+/// proceeds never carry rounding dust the treasury didn't receive
+pub fn treasury_fee_split(price: u64, fee_bps: u16) -> Result<Split> {
+    split_bps(price, fee_bps, Rounding::Ceil)
+}
+
+/// 🏷️ Apply a basis-point discount to a charge; the discount rounds down
+pub fn discounted(amount: u64, discount_bps: u16) -> Result<u64> {
+    Ok(amount - bps_of(amount, discount_bps.min(MAX_BPS))?)
+}
+
+/// 📈 Price after `steps` compounding increases of `step_bps` each, every
+/// step rounded up so the curve never undercharges
+pub fn compound_bps(base: u64, step_bps: u16, steps: u32) -> Result<u64> {
+    let mut price = base;
+    for _ in 0..steps {
+        let step = mul_div(price, step_bps as u64, MAX_BPS as u64, Rounding::Ceil)?;
+        price = price.checked_add(step).ok_or(NftError::MathOverflow)?;
+    }
+    Ok(price)
+}
+
+/// 📈 `base * (1 + step_bps)^steps` by squaring in 1e12 fixed point, so long
+/// curves cost O(log steps); every product rounds up like `compound_bps`
+pub fn pow_bps(base: u64, step_bps: u16, steps: u32) -> Result<u64> {
+    const ONE: u128 = 1_000_000_000_000;
+    let mul_fixed = |a: u128, b: u128| {
+        a.checked_mul(b).map(|product| product.div_ceil(ONE)).ok_or(NftError::MathOverflow)
+    };
+    let mut factor = ONE;
+    let mut square = ONE + ONE * step_bps as u128 / MAX_BPS as u128;
+    let mut remaining = steps;
+    while remaining > 0 {
+        if remaining & 1 == 1 {
+            factor = mul_fixed(factor, square)?;
+        }
+        remaining >>= 1;
+        if remaining > 0 {
+            square = mul_fixed(square, square)?;
+        }
+    }
+    let price = mul_fixed(base as u128, factor)?;
+    u64::try_from(price).map_err(|_| error!(NftError::MathOverflow))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// xorshift64*: deterministic inputs without a property-testing crate
+    struct Inputs(u64);
+
+    impl Inputs {
+        fn next(&mut self) -> u64 {
+            self.0 ^= self.0 >> 12;
+            self.0 ^= self.0 << 25;
+            self.0 ^= self.0 >> 27;
+            self.0.wrapping_mul(0x2545_f491_4f6c_dd1d)
+        }
+
+        /// Mixes small, mid-range and huge values so edge cases come up often
+        fn amount(&mut self) -> u64 {
+            match self.next() % 4 {
+                0 => self.next() % 100,
+                1 => self.next() % 1_000_000_000,
+                2 => u64::MAX - self.next() % 100,
+                _ => self.next(),
+            }
+        }
+
+        fn bps(&mut self) -> u16 {
+            (self.next() % (MAX_BPS as u64 + 1)) as u16
+        }
+    }
+
+    const CASES: usize = 10_000;
+
+    fn exact_bps(amount: u64, bps: u16) -> (u128, u128) {
+        let product = amount as u128 * bps as u128;
+        (product / MAX_BPS as u128, product.div_ceil(MAX_BPS as u128))
+    }
+
+    #[test]
+    fn mul_div_brackets_the_exact_quotient() {
+        let mut inputs = Inputs(0x9e37_79b9_7f4a_7c15);
+        for _ in 0..CASES {
+            let (amount, numerator) = (inputs.amount(), inputs.amount());
+            let denominator = inputs.amount().max(1);
+            let product = amount as u128 * numerator as u128;
+            let (Ok(floor), Ok(ceil)) = (
+                mul_div(amount, numerator, denominator, Rounding::Floor),
+                mul_div(amount, numerator, denominator, Rounding::Ceil),
+            ) else {
+                // Only a quotient past u64 may fail
+                assert!(product.div_ceil(denominator as u128) > u64::MAX as u128);
+                continue;
+            };
+            assert!(floor as u128 * denominator as u128 <= product);
+            assert!(product < (floor as u128 + 1) * denominator as u128);
+            let exact = product.is_multiple_of(denominator as u128);
+            assert_eq!(ceil, if exact { floor } else { floor + 1 });
+        }
+        assert!(mul_div(1, 1, 0, Rounding::Floor).is_err());
+    }
+
+    #[test]
+    fn split_bps_accounts_for_every_lamport() {
+        let mut inputs = Inputs(0xdead_beef_cafe_f00d);
+        for _ in 0..CASES {
+            let (amount, bps) = (inputs.amount(), inputs.bps());
+            let (floor, ceil) = exact_bps(amount, bps);
+            for (rounding, bound) in [(Rounding::Floor, floor), (Rounding::Ceil, ceil)] {
+                let split = split_bps(amount, bps, rounding).unwrap();
+                assert_eq!(split.share as u128 + split.rest as u128, amount as u128);
+                assert_eq!(split.share as u128, bound);
+            }
+        }
+        assert!(split_bps(100, MAX_BPS + 1, Rounding::Floor).is_err());
+    }
+
+    #[test]
+    fn treasury_fee_split_routes_the_remainder_to_the_treasury() {
+        let mut inputs = Inputs(0x0123_4567_89ab_cdef);
+        for _ in 0..CASES {
+            let (price, fee_bps) = (inputs.amount(), inputs.bps());
+            let split = treasury_fee_split(price, fee_bps).unwrap();
+            let (floor, ceil) = exact_bps(price, fee_bps);
+            assert_eq!(split.share as u128 + split.rest as u128, price as u128);
+            // 🏦 The fee is never under the exact share, so the dust is the treasury's
+            assert_eq!(split.share as u128, ceil);
+            assert!(split.share as u128 - floor <= 1);
+            assert!(split.rest as u128 <= price as u128 - floor);
+        }
+    }
+
+    #[test]
+    fn discounts_never_exceed_the_exact_discount() {
+        let mut inputs = Inputs(0xfeed_face_0bad_f00d);
+        for _ in 0..CASES {
+            let (amount, discount_bps) = (inputs.amount(), inputs.next() as u16);
+            let (floor, _) = exact_bps(amount, discount_bps.min(MAX_BPS));
+            assert_eq!(discounted(amount, discount_bps).unwrap() as u128, amount as u128 - floor);
+        }
+    }
+
+    #[test]
+    fn curves_never_undercharge() {
+        let mut inputs = Inputs(0x5555_aaaa_3333_cccc);
+        for _ in 0..CASES {
+            let base = inputs.next() % 1_000_000_000_000;
+            let step_bps = inputs.bps();
+            let steps = (inputs.next() % 5) as u32;
+            // base * (MAX_BPS + step_bps)^steps / MAX_BPS^steps, exactly
+            let growth = (MAX_BPS as u128 + step_bps as u128).pow(steps);
+            let scale = (MAX_BPS as u128).pow(steps);
+            let (floor, ceil) = ((base as u128 * growth) / scale, (base as u128 * growth).div_ceil(scale));
+
+            let compounded = compound_bps(base, step_bps, steps).unwrap() as u128;
+            let powered = pow_bps(base, step_bps, steps).unwrap() as u128;
+            assert!(compounded >= ceil, "compound_bps undercharged");
+            assert!(powered >= ceil, "pow_bps undercharged");
+            // A lamport rounded up at step k has grown by (1 + step_bps)^(steps - 1 - k) by the end
+            let dust: u128 = (0..steps)
+                .map(|k| (MAX_BPS as u128 + step_bps as u128).pow(k).div_ceil((MAX_BPS as u128).pow(k)))
+                .sum();
+            assert!(compounded <= floor + dust);
+            // Fixed point rounds each of its few products up by at most 1e-12
+            assert!(powered <= ceil + 1 + ceil / 100_000_000_000);
+        }
+        assert_eq!(pow_bps(1_000, 500, 0).unwrap(), 1_000);
+        assert!(pow_bps(u64::MAX, MAX_BPS, 64).is_err());
+    }
+}